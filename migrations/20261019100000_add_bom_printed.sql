@@ -0,0 +1 @@
+ALTER TABLE bom ADD COLUMN printed INTEGER NOT NULL DEFAULT 0;
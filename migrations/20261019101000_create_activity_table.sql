@@ -0,0 +1,10 @@
+create table activity (
+  seq integer primary key autoincrement,
+  occurred_at datetime not null,
+  actor varchar(100) not null,
+  event varchar(50) not null,
+  entity varchar(20) not null,
+  entity_id text not null,
+  changes text not null
+);
+create index activity_entity_idx on activity (entity_id, seq);
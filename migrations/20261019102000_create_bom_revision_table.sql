@@ -0,0 +1,26 @@
+create table bom_revision (
+  project_id text not null,
+  revision integer not null,
+  created_at datetime not null,
+  actor varchar(100) not null,
+  primary key (project_id, revision),
+  foreign key (project_id) references project (id) on delete cascade
+);
+create table bom_revision_line (
+  project_id text not null,
+  revision integer not null,
+  position integer not null,
+  part_id text not null,
+  quantity integer not null,
+  primary key (project_id, revision, position),
+  foreign key (project_id, revision) references bom_revision (project_id, revision) on delete cascade,
+  foreign key (part_id) references part (id)
+);
+
+-- Existing BOMs become the first revision of their projects.
+insert into bom_revision (project_id, revision, created_at, actor)
+select distinct project_id, 1, strftime('%Y-%m-%dT%H:%M:%SZ', 'now'), 'anonymous'
+from bom;
+insert into bom_revision_line (project_id, revision, position, part_id, quantity)
+select project_id, 1, row_number() over (partition by project_id order by rowid), part_id, quantity
+from bom;
@@ -0,0 +1 @@
+ALTER TABLE project ADD COLUMN template BOOLEAN NOT NULL DEFAULT FALSE;
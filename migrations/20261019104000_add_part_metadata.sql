@@ -0,0 +1,8 @@
+ALTER TABLE part ADD COLUMN material VARCHAR(20);
+ALTER TABLE part ADD COLUMN color VARCHAR(50);
+ALTER TABLE part ADD COLUMN grams REAL;
+ALTER TABLE part ADD COLUMN minutes INTEGER;
+ALTER TABLE part ADD COLUMN size_x REAL;
+ALTER TABLE part ADD COLUMN size_y REAL;
+ALTER TABLE part ADD COLUMN size_z REAL;
+ALTER TABLE part ADD COLUMN notes TEXT;
@@ -0,0 +1,20 @@
+create table spool (
+  id text primary key not null,
+  material varchar(20) not null,
+  color varchar(50) not null,
+  brand varchar(50),
+  initial_grams real not null,
+  remaining_grams real not null,
+  cost real
+);
+create table spool_consumption (
+  seq integer primary key autoincrement,
+  spool_id text not null,
+  project_id text not null,
+  grams real not null,
+  consumed_at datetime not null,
+  actor varchar(100) not null,
+  foreign key (spool_id) references spool (id),
+  foreign key (project_id) references project (id) on delete cascade
+);
+create index spool_consumption_project_idx on spool_consumption (project_id, seq);
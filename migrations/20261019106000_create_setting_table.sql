@@ -0,0 +1,4 @@
+create table setting (
+  key varchar(50) primary key not null,
+  value text not null
+);
@@ -0,0 +1,11 @@
+create table part_file (
+  id text primary key not null,
+  part_id text not null,
+  file_name varchar(255) not null,
+  format varchar(10) not null,
+  sha256 char(64) not null,
+  size integer not null,
+  uploaded_at datetime not null,
+  foreign key (part_id) references part (id) on delete cascade
+);
+create index part_file_part_idx on part_file (part_id, uploaded_at);
@@ -0,0 +1 @@
+alter table part add column geometry text;
@@ -0,0 +1,8 @@
+alter table part add column category text;
+create table part_tag (
+  part_id text not null,
+  tag varchar(50) not null,
+  primary key (part_id, tag),
+  foreign key (part_id) references part (id) on delete cascade
+);
+create index part_tag_tag_idx on part_tag (tag);
@@ -0,0 +1,12 @@
+alter table part add column revision integer not null default 1;
+alter table part_file add column revision integer not null default 1;
+alter table bom add column revision integer not null default 1;
+create table part_revision (
+  part_id text not null,
+  revision integer not null,
+  -- Printing properties of the revision as JSON.
+  metadata text not null,
+  replaced_at datetime not null,
+  primary key (part_id, revision),
+  foreign key (part_id) references part (id) on delete cascade
+);
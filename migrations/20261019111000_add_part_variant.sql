@@ -0,0 +1,4 @@
+alter table part add column variant_of text references part (id);
+alter table part add column variant_color text;
+alter table part add column variant_size text;
+create index part_variant_of_idx on part (variant_of);
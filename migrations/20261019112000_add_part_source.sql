@@ -0,0 +1,4 @@
+alter table part add column source_url text;
+alter table part add column designer text;
+alter table part add column license text;
+alter table part add column commercial_use boolean;
@@ -0,0 +1,260 @@
+//! Turns domain events into activity entries.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::activity::domain::entry::{ActivityEntry, EntityKind};
+use crate::app::actor::Actor;
+use crate::app::events::DomainEvent;
+use crate::inventory::domain::event::PartEvent;
+use crate::projects::domain::event::ProjectEvent;
+use crate::projects::domain::project::ProjectPart;
+
+/// Describes committed events as activity entries of the actor.
+pub fn entries(events: &[DomainEvent], actor: &Actor, at: DateTime<Utc>) -> Vec<ActivityEntry> {
+    events
+        .iter()
+        .map(|event| {
+            let (event, entity, entity_id, changes) = describe(event);
+            ActivityEntry {
+                seq: 0,
+                occurred_at: at,
+                actor: actor.to_string(),
+                event: event.to_string(),
+                entity,
+                entity_id,
+                changes,
+            }
+        })
+        .collect()
+}
+
+fn describe(event: &DomainEvent) -> (&'static str, EntityKind, uuid::Uuid, Value) {
+    match event {
+        DomainEvent::Project(ProjectEvent::ProjectRegistered { project, name }) => (
+            "project-registered",
+            EntityKind::Project,
+            *project,
+            json!({ "name": change(Value::Null, name) }),
+        ),
+        DomainEvent::Project(ProjectEvent::BomDefined {
+            project,
+            previous,
+            parts,
+        }) => (
+            "bom-defined",
+            EntityKind::Project,
+            *project,
+            json!({ "bom": bom_diff(previous, parts) }),
+        ),
+        DomainEvent::Project(ProjectEvent::ProgressReported {
+            project,
+            part,
+            printed,
+            total,
+        }) => (
+            "progress-reported",
+            EntityKind::Project,
+            *project,
+            json!({ "part": part, "printed": change(total - printed, total) }),
+        ),
+        DomainEvent::Project(ProjectEvent::TemplateMarked { project, template }) => (
+            "template-marked",
+            EntityKind::Project,
+            *project,
+            json!({ "template": change(!template, template) }),
+        ),
+        DomainEvent::Project(ProjectEvent::PartUpgraded {
+            project,
+            part,
+            from,
+            to,
+        }) => (
+            "part-upgraded",
+            EntityKind::Project,
+            *project,
+            json!({ "part": part, "revision": change(from, to) }),
+        ),
+        DomainEvent::Part(PartEvent::PartRegistered { part, name }) => (
+            "part-registered",
+            EntityKind::Part,
+            *part,
+            json!({ "name": change(Value::Null, name) }),
+        ),
+        DomainEvent::Part(PartEvent::PartRenamed { part, from, to }) => (
+            "part-renamed",
+            EntityKind::Part,
+            *part,
+            json!({ "name": change(from, to) }),
+        ),
+        DomainEvent::Part(PartEvent::PartDescribed {
+            part,
+            previous,
+            metadata,
+        }) => (
+            "part-described",
+            EntityKind::Part,
+            *part,
+            attribute_changes(previous, metadata),
+        ),
+        DomainEvent::Part(PartEvent::PartClassified {
+            part,
+            previous,
+            classification,
+        }) => (
+            "part-classified",
+            EntityKind::Part,
+            *part,
+            attribute_changes(previous, classification),
+        ),
+        DomainEvent::Part(PartEvent::PartAttributed {
+            part,
+            previous,
+            source,
+        }) => (
+            "part-attributed",
+            EntityKind::Part,
+            *part,
+            attribute_changes(previous, source),
+        ),
+        DomainEvent::Part(PartEvent::PartRevised { part, revision }) => (
+            "part-revised",
+            EntityKind::Part,
+            *part,
+            json!({ "revision": change(revision - 1, revision) }),
+        ),
+        DomainEvent::Part(PartEvent::VariantDefined {
+            part,
+            previous,
+            variant,
+        }) => (
+            "variant-defined",
+            EntityKind::Part,
+            *part,
+            json!({ "variant": change(previous, variant) }),
+        ),
+    }
+}
+
+/// Maps attributes with different values to their changes.
+fn attribute_changes(from: impl Serialize, to: impl Serialize) -> Value {
+    let (Value::Object(from), Value::Object(to)) = (json!(from), json!(to)) else {
+        return Value::Null;
+    };
+    let changes = to
+        .into_iter()
+        .filter_map(|(attribute, to)| {
+            let from = from.get(&attribute).cloned().unwrap_or(Value::Null);
+            (from != to).then(|| (attribute, change(from, to)))
+        })
+        .collect();
+    Value::Object(changes)
+}
+
+fn change(from: impl Serialize, to: impl Serialize) -> Value {
+    json!({ "from": from, "to": to })
+}
+
+/// Lists BOM lines with changed quantities. Added lines change from zero, removed lines to zero.
+fn bom_diff(previous: &[ProjectPart], parts: &[ProjectPart]) -> Vec<Value> {
+    let before: HashMap<_, _> = previous.iter().map(|p| (p.part(), p.quantity())).collect();
+    let after: HashMap<_, _> = parts.iter().map(|p| (p.part(), p.quantity())).collect();
+    let changed = parts
+        .iter()
+        .map(|p| {
+            (
+                p.part(),
+                before.get(&p.part()).copied().unwrap_or(0),
+                p.quantity(),
+            )
+        })
+        .chain(
+            previous
+                .iter()
+                .filter(|p| !after.contains_key(&p.part()))
+                .map(|p| (p.part(), p.quantity(), 0)),
+        )
+        .filter(|(_, from, to)| from != to);
+    changed
+        .map(|(part, from, to)| json!({ "part": part, "quantity": change(from, to) }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::inventory::domain::metadata::{Material, Minutes, PartMetadata};
+
+    #[test]
+    fn describe_bom_changes() {
+        let (bin, drawer, lid) = (Uuid::now_v7(), Uuid::now_v7(), Uuid::now_v7());
+        let event = DomainEvent::Project(ProjectEvent::BomDefined {
+            project: Uuid::now_v7(),
+            previous: vec![ProjectPart::new(bin, 5), ProjectPart::new(drawer, 9)],
+            parts: vec![ProjectPart::new(bin, 5), ProjectPart::new(lid, 2)],
+        });
+
+        let entries = entries(&[event], &Actor::named("alice"), Utc::now());
+
+        assert_eq!(entries[0].event, "bom-defined");
+        assert_eq!(entries[0].actor, "alice");
+        assert_eq!(
+            entries[0].changes,
+            json!({ "bom": [
+                { "part": lid, "quantity": { "from": 0, "to": 2 } },
+                { "part": drawer, "quantity": { "from": 9, "to": 0 } },
+            ]})
+        );
+    }
+
+    #[test]
+    fn describe_metadata_changes() {
+        let previous = PartMetadata {
+            material: Some(Material::Pla),
+            minutes: Some(Minutes::parse(90).unwrap()),
+            ..PartMetadata::default()
+        };
+        let metadata = PartMetadata {
+            material: Some(Material::Petg),
+            ..previous.clone()
+        };
+        let event = DomainEvent::Part(PartEvent::PartDescribed {
+            part: Uuid::now_v7(),
+            previous,
+            metadata,
+        });
+
+        let entries = entries(&[event], &Actor::anonymous(), Utc::now());
+
+        assert_eq!(entries[0].event, "part-described");
+        assert_eq!(
+            entries[0].changes,
+            json!({ "material": { "from": "PLA", "to": "PETG" } })
+        );
+    }
+
+    #[test]
+    fn describe_rename() {
+        let part = Uuid::now_v7();
+        let event = DomainEvent::Part(PartEvent::PartRenamed {
+            part,
+            from: "Bin".to_string(),
+            to: "Bin, small".to_string(),
+        });
+
+        let entries = entries(&[event], &Actor::anonymous(), Utc::now());
+
+        assert_eq!(entries[0].entity, EntityKind::Part);
+        assert_eq!(entries[0].entity_id, part);
+        assert_eq!(
+            entries[0].changes,
+            json!({ "name": { "from": "Bin", "to": "Bin, small" } })
+        );
+    }
+}
@@ -0,0 +1,48 @@
+//! Reads the activity log.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::activity::domain::entry::ActivityEntry;
+use crate::activity::domain::repository::ActivityRepository;
+use crate::shared::pagination::{Page, PageRequest};
+
+#[async_trait]
+pub trait ActivityService: Send + Sync {
+    /// Lists changes of a single project or part, the most recent first.
+    async fn history(
+        &self,
+        entity_id: Uuid,
+        page: PageRequest,
+    ) -> anyhow::Result<Page<ActivityEntry>>;
+
+    /// Lists all changes, the most recent first.
+    async fn feed(&self, page: PageRequest) -> anyhow::Result<Page<ActivityEntry>>;
+}
+
+pub struct DefaultActivityService {
+    activity_repo: Arc<dyn ActivityRepository>,
+}
+
+impl DefaultActivityService {
+    pub fn new(activity_repo: Arc<dyn ActivityRepository>) -> Self {
+        Self { activity_repo }
+    }
+}
+
+#[async_trait]
+impl ActivityService for DefaultActivityService {
+    async fn history(
+        &self,
+        entity_id: Uuid,
+        page: PageRequest,
+    ) -> anyhow::Result<Page<ActivityEntry>> {
+        self.activity_repo.list(Some(entity_id), page).await
+    }
+
+    async fn feed(&self, page: PageRequest) -> anyhow::Result<Page<ActivityEntry>> {
+        self.activity_repo.list(None, page).await
+    }
+}
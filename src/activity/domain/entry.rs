@@ -0,0 +1,49 @@
+//! Single entry of the activity log.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Kind of the changed entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EntityKind {
+    Project,
+    Part,
+}
+
+/// Recorded change.
+/// The `changes` payload maps changed attributes to their `from` and `to` values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActivityEntry {
+    /// Position in the log, assigned when the entry is appended.
+    pub seq: i64,
+    pub occurred_at: DateTime<Utc>,
+    pub actor: String,
+    /// Kebab-case event name, e.g., `bom-defined`.
+    pub event: String,
+    pub entity: EntityKind,
+    pub entity_id: Uuid,
+    pub changes: serde_json::Value,
+}
+
+impl EntityKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EntityKind::Project => "project",
+            EntityKind::Part => "part",
+        }
+    }
+}
+
+impl TryFrom<&str> for EntityKind {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "project" => Ok(EntityKind::Project),
+            "part" => Ok(EntityKind::Part),
+            _ => Err(anyhow::anyhow!("unknown entity kind {}", value)),
+        }
+    }
+}
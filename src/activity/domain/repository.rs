@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::shared::pagination::{Page, PageRequest};
+
+use super::entry::ActivityEntry;
+
+/// Append-only storage of activity entries.
+/// This trait must be implemented in the infrastructure layer.
+#[async_trait]
+pub trait ActivityRepository: Send + Sync {
+    /// Appends entries to the end of the log, assigning their sequence numbers.
+    async fn append(&self, entries: Vec<ActivityEntry>) -> anyhow::Result<()>;
+
+    /// Lists entries, the most recent first.
+    /// Lists entries of a single entity when the entity ID is set.
+    async fn list(
+        &self,
+        entity_id: Option<Uuid>,
+        page: PageRequest,
+    ) -> anyhow::Result<Page<ActivityEntry>>;
+}
@@ -0,0 +1,6 @@
+//! Activity keeps the append-only log of changes made in other modules.
+//!
+//! Every committed unit of work appends an entry per domain event, recording who changed what
+//! and when. The log is never updated or truncated.
+pub mod app;
+pub mod domain;
@@ -0,0 +1,89 @@
+//! The user performing changes.
+//!
+//! Interfaces run operations on behalf of an actor with [`act_as`]. The server takes the actor
+//! from the `X-Actor` request header. Operations started outside of [`act_as`] are attributed to
+//! the user running the process.
+
+use std::fmt::Display;
+use std::future::Future;
+
+use serde::{Deserialize, Serialize};
+
+tokio::task_local! {
+    static ACTOR: Actor;
+}
+
+/// Longest accepted actor name. Longer names are truncated.
+const MAX_ACTOR_LENGTH: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Actor(String);
+
+impl Actor {
+    /// Actor of the requests that did not introduce themselves.
+    pub fn anonymous() -> Self {
+        Self("anonymous".to_string())
+    }
+
+    /// Creates an actor from a user-provided name.
+    /// Blank names produce the anonymous actor.
+    pub fn named(name: &str) -> Self {
+        let name: String = name.trim().chars().take(MAX_ACTOR_LENGTH).collect();
+        if name.is_empty() {
+            Self::anonymous()
+        } else {
+            Self(name)
+        }
+    }
+
+    /// The user running the process. Configured with the `PRINTTABLES_ACTOR` variable and
+    /// defaults to the OS user name.
+    pub fn from_env() -> Self {
+        ["PRINTTABLES_ACTOR", "USER", "USERNAME"]
+            .iter()
+            .find_map(|var| std::env::var(var).ok())
+            .map(|name| Self::named(&name))
+            .unwrap_or_else(Self::anonymous)
+    }
+
+    /// The actor of the running operation.
+    pub fn current() -> Self {
+        ACTOR
+            .try_with(Actor::clone)
+            .unwrap_or_else(|_| Self::from_env())
+    }
+}
+
+impl AsRef<str> for Actor {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for Actor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Runs the operation on behalf of the actor.
+pub async fn act_as<F: Future>(actor: Actor, operation: F) -> F::Output {
+    ACTOR.scope(actor, operation).await
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn run_as_actor() {
+        let actor = act_as(Actor::named(" alice "), async { Actor::current() }).await;
+        assert_eq!(actor.as_ref(), "alice");
+    }
+
+    #[test]
+    fn blank_name_is_anonymous() {
+        assert_eq!(Actor::named("  "), Actor::anonymous());
+    }
+}
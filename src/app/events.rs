@@ -0,0 +1,72 @@
+//! In-process domain events.
+//!
+//! Aggregates record events while they change. Events are dispatched to subscribers after the
+//! unit of work storing the aggregates is committed, so subscribers never observe changes that
+//! were rolled back. Modules react to each other's changes by subscribing to events instead of
+//! calling each other directly.
+
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use tracing::error;
+
+use crate::inventory::domain::event::PartEvent;
+use crate::projects::domain::event::ProjectEvent;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DomainEvent {
+    Project(ProjectEvent),
+    Part(PartEvent),
+}
+
+impl From<ProjectEvent> for DomainEvent {
+    fn from(value: ProjectEvent) -> Self {
+        DomainEvent::Project(value)
+    }
+}
+
+impl From<PartEvent> for DomainEvent {
+    fn from(value: PartEvent) -> Self {
+        DomainEvent::Part(value)
+    }
+}
+
+#[async_trait]
+pub trait EventSubscriber: Send + Sync {
+    /// Handles the committed event.
+    /// Errors are logged and do not affect other subscribers, as the change is already stored.
+    async fn on_event(&self, event: &DomainEvent) -> anyhow::Result<()>;
+}
+
+/// Delivers committed events to subscribers in the order of subscription.
+#[derive(Clone, Default)]
+pub struct EventBus {
+    subscribers: Arc<RwLock<Vec<Arc<dyn EventSubscriber>>>>,
+}
+
+impl EventBus {
+    pub fn subscribe(&self, subscriber: Arc<dyn EventSubscriber>) {
+        self.subscribers
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(subscriber);
+    }
+
+    pub async fn dispatch(&self, events: Vec<DomainEvent>) {
+        if events.is_empty() {
+            return;
+        }
+        let subscribers = self
+            .subscribers
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        for event in &events {
+            for subscriber in &subscribers {
+                if let Err(e) = subscriber.on_event(event).await {
+                    error!("event subscriber failed to handle {:?}: {:?}", event, e);
+                }
+            }
+        }
+    }
+}
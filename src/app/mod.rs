@@ -0,0 +1,4 @@
+//! Application layer shared by the inventory and projects subdomains.
+pub mod actor;
+pub mod events;
+pub mod unit_of_work;
@@ -0,0 +1,310 @@
+//! Unit of work groups repository changes into a single atomic transaction.
+//!
+//! Application services begin a unit of work, make changes through repositories it provides and
+//! commit it. A unit of work dropped without commit is rolled back, so returning early with an
+//! error never leaves partial changes behind.
+
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::activity::app::record;
+use crate::activity::domain::repository::ActivityRepository;
+use crate::app::actor::Actor;
+use crate::app::events::{DomainEvent, EventBus};
+use crate::filament::domain::repository::SpoolRepository;
+use crate::inventory::domain::classification::PartFilter;
+use crate::inventory::domain::file_repository::PartFileRepository;
+use crate::inventory::domain::name::Name;
+use crate::inventory::domain::part::{Part, PartId};
+use crate::inventory::domain::part_repository::{PartRepository, PartRevisionRepository};
+use crate::projects::app::revisions;
+use crate::projects::domain::project::{Project, ProjectId};
+use crate::projects::domain::repository::{BomRevisionRepository, ProjectRepository};
+
+#[async_trait]
+pub trait UnitOfWork: Send + Sync {
+    /// Projects repository bound to this unit of work.
+    fn projects(&self) -> Arc<dyn ProjectRepository>;
+
+    /// Parts repository bound to this unit of work.
+    fn parts(&self) -> Arc<dyn PartRepository>;
+
+    /// Activity log bound to this unit of work.
+    fn activity(&self) -> Arc<dyn ActivityRepository>;
+
+    /// BOM revisions bound to this unit of work.
+    fn bom_revisions(&self) -> Arc<dyn BomRevisionRepository>;
+
+    /// Filament spools bound to this unit of work.
+    fn spools(&self) -> Arc<dyn SpoolRepository>;
+
+    /// Metadata of part files bound to this unit of work.
+    fn files(&self) -> Arc<dyn PartFileRepository>;
+
+    /// Replaced part revisions bound to this unit of work.
+    fn part_revisions(&self) -> Arc<dyn PartRevisionRepository>;
+
+    /// Applies all changes made through the unit of work repositories.
+    async fn commit(self: Box<Self>) -> anyhow::Result<()>;
+
+    /// Discards all changes made through the unit of work repositories.
+    async fn rollback(self: Box<Self>) -> anyhow::Result<()>;
+}
+
+/// Starts units of work. This trait must be implemented in the infrastructure layer.
+#[async_trait]
+pub trait UnitOfWorkFactory: Send + Sync {
+    async fn begin(&self) -> anyhow::Result<Box<dyn UnitOfWork>>;
+}
+
+/// Collects events of aggregates stored within units of work.
+/// Events are appended to the activity log and BOM changes to BOM revisions in the same
+/// transaction. Events are dispatched to subscribers after commit.
+pub struct DispatchingUnitOfWorkFactory {
+    units: Arc<dyn UnitOfWorkFactory>,
+    events: EventBus,
+}
+
+impl DispatchingUnitOfWorkFactory {
+    pub fn new(units: Arc<dyn UnitOfWorkFactory>, events: EventBus) -> Self {
+        Self { units, events }
+    }
+}
+
+#[async_trait]
+impl UnitOfWorkFactory for DispatchingUnitOfWorkFactory {
+    async fn begin(&self) -> anyhow::Result<Box<dyn UnitOfWork>> {
+        let inner = self.units.begin().await?;
+        Ok(Box::new(DispatchingUnitOfWork {
+            inner,
+            bus: self.events.clone(),
+            recorded: Arc::default(),
+        }))
+    }
+}
+
+type RecordedEvents = Arc<Mutex<Vec<DomainEvent>>>;
+
+struct DispatchingUnitOfWork {
+    inner: Box<dyn UnitOfWork>,
+    bus: EventBus,
+    recorded: RecordedEvents,
+}
+
+#[async_trait]
+impl UnitOfWork for DispatchingUnitOfWork {
+    fn projects(&self) -> Arc<dyn ProjectRepository> {
+        Arc::new(RecordingProjectRepository {
+            inner: self.inner.projects(),
+            recorded: Arc::clone(&self.recorded),
+        })
+    }
+
+    fn parts(&self) -> Arc<dyn PartRepository> {
+        Arc::new(RecordingPartRepository {
+            inner: self.inner.parts(),
+            recorded: Arc::clone(&self.recorded),
+        })
+    }
+
+    fn activity(&self) -> Arc<dyn ActivityRepository> {
+        self.inner.activity()
+    }
+
+    fn bom_revisions(&self) -> Arc<dyn BomRevisionRepository> {
+        self.inner.bom_revisions()
+    }
+
+    fn spools(&self) -> Arc<dyn SpoolRepository> {
+        self.inner.spools()
+    }
+
+    fn files(&self) -> Arc<dyn PartFileRepository> {
+        self.inner.files()
+    }
+
+    fn part_revisions(&self) -> Arc<dyn PartRevisionRepository> {
+        self.inner.part_revisions()
+    }
+
+    async fn commit(self: Box<Self>) -> anyhow::Result<()> {
+        let events = std::mem::take(&mut *self.recorded.lock().unwrap_or_else(|e| e.into_inner()));
+        if !events.is_empty() {
+            let (actor, now) = (Actor::current(), Utc::now());
+            self.inner
+                .activity()
+                .append(record::entries(&events, &actor, now))
+                .await?;
+            let bom_revisions = self.inner.bom_revisions();
+            for revision in revisions::revisions(&events, &actor, now) {
+                bom_revisions.append(revision).await?;
+            }
+        }
+        self.inner.commit().await?;
+        self.bus.dispatch(events).await;
+        Ok(())
+    }
+
+    async fn rollback(self: Box<Self>) -> anyhow::Result<()> {
+        self.inner.rollback().await
+    }
+}
+
+fn record(recorded: &RecordedEvents, events: Vec<impl Into<DomainEvent>>) {
+    recorded
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .extend(events.into_iter().map(Into::into));
+}
+
+struct RecordingProjectRepository {
+    inner: Arc<dyn ProjectRepository>,
+    recorded: RecordedEvents,
+}
+
+#[async_trait]
+impl ProjectRepository for RecordingProjectRepository {
+    async fn create(&self, mut project: Project) -> anyhow::Result<ProjectId> {
+        let events = project.take_events();
+        let id = self.inner.create(project).await?;
+        record(&self.recorded, events);
+        Ok(id)
+    }
+
+    async fn find_by_id(&self, id: ProjectId) -> anyhow::Result<Option<Project>> {
+        self.inner.find_by_id(id).await
+    }
+
+    async fn update(&self, mut project: Project) -> anyhow::Result<()> {
+        let events = project.take_events();
+        self.inner.update(project).await?;
+        record(&self.recorded, events);
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct RecordingPartRepository {
+    inner: Arc<dyn PartRepository>,
+    recorded: RecordedEvents,
+}
+
+#[async_trait]
+impl PartRepository for RecordingPartRepository {
+    async fn insert(&self, mut part: Part) -> anyhow::Result<()> {
+        let events = part.take_events();
+        self.inner.insert(part).await?;
+        record(&self.recorded, events);
+        Ok(())
+    }
+
+    async fn update(&self, mut part: Part) -> anyhow::Result<()> {
+        let events = part.take_events();
+        self.inner.update(part).await?;
+        record(&self.recorded, events);
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: PartId) -> anyhow::Result<Option<Part>> {
+        self.inner.find_by_id(id).await
+    }
+
+    async fn find_by_name(&self, name: &Name) -> anyhow::Result<Option<Part>> {
+        self.inner.find_by_name(name).await
+    }
+
+    async fn list(&self, filter: &PartFilter) -> anyhow::Result<Vec<Part>> {
+        self.inner.list(filter).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::app::actor::act_as;
+    use crate::app::events::EventSubscriber;
+    use crate::infra::memory::unit_of_work::InMemoryUnitOfWorkFactory;
+    use crate::infra::memory::MemoryStorage;
+    use crate::inventory::domain::event::PartEvent;
+    use crate::shared::pagination::PageRequest;
+
+    #[derive(Default)]
+    struct CollectingSubscriber {
+        events: Mutex<Vec<DomainEvent>>,
+    }
+
+    #[async_trait]
+    impl EventSubscriber for CollectingSubscriber {
+        async fn on_event(&self, event: &DomainEvent) -> anyhow::Result<()> {
+            self.events.lock().unwrap().push(event.clone());
+            Ok(())
+        }
+    }
+
+    fn units() -> (DispatchingUnitOfWorkFactory, Arc<CollectingSubscriber>) {
+        let events = EventBus::default();
+        let subscriber = Arc::new(CollectingSubscriber::default());
+        events.subscribe(subscriber.clone());
+        let units = DispatchingUnitOfWorkFactory::new(
+            Arc::new(InMemoryUnitOfWorkFactory::new(MemoryStorage::new())),
+            events,
+        );
+        (units, subscriber)
+    }
+
+    #[tokio::test]
+    async fn dispatch_events_after_commit() {
+        let (units, subscriber) = units();
+        let part = Part::new(Name::parse("Drawer").unwrap());
+        let id = part.id();
+
+        let uow = units.begin().await.unwrap();
+        uow.parts().insert(part).await.unwrap();
+        assert!(subscriber.events.lock().unwrap().is_empty());
+        uow.commit().await.unwrap();
+
+        assert_eq!(
+            *subscriber.events.lock().unwrap(),
+            vec![DomainEvent::Part(PartEvent::PartRegistered {
+                part: id,
+                name: "Drawer".to_string(),
+            })]
+        );
+    }
+
+    #[tokio::test]
+    async fn log_activity_of_actor() {
+        let (units, _) = units();
+
+        let uow = units.begin().await.unwrap();
+        uow.parts()
+            .insert(Part::new(Name::parse("Drawer").unwrap()))
+            .await
+            .unwrap();
+        act_as(Actor::named("alice"), uow.commit()).await.unwrap();
+
+        let uow = units.begin().await.unwrap();
+        let log = uow.activity().list(None, PageRequest::default()).await;
+        let entries = log.unwrap().items;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].event, "part-registered");
+        assert_eq!(entries[0].actor, "alice");
+    }
+
+    #[tokio::test]
+    async fn discard_events_on_rollback() {
+        let (units, subscriber) = units();
+
+        let uow = units.begin().await.unwrap();
+        uow.parts()
+            .insert(Part::new(Name::parse("Drawer").unwrap()))
+            .await
+            .unwrap();
+        uow.rollback().await.unwrap();
+
+        assert!(subscriber.events.lock().unwrap().is_empty());
+    }
+}
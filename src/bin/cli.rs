@@ -0,0 +1,44 @@
+use std::process::ExitCode;
+
+use clap::Parser;
+use printtables::cli::{self, Cli, Command};
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Cli::parse();
+    let command = match args.command {
+        Command::Services(command) => command,
+        Command::Backup { target } => {
+            let mut stdout = std::io::stdout().lock();
+            return match cli::backup(args.server.as_deref(), &target, args.output, &mut stdout)
+                .await
+            {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(e) => {
+                    eprintln!("error: {}", e);
+                    e.exit_code()
+                }
+            };
+        }
+        Command::Completions { shell } => {
+            cli::print_completions(shell, &mut std::io::stdout());
+            return ExitCode::SUCCESS;
+        }
+    };
+
+    let services = match cli::connect(args.server.as_deref()).await {
+        Ok(services) => services,
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut stdout = std::io::stdout().lock();
+    match cli::execute(command, args.output, &services, &mut stdout).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            e.exit_code()
+        }
+    }
+}
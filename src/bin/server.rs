@@ -1,23 +1,40 @@
+use clap::Parser;
+use printtables::infra::files::file_store_dir;
+use printtables::infra::sqlx::{database_pool, database_url};
 use printtables::server::startup::start_server;
-use sqlx::SqlitePool;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use tokio::net::TcpListener;
 
 const DEFAULT_PORT: u16 = 4229;
 
+/// REST API server of printtables.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Args {
+    /// Keep all data in memory and start with sample projects.
+    /// Nothing is written to the database.
+    #[cfg(feature = "memory")]
+    #[arg(long)]
+    demo: bool,
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    // Without in-memory repositories the arguments only provide help and version.
+    #[cfg_attr(not(feature = "memory"), allow(unused_variables))]
+    let args = Args::parse();
     let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), DEFAULT_PORT);
     let listener = TcpListener::bind(addr).await?;
 
-    let db_pool = dev_database_pool().await?;
+    #[cfg(feature = "memory")]
+    if args.demo {
+        let services = printtables::infra::services::Services::in_memory();
+        printtables::infra::memory::demo::seed(&services).await?;
+        return printtables::server::startup::serve(listener, services).await;
+    }
 
-    start_server(listener, db_pool).await
-}
+    let db_pool = database_pool().await?;
+    let files_dir = file_store_dir(&database_url())?;
 
-async fn dev_database_pool() -> anyhow::Result<SqlitePool> {
-    let db_url = dotenvy::var("DATABASE_URL").unwrap_or_else(|_| "sqlite:dev.db".to_string());
-    SqlitePool::connect(&db_url)
-        .await
-        .map_err(anyhow::Error::new)
+    start_server(listener, db_pool, files_dir).await
 }
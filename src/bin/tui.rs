@@ -1,3 +1,23 @@
-fn main() {
-    println!("printtables tui");
+use clap::Parser;
+use printtables::infra::services::Services;
+
+/// Terminal interface of printtables.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Args {
+    /// URL of a running printtables server, e.g. http://raspberrypi:4229.
+    /// The local database is used when the server is not set.
+    #[arg(long, env = "PRINTTABLES_SERVER")]
+    server: Option<String>,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let services = match args.server {
+        Some(server) => Services::remote(&server)?,
+        None => Services::local().await?,
+    };
+
+    printtables::tui::run(services).await
 }
@@ -0,0 +1,130 @@
+//! Errors of command-line operations and their exit codes.
+
+use std::process::ExitCode;
+
+use thiserror::Error;
+
+use crate::filament::app::service::FilamentError;
+use crate::infra::http::RemoteError;
+use crate::inventory::app::files::PartFileError;
+use crate::inventory::app::service::InventoryError;
+use crate::projects::app::import::BomImportError;
+use crate::projects::app::model_import::ModelImportError;
+use crate::projects::app::packing::PackingError;
+use crate::projects::app::service::ProjectError;
+use crate::shared::validation::error::{describe, ValidationError};
+
+/// Exit code of invalid input rejected by validation.
+pub const EXIT_INVALID: u8 = 3;
+/// Exit code of operations on missing projects, parts, files or spools.
+pub const EXIT_NOT_FOUND: u8 = 4;
+
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error("{}", describe(.0))]
+    Invalid(Vec<ValidationError>),
+    #[error("{0} not found")]
+    NotFound(&'static str),
+    #[error(transparent)]
+    General(anyhow::Error),
+}
+
+impl CliError {
+    /// Exit codes are `3` for invalid input, `4` for missing entities and `1` for other errors.
+    /// Command-line parsing errors exit with `2`.
+    pub fn exit_code(&self) -> ExitCode {
+        match self {
+            CliError::Invalid(_) => ExitCode::from(EXIT_INVALID),
+            CliError::NotFound(_) => ExitCode::from(EXIT_NOT_FOUND),
+            CliError::General(_) => ExitCode::FAILURE,
+        }
+    }
+}
+
+impl From<anyhow::Error> for CliError {
+    fn from(value: anyhow::Error) -> Self {
+        match value.downcast::<RemoteError>() {
+            Ok(RemoteError::Rejected(errors)) => CliError::Invalid(errors),
+            Ok(e) => CliError::General(e.into()),
+            Err(e) => CliError::General(e),
+        }
+    }
+}
+
+impl From<ValidationError> for CliError {
+    fn from(value: ValidationError) -> Self {
+        CliError::Invalid(vec![value])
+    }
+}
+
+impl From<ProjectError> for CliError {
+    fn from(value: ProjectError) -> Self {
+        match value {
+            ProjectError::MissingProject => CliError::NotFound("project"),
+            ProjectError::ValidationFailed(errors) => CliError::Invalid(errors),
+            ProjectError::GeneralError(e) => CliError::from(e),
+        }
+    }
+}
+
+impl From<InventoryError> for CliError {
+    fn from(value: InventoryError) -> Self {
+        match value {
+            InventoryError::MissingPart => CliError::NotFound("part"),
+            InventoryError::ValidationFailed(errors) => CliError::Invalid(errors),
+            InventoryError::GeneralError(e) => CliError::from(e),
+        }
+    }
+}
+
+impl From<PartFileError> for CliError {
+    fn from(value: PartFileError) -> Self {
+        match value {
+            PartFileError::MissingPart => CliError::NotFound("part"),
+            PartFileError::MissingFile => CliError::NotFound("file"),
+            PartFileError::ValidationFailed(errors) => CliError::Invalid(errors),
+            PartFileError::GeneralError(e) => CliError::from(e),
+        }
+    }
+}
+
+impl From<BomImportError> for CliError {
+    fn from(value: BomImportError) -> Self {
+        match value {
+            BomImportError::MissingProject => CliError::NotFound("project"),
+            BomImportError::InvalidBom(errors) => CliError::Invalid(errors),
+            BomImportError::GeneralError(e) => CliError::from(e),
+        }
+    }
+}
+
+impl From<ModelImportError> for CliError {
+    fn from(value: ModelImportError) -> Self {
+        match value {
+            ModelImportError::InvalidModel(errors) => CliError::Invalid(errors),
+            ModelImportError::GeneralError(e) => CliError::from(e),
+        }
+    }
+}
+
+impl From<PackingError> for CliError {
+    fn from(value: PackingError) -> Self {
+        match value {
+            PackingError::MissingPart => CliError::NotFound("part"),
+            PackingError::MissingProject => CliError::NotFound("project"),
+            PackingError::ValidationFailed(errors) => CliError::Invalid(errors),
+            PackingError::GeneralError(e) => CliError::from(e),
+        }
+    }
+}
+
+impl From<FilamentError> for CliError {
+    fn from(value: FilamentError) -> Self {
+        match value {
+            FilamentError::MissingSpool => CliError::NotFound("spool"),
+            FilamentError::MissingProject => CliError::NotFound("project"),
+            FilamentError::ValidationFailed(errors) => CliError::Invalid(errors),
+            FilamentError::GeneralError(e) => CliError::from(e),
+        }
+    }
+}
@@ -0,0 +1,968 @@
+//! Scriptable command-line interface of printtables.
+//!
+//! Subcommands mirror the REST API. Results are printed as tables or JSON, and failures are
+//! reported with distinct exit codes, see [`error::CliError::exit_code`].
+pub mod error;
+pub mod output;
+
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+use uuid::Uuid;
+
+use crate::filament::domain::spool::{RawSpool, Spool, SpoolId};
+use crate::infra::backup;
+use crate::infra::files::file_store_dir;
+use crate::infra::services::Services;
+use crate::infra::sqlx::{database_pool, database_url};
+use crate::inventory::domain::classification::{
+    Category, Classification, PartFilter, RawClassification, Tag,
+};
+use crate::inventory::domain::file::{FileName, PartFileId};
+use crate::inventory::domain::metadata::{
+    Dimensions, Grams, PartMetadata, RawDimensions, RawPartMetadata,
+};
+use crate::inventory::domain::name::Name as PartName;
+use crate::inventory::domain::part::PartId;
+use crate::inventory::domain::source::{PartSource, RawPartSource};
+use crate::inventory::domain::thumbnail::ThumbnailFormat;
+use crate::inventory::domain::variant::{RawVariantAttributes, Variant, VariantAttributes};
+use crate::projects::app::import::BomImportOptions;
+use crate::projects::app::model_import::ModelImportOptions;
+use crate::projects::app::packing::PackingOptions;
+use crate::projects::domain::multiplier::Multiplier;
+use crate::projects::domain::name::Name as ProjectName;
+use crate::projects::domain::project::{ProjectId, ProjectPart};
+use crate::projects::domain::quantity::Quantity;
+use crate::projects::view::demand::DemandGrouping;
+use crate::projects::view::project::BomGrouping;
+use crate::settings::domain::costs::{CostSettings, RawCostSettings};
+use crate::shared::validation::error::ValidationError;
+use crate::shared::validation::validator::CollectingValidator;
+use error::CliError;
+use output::{OutputFormat, Printer};
+
+/// Track multipart 3D-printing projects.
+#[derive(Debug, Parser)]
+#[command(name = "printtables", version, about)]
+pub struct Cli {
+    /// URL of a running printtables server, e.g. http://raspberrypi:4229.
+    /// The local database is used when the server is not set.
+    #[arg(long, global = true, env = "PRINTTABLES_SERVER")]
+    pub server: Option<String>,
+
+    /// Output format.
+    #[arg(long, short, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    pub output: OutputFormat,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    #[command(flatten)]
+    Services(ServiceCommand),
+    /// Copy the local database and part files into the directory.
+    /// Backups are made on the machine keeping the database, so the server must not be set.
+    Backup { target: PathBuf },
+    /// Print shell completion script.
+    Completions {
+        /// Target shell.
+        shell: Shell,
+    },
+}
+
+/// Commands executed with services of the server or of the local database.
+#[derive(Debug, Subcommand)]
+pub enum ServiceCommand {
+    /// Manage projects.
+    #[command(subcommand)]
+    Project(ProjectCommand),
+    /// Manage inventory parts.
+    #[command(subcommand)]
+    Part(PartCommand),
+    /// Track printing progress.
+    #[command(subcommand)]
+    Progress(ProgressCommand),
+    /// Manage filament spools.
+    #[command(subcommand)]
+    Spool(SpoolCommand),
+    /// Manage shared settings.
+    #[command(subcommand)]
+    Settings(SettingsCommand),
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ProjectCommand {
+    /// Register a new project and print its ID.
+    Create { name: String },
+    /// List all projects.
+    List,
+    /// Show project BOM and progress.
+    Show {
+        project: ProjectId,
+        /// Group BOM lines by the category of their parts or group variants by parent part.
+        #[arg(long, value_name = "category|parent", value_parser = BomGrouping::parse)]
+        group_by: Option<BomGrouping>,
+    },
+    /// Copy the project with its BOM and print the ID of the copy.
+    Duplicate {
+        project: ProjectId,
+        /// Name of the copy. Defaults to the project name with the ` (copy)` suffix.
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Mark the project as a template of other projects.
+    Template {
+        project: ProjectId,
+        /// Turn the template back into a regular project.
+        #[arg(long)]
+        unset: bool,
+    },
+    /// Create a project from the template and print its ID.
+    Instantiate {
+        template: ProjectId,
+        /// Multiplier applied to every BOM quantity.
+        #[arg(long, short = 'x', default_value_t = 1)]
+        times: u32,
+        /// Name of the project. Defaults to the template name with the multiplier.
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Propose plates for parts left to print.
+    Plates {
+        project: ProjectId,
+        /// Printable area of the bed in millimeters, e.g., 250x210.
+        #[arg(long, value_name = "WIDTHxDEPTH")]
+        bed: String,
+        /// Gap between parts in millimeters.
+        #[arg(long)]
+        spacing: Option<f64>,
+    },
+    /// List projects using older revisions of parts than the latest ones.
+    Outdated {
+        /// Keep projects using an older revision of the part. Part ID or part name.
+        #[arg(long)]
+        part: Option<String>,
+    },
+    /// Pin the latest revisions of parts in the project BOM.
+    Upgrade {
+        project: ProjectId,
+        /// Upgrade only the part. Part ID or part name.
+        #[arg(long)]
+        part: Option<String>,
+    },
+    /// List copies of parts needed by all regular projects.
+    Demand {
+        /// Report every variant on its own, or add variants up under their parent part.
+        #[arg(
+            long,
+            value_name = "variant|parent",
+            default_value = "variant",
+            value_parser = DemandGrouping::parse
+        )]
+        group_by: DemandGrouping,
+    },
+    /// List licenses of parts in the project BOM and flag parts forbidding commercial use.
+    Licenses { project: ProjectId },
+    /// Manage project BOM.
+    #[command(subcommand)]
+    Bom(BomCommand),
+}
+
+#[derive(Debug, Subcommand)]
+pub enum BomCommand {
+    /// Replace the project BOM.
+    Set {
+        project: ProjectId,
+        /// BOM lines. PART is either a part ID or a part name.
+        #[arg(value_name = "PART=QUANTITY", required_unless_present = "csv")]
+        lines: Vec<String>,
+        /// Import BOM from CSV with `part name,quantity` rows. Use `-` to read from stdin.
+        #[arg(long, conflicts_with = "lines")]
+        csv: Option<PathBuf>,
+        /// Show changes without applying them.
+        #[arg(long, conflicts_with = "lines")]
+        dry_run: bool,
+        /// Register parts missing in the inventory.
+        #[arg(long, conflicts_with = "lines")]
+        register_missing: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum PartCommand {
+    /// Register a new part and print its ID.
+    Add { name: String },
+    /// List inventory parts.
+    List {
+        /// Keep parts with the tag.
+        #[arg(long)]
+        tag: Option<String>,
+        /// Keep parts of the category and of categories below it, e.g., "Storage › Gridfinity".
+        #[arg(long)]
+        category: Option<String>,
+        /// Keep variants of the part. Part ID or part name.
+        #[arg(long, value_name = "PART")]
+        variant_of: Option<String>,
+    },
+    /// Register a variant of a part and print its ID.
+    /// The variant takes printing properties, tags, the category and the license of the part.
+    Variant {
+        /// Parent part ID or part name.
+        part: String,
+        /// Color of the variant. Replaces the filament color of the part.
+        #[arg(long, required_unless_present = "size")]
+        color: Option<String>,
+        /// Size of the variant, e.g., "3 units".
+        #[arg(long)]
+        size: Option<String>,
+        /// Name of the variant. Defaults to the part name with the color and the size.
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Make a part a variant of another part.
+    Link {
+        /// Part ID or part name.
+        part: String,
+        /// Parent part ID or part name.
+        #[arg(long, required_unless_present = "unset")]
+        parent: Option<String>,
+        /// Color of the variant. Replaces the filament color of the part.
+        #[arg(long)]
+        color: Option<String>,
+        /// Size of the variant, e.g., "3 units".
+        #[arg(long)]
+        size: Option<String>,
+        /// Turn the variant back into a standalone part.
+        #[arg(long, conflicts_with_all = ["parent", "color", "size"])]
+        unset: bool,
+    },
+    /// Set tags and the category of a part, replacing the ones set before.
+    Classify {
+        /// Part ID or part name.
+        part: String,
+        /// Category path, e.g., "Storage › Gridfinity › Bins" or Storage/Gridfinity/Bins.
+        #[arg(long)]
+        category: Option<String>,
+        /// Tag of the part. Repeat to set several tags.
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+    },
+    /// Set printing properties of a part. Properties not given are kept.
+    Describe {
+        /// Part ID or part name.
+        part: String,
+        /// Filament material, e.g., PLA or PETG.
+        #[arg(long)]
+        material: Option<String>,
+        #[arg(long)]
+        color: Option<String>,
+        /// Estimated filament weight in grams.
+        #[arg(long)]
+        grams: Option<f64>,
+        /// Estimated print time in minutes.
+        #[arg(long)]
+        minutes: Option<u32>,
+        /// Bounding box in millimeters, e.g., 120x80x35.
+        #[arg(long, value_name = "XxYxZ")]
+        size: Option<String>,
+        #[arg(long)]
+        notes: Option<String>,
+    },
+    /// Set where a part comes from and its license. Properties not given are kept, and empty
+    /// values clear them.
+    Source {
+        /// Part ID or part name.
+        part: String,
+        /// Web page of the model, e.g., `https://www.printables.com/model/1234`.
+        #[arg(long)]
+        url: Option<String>,
+        #[arg(long)]
+        designer: Option<String>,
+        /// SPDX license identifier, e.g., CC-BY-NC-SA-4.0.
+        #[arg(long)]
+        license: Option<String>,
+        /// Whether prints may be sold regardless of the license.
+        #[arg(long, value_name = "true|false")]
+        commercial: Option<bool>,
+        /// Clear the commercial use flag, so the license tells whether prints may be sold.
+        #[arg(long, conflicts_with = "commercial")]
+        clear_commercial: bool,
+    },
+    /// Attach an STL, 3MF, STEP or G-code file to a part.
+    Attach {
+        /// Part ID or part name.
+        part: String,
+        file: PathBuf,
+    },
+    /// Check whether a part fits the bed and how many copies fit on one plate.
+    Pack {
+        /// Part ID or part name.
+        part: String,
+        /// Printable area of the bed in millimeters, e.g., 250x210.
+        #[arg(long, value_name = "WIDTHxDEPTH")]
+        bed: String,
+        /// Gap between copies in millimeters.
+        #[arg(long)]
+        spacing: Option<f64>,
+    },
+    /// Register parts for objects of a 3MF model, e.g., one downloaded from Printables.
+    Import {
+        file: PathBuf,
+        /// Create a project with the given name and a BOM counting copies of every object.
+        #[arg(long)]
+        project: Option<String>,
+    },
+    /// Start a new revision of a part, e.g., when its model is updated.
+    /// Files attached afterwards belong to the new revision.
+    Revise {
+        /// Part ID or part name.
+        part: String,
+    },
+    /// List revisions of a part, the latest first.
+    Revisions {
+        /// Part ID or part name.
+        part: String,
+    },
+    /// List files attached to a part.
+    Files {
+        /// Part ID or part name.
+        part: String,
+    },
+    /// Save a file attached to a part.
+    Download {
+        /// Part ID or part name.
+        part: String,
+        file: PartFileId,
+        /// Where to save the file. Defaults to the original file name in the working directory.
+        target: Option<PathBuf>,
+    },
+    /// Save a thumbnail rendered from the STL mesh attached to a part last.
+    Thumbnail {
+        /// Part ID or part name.
+        part: String,
+        /// Where to save the image. Defaults to the part ID with the format extension.
+        target: Option<PathBuf>,
+        /// Image format, png or svg.
+        #[arg(long, default_value_t = ThumbnailFormat::Png, value_parser = ThumbnailFormat::parse)]
+        format: ThumbnailFormat,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ProgressCommand {
+    /// Report printed parts of the project.
+    Report {
+        project: ProjectId,
+        /// Part ID or part name.
+        part: String,
+        /// Number of parts printed since the last report.
+        #[arg(default_value_t = 1)]
+        printed: u32,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum SpoolCommand {
+    /// Put a new spool on the shelf and print its ID.
+    Add {
+        /// Filament material, e.g., PLA or PETG.
+        #[arg(long)]
+        material: String,
+        #[arg(long)]
+        color: String,
+        #[arg(long)]
+        brand: Option<String>,
+        /// Net filament weight of a new spool in grams.
+        #[arg(long)]
+        grams: f64,
+        /// Filament left on a used spool in grams. Defaults to the net weight.
+        #[arg(long)]
+        remaining: Option<f64>,
+        /// Price paid for the spool.
+        #[arg(long)]
+        cost: Option<f64>,
+    },
+    /// List spools on the shelf.
+    List,
+    /// Take filament used by the project off the spool.
+    Consume {
+        spool: SpoolId,
+        project: ProjectId,
+        /// Used filament in grams.
+        grams: f64,
+    },
+    /// List filament the project needs but the shelf lacks.
+    Shortages { project: ProjectId },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum SettingsCommand {
+    /// Show prices used to calculate costs. Given prices are changed, others are kept.
+    Costs {
+        /// Filament price per kilogram.
+        #[arg(long)]
+        filament_price: Option<f64>,
+        /// Power draw of the printer in watts.
+        #[arg(long)]
+        power: Option<f64>,
+        /// Electricity price per kWh.
+        #[arg(long)]
+        electricity_price: Option<f64>,
+        /// Wear of the printer per printing hour.
+        #[arg(long)]
+        machine_wear: Option<f64>,
+    },
+}
+
+/// Prints completion script of the shell.
+pub fn print_completions(shell: Shell, out: &mut impl Write) {
+    let mut command = Cli::command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, out);
+}
+
+/// Executes the command and prints results.
+pub async fn execute(
+    command: ServiceCommand,
+    format: OutputFormat,
+    services: &Services,
+    out: &mut impl Write,
+) -> Result<(), CliError> {
+    let mut printer = Printer::new(format, out);
+    match command {
+        ServiceCommand::Project(ProjectCommand::Create { name }) => {
+            let name = ProjectName::parse(name)?;
+            let id = services.projects.register_project(name).await?;
+            printer.created(id)?;
+        }
+        ServiceCommand::Project(ProjectCommand::List) => {
+            let projects = services.projects.list_projects().await?;
+            printer.projects(&projects)?;
+        }
+        ServiceCommand::Project(ProjectCommand::Show { project, group_by }) => {
+            let mut project = services.projects.view_project(project).await?;
+            if let Some(grouping) = group_by {
+                project = project.grouped(grouping);
+            }
+            printer.project(&project)?;
+        }
+        ServiceCommand::Project(ProjectCommand::Duplicate { project, name }) => {
+            let name = name.map(ProjectName::parse).transpose()?;
+            let id = services.projects.duplicate_project(project, name).await?;
+            printer.created(id)?;
+        }
+        ServiceCommand::Project(ProjectCommand::Template { project, unset }) => {
+            services.projects.mark_template(project, !unset).await?;
+            let project = services.projects.view_project(project).await?;
+            printer.project(&project)?;
+        }
+        ServiceCommand::Project(ProjectCommand::Instantiate {
+            template,
+            times,
+            name,
+        }) => {
+            let multiplier = Multiplier::parse(times)?;
+            let name = name.map(ProjectName::parse).transpose()?;
+            let id = services
+                .projects
+                .instantiate_template(template, multiplier, name)
+                .await?;
+            printer.created(id)?;
+        }
+        ServiceCommand::Project(ProjectCommand::Plates {
+            project,
+            bed,
+            spacing,
+        }) => {
+            let options = PackingOptions::parse(bed, spacing).map_err(CliError::Invalid)?;
+            let plan = services.packing.plan_plates(project, options).await?;
+            printer.plate_plan(&plan)?;
+        }
+        ServiceCommand::Project(ProjectCommand::Outdated { part }) => {
+            let part = match part {
+                Some(part) => Some(resolve_part(services, &part).await?),
+                None => None,
+            };
+            let projects = services.projects.list_outdated(part).await?;
+            printer.outdated_projects(&projects)?;
+        }
+        ServiceCommand::Project(ProjectCommand::Upgrade { project, part }) => {
+            let part = match part {
+                Some(part) => Some(resolve_part(services, &part).await?),
+                None => None,
+            };
+            services.projects.upgrade_parts(project, part).await?;
+            let project = services.projects.view_project(project).await?;
+            printer.project(&project)?;
+        }
+        ServiceCommand::Project(ProjectCommand::Demand { group_by }) => {
+            let demand = services.projects.part_demand(group_by).await?;
+            printer.part_demand(&demand)?;
+        }
+        ServiceCommand::Project(ProjectCommand::Licenses { project }) => {
+            let report = services.projects.license_report(project).await?;
+            printer.license_report(&report)?;
+        }
+        ServiceCommand::Project(ProjectCommand::Bom(BomCommand::Set {
+            project,
+            lines,
+            csv: Some(csv),
+            dry_run,
+            register_missing,
+        })) => {
+            debug_assert!(lines.is_empty());
+            let csv = read_input(&csv)?;
+            let options = BomImportOptions {
+                dry_run,
+                register_missing,
+            };
+            let report = services
+                .bom_importer
+                .import_csv(project, &csv, options)
+                .await?;
+            printer.import_report(&report)?;
+        }
+        ServiceCommand::Project(ProjectCommand::Bom(BomCommand::Set {
+            project,
+            lines,
+            csv: None,
+            ..
+        })) => {
+            let lines = parse_bom_lines(&lines)?;
+            let mut bom = Vec::with_capacity(lines.len());
+            for (part, quantity) in lines {
+                let part = resolve_part(services, &part).await?;
+                bom.push(ProjectPart::new(part, quantity.value()));
+            }
+            services.projects.set_project_bom(project, bom).await?;
+            let project = services.projects.view_project(project).await?;
+            printer.project(&project)?;
+        }
+        ServiceCommand::Part(PartCommand::Add { name }) => {
+            let name = PartName::parse(name)?;
+            let id = services.inventory.register_part(name).await?;
+            printer.created(id)?;
+        }
+        ServiceCommand::Part(PartCommand::List {
+            tag,
+            category,
+            variant_of,
+        }) => {
+            let variant_of = match variant_of {
+                Some(part) => Some(resolve_part(services, &part).await?),
+                None => None,
+            };
+            let filter = PartFilter {
+                tag: tag.map(Tag::parse).transpose()?,
+                category: category.map(Category::parse).transpose()?,
+                variant_of,
+            };
+            let parts = services.inventory.list_parts(&filter).await?;
+            printer.parts(&parts)?;
+        }
+        ServiceCommand::Part(PartCommand::Variant {
+            part,
+            color,
+            size,
+            name,
+        }) => {
+            let attributes = VariantAttributes::parse(RawVariantAttributes { color, size })
+                .map_err(CliError::Invalid)?;
+            let name = name.map(PartName::parse).transpose()?;
+            let part = resolve_part(services, &part).await?;
+            let id = services
+                .inventory
+                .register_variant(part, name, attributes)
+                .await?;
+            printer.created(id)?;
+        }
+        ServiceCommand::Part(PartCommand::Link {
+            part,
+            parent,
+            color,
+            size,
+            unset,
+        }) => {
+            let part = resolve_part(services, &part).await?;
+            let variant = match (parent, unset) {
+                (Some(parent), false) => {
+                    let attributes = VariantAttributes::parse(RawVariantAttributes { color, size })
+                        .map_err(CliError::Invalid)?;
+                    Some(Variant {
+                        parent: resolve_part(services, &parent).await?,
+                        attributes,
+                    })
+                }
+                _ => None,
+            };
+            services.inventory.define_variant(part, variant).await?;
+            let part = services.inventory.view_part(part).await?;
+            printer.parts(&[part])?;
+        }
+        ServiceCommand::Part(PartCommand::Classify {
+            part,
+            category,
+            tags,
+        }) => {
+            let classification = Classification::parse(RawClassification { category, tags })
+                .map_err(CliError::Invalid)?;
+            let part = resolve_part(services, &part).await?;
+            services
+                .inventory
+                .classify_part(part, classification)
+                .await?;
+            let part = services.inventory.view_part(part).await?;
+            printer.parts(&[part])?;
+        }
+        ServiceCommand::Part(PartCommand::Describe {
+            part,
+            material,
+            color,
+            grams,
+            minutes,
+            size,
+            notes,
+        }) => {
+            let part = resolve_part(services, &part).await?;
+            let current = services.inventory.view_part(part).await?;
+            let mut raw = RawPartMetadata::from(current.metadata());
+            raw.material = material.or(raw.material);
+            raw.color = color.or(raw.color);
+            raw.grams = grams.or(raw.grams);
+            raw.minutes = minutes.or(raw.minutes);
+            raw.notes = notes.or(raw.notes);
+            if let Some(size) = size {
+                let size = Dimensions::try_from(size)?;
+                raw.dimensions = Some(RawDimensions {
+                    x: size.x(),
+                    y: size.y(),
+                    z: size.z(),
+                });
+            }
+            let metadata = PartMetadata::parse(raw).map_err(CliError::Invalid)?;
+            services.inventory.describe_part(part, metadata).await?;
+            let part = services.inventory.view_part(part).await?;
+            printer.parts(&[part])?;
+        }
+        ServiceCommand::Part(PartCommand::Source {
+            part,
+            url,
+            designer,
+            license,
+            commercial,
+            clear_commercial,
+        }) => {
+            let part = resolve_part(services, &part).await?;
+            let current = services.inventory.view_part(part).await?;
+            let mut raw = RawPartSource::from(current.source());
+            raw.source_url = url.or(raw.source_url);
+            raw.designer = designer.or(raw.designer);
+            raw.license = license.or(raw.license);
+            raw.commercial_use = match clear_commercial {
+                true => None,
+                false => commercial.or(raw.commercial_use),
+            };
+            let source = PartSource::parse(raw).map_err(CliError::Invalid)?;
+            services.inventory.attribute_part(part, source).await?;
+            let part = services.inventory.view_part(part).await?;
+            printer.parts(&[part])?;
+        }
+        ServiceCommand::Part(PartCommand::Attach { part, file }) => {
+            let part = resolve_part(services, &part).await?;
+            let file_name = FileName::parse(file.to_string_lossy())?;
+            let mut content = tokio::fs::File::open(&file)
+                .await
+                .map_err(anyhow::Error::from)?;
+            let file = services
+                .files
+                .attach_file(part, file_name, &mut content)
+                .await?;
+            printer.files(&[file])?;
+        }
+        ServiceCommand::Part(PartCommand::Pack { part, bed, spacing }) => {
+            let options = PackingOptions::parse(bed, spacing).map_err(CliError::Invalid)?;
+            let part = resolve_part(services, &part).await?;
+            let packing = services.packing.pack_part(part, options).await?;
+            printer.part_packing(&packing)?;
+        }
+        ServiceCommand::Part(PartCommand::Import { file, project }) => {
+            let project = project.map(ProjectName::parse).transpose()?;
+            let content = tokio::fs::read(&file).await.map_err(anyhow::Error::from)?;
+            let report = services
+                .model_importer
+                .import_3mf(&content, ModelImportOptions { project })
+                .await?;
+            printer.model_import_report(&report)?;
+        }
+        ServiceCommand::Part(PartCommand::Revise { part }) => {
+            let part = resolve_part(services, &part).await?;
+            services.inventory.revise_part(part).await?;
+            let revisions = services.inventory.list_revisions(part).await?;
+            printer.part_revisions(&revisions)?;
+        }
+        ServiceCommand::Part(PartCommand::Revisions { part }) => {
+            let part = resolve_part(services, &part).await?;
+            let revisions = services.inventory.list_revisions(part).await?;
+            printer.part_revisions(&revisions)?;
+        }
+        ServiceCommand::Part(PartCommand::Files { part }) => {
+            let part = resolve_part(services, &part).await?;
+            let files = services.files.list_files(part).await?;
+            printer.files(&files)?;
+        }
+        ServiceCommand::Part(PartCommand::Download { part, file, target }) => {
+            let part = resolve_part(services, &part).await?;
+            let (file, mut content) = services.files.open_file(part, file).await?;
+            let target = target.unwrap_or_else(|| PathBuf::from(file.file_name.as_ref()));
+            let mut saved = tokio::fs::File::create(&target)
+                .await
+                .map_err(anyhow::Error::from)?;
+            tokio::io::copy(&mut content, &mut saved)
+                .await
+                .map_err(anyhow::Error::from)?;
+            printer.saved(&target)?;
+        }
+        ServiceCommand::Part(PartCommand::Thumbnail {
+            part,
+            target,
+            format,
+        }) => {
+            let part = resolve_part(services, &part).await?;
+            let image = services.files.thumbnail(part, format).await?;
+            let target = target.unwrap_or_else(|| PathBuf::from(format!("{}.{}", part, format)));
+            tokio::fs::write(&target, image)
+                .await
+                .map_err(anyhow::Error::from)?;
+            printer.saved(&target)?;
+        }
+        ServiceCommand::Progress(ProgressCommand::Report {
+            project,
+            part,
+            printed,
+        }) => {
+            let part = resolve_part(services, &part).await?;
+            services
+                .projects
+                .report_progress(project, part, printed)
+                .await?;
+            let project = services.projects.view_project(project).await?;
+            printer.project(&project)?;
+        }
+        ServiceCommand::Spool(SpoolCommand::Add {
+            material,
+            color,
+            brand,
+            grams,
+            remaining,
+            cost,
+        }) => {
+            let spool = Spool::new(RawSpool {
+                material,
+                color,
+                brand,
+                initial_grams: grams,
+                remaining_grams: remaining,
+                cost,
+            })
+            .map_err(CliError::Invalid)?;
+            let id = services.filament.register_spool(spool).await?;
+            printer.created(id)?;
+        }
+        ServiceCommand::Spool(SpoolCommand::List) => {
+            let spools = services.filament.list_spools().await?;
+            printer.spools(&spools)?;
+        }
+        ServiceCommand::Spool(SpoolCommand::Consume {
+            spool,
+            project,
+            grams,
+        }) => {
+            let grams = Grams::parse(grams)?;
+            let spool = services.filament.consume(spool, project, grams).await?;
+            printer.spools(&[spool])?;
+        }
+        ServiceCommand::Spool(SpoolCommand::Shortages { project }) => {
+            let shortages = services.filament.shortages(project).await?;
+            printer.shortages(&shortages)?;
+        }
+        ServiceCommand::Settings(SettingsCommand::Costs {
+            filament_price,
+            power,
+            electricity_price,
+            machine_wear,
+        }) => {
+            let current = services.settings.cost_settings().await?;
+            let changed = filament_price.is_some()
+                || power.is_some()
+                || electricity_price.is_some()
+                || machine_wear.is_some();
+            let costs = match (current, changed) {
+                (current, true) => {
+                    let mut raw = current
+                        .as_ref()
+                        .map(RawCostSettings::from)
+                        .unwrap_or_default();
+                    raw.filament_price_per_kg = filament_price.unwrap_or(raw.filament_price_per_kg);
+                    raw.printer_power_watts = power.unwrap_or(raw.printer_power_watts);
+                    raw.electricity_price_per_kwh =
+                        electricity_price.unwrap_or(raw.electricity_price_per_kwh);
+                    raw.machine_wear_per_hour = machine_wear.or(raw.machine_wear_per_hour);
+                    let costs = CostSettings::parse(raw).map_err(CliError::Invalid)?;
+                    services.settings.configure_costs(costs).await?;
+                    costs
+                }
+                (Some(current), false) => current,
+                (None, false) => return Err(CliError::NotFound("cost settings")),
+            };
+            printer.cost_settings(&costs)?;
+        }
+    }
+    Ok(())
+}
+
+/// Parses `PART=QUANTITY` lines, reporting every invalid line.
+fn parse_bom_lines(lines: &[String]) -> Result<Vec<(String, Quantity)>, CliError> {
+    let mut validator = CollectingValidator::default();
+    let mut parsed = Vec::with_capacity(lines.len());
+    for (index, line) in lines.iter().enumerate() {
+        let path = format!("lines[{}]", index + 1);
+        let Some((part, quantity)) = line.rsplit_once('=') else {
+            validator.report(ValidationError::new(
+                path,
+                "bom.line.malformed",
+                "BOM line must look like PART=QUANTITY",
+            ));
+            continue;
+        };
+        if let Ok(quantity) = validator.parse_string_at::<Quantity>(&path, quantity) {
+            parsed.push((part.trim().to_string(), quantity));
+        }
+    }
+    if validator.has_errors() {
+        Err(CliError::Invalid(validator.into_errors()))
+    } else {
+        Ok(parsed)
+    }
+}
+
+/// Backs up the local database and the file store next to it into the target directory.
+pub async fn backup(
+    server: Option<&str>,
+    target: &Path,
+    format: OutputFormat,
+    out: &mut impl Write,
+) -> Result<(), CliError> {
+    if server.is_some() {
+        return Err(CliError::General(anyhow!(
+            "backups are made on the machine keeping the database, run the command there without the server"
+        )));
+    }
+    let backup = backup::backup(
+        &database_pool().await?,
+        &file_store_dir(&database_url())?,
+        target,
+    )
+    .await?;
+    Printer::new(format, out).backup(&backup)?;
+    Ok(())
+}
+
+/// Connects to the server when it is set, or opens the local database.
+pub async fn connect(server: Option<&str>) -> anyhow::Result<Services> {
+    match server {
+        Some(server) => Services::remote(server),
+        None => Services::local().await,
+    }
+}
+
+/// Finds the part by ID or by name.
+async fn resolve_part(services: &Services, part: &str) -> Result<PartId, CliError> {
+    if let Ok(id) = Uuid::parse_str(part) {
+        return Ok(id);
+    }
+    let name = PartName::parse(part)?;
+    let part = services.inventory.find_part_by_name(&name).await?;
+    part.map(|p| p.id()).ok_or(CliError::NotFound("part"))
+}
+
+fn read_input(path: &PathBuf) -> anyhow::Result<String> {
+    let mut content = String::new();
+    if path.as_os_str() == "-" {
+        std::io::stdin().read_to_string(&mut content)?;
+    } else {
+        content = std::fs::read_to_string(path)?;
+    }
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn parse_bom_set_command() {
+        let cli = Cli::try_parse_from([
+            "printtables",
+            "project",
+            "bom",
+            "set",
+            "0193a0b4-5f0e-7c4e-9a52-2b1b7a0c8d11",
+            "Bin=5",
+            "Drawer=9",
+            "--output",
+            "json",
+        ])
+        .unwrap();
+
+        assert_eq!(cli.output, OutputFormat::Json);
+        assert!(matches!(
+            cli.command,
+            Command::Services(ServiceCommand::Project(ProjectCommand::Bom(BomCommand::Set { lines, .. }))) if lines.len() == 2
+        ));
+    }
+
+    #[test]
+    fn dry_run_only_applies_to_csv() {
+        let result = Cli::try_parse_from([
+            "printtables",
+            "project",
+            "bom",
+            "set",
+            "0193a0b4-5f0e-7c4e-9a52-2b1b7a0c8d11",
+            "Bin=5",
+            "--dry-run",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_valid_bom_lines() {
+        let lines = parse_bom_lines(&["Bin, small=5".to_string()]).unwrap();
+        assert_eq!(lines[0].0, "Bin, small");
+        assert_eq!(lines[0].1.value(), 5);
+    }
+
+    #[test]
+    fn report_invalid_bom_lines() {
+        let result = parse_bom_lines(&["Bin".to_string(), "Drawer=0".to_string()]);
+        let Err(CliError::Invalid(errors)) = result else {
+            panic!("invalid lines must be rejected");
+        };
+        let attributes: Vec<&str> = errors.iter().map(|e| e.attribute()).collect();
+        assert_eq!(attributes, vec!["lines[1]", "lines[2].quantity"]);
+    }
+
+    #[test]
+    fn generate_completions() {
+        let mut script = vec![];
+        print_completions(Shell::Bash, &mut script);
+        assert!(String::from_utf8(script).unwrap().contains("printtables"));
+    }
+}
@@ -0,0 +1,658 @@
+//! Prints command results as human-readable tables or JSON.
+
+use std::io::Write;
+use std::path::Path;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::filament::app::shortage::Shortage;
+use crate::filament::domain::spool::Spool;
+use crate::infra::backup::Backup;
+use crate::inventory::domain::classification::Classification;
+use crate::inventory::domain::file::PartFile;
+use crate::inventory::domain::metadata::PartMetadata;
+use crate::inventory::domain::part::{Part, PartId};
+use crate::inventory::domain::revision::{PartRevision, PartRevisionNumber};
+use crate::inventory::domain::source::PartSource;
+use crate::inventory::domain::variant::Variant;
+use crate::projects::app::import::BomImportReport;
+use crate::projects::app::model_import::{ModelImportReport, ObjectStatus};
+use crate::projects::app::packing::{PartPacking, PlatePlan, UnplacedReason};
+use crate::projects::view::demand::PartDemand;
+use crate::projects::view::license::LicenseReport;
+use crate::projects::view::outdated::OutdatedProject;
+use crate::projects::view::project::{ProjectListItem, ProjectPart, ProjectView};
+use crate::projects::view::summary::ProjectSummary;
+use crate::settings::domain::costs::{CostSettings, RawCostSettings};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Aligned text columns.
+    Table,
+    /// JSON documents, the same as the REST API responses.
+    Json,
+}
+
+pub struct Printer<'a, W: Write> {
+    format: OutputFormat,
+    out: &'a mut W,
+}
+
+#[derive(Debug, Serialize)]
+struct Created {
+    id: uuid::Uuid,
+}
+
+#[derive(Debug, Serialize)]
+struct Saved<'a> {
+    path: &'a Path,
+}
+
+#[derive(Debug, Serialize)]
+struct PartRow {
+    id: PartId,
+    name: String,
+    revision: PartRevisionNumber,
+    #[serde(flatten)]
+    metadata: PartMetadata,
+    #[serde(flatten)]
+    classification: Classification,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    variant: Option<Variant>,
+    #[serde(flatten)]
+    source: PartSource,
+}
+
+impl<'a, W: Write> Printer<'a, W> {
+    pub fn new(format: OutputFormat, out: &'a mut W) -> Self {
+        Self { format, out }
+    }
+
+    pub fn created(&mut self, id: uuid::Uuid) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => self.json(&Created { id }),
+            OutputFormat::Table => Ok(writeln!(self.out, "{}", id)?),
+        }
+    }
+
+    pub fn projects(&mut self, projects: &[ProjectListItem]) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => self.json(&projects),
+            OutputFormat::Table => {
+                let rows = projects
+                    .iter()
+                    .map(|p| {
+                        vec![
+                            p.id().to_string(),
+                            p.name().to_string(),
+                            p.bom_size().to_string(),
+                        ]
+                    })
+                    .collect();
+                self.table(&["ID", "NAME", "PARTS"], rows)
+            }
+        }
+    }
+
+    pub fn project(&mut self, project: &ProjectView) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => self.json(project),
+            OutputFormat::Table => {
+                writeln!(self.out, "{} ({})", project.name(), project.id())?;
+                let row = |p: &ProjectPart| {
+                    vec![
+                        p.id().to_string(),
+                        p.name().to_string(),
+                        revision(p),
+                        p.quantity().to_string(),
+                        p.printed().to_string(),
+                        p.remaining().to_string(),
+                    ]
+                };
+                match project.groups() {
+                    Some(groups) => {
+                        // Lines are listed group after group with the category or the parent
+                        // part in front.
+                        let by_parent = groups.iter().any(|g| g.part_id.is_some());
+                        let rows = groups
+                            .iter()
+                            .flat_map(|g| {
+                                let label = match by_parent {
+                                    true => g.name.clone(),
+                                    false => g.category.clone(),
+                                }
+                                .unwrap_or_default();
+                                g.bom.iter().map(move |p| {
+                                    let mut row = row(p);
+                                    row.insert(0, label.clone());
+                                    row
+                                })
+                            })
+                            .collect();
+                        self.table(
+                            &[
+                                if by_parent { "PARENT" } else { "CATEGORY" },
+                                "PART ID",
+                                "PART",
+                                "REV",
+                                "QUANTITY",
+                                "PRINTED",
+                                "REMAINING",
+                            ],
+                            rows,
+                        )?;
+                    }
+                    None => {
+                        let rows = project.parts().iter().map(row).collect();
+                        self.table(
+                            &["PART ID", "PART", "REV", "QUANTITY", "PRINTED", "REMAINING"],
+                            rows,
+                        )?;
+                    }
+                }
+                self.summary(project.summary())
+            }
+        }
+    }
+
+    /// Prints totals below the project BOM. Projects without estimates have no totals.
+    fn summary(&mut self, summary: &ProjectSummary) -> anyhow::Result<()> {
+        for total in &summary.filament {
+            let filament = [total.material.as_deref(), total.color.as_deref()]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(" ");
+            writeln!(
+                self.out,
+                "{}: {} g, {} g remaining",
+                if filament.is_empty() {
+                    "filament"
+                } else {
+                    &filament
+                },
+                total.grams,
+                total.remaining_grams
+            )?;
+        }
+        if summary.print_minutes > 0 {
+            writeln!(
+                self.out,
+                "print time: {} h, {} h remaining",
+                summary.print_hours, summary.remaining_print_hours
+            )?;
+        }
+        if let (Some(cost), Some(remaining)) = (&summary.cost, &summary.remaining_cost) {
+            writeln!(
+                self.out,
+                "cost: {:.2}, {:.2} remaining",
+                cost.total, remaining.total
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn cost_settings(&mut self, costs: &CostSettings) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => self.json(costs),
+            OutputFormat::Table => {
+                let raw = RawCostSettings::from(costs);
+                let rows = vec![
+                    vec![
+                        "filament price per kg".to_string(),
+                        raw.filament_price_per_kg.to_string(),
+                    ],
+                    vec![
+                        "printer power watts".to_string(),
+                        raw.printer_power_watts.to_string(),
+                    ],
+                    vec![
+                        "electricity price per kWh".to_string(),
+                        raw.electricity_price_per_kwh.to_string(),
+                    ],
+                    vec![
+                        "machine wear per hour".to_string(),
+                        optional(raw.machine_wear_per_hour),
+                    ],
+                ];
+                self.table(&["SETTING", "VALUE"], rows)
+            }
+        }
+    }
+
+    pub fn parts(&mut self, parts: &[Part]) -> anyhow::Result<()> {
+        let parts: Vec<PartRow> = parts
+            .iter()
+            .map(|p| PartRow {
+                id: p.id(),
+                name: p.name().to_string(),
+                revision: p.revision(),
+                metadata: p.metadata().clone(),
+                classification: p.classification().clone(),
+                variant: p.variant().cloned(),
+                source: p.source().clone(),
+            })
+            .collect();
+        match self.format {
+            OutputFormat::Json => self.json(&parts),
+            OutputFormat::Table => {
+                let rows = parts
+                    .into_iter()
+                    .map(|p| {
+                        let metadata = p.metadata;
+                        vec![
+                            p.id.to_string(),
+                            p.name,
+                            optional(p.variant.map(|v| v.attributes)),
+                            p.revision.to_string(),
+                            optional(metadata.material),
+                            optional(metadata.color),
+                            optional(metadata.grams.map(|g| g.value())),
+                            optional(metadata.minutes.map(|m| m.value())),
+                            optional(p.classification.category),
+                            p.classification
+                                .tags
+                                .iter()
+                                .map(|t| t.as_ref())
+                                .collect::<Vec<_>>()
+                                .join(", "),
+                            optional(p.source.license),
+                        ]
+                    })
+                    .collect();
+                self.table(
+                    &[
+                        "ID", "NAME", "VARIANT", "REV", "MATERIAL", "COLOR", "GRAMS", "MINUTES",
+                        "CATEGORY", "TAGS", "LICENSE",
+                    ],
+                    rows,
+                )
+            }
+        }
+    }
+
+    pub fn files(&mut self, files: &[PartFile]) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => self.json(files),
+            OutputFormat::Table => {
+                let rows = files
+                    .iter()
+                    .map(|f| {
+                        vec![
+                            f.id.to_string(),
+                            f.file_name.to_string(),
+                            f.revision.to_string(),
+                            f.format.to_string(),
+                            f.size.to_string(),
+                            f.sha256.as_ref()[..12].to_string(),
+                        ]
+                    })
+                    .collect();
+                self.table(&["ID", "NAME", "REV", "FORMAT", "BYTES", "SHA256"], rows)
+            }
+        }
+    }
+
+    pub fn part_revisions(&mut self, revisions: &[PartRevision]) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => self.json(revisions),
+            OutputFormat::Table => {
+                let rows = revisions
+                    .iter()
+                    .map(|r| {
+                        let metadata = &r.metadata;
+                        vec![
+                            r.revision.to_string(),
+                            r.replaced_at
+                                .map(|at| at.format("%Y-%m-%d %H:%M").to_string())
+                                .unwrap_or_else(|| "latest".to_string()),
+                            optional(metadata.material),
+                            optional(metadata.color.as_ref()),
+                            optional(metadata.grams.map(|g| g.value())),
+                            optional(metadata.minutes.map(|m| m.value())),
+                        ]
+                    })
+                    .collect();
+                self.table(
+                    &["REV", "REPLACED", "MATERIAL", "COLOR", "GRAMS", "MINUTES"],
+                    rows,
+                )
+            }
+        }
+    }
+
+    pub fn outdated_projects(&mut self, projects: &[OutdatedProject]) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => self.json(&projects),
+            OutputFormat::Table => {
+                let rows = projects
+                    .iter()
+                    .flat_map(|project| {
+                        project.parts.iter().map(|part| {
+                            vec![
+                                project.id.to_string(),
+                                project.name.clone(),
+                                part.name.clone(),
+                                part.revision.to_string(),
+                                part.latest_revision.to_string(),
+                            ]
+                        })
+                    })
+                    .collect();
+                self.table(&["PROJECT ID", "PROJECT", "PART", "REV", "LATEST"], rows)
+            }
+        }
+    }
+
+    pub fn part_demand(&mut self, demand: &[PartDemand]) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => self.json(&demand),
+            OutputFormat::Table => {
+                let rows = demand
+                    .iter()
+                    .map(|d| {
+                        vec![
+                            d.part_id.to_string(),
+                            d.name.clone(),
+                            optional(d.color.as_deref()),
+                            optional(d.size.as_deref()),
+                            d.quantity.to_string(),
+                            d.printed.to_string(),
+                            d.remaining.to_string(),
+                            d.projects.to_string(),
+                        ]
+                    })
+                    .collect();
+                self.table(
+                    &[
+                        "PART ID",
+                        "PART",
+                        "COLOR",
+                        "SIZE",
+                        "QUANTITY",
+                        "PRINTED",
+                        "REMAINING",
+                        "PROJECTS",
+                    ],
+                    rows,
+                )
+            }
+        }
+    }
+
+    /// Lists licenses of parts, followed by the number of parts which keep prints of the
+    /// project from being sold.
+    pub fn license_report(&mut self, report: &LicenseReport) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => self.json(report),
+            OutputFormat::Table => {
+                let rows = report
+                    .parts
+                    .iter()
+                    .map(|p| {
+                        let commercial_use = match p.commercial_use {
+                            Some(true) => "yes",
+                            Some(false) => "no",
+                            None if !p.errors.is_empty() => "invalid",
+                            None => "",
+                        };
+                        vec![
+                            p.part_id.to_string(),
+                            p.name.clone(),
+                            optional(p.license.as_deref()),
+                            optional(p.designer.as_deref()),
+                            commercial_use.to_string(),
+                            optional(p.source_url.as_deref()),
+                        ]
+                    })
+                    .collect();
+                self.table(
+                    &[
+                        "PART ID",
+                        "PART",
+                        "LICENSE",
+                        "DESIGNER",
+                        "COMMERCIAL",
+                        "SOURCE",
+                    ],
+                    rows,
+                )?;
+                if report.non_commercial > 0 {
+                    writeln!(
+                        self.out,
+                        "parts forbidding commercial use: {}",
+                        report.non_commercial
+                    )?;
+                }
+                if report.unknown > 0 {
+                    writeln!(self.out, "parts without a license: {}", report.unknown)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    pub fn saved(&mut self, path: &Path) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => self.json(&Saved { path }),
+            OutputFormat::Table => Ok(writeln!(self.out, "{}", path.display())?),
+        }
+    }
+
+    pub fn backup(&mut self, backup: &Backup) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => self.json(backup),
+            OutputFormat::Table => {
+                writeln!(self.out, "database  {}", backup.database.display())?;
+                writeln!(
+                    self.out,
+                    "files     {} ({} files)",
+                    backup.files_dir.display(),
+                    backup.files
+                )?;
+                Ok(())
+            }
+        }
+    }
+
+    pub fn spools(&mut self, spools: &[Spool]) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => self.json(spools),
+            OutputFormat::Table => {
+                let rows = spools
+                    .iter()
+                    .map(|s| {
+                        vec![
+                            s.id().to_string(),
+                            s.material().to_string(),
+                            s.color().to_string(),
+                            optional(s.brand()),
+                            s.remaining_grams().to_string(),
+                            s.initial_grams().value().to_string(),
+                        ]
+                    })
+                    .collect();
+                self.table(
+                    &["ID", "MATERIAL", "COLOR", "BRAND", "REMAINING", "GRAMS"],
+                    rows,
+                )
+            }
+        }
+    }
+
+    pub fn shortages(&mut self, shortages: &[Shortage]) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => self.json(shortages),
+            OutputFormat::Table if shortages.is_empty() => {
+                Ok(writeln!(self.out, "enough filament on the shelf")?)
+            }
+            OutputFormat::Table => {
+                let rows = shortages
+                    .iter()
+                    .map(|s| {
+                        vec![
+                            optional(s.material.as_ref()),
+                            optional(s.color.as_ref()),
+                            s.required_grams.to_string(),
+                            s.available_grams.to_string(),
+                            s.missing_grams.to_string(),
+                        ]
+                    })
+                    .collect();
+                self.table(
+                    &["MATERIAL", "COLOR", "REQUIRED", "AVAILABLE", "MISSING"],
+                    rows,
+                )
+            }
+        }
+    }
+
+    pub fn import_report(&mut self, report: &BomImportReport) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => self.json(report),
+            OutputFormat::Table => {
+                if report.dry_run {
+                    writeln!(self.out, "dry run, no changes applied")?;
+                }
+                let rows = report
+                    .changes
+                    .iter()
+                    .map(|c| vec![c.name.clone(), c.before.to_string(), c.after.to_string()])
+                    .collect();
+                self.table(&["PART", "BEFORE", "AFTER"], rows)
+            }
+        }
+    }
+
+    /// Lists imported objects with the part they became or the reason they were rejected.
+    pub fn model_import_report(&mut self, report: &ModelImportReport) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => self.json(report),
+            OutputFormat::Table => {
+                if let Some(project) = report.project_id {
+                    writeln!(self.out, "project {}", project)?;
+                }
+                let rows = report
+                    .objects
+                    .iter()
+                    .map(|o| {
+                        let outcome = match o.part_id {
+                            Some(part) => part.to_string(),
+                            None => o
+                                .errors
+                                .iter()
+                                .map(|e| e.message())
+                                .collect::<Vec<_>>()
+                                .join("; "),
+                        };
+                        let status = match o.status {
+                            ObjectStatus::Matched => "matched",
+                            ObjectStatus::Registered => "registered",
+                            ObjectStatus::Rejected => "rejected",
+                        };
+                        vec![
+                            o.name.clone(),
+                            o.count.to_string(),
+                            status.to_string(),
+                            outcome,
+                        ]
+                    })
+                    .collect();
+                self.table(&["OBJECT", "COUNT", "STATUS", "PART"], rows)
+            }
+        }
+    }
+
+    pub fn part_packing(&mut self, packing: &PartPacking) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => self.json(packing),
+            OutputFormat::Table => match packing.grid {
+                Some(grid) => {
+                    let rotated = if grid.rotated { ", rotated" } else { "" };
+                    Ok(writeln!(
+                        self.out,
+                        "{} copies per plate: {} columns x {} rows{}",
+                        grid.copies(),
+                        grid.columns,
+                        grid.rows,
+                        rotated
+                    )?)
+                }
+                None => Ok(writeln!(
+                    self.out,
+                    "part does not fit the {} mm bed",
+                    packing.bed
+                )?),
+            },
+        }
+    }
+
+    /// Lists parts on every plate, then parts that cannot be placed.
+    pub fn plate_plan(&mut self, plan: &PlatePlan) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => self.json(plan),
+            OutputFormat::Table => {
+                let mut rows: Vec<Vec<String>> = vec![];
+                for (index, plate) in plan.plates.iter().enumerate() {
+                    for part in &plate.parts {
+                        let plate = (index + 1).to_string();
+                        rows.push(vec![plate, part.name.clone(), part.copies.to_string()]);
+                    }
+                }
+                for part in &plan.unplaced {
+                    let reason = match part.reason {
+                        UnplacedReason::MissingDimensions => "no dimensions",
+                        UnplacedReason::TooLarge => "too large",
+                    };
+                    rows.push(vec![
+                        reason.to_string(),
+                        part.name.clone(),
+                        part.copies.to_string(),
+                    ]);
+                }
+                self.table(&["PLATE", "PART", "COPIES"], rows)
+            }
+        }
+    }
+
+    fn json<T: Serialize + ?Sized>(&mut self, value: &T) -> anyhow::Result<()> {
+        serde_json::to_writer_pretty(&mut *self.out, value)?;
+        writeln!(self.out)?;
+        Ok(())
+    }
+
+    fn table(&mut self, headers: &[&str], rows: Vec<Vec<String>>) -> anyhow::Result<()> {
+        let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        let headers = headers.iter().map(|h| h.to_string()).collect();
+        for row in std::iter::once(headers).chain(rows) {
+            let line = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:<w$}", cell, w = width))
+                .collect::<Vec<_>>()
+                .join("  ");
+            writeln!(self.out, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats missing values as empty cells.
+fn optional(value: Option<impl ToString>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// Pinned revision of the BOM line, followed by the latest one when the line is outdated.
+fn revision(part: &ProjectPart) -> String {
+    if part.is_outdated() {
+        format!("{} → {}", part.revision(), part.latest_revision())
+    } else {
+        part.revision().to_string()
+    }
+}
@@ -0,0 +1,225 @@
+//! Defines application service for filament spools.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use thiserror::Error;
+use tracing::info;
+
+use crate::app::actor::Actor;
+use crate::app::unit_of_work::UnitOfWorkFactory;
+use crate::filament::domain::repository::SpoolRepository;
+use crate::filament::domain::spool::{Consumption, Spool, SpoolId};
+use crate::inventory::domain::metadata::Grams;
+use crate::projects::app::service::{ProjectError, ProjectsService};
+use crate::projects::domain::project::ProjectId;
+use crate::shared::validation::error::ValidationError;
+
+use super::shortage::{shortages, Shortage};
+
+#[derive(Debug, Error)]
+pub enum FilamentError {
+    #[error("requested spool not found")]
+    MissingSpool,
+    #[error("requested project does not exist")]
+    MissingProject,
+    /// Requested change violates spool rules.
+    #[error("invalid spool change")]
+    ValidationFailed(Vec<ValidationError>),
+    #[error("general error")]
+    GeneralError(#[from] anyhow::Error),
+}
+
+#[async_trait]
+pub trait FilamentService: Send + Sync {
+    /// Puts a new spool on the shelf.
+    async fn register_spool(&self, spool: Spool) -> Result<SpoolId, FilamentError>;
+
+    async fn view_spool(&self, id: SpoolId) -> Result<Spool, FilamentError>;
+
+    /// Lists all spools ordered by material and color.
+    async fn list_spools(&self) -> Result<Vec<Spool>, FilamentError>;
+
+    /// Takes filament used to print parts of the project off the spool.
+    /// Returns the spool with the decreased remaining weight.
+    async fn consume(
+        &self,
+        spool: SpoolId,
+        project: ProjectId,
+        grams: Grams,
+    ) -> Result<Spool, FilamentError>;
+
+    /// Lists filament used by the project, the oldest first.
+    async fn list_consumption(&self, project: ProjectId)
+        -> Result<Vec<Consumption>, FilamentError>;
+
+    /// Lists filament the project still needs but the shelf lacks.
+    async fn shortages(&self, project: ProjectId) -> Result<Vec<Shortage>, FilamentError>;
+}
+
+/// Changes spools within units of work and reads them from the spools repository.
+pub struct DefaultFilamentService {
+    units: Arc<dyn UnitOfWorkFactory>,
+    spools_repo: Arc<dyn SpoolRepository>,
+    projects: Arc<dyn ProjectsService>,
+}
+
+impl DefaultFilamentService {
+    pub fn new(
+        units: Arc<dyn UnitOfWorkFactory>,
+        spools_repo: Arc<dyn SpoolRepository>,
+        projects: Arc<dyn ProjectsService>,
+    ) -> Self {
+        Self {
+            units,
+            spools_repo,
+            projects,
+        }
+    }
+}
+
+#[async_trait]
+impl FilamentService for DefaultFilamentService {
+    async fn register_spool(&self, spool: Spool) -> Result<SpoolId, FilamentError> {
+        let id = spool.id();
+        let uow = self.units.begin().await?;
+        uow.spools().insert(spool).await?;
+        uow.commit().await?;
+        info!("spool registered with id {}", id);
+        Ok(id)
+    }
+
+    async fn view_spool(&self, id: SpoolId) -> Result<Spool, FilamentError> {
+        self.spools_repo
+            .find_by_id(id)
+            .await?
+            .ok_or(FilamentError::MissingSpool)
+    }
+
+    async fn list_spools(&self) -> Result<Vec<Spool>, FilamentError> {
+        Ok(self.spools_repo.list_all().await?)
+    }
+
+    async fn consume(
+        &self,
+        spool_id: SpoolId,
+        project: ProjectId,
+        grams: Grams,
+    ) -> Result<Spool, FilamentError> {
+        let uow = self.units.begin().await?;
+        uow.projects()
+            .find_by_id(project)
+            .await?
+            .ok_or(FilamentError::MissingProject)?;
+        let spools = uow.spools();
+        let mut spool = spools
+            .find_by_id(spool_id)
+            .await?
+            .ok_or(FilamentError::MissingSpool)?;
+        spool
+            .consume(grams)
+            .map_err(|e| FilamentError::ValidationFailed(vec![e]))?;
+        spools.update(spool.clone()).await?;
+        spools
+            .record_consumption(Consumption {
+                spool: spool_id,
+                project,
+                grams: grams.value(),
+                consumed_at: Utc::now(),
+                actor: Actor::current().to_string(),
+            })
+            .await?;
+        uow.commit().await?;
+        info!(
+            "{} grams of spool {} used by project {}",
+            grams.value(),
+            spool_id,
+            project
+        );
+        Ok(spool)
+    }
+
+    async fn list_consumption(
+        &self,
+        project: ProjectId,
+    ) -> Result<Vec<Consumption>, FilamentError> {
+        self.projects.view_project(project).await?;
+        Ok(self.spools_repo.list_consumption(project).await?)
+    }
+
+    async fn shortages(&self, project: ProjectId) -> Result<Vec<Shortage>, FilamentError> {
+        let project = self.projects.view_project(project).await?;
+        let spools = self.spools_repo.list_all().await?;
+        Ok(shortages(&project.summary().filament, &spools))
+    }
+}
+
+impl From<ProjectError> for FilamentError {
+    fn from(value: ProjectError) -> Self {
+        match value {
+            ProjectError::MissingProject => FilamentError::MissingProject,
+            ProjectError::ValidationFailed(errors) => FilamentError::ValidationFailed(errors),
+            ProjectError::GeneralError(e) => FilamentError::GeneralError(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::filament::domain::spool::RawSpool;
+    use crate::infra::services::Services;
+    use crate::projects::domain::name::Name;
+
+    fn black_pla() -> Spool {
+        Spool::new(RawSpool {
+            material: "PLA".to_string(),
+            color: "black".to_string(),
+            initial_grams: 1000.0,
+            ..RawSpool::default()
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn record_consumption_of_project() {
+        let Services {
+            filament, projects, ..
+        } = Services::in_memory();
+        let project = projects
+            .register_project(Name::parse("Organizers").unwrap())
+            .await
+            .unwrap();
+        let spool = filament.register_spool(black_pla()).await.unwrap();
+
+        let used = filament
+            .consume(spool, project, Grams::parse(120.5).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(used.remaining_grams(), 879.5);
+        let stored = filament.view_spool(spool).await.unwrap();
+        assert_eq!(stored.remaining_grams(), 879.5);
+        let consumption = filament.list_consumption(project).await.unwrap();
+        assert_eq!(consumption.len(), 1);
+        assert_eq!(consumption[0].grams, 120.5);
+    }
+
+    #[tokio::test]
+    async fn reject_consumption_of_missing_project() {
+        let Services { filament, .. } = Services::in_memory();
+        let spool = filament.register_spool(black_pla()).await.unwrap();
+
+        let result = filament
+            .consume(spool, Uuid::now_v7(), Grams::parse(1.0).unwrap())
+            .await;
+
+        assert!(matches!(result, Err(FilamentError::MissingProject)));
+        let stored = filament.view_spool(spool).await.unwrap();
+        assert_eq!(stored.remaining_grams(), 1000.0);
+    }
+}
@@ -0,0 +1,129 @@
+//! Compares filament projects still need with filament left on the shelf.
+
+use serde::{Deserialize, Serialize};
+
+use crate::filament::domain::spool::Spool;
+use crate::projects::view::summary::FilamentTotal;
+use crate::shared::rounding::round;
+
+/// Filament of a material and color that the shelf lacks to finish the project.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Shortage {
+    pub material: Option<String>,
+    pub color: Option<String>,
+    /// Filament needed to print the remaining parts.
+    pub required_grams: f64,
+    /// Filament left on matching spools.
+    pub available_grams: f64,
+    pub missing_grams: f64,
+}
+
+/// Lists filament demands exceeding what matching spools hold.
+/// Demands without material or color are matched by the known property only. Colors are compared
+/// ignoring case.
+pub fn shortages(demand: &[FilamentTotal], spools: &[Spool]) -> Vec<Shortage> {
+    demand
+        .iter()
+        .filter(|total| total.remaining_grams > 0.0)
+        .filter_map(|total| {
+            let available_grams: f64 = spools
+                .iter()
+                .filter(|spool| matches(total, spool))
+                .map(Spool::remaining_grams)
+                .sum();
+            let available_grams = round(available_grams);
+            (total.remaining_grams > available_grams).then(|| Shortage {
+                material: total.material.clone(),
+                color: total.color.clone(),
+                required_grams: total.remaining_grams,
+                available_grams,
+                missing_grams: round(total.remaining_grams - available_grams),
+            })
+        })
+        .collect()
+}
+
+fn matches(total: &FilamentTotal, spool: &Spool) -> bool {
+    let material = total
+        .material
+        .as_deref()
+        .is_none_or(|m| m.eq_ignore_ascii_case(spool.material().as_str()));
+    let color = total
+        .color
+        .as_deref()
+        .is_none_or(|c| c.eq_ignore_ascii_case(spool.color().as_ref()));
+    material && color
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::filament::domain::spool::RawSpool;
+
+    fn spool(material: &str, color: &str, remaining_grams: f64) -> Spool {
+        Spool::new(RawSpool {
+            material: material.to_string(),
+            color: color.to_string(),
+            initial_grams: 1000.0,
+            remaining_grams: Some(remaining_grams),
+            ..RawSpool::default()
+        })
+        .unwrap()
+    }
+
+    fn demand(material: Option<&str>, color: Option<&str>, remaining_grams: f64) -> FilamentTotal {
+        FilamentTotal {
+            material: material.map(str::to_string),
+            color: color.map(str::to_string),
+            grams: remaining_grams,
+            remaining_grams,
+        }
+    }
+
+    #[test]
+    fn report_colors_missing_on_the_shelf() {
+        let spools = vec![
+            spool("PLA", "Black", 300.0),
+            spool("PLA", "black", 200.0),
+            spool("PETG", "black", 1000.0),
+            spool("PLA", "white", 1000.0),
+        ];
+        let demand = vec![
+            demand(Some("PLA"), Some("black"), 750.5),
+            demand(Some("PLA"), Some("white"), 400.0),
+            demand(Some("ASA"), Some("red"), 10.0),
+        ];
+
+        let shortages = shortages(&demand, &spools);
+
+        assert_eq!(
+            shortages,
+            vec![
+                Shortage {
+                    material: Some("PLA".to_string()),
+                    color: Some("black".to_string()),
+                    required_grams: 750.5,
+                    available_grams: 500.0,
+                    missing_grams: 250.5,
+                },
+                Shortage {
+                    material: Some("ASA".to_string()),
+                    color: Some("red".to_string()),
+                    required_grams: 10.0,
+                    available_grams: 0.0,
+                    missing_grams: 10.0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn match_demand_without_material_by_color() {
+        let spools = vec![spool("PLA", "black", 300.0), spool("PETG", "black", 300.0)];
+
+        let shortages = shortages(&[demand(None, Some("black"), 500.0)], &spools);
+
+        assert!(shortages.is_empty());
+    }
+}
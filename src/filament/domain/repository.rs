@@ -0,0 +1,30 @@
+//! The repository trait to interact with filament spools.
+
+use async_trait::async_trait;
+
+use crate::projects::domain::project::ProjectId;
+
+use super::spool::{Consumption, Spool, SpoolId};
+
+/// Repository of spools and their consumption.
+/// This trait must be implemented in the infrastructure layer.
+#[async_trait]
+pub trait SpoolRepository: Send + Sync {
+    /// Inserts a new spool into the storage.
+    async fn insert(&self, spool: Spool) -> anyhow::Result<()>;
+
+    /// Saves changes of an existing spool.
+    async fn update(&self, spool: Spool) -> anyhow::Result<()>;
+
+    /// The implementation must return Ok(None) if the spool is not found.
+    async fn find_by_id(&self, id: SpoolId) -> anyhow::Result<Option<Spool>>;
+
+    /// Lists all spools ordered by material and color.
+    async fn list_all(&self) -> anyhow::Result<Vec<Spool>>;
+
+    /// Appends filament used by a project. Consumption is never changed once stored.
+    async fn record_consumption(&self, consumption: Consumption) -> anyhow::Result<()>;
+
+    /// Lists filament used by the project, the oldest first.
+    async fn list_consumption(&self, project: ProjectId) -> anyhow::Result<Vec<Consumption>>;
+}
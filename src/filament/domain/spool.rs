@@ -0,0 +1,345 @@
+//! Filament spool domain entity.
+//! Keeps track of filament left on a single spool.
+
+use std::fmt::Display;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::inventory::domain::metadata::{Grams, Material};
+use crate::projects::domain::project::ProjectId;
+use crate::shared::rounding::round;
+use crate::shared::validation::error::ValidationError;
+use crate::shared::validation::validator::CollectingValidator;
+
+pub type SpoolId = Uuid;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Spool {
+    id: SpoolId,
+    material: Material,
+    color: SpoolColor,
+    brand: Option<Brand>,
+    /// Net weight of filament on a new spool.
+    initial_grams: Grams,
+    /// Net weight of filament left on the spool.
+    remaining_grams: f64,
+    /// Price paid for the spool.
+    cost: Option<Cost>,
+}
+
+/// Unvalidated spool properties, e.g., from request payloads or storage.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RawSpool {
+    pub material: String,
+    pub color: String,
+    pub brand: Option<String>,
+    pub initial_grams: f64,
+    /// Defaults to the initial weight, so new spools are full.
+    pub remaining_grams: Option<f64>,
+    pub cost: Option<f64>,
+}
+
+impl Spool {
+    /// Creates a new spool, validating all properties.
+    pub fn new(raw: RawSpool) -> Result<Self, Vec<ValidationError>> {
+        Self::parse(Uuid::now_v7(), raw)
+    }
+
+    /// Validates properties of the spool with the given ID, reporting every invalid one.
+    pub fn parse(id: SpoolId, raw: RawSpool) -> Result<Self, Vec<ValidationError>> {
+        let mut validator = CollectingValidator::default();
+        let material = validator.parse_string::<Material>(raw.material);
+        let color = validator.parse_string::<SpoolColor>(raw.color);
+        let brand = raw
+            .brand
+            .filter(|b| !b.trim().is_empty())
+            .map(|b| validator.parse_string::<Brand>(b));
+        let initial_grams = validator.parse::<Grams, _>(raw.initial_grams);
+        let remaining_grams = raw.remaining_grams.unwrap_or(raw.initial_grams);
+        if !remaining_grams.is_finite()
+            || remaining_grams < 0.0
+            || remaining_grams > raw.initial_grams
+        {
+            validator.report(ValidationError::new(
+                "remaining_grams",
+                "spool.remaining.out-of-range",
+                "remaining weight must be between 0 and the initial weight",
+            ));
+        }
+        let cost = raw.cost.map(|c| validator.parse::<Cost, _>(c));
+        if validator.has_errors() {
+            return Err(validator.into_errors());
+        }
+        Ok(Self {
+            id,
+            material: material.unwrap(),
+            color: color.unwrap(),
+            brand: brand.transpose().unwrap(),
+            initial_grams: initial_grams.unwrap(),
+            remaining_grams,
+            cost: cost.transpose().unwrap(),
+        })
+    }
+
+    /// Takes used filament off the spool.
+    /// Using more filament than is left on the spool is rejected.
+    pub fn consume(&mut self, grams: Grams) -> Result<(), ValidationError> {
+        if grams.value() > self.remaining_grams {
+            return Err(ValidationError::new(
+                "grams",
+                "spool.grams.exceeds-remaining",
+                format!("only {} grams are left on the spool", self.remaining_grams),
+            ));
+        }
+        // Rounding keeps repeated consumption from accumulating float errors.
+        self.remaining_grams = round(self.remaining_grams - grams.value());
+        Ok(())
+    }
+
+    pub fn id(&self) -> SpoolId {
+        self.id
+    }
+
+    pub fn material(&self) -> Material {
+        self.material
+    }
+
+    pub fn color(&self) -> &SpoolColor {
+        &self.color
+    }
+
+    pub fn brand(&self) -> Option<&Brand> {
+        self.brand.as_ref()
+    }
+
+    pub fn initial_grams(&self) -> Grams {
+        self.initial_grams
+    }
+
+    pub fn remaining_grams(&self) -> f64 {
+        self.remaining_grams
+    }
+
+    pub fn cost(&self) -> Option<Cost> {
+        self.cost
+    }
+}
+
+impl From<&Spool> for RawSpool {
+    fn from(value: &Spool) -> Self {
+        Self {
+            material: value.material.to_string(),
+            color: value.color.to_string(),
+            brand: value.brand.as_ref().map(|b| b.to_string()),
+            initial_grams: value.initial_grams.value(),
+            remaining_grams: Some(value.remaining_grams),
+            cost: value.cost.map(|c| c.value()),
+        }
+    }
+}
+
+/// Filament used from the spool to print parts of the project.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Consumption {
+    pub spool: SpoolId,
+    pub project: ProjectId,
+    pub grams: f64,
+    pub consumed_at: DateTime<Utc>,
+    pub actor: String,
+}
+
+/// Filament manufacturer.
+/// Filament color as named by the brand, matched against part colors ignoring case.
+/// Color cannot be empty and it's max length is 50 bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct SpoolColor(String);
+
+impl SpoolColor {
+    pub fn parse(value: impl Into<String>) -> Result<Self, ValidationError> {
+        let value = value.into();
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            return Err(ValidationError::new(
+                "color",
+                "spool.color.too-short",
+                "color is too short",
+            ));
+        }
+        if trimmed.len() > 50 {
+            return Err(ValidationError::new(
+                "color",
+                "spool.color.too-long",
+                "color is too long",
+            ));
+        }
+        Ok(Self(trimmed.to_string()))
+    }
+}
+
+impl TryFrom<String> for SpoolColor {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl AsRef<str> for SpoolColor {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for SpoolColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Brand cannot be empty and it's max length is 50 bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct Brand(String);
+
+impl Brand {
+    pub fn parse(value: impl Into<String>) -> Result<Self, ValidationError> {
+        let value = value.into();
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            return Err(ValidationError::new(
+                "brand",
+                "spool.brand.too-short",
+                "brand is too short",
+            ));
+        }
+        if trimmed.len() > 50 {
+            return Err(ValidationError::new(
+                "brand",
+                "spool.brand.too-long",
+                "brand is too long",
+            ));
+        }
+        Ok(Self(trimmed.to_string()))
+    }
+}
+
+impl TryFrom<String> for Brand {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl Display for Brand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Price of the spool.
+/// Price must not be negative and must not exceed 100000.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize)]
+#[serde(transparent)]
+pub struct Cost(f64);
+
+impl Cost {
+    pub fn parse(value: f64) -> Result<Self, ValidationError> {
+        if !value.is_finite() || !(0.0..=100_000.0).contains(&value) {
+            return Err(ValidationError::new(
+                "cost",
+                "spool.cost.out-of-range",
+                "cost must be between 0 and 100000",
+            ));
+        }
+        Ok(Self(value))
+    }
+
+    pub fn value(&self) -> f64 {
+        self.0
+    }
+}
+
+impl TryFrom<f64> for Cost {
+    type Error = ValidationError;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn pla_spool() -> RawSpool {
+        RawSpool {
+            material: "pla".to_string(),
+            color: "black".to_string(),
+            brand: Some("Prusament".to_string()),
+            initial_grams: 1000.0,
+            remaining_grams: None,
+            cost: Some(24.99),
+        }
+    }
+
+    #[test]
+    fn new_spool_is_full() {
+        let spool = Spool::new(pla_spool()).unwrap();
+
+        assert_eq!(spool.material(), Material::Pla);
+        assert_eq!(spool.remaining_grams(), 1000.0);
+    }
+
+    #[test]
+    fn report_all_invalid_properties() {
+        let raw = RawSpool {
+            material: "wood".to_string(),
+            color: " ".to_string(),
+            remaining_grams: Some(1200.0),
+            cost: Some(-1.0),
+            ..pla_spool()
+        };
+
+        let errors = Spool::new(raw).unwrap_err();
+
+        let codes: Vec<&str> = errors.iter().map(|e| e.code()).collect();
+        assert_eq!(
+            codes,
+            vec![
+                "part.material.unknown",
+                "spool.color.too-short",
+                "spool.remaining.out-of-range",
+                "spool.cost.out-of-range"
+            ]
+        );
+    }
+
+    #[test]
+    fn consume_filament() {
+        let mut spool = Spool::new(pla_spool()).unwrap();
+
+        spool.consume(Grams::parse(250.1).unwrap()).unwrap();
+        spool.consume(Grams::parse(249.9).unwrap()).unwrap();
+
+        assert_eq!(spool.remaining_grams(), 500.0);
+    }
+
+    #[test]
+    fn reject_consumption_exceeding_remaining_filament() {
+        let mut spool = Spool::new(RawSpool {
+            remaining_grams: Some(100.0),
+            ..pla_spool()
+        })
+        .unwrap();
+
+        let error = spool.consume(Grams::parse(100.5).unwrap()).unwrap_err();
+
+        assert_eq!(error.code(), "spool.grams.exceeds-remaining");
+        assert_eq!(spool.remaining_grams(), 100.0);
+    }
+}
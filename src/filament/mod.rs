@@ -0,0 +1,6 @@
+//! Filament spools on the shelf.
+//!
+//! Spools are used up by printing projects. Comparing what is left on the shelf with the
+//! filament projects still need warns about shortages before printing starts.
+pub mod app;
+pub mod domain;
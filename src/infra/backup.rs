@@ -0,0 +1,126 @@
+//! Backups of local data.
+//!
+//! A backup is a directory holding a consistent copy of the SQLite database and a copy of the
+//! file store with part files. Restoring is copying both back next to each other.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::bail;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use tokio::fs;
+
+use crate::infra::files::{THUMBNAILS_DIR, TMP_DIR};
+
+/// Name of the database copy in the backup directory.
+pub const DATABASE_FILE: &str = "printtables.db";
+/// Name of the file store copy in the backup directory.
+pub const FILES_DIR: &str = "printtables.files";
+
+/// Copies made by a backup.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Backup {
+    pub database: PathBuf,
+    pub files_dir: PathBuf,
+    /// Number of stored files copied.
+    pub files: u64,
+}
+
+/// Backs up the database and the file store into the target directory.
+/// The database is copied with `vacuum into`, so the backup is consistent while the database
+/// is in use. Refuses to overwrite an existing backup.
+pub async fn backup(
+    db_pool: &SqlitePool,
+    file_store: &Path,
+    target: &Path,
+) -> anyhow::Result<Backup> {
+    let database = target.join(DATABASE_FILE);
+    let files_dir = target.join(FILES_DIR);
+    if fs::try_exists(&database).await? || fs::try_exists(&files_dir).await? {
+        bail!("{} already contains a backup", target.display());
+    }
+    fs::create_dir_all(target).await?;
+    sqlx::query("vacuum into ?")
+        .bind(database.to_string_lossy())
+        .execute(db_pool)
+        .await?;
+    let files = copy_store(file_store, &files_dir).await?;
+    Ok(Backup {
+        database,
+        files_dir,
+        files,
+    })
+}
+
+/// Copies stored files, skipping unfinished uploads and cached thumbnails.
+/// A missing store has no files to copy.
+async fn copy_store(from: &Path, to: &Path) -> anyhow::Result<u64> {
+    fs::create_dir_all(to).await?;
+    if !fs::try_exists(from).await? {
+        return Ok(0);
+    }
+    let mut copied = 0;
+    let mut dirs = fs::read_dir(from).await?;
+    while let Some(dir) = dirs.next_entry().await? {
+        if !dir.file_type().await?.is_dir()
+            || [TMP_DIR, THUMBNAILS_DIR].contains(&dir.file_name().to_string_lossy().as_ref())
+        {
+            continue;
+        }
+        let target_dir = to.join(dir.file_name());
+        fs::create_dir_all(&target_dir).await?;
+        let mut files = fs::read_dir(dir.path()).await?;
+        while let Some(file) = files.next_entry().await? {
+            fs::copy(file.path(), target_dir.join(file.file_name())).await?;
+            copied += 1;
+        }
+    }
+    Ok(copied)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::Cursor;
+
+    use sqlx::migrate;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::infra::files::FsFileStore;
+    use crate::inventory::domain::file_repository::FileStore;
+
+    #[tokio::test]
+    async fn back_up_database_and_files() {
+        let dir = std::env::temp_dir().join(format!("printtables-test-{}", Uuid::now_v7()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let pool = SqlitePool::connect(&format!(
+            "sqlite:{}?mode=rwc",
+            dir.join("data.db").display()
+        ))
+        .await
+        .unwrap();
+        migrate!("./migrations").run(&pool).await.unwrap();
+        let store_dir = dir.join("data.files");
+        let stored = FsFileStore::new(&store_dir)
+            .store(&mut Cursor::new(b"solid cube"))
+            .await
+            .unwrap();
+
+        let backup = backup(&pool, &store_dir, &dir.join("backup"))
+            .await
+            .unwrap();
+
+        assert!(backup.database.is_file());
+        assert_eq!(backup.files, 1);
+        let copied = FsFileStore::new(&backup.files_dir)
+            .open(&stored.sha256)
+            .await
+            .unwrap();
+        assert!(copied.is_some());
+        assert!(super::backup(&pool, &store_dir, &dir.join("backup"))
+            .await
+            .is_err());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
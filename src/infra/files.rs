@@ -0,0 +1,182 @@
+//! Content-addressed file store in a local directory.
+//!
+//! Files are named by the SHA-256 digest of their content and spread over subdirectories named
+//! by the first two digits, e.g., `3f/3fa2…`. Uploads are written to the `tmp` subdirectory and
+//! moved in place when complete, so readers never see partial files. Thumbnails rendered from
+//! stored files are cached in the `thumbnails` subdirectory, e.g., `thumbnails/3fa2….png`.
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use sha2::{Digest as _, Sha256};
+use sqlx::sqlite::SqliteConnectOptions;
+use tokio::fs::{self, File};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use uuid::Uuid;
+
+use crate::inventory::domain::file::{Digest, FileContent, StoredFile, Upload};
+use crate::inventory::domain::file_repository::FileStore;
+use crate::inventory::domain::thumbnail::ThumbnailFormat;
+
+/// Subdirectory of unfinished uploads.
+pub const TMP_DIR: &str = "tmp";
+/// Subdirectory of cached thumbnails. They can be rendered again, so backups skip them.
+pub const THUMBNAILS_DIR: &str = "thumbnails";
+
+#[derive(Debug, Clone)]
+pub struct FsFileStore {
+    root: PathBuf,
+}
+
+impl FsFileStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, sha256: &Digest) -> PathBuf {
+        let digest = sha256.as_ref();
+        self.root.join(&digest[..2]).join(digest)
+    }
+
+    fn thumbnail_path(&self, sha256: &Digest, format: ThumbnailFormat) -> PathBuf {
+        self.root
+            .join(THUMBNAILS_DIR)
+            .join(format!("{}.{}", sha256.as_ref(), format))
+    }
+}
+
+#[async_trait]
+impl FileStore for FsFileStore {
+    async fn store(&self, content: Upload<'_>) -> anyhow::Result<StoredFile> {
+        let tmp_dir = self.root.join(TMP_DIR);
+        fs::create_dir_all(&tmp_dir).await?;
+        let tmp_path = tmp_dir.join(Uuid::now_v7().to_string());
+        let written = write_hashed(content, &tmp_path).await;
+        let (sha256, size) = match written {
+            Ok(written) => written,
+            Err(e) => {
+                let _ = fs::remove_file(&tmp_path).await;
+                return Err(e);
+            }
+        };
+        let path = self.path(&sha256);
+        if fs::try_exists(&path).await? {
+            fs::remove_file(&tmp_path).await?;
+        } else {
+            fs::create_dir_all(path.parent().expect("stored files are in subdirectories")).await?;
+            fs::rename(&tmp_path, &path).await?;
+        }
+        Ok(StoredFile { sha256, size })
+    }
+
+    async fn open(&self, sha256: &Digest) -> anyhow::Result<Option<FileContent>> {
+        match File::open(self.path(sha256)).await {
+            Ok(file) => Ok(Some(Box::pin(file))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn load_thumbnail(
+        &self,
+        sha256: &Digest,
+        format: ThumbnailFormat,
+    ) -> anyhow::Result<Option<Vec<u8>>> {
+        match fs::read(self.thumbnail_path(sha256, format)).await {
+            Ok(image) => Ok(Some(image)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// The image is written to the `tmp` subdirectory first, like uploads.
+    async fn store_thumbnail(
+        &self,
+        sha256: &Digest,
+        format: ThumbnailFormat,
+        image: &[u8],
+    ) -> anyhow::Result<()> {
+        let tmp_dir = self.root.join(TMP_DIR);
+        fs::create_dir_all(&tmp_dir).await?;
+        fs::create_dir_all(self.root.join(THUMBNAILS_DIR)).await?;
+        let tmp_path = tmp_dir.join(Uuid::now_v7().to_string());
+        if let Err(e) = fs::write(&tmp_path, image).await {
+            let _ = fs::remove_file(&tmp_path).await;
+            return Err(e.into());
+        }
+        fs::rename(&tmp_path, self.thumbnail_path(sha256, format)).await?;
+        Ok(())
+    }
+}
+
+/// Copies the content into the file, calculating its digest and size on the way.
+async fn write_hashed(content: Upload<'_>, path: &Path) -> anyhow::Result<(Digest, u64)> {
+    let mut file = File::create(path).await?;
+    let mut hasher = Sha256::new();
+    let mut size = 0;
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let read = content.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        file.write_all(&buffer[..read]).await?;
+        size += read as u64;
+    }
+    file.sync_all().await?;
+    Ok((Digest::from_bytes(&hasher.finalize()), size))
+}
+
+/// Directory of the file store next to the SQLite database, e.g., `dev.files` for `dev.db`.
+/// Databases kept in memory store files in a temporary directory.
+pub fn file_store_dir(database_url: &str) -> anyhow::Result<PathBuf> {
+    let options = SqliteConnectOptions::from_str(database_url)?;
+    let database = options.get_filename();
+    if database_url.contains(":memory:") || database_url.contains("mode=memory") {
+        let name = database.file_stem().unwrap_or_default().to_string_lossy();
+        return Ok(std::env::temp_dir().join(format!("printtables-{}.files", name)));
+    }
+    Ok(database.with_extension("files"))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn store_identical_content_once() {
+        let root = std::env::temp_dir().join(format!("printtables-test-{}", Uuid::now_v7()));
+        let store = FsFileStore::new(&root);
+
+        let first = store.store(&mut Cursor::new(b"solid cube")).await.unwrap();
+        let second = store.store(&mut Cursor::new(b"solid cube")).await.unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(first.size, 10);
+        let mut stored = String::new();
+        store
+            .open(&first.sha256)
+            .await
+            .unwrap()
+            .unwrap()
+            .read_to_string(&mut stored)
+            .await
+            .unwrap();
+        assert_eq!(stored, "solid cube");
+        let mut entries = std::fs::read_dir(root.join(&first.sha256.as_ref()[..2])).unwrap();
+        assert!(entries.next().is_some() && entries.next().is_none());
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn keep_files_next_to_database() {
+        let dir = file_store_dir("sqlite:data/printtables.db").unwrap();
+
+        assert_eq!(dir, PathBuf::from("data/printtables.files"));
+    }
+}
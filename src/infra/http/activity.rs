@@ -0,0 +1,58 @@
+//! Activity service over REST API.
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use super::{json, json_or_missing, RestClient};
+use crate::activity::app::service::ActivityService;
+use crate::activity::domain::entry::ActivityEntry;
+use crate::shared::pagination::{Page, PageRequest};
+
+#[derive(Debug, Clone)]
+pub struct RestActivityService {
+    client: RestClient,
+}
+
+impl RestActivityService {
+    pub fn new(client: RestClient) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl ActivityService for RestActivityService {
+    /// The server keeps history of projects only. Unknown projects have empty history.
+    async fn history(
+        &self,
+        entity_id: Uuid,
+        page: PageRequest,
+    ) -> anyhow::Result<Page<ActivityEntry>> {
+        let response = self
+            .client
+            .client
+            .get(
+                self.client
+                    .url(&format!("/v1/projects/{}/history", entity_id)),
+            )
+            .query(&page_query(page))
+            .send()
+            .await?;
+        let history = json_or_missing(response).await?;
+        Ok(history.unwrap_or_else(|| Page::new(vec![], page, 0)))
+    }
+
+    async fn feed(&self, page: PageRequest) -> anyhow::Result<Page<ActivityEntry>> {
+        let response = self
+            .client
+            .client
+            .get(self.client.url("/v1/activity"))
+            .query(&page_query(page))
+            .send()
+            .await?;
+        json(response).await
+    }
+}
+
+fn page_query(page: PageRequest) -> [(&'static str, u32); 2] {
+    [("page", page.page()), ("per_page", page.per_page())]
+}
@@ -0,0 +1,166 @@
+//! Filament service over REST API.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::{json, json_or_missing, RemoteError, RestClient};
+use crate::filament::app::service::{FilamentError, FilamentService};
+use crate::filament::app::shortage::Shortage;
+use crate::filament::domain::spool::{Consumption, RawSpool, Spool, SpoolId};
+use crate::inventory::domain::metadata::Grams;
+use crate::projects::domain::project::ProjectId;
+use crate::shared::validation::error::describe;
+
+#[derive(Debug, Clone)]
+pub struct RestFilamentService {
+    client: RestClient,
+}
+
+impl RestFilamentService {
+    pub fn new(client: RestClient) -> Self {
+        Self { client }
+    }
+}
+
+/// The server responds with 404 to both missing spools and missing projects.
+/// Consumption errors are reported as missing spools.
+#[async_trait]
+impl FilamentService for RestFilamentService {
+    /// The server assigns a new ID to the spool.
+    async fn register_spool(&self, spool: Spool) -> Result<SpoolId, FilamentError> {
+        let response = self
+            .client
+            .client
+            .post(self.client.url("/v1/filament/spools"))
+            .json(&RawSpool::from(&spool))
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        let created: CreatedPayload = json(response).await.map_err(filament_error)?;
+        Ok(created.id)
+    }
+
+    async fn view_spool(&self, id: SpoolId) -> Result<Spool, FilamentError> {
+        let response = self
+            .client
+            .client
+            .get(self.client.url(&format!("/v1/filament/spools/{}", id)))
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        let spool: Option<SpoolPayload> = json_or_missing(response).await?;
+        Ok(spool.ok_or(FilamentError::MissingSpool)?.try_into()?)
+    }
+
+    async fn list_spools(&self) -> Result<Vec<Spool>, FilamentError> {
+        let response = self
+            .client
+            .client
+            .get(self.client.url("/v1/filament/spools"))
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        let spools: Vec<SpoolPayload> = json(response).await?;
+        Ok(spools
+            .into_iter()
+            .map(Spool::try_from)
+            .collect::<anyhow::Result<_>>()?)
+    }
+
+    async fn consume(
+        &self,
+        spool: SpoolId,
+        project: ProjectId,
+        grams: Grams,
+    ) -> Result<Spool, FilamentError> {
+        let response = self
+            .client
+            .client
+            .post(
+                self.client
+                    .url(&format!("/v1/filament/spools/{}/consumption", spool)),
+            )
+            .json(&ConsumePayload {
+                project,
+                grams: grams.value(),
+            })
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        let spool: Option<SpoolPayload> =
+            json_or_missing(response).await.map_err(filament_error)?;
+        Ok(spool.ok_or(FilamentError::MissingSpool)?.try_into()?)
+    }
+
+    async fn list_consumption(
+        &self,
+        project: ProjectId,
+    ) -> Result<Vec<Consumption>, FilamentError> {
+        let response = self
+            .client
+            .client
+            .get(
+                self.client
+                    .url(&format!("/v1/projects/{}/consumption", project)),
+            )
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        json_or_missing(response)
+            .await?
+            .ok_or(FilamentError::MissingProject)
+    }
+
+    async fn shortages(&self, project: ProjectId) -> Result<Vec<Shortage>, FilamentError> {
+        let response = self
+            .client
+            .client
+            .get(
+                self.client
+                    .url(&format!("/v1/projects/{}/shortages", project)),
+            )
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        json_or_missing(response)
+            .await?
+            .ok_or(FilamentError::MissingProject)
+    }
+}
+
+/// Translates rejected requests into validation errors.
+fn filament_error(e: anyhow::Error) -> FilamentError {
+    match e.downcast::<RemoteError>() {
+        Ok(RemoteError::Rejected(errors)) => FilamentError::ValidationFailed(errors),
+        Ok(e) => FilamentError::GeneralError(e.into()),
+        Err(e) => FilamentError::GeneralError(e),
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ConsumePayload {
+    project: ProjectId,
+    grams: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreatedPayload {
+    id: SpoolId,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpoolPayload {
+    id: SpoolId,
+    #[serde(flatten)]
+    spool: RawSpool,
+}
+
+impl TryFrom<SpoolPayload> for Spool {
+    type Error = anyhow::Error;
+
+    fn try_from(value: SpoolPayload) -> Result<Self, Self::Error> {
+        Spool::parse(value.id, value.spool)
+            .map_err(|errors| anyhow!("invalid spool {}: {}", value.id, describe(&errors)))
+    }
+}
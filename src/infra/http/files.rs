@@ -0,0 +1,160 @@
+//! Part files service over REST API.
+
+use async_trait::async_trait;
+use futures_util::{stream, StreamExt, TryStreamExt};
+use reqwest::multipart::{Form, Part};
+use reqwest::{Body, StatusCode};
+use tokio::sync::mpsc;
+use tokio_util::bytes::Bytes;
+use tokio_util::io::{ReaderStream, StreamReader};
+
+use super::{json_or_missing, remote_error, RemoteError, RestClient};
+use crate::inventory::app::files::{PartFileError, PartFileService};
+use crate::inventory::domain::file::{FileContent, FileName, PartFile, PartFileId, Upload};
+use crate::inventory::domain::part::PartId;
+use crate::inventory::domain::thumbnail::ThumbnailFormat;
+
+#[derive(Debug, Clone)]
+pub struct RestPartFileService {
+    client: RestClient,
+}
+
+impl RestPartFileService {
+    pub fn new(client: RestClient) -> Self {
+        Self { client }
+    }
+}
+
+/// The server responds with 404 to both missing parts and missing files.
+/// Downloads report them as missing files.
+#[async_trait]
+impl PartFileService for RestPartFileService {
+    /// Content is streamed to the server as a multipart form while it's being read. Read errors
+    /// are passed to the body, so the server never gets a truncated file.
+    async fn attach_file(
+        &self,
+        part: PartId,
+        file_name: FileName,
+        content: Upload<'_>,
+    ) -> Result<PartFile, PartFileError> {
+        // The body must own its stream, while the content is borrowed, so chunks are passed
+        // through a channel filled next to the request.
+        let (sender, receiver) = mpsc::channel::<std::io::Result<Bytes>>(4);
+        let chunks = stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|chunk| (chunk, receiver))
+        });
+        let file = Part::stream(Body::wrap_stream(chunks))
+            .file_name(file_name.to_string())
+            .mime_str(file_name.format().media_type())
+            .map_err(anyhow::Error::from)?;
+        let read = async move {
+            let mut content = ReaderStream::new(content);
+            while let Some(chunk) = content.next().await {
+                let failed = chunk.is_err();
+                if sender.send(chunk).await.is_err() || failed {
+                    break;
+                }
+            }
+        };
+        let response = self
+            .client
+            .client
+            .post(
+                self.client
+                    .url(&format!("/v1/inventory/parts/{}/files", part)),
+            )
+            .multipart(Form::new().part("file", file))
+            .send();
+        let (_, response) = tokio::join!(read, response);
+        let response = response.map_err(anyhow::Error::from)?;
+        json_or_missing(response)
+            .await
+            .map_err(files_error)?
+            .ok_or(PartFileError::MissingPart)
+    }
+
+    async fn list_files(&self, part: PartId) -> Result<Vec<PartFile>, PartFileError> {
+        let response = self
+            .client
+            .client
+            .get(
+                self.client
+                    .url(&format!("/v1/inventory/parts/{}/files", part)),
+            )
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        json_or_missing(response)
+            .await?
+            .ok_or(PartFileError::MissingPart)
+    }
+
+    /// Metadata is requested first, then content is streamed from the server.
+    async fn open_file(
+        &self,
+        part: PartId,
+        id: PartFileId,
+    ) -> Result<(PartFile, FileContent), PartFileError> {
+        let files = self.list_files(part).await.map_err(|e| match e {
+            PartFileError::MissingPart => PartFileError::MissingFile,
+            e => e,
+        })?;
+        let file = files
+            .into_iter()
+            .find(|f| f.id == id)
+            .ok_or(PartFileError::MissingFile)?;
+        let response = self
+            .client
+            .client
+            .get(
+                self.client
+                    .url(&format!("/v1/inventory/parts/{}/files/{}", part, id)),
+            )
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        match response.status() {
+            StatusCode::NOT_FOUND => Err(PartFileError::MissingFile),
+            status if status.is_success() => {
+                let stream = response.bytes_stream().map_err(std::io::Error::other);
+                Ok((file, Box::pin(StreamReader::new(stream))))
+            }
+            _ => Err(anyhow::Error::from(remote_error(response).await).into()),
+        }
+    }
+
+    async fn thumbnail(
+        &self,
+        part: PartId,
+        format: ThumbnailFormat,
+    ) -> Result<Vec<u8>, PartFileError> {
+        let response = self
+            .client
+            .client
+            .get(
+                self.client
+                    .url(&format!("/v1/inventory/parts/{}/thumbnail", part)),
+            )
+            .query(&[("format", format.to_string())])
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        match response.status() {
+            StatusCode::NOT_FOUND => Err(PartFileError::MissingFile),
+            status if status.is_success() => {
+                let image = response.bytes().await.map_err(anyhow::Error::from)?;
+                Ok(image.to_vec())
+            }
+            _ => Err(files_error(remote_error(response).await.into())),
+        }
+    }
+}
+
+/// Translates rejected uploads into validation errors.
+fn files_error(e: anyhow::Error) -> PartFileError {
+    match e.downcast::<RemoteError>() {
+        Ok(RemoteError::Rejected(errors)) => PartFileError::ValidationFailed(errors),
+        Ok(e) => PartFileError::GeneralError(e.into()),
+        Err(e) => PartFileError::GeneralError(e),
+    }
+}
@@ -0,0 +1,427 @@
+//! Inventory services over REST API.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{json, json_or_missing, success_or_missing, RemoteError, RestClient};
+use crate::inventory::app::service::{InventoryError, InventoryService};
+use crate::inventory::domain::classification::{Classification, PartFilter, RawClassification};
+use crate::inventory::domain::geometry::Geometry;
+use crate::inventory::domain::metadata::{PartMetadata, RawPartMetadata};
+use crate::inventory::domain::name::Name;
+use crate::inventory::domain::part::{Part, PartId};
+use crate::inventory::domain::revision::{PartRevision, PartRevisionNumber};
+use crate::inventory::domain::source::{PartSource, RawPartSource};
+use crate::inventory::domain::variant::{RawVariantAttributes, Variant, VariantAttributes};
+use crate::projects::app::model_import::{
+    ModelImportError, ModelImportOptions, ModelImportReport, ModelImporter,
+};
+use crate::shared::validation::error::describe;
+
+#[derive(Debug, Clone)]
+pub struct RestInventoryService {
+    client: RestClient,
+}
+
+impl RestInventoryService {
+    pub fn new(client: RestClient) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl InventoryService for RestInventoryService {
+    async fn register_part(&self, name: Name) -> Result<PartId, anyhow::Error> {
+        let response = self
+            .client
+            .client
+            .post(self.client.url("/v1/inventory/parts"))
+            .json(&RegisterPartPayload {
+                name: name.to_string(),
+            })
+            .send()
+            .await?;
+        let created: CreatedPayload = json(response).await?;
+        Ok(created.id)
+    }
+
+    async fn view_part(&self, id: PartId) -> Result<Part, InventoryError> {
+        let response = self
+            .client
+            .client
+            .get(self.client.url(&format!("/v1/inventory/parts/{}", id)))
+            .send()
+            .await
+            .map_err(general_error)?;
+        let part: Option<PartPayload> = json_or_missing(response)
+            .await
+            .map_err(InventoryError::GeneralError)?;
+        part.ok_or(InventoryError::MissingPart)?
+            .try_into()
+            .map_err(InventoryError::GeneralError)
+    }
+
+    async fn rename_part(&self, id: PartId, name: Name) -> Result<(), InventoryError> {
+        let response = self
+            .client
+            .client
+            .put(self.client.url(&format!("/v1/inventory/parts/{}", id)))
+            .json(&RegisterPartPayload {
+                name: name.to_string(),
+            })
+            .send()
+            .await
+            .map_err(general_error)?;
+        match success_or_missing(response).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(InventoryError::MissingPart),
+            Err(e) => Err(InventoryError::GeneralError(e)),
+        }
+    }
+
+    async fn describe_part(
+        &self,
+        id: PartId,
+        metadata: PartMetadata,
+    ) -> Result<(), InventoryError> {
+        let response = self
+            .client
+            .client
+            .put(
+                self.client
+                    .url(&format!("/v1/inventory/parts/{}/metadata", id)),
+            )
+            .json(&RawPartMetadata::from(&metadata))
+            .send()
+            .await
+            .map_err(general_error)?;
+        match success_or_missing(response).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(InventoryError::MissingPart),
+            Err(e) => Err(InventoryError::GeneralError(e)),
+        }
+    }
+
+    async fn classify_part(
+        &self,
+        id: PartId,
+        classification: Classification,
+    ) -> Result<(), InventoryError> {
+        let response = self
+            .client
+            .client
+            .put(
+                self.client
+                    .url(&format!("/v1/inventory/parts/{}/classification", id)),
+            )
+            .json(&RawClassification::from(&classification))
+            .send()
+            .await
+            .map_err(general_error)?;
+        match success_or_missing(response).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(InventoryError::MissingPart),
+            Err(e) => Err(InventoryError::GeneralError(e)),
+        }
+    }
+
+    async fn attribute_part(&self, id: PartId, source: PartSource) -> Result<(), InventoryError> {
+        let response = self
+            .client
+            .client
+            .put(
+                self.client
+                    .url(&format!("/v1/inventory/parts/{}/source", id)),
+            )
+            .json(&RawPartSource::from(&source))
+            .send()
+            .await
+            .map_err(general_error)?;
+        match success_or_missing(response).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(InventoryError::MissingPart),
+            Err(e) => Err(inventory_error(e)),
+        }
+    }
+
+    async fn revise_part(&self, id: PartId) -> Result<PartRevisionNumber, InventoryError> {
+        let response = self
+            .client
+            .client
+            .post(
+                self.client
+                    .url(&format!("/v1/inventory/parts/{}/revisions", id)),
+            )
+            .send()
+            .await
+            .map_err(general_error)?;
+        let revised: Option<RevisedPayload> = json_or_missing(response)
+            .await
+            .map_err(InventoryError::GeneralError)?;
+        Ok(revised.ok_or(InventoryError::MissingPart)?.revision)
+    }
+
+    async fn list_revisions(&self, id: PartId) -> Result<Vec<PartRevision>, InventoryError> {
+        let response = self
+            .client
+            .client
+            .get(
+                self.client
+                    .url(&format!("/v1/inventory/parts/{}/revisions", id)),
+            )
+            .send()
+            .await
+            .map_err(general_error)?;
+        let revisions: Option<Vec<PartRevisionPayload>> = json_or_missing(response)
+            .await
+            .map_err(InventoryError::GeneralError)?;
+        revisions
+            .ok_or(InventoryError::MissingPart)?
+            .into_iter()
+            .map(PartRevision::try_from)
+            .collect::<anyhow::Result<_>>()
+            .map_err(InventoryError::GeneralError)
+    }
+
+    async fn register_variant(
+        &self,
+        parent: PartId,
+        name: Option<Name>,
+        attributes: VariantAttributes,
+    ) -> Result<PartId, InventoryError> {
+        let response = self
+            .client
+            .client
+            .post(
+                self.client
+                    .url(&format!("/v1/inventory/parts/{}/variants", parent)),
+            )
+            .json(&RegisterVariantPayload {
+                name: name.map(|n| n.to_string()),
+                attributes: RawVariantAttributes::from(&attributes),
+            })
+            .send()
+            .await
+            .map_err(general_error)?;
+        match json_or_missing::<CreatedPayload>(response).await {
+            Ok(Some(created)) => Ok(created.id),
+            Ok(None) => Err(InventoryError::MissingPart),
+            Err(e) => Err(inventory_error(e)),
+        }
+    }
+
+    async fn define_variant(
+        &self,
+        id: PartId,
+        variant: Option<Variant>,
+    ) -> Result<(), InventoryError> {
+        let url = self
+            .client
+            .url(&format!("/v1/inventory/parts/{}/variant", id));
+        let request = match variant {
+            Some(variant) => self.client.client.put(url).json(&VariantPayload {
+                parent: variant.parent,
+                attributes: RawVariantAttributes::from(&variant.attributes),
+            }),
+            None => self.client.client.delete(url),
+        };
+        let response = request.send().await.map_err(general_error)?;
+        match success_or_missing(response).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(InventoryError::MissingPart),
+            Err(e) => Err(inventory_error(e)),
+        }
+    }
+
+    async fn find_part_by_name(&self, name: &Name) -> Result<Option<Part>, InventoryError> {
+        let response = self
+            .client
+            .client
+            .get(self.client.url("/v1/inventory/parts"))
+            .query(&[("name", name.as_ref())])
+            .send()
+            .await
+            .map_err(general_error)?;
+        let parts: Vec<PartPayload> = json(response).await.map_err(InventoryError::GeneralError)?;
+        parts
+            .into_iter()
+            .next()
+            .map(Part::try_from)
+            .transpose()
+            .map_err(InventoryError::GeneralError)
+    }
+
+    async fn list_parts(&self, filter: &PartFilter) -> Result<Vec<Part>, InventoryError> {
+        let mut query = vec![];
+        if let Some(tag) = &filter.tag {
+            query.push(("tag", tag.to_string()));
+        }
+        if let Some(category) = &filter.category {
+            query.push(("category", category.to_string()));
+        }
+        if let Some(parent) = &filter.variant_of {
+            query.push(("variant_of", parent.to_string()));
+        }
+        let response = self
+            .client
+            .client
+            .get(self.client.url("/v1/inventory/parts"))
+            .query(&query)
+            .send()
+            .await
+            .map_err(general_error)?;
+        let parts: Vec<PartPayload> = json(response).await.map_err(InventoryError::GeneralError)?;
+        parts
+            .into_iter()
+            .map(Part::try_from)
+            .collect::<anyhow::Result<_>>()
+            .map_err(InventoryError::GeneralError)
+    }
+}
+
+#[async_trait]
+impl ModelImporter for RestInventoryService {
+    async fn import_3mf(
+        &self,
+        content: &[u8],
+        options: ModelImportOptions,
+    ) -> Result<ModelImportReport, ModelImportError> {
+        let mut request = self
+            .client
+            .client
+            .post(self.client.url("/v1/inventory/parts/import"))
+            .header("Content-Type", "model/3mf")
+            .body(content.to_vec());
+        if let Some(project) = options.project {
+            request = request.query(&[("project", project.to_string())]);
+        }
+        let response = request.send().await.map_err(anyhow::Error::from)?;
+        json(response)
+            .await
+            .map_err(|e| match e.downcast::<RemoteError>() {
+                Ok(RemoteError::Rejected(errors)) => ModelImportError::InvalidModel(errors),
+                Ok(e) => ModelImportError::GeneralError(e.into()),
+                Err(e) => ModelImportError::GeneralError(e),
+            })
+    }
+}
+
+fn general_error(e: reqwest::Error) -> InventoryError {
+    InventoryError::GeneralError(e.into())
+}
+
+/// Translates rejected requests into validation errors.
+fn inventory_error(e: anyhow::Error) -> InventoryError {
+    match e.downcast::<RemoteError>() {
+        Ok(RemoteError::Rejected(errors)) => InventoryError::ValidationFailed(errors),
+        Ok(e) => InventoryError::GeneralError(e.into()),
+        Err(e) => InventoryError::GeneralError(e),
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct RegisterPartPayload {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreatedPayload {
+    id: PartId,
+}
+
+#[derive(Debug, Deserialize)]
+struct PartPayload {
+    id: PartId,
+    name: String,
+    revision: PartRevisionNumber,
+    #[serde(flatten)]
+    metadata: RawPartMetadata,
+    #[serde(flatten)]
+    classification: RawClassification,
+    #[serde(flatten)]
+    source: RawPartSource,
+    geometry: Option<Geometry>,
+    variant: Option<VariantPayload>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct VariantPayload {
+    parent: PartId,
+    #[serde(flatten)]
+    attributes: RawVariantAttributes,
+}
+
+#[derive(Debug, Serialize)]
+struct RegisterVariantPayload {
+    name: Option<String>,
+    #[serde(flatten)]
+    attributes: RawVariantAttributes,
+}
+
+impl TryFrom<PartPayload> for Part {
+    type Error = anyhow::Error;
+
+    fn try_from(value: PartPayload) -> Result<Self, Self::Error> {
+        let name = Name::try_from(value.name)?;
+        let metadata = PartMetadata::parse(value.metadata)
+            .map_err(|errors| anyhow!("invalid part {}: {}", value.id, describe(&errors)))?;
+        let classification = Classification::parse(value.classification)
+            .map_err(|errors| anyhow!("invalid part {}: {}", value.id, describe(&errors)))?;
+        let source = PartSource::parse(value.source)
+            .map_err(|errors| anyhow!("invalid part {}: {}", value.id, describe(&errors)))?;
+        let variant = value
+            .variant
+            .map(|v| {
+                VariantAttributes::parse(v.attributes).map(|attributes| Variant {
+                    parent: v.parent,
+                    attributes,
+                })
+            })
+            .transpose()
+            .map_err(|errors| anyhow!("invalid part {}: {}", value.id, describe(&errors)))?;
+        Ok(Part::hydrate(value.id, name)
+            .with_revision(value.revision)
+            .with_metadata(metadata)
+            .with_classification(classification)
+            .with_source(source)
+            .with_geometry(value.geometry)
+            .with_variant(variant))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RevisedPayload {
+    revision: PartRevisionNumber,
+}
+
+#[derive(Debug, Deserialize)]
+struct PartRevisionPayload {
+    part: PartId,
+    revision: PartRevisionNumber,
+    #[serde(flatten)]
+    metadata: RawPartMetadata,
+    replaced_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<PartRevisionPayload> for PartRevision {
+    type Error = anyhow::Error;
+
+    fn try_from(value: PartRevisionPayload) -> Result<Self, Self::Error> {
+        let metadata = PartMetadata::parse(value.metadata).map_err(|errors| {
+            anyhow!(
+                "invalid revision {} of part {}: {}",
+                value.revision,
+                value.part,
+                describe(&errors)
+            )
+        })?;
+        Ok(PartRevision {
+            part: value.part,
+            revision: value.revision,
+            metadata,
+            replaced_at: value.replaced_at,
+        })
+    }
+}
@@ -0,0 +1,96 @@
+//! REST client of a running printtables server.
+//!
+//! Implements application services over the `/v1` API. Interfaces use it to work with a remote
+//! server instead of the local database.
+pub mod activity;
+pub mod filament;
+pub mod files;
+pub mod inventory;
+pub mod packing;
+pub mod projects;
+pub mod settings;
+
+use anyhow::bail;
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::{Client, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+
+use crate::app::actor::Actor;
+use crate::server::rest::{ValidationMessage, ACTOR_HEADER};
+use crate::shared::validation::error::{describe, ValidationError};
+
+/// Errors reported by the remote server.
+#[derive(Debug, Error)]
+pub enum RemoteError {
+    #[error("server rejected the request: {}", describe(.0))]
+    Rejected(Vec<ValidationError>),
+    #[error("server responded with status {0}")]
+    Status(StatusCode),
+}
+
+#[derive(Debug, Clone)]
+pub struct RestClient {
+    client: Client,
+    base_url: String,
+}
+
+impl RestClient {
+    /// Creates a client of the server running at `base_url`, e.g. `http://raspberrypi:4229`.
+    /// Requests are made on behalf of the user running the process.
+    pub fn new(base_url: impl Into<String>) -> anyhow::Result<Self> {
+        let base_url = base_url.into().trim_end_matches('/').to_string();
+        if !(base_url.starts_with("http://") || base_url.starts_with("https://")) {
+            bail!("server URL must start with http:// or https://");
+        }
+        let mut headers = HeaderMap::new();
+        if let Ok(actor) = HeaderValue::from_str(Actor::from_env().as_ref()) {
+            headers.insert(ACTOR_HEADER, actor);
+        }
+        let client = Client::builder().default_headers(headers).build()?;
+        Ok(Self { client, base_url })
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+}
+
+/// Deserializes payload of a successful response.
+async fn json<T: DeserializeOwned>(response: Response) -> anyhow::Result<T> {
+    if response.status().is_success() {
+        Ok(response.json().await?)
+    } else {
+        Err(remote_error(response).await.into())
+    }
+}
+
+/// Deserializes payload of a successful response.
+/// Returns Ok(None) when the server responds with 404.
+async fn json_or_missing<T: DeserializeOwned>(response: Response) -> anyhow::Result<Option<T>> {
+    match response.status() {
+        StatusCode::NOT_FOUND => Ok(None),
+        status if status.is_success() => Ok(Some(response.json().await?)),
+        _ => Err(remote_error(response).await.into()),
+    }
+}
+
+/// Checks the response status, ignoring the payload.
+/// Returns Ok(false) when the server responds with 404.
+async fn success_or_missing(response: Response) -> anyhow::Result<bool> {
+    match response.status() {
+        StatusCode::NOT_FOUND => Ok(false),
+        status if status.is_success() => Ok(true),
+        _ => Err(remote_error(response).await.into()),
+    }
+}
+
+async fn remote_error(response: Response) -> RemoteError {
+    let status = response.status();
+    if status == StatusCode::BAD_REQUEST {
+        if let Ok(message) = response.json::<ValidationMessage>().await {
+            return RemoteError::Rejected(message.errors);
+        }
+    }
+    RemoteError::Status(status)
+}
@@ -0,0 +1,69 @@
+//! Packing service over REST API.
+
+use async_trait::async_trait;
+
+use super::{json_or_missing, RemoteError, RestClient};
+use crate::inventory::domain::part::PartId;
+use crate::projects::app::packing::{
+    PackingError, PackingOptions, PackingService, PartPacking, PlatePlan,
+};
+use crate::projects::domain::project::ProjectId;
+
+#[derive(Debug, Clone)]
+pub struct RestPackingService {
+    client: RestClient,
+}
+
+impl RestPackingService {
+    pub fn new(client: RestClient) -> Self {
+        Self { client }
+    }
+
+    async fn post<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        options: PackingOptions,
+    ) -> Result<Option<T>, PackingError> {
+        let response = self
+            .client
+            .client
+            .post(self.client.url(path))
+            .query(&[
+                ("bed", options.bed.to_string()),
+                ("spacing", options.spacing.value().to_string()),
+            ])
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        json_or_missing(response)
+            .await
+            .map_err(|e| match e.downcast::<RemoteError>() {
+                Ok(RemoteError::Rejected(errors)) => PackingError::ValidationFailed(errors),
+                Ok(e) => PackingError::GeneralError(e.into()),
+                Err(e) => PackingError::GeneralError(e),
+            })
+    }
+}
+
+#[async_trait]
+impl PackingService for RestPackingService {
+    async fn pack_part(
+        &self,
+        part: PartId,
+        options: PackingOptions,
+    ) -> Result<PartPacking, PackingError> {
+        self.post(&format!("/v1/inventory/parts/{}/packing", part), options)
+            .await?
+            .ok_or(PackingError::MissingPart)
+    }
+
+    async fn plan_plates(
+        &self,
+        project: ProjectId,
+        options: PackingOptions,
+    ) -> Result<PlatePlan, PackingError> {
+        self.post(&format!("/v1/projects/{}/plates", project), options)
+            .await?
+            .ok_or(PackingError::MissingProject)
+    }
+}
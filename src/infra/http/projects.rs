@@ -0,0 +1,421 @@
+//! Projects services over REST API.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::{json, json_or_missing, success_or_missing, RemoteError, RestClient};
+use crate::inventory::domain::part::PartId;
+use crate::projects::app::import::{
+    BomImportError, BomImportOptions, BomImportReport, BomImporter,
+};
+use crate::projects::app::revisions::{BomRevisionError, BomRevisionService};
+use crate::projects::app::service::{ProjectError, ProjectsService};
+use crate::projects::domain::multiplier::Multiplier;
+use crate::projects::domain::name::Name;
+use crate::projects::domain::project::{ProjectId, ProjectPart};
+use crate::projects::domain::revision::{BomDiff, BomRevision, RevisionNumber};
+use crate::projects::view::demand::{DemandGrouping, PartDemand};
+use crate::projects::view::license::LicenseReport;
+use crate::projects::view::outdated::OutdatedProject;
+use crate::projects::view::project::{ProjectListItem, ProjectView};
+
+#[derive(Debug, Clone)]
+pub struct RestProjectsService {
+    client: RestClient,
+}
+
+impl RestProjectsService {
+    pub fn new(client: RestClient) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl ProjectsService for RestProjectsService {
+    async fn register_project(&self, name: Name) -> Result<ProjectId, ProjectError> {
+        let response = self
+            .client
+            .client
+            .post(self.client.url("/v1/projects"))
+            .json(&RegisterProjectPayload {
+                name: name.to_string(),
+            })
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        let created: CreatedPayload = json(response).await?;
+        Ok(created.id)
+    }
+
+    async fn view_project(&self, id: ProjectId) -> Result<ProjectView, ProjectError> {
+        let response = self
+            .client
+            .client
+            .get(self.client.url(&format!("/v1/projects/{}", id)))
+            .header("Accept", "application/json")
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        json_or_missing(response)
+            .await?
+            .ok_or(ProjectError::MissingProject)
+    }
+
+    async fn list_projects(&self) -> Result<Vec<ProjectListItem>, ProjectError> {
+        let response = self
+            .client
+            .client
+            .get(self.client.url("/v1/projects"))
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        Ok(json(response).await?)
+    }
+
+    async fn set_project_bom(
+        &self,
+        project: ProjectId,
+        parts: Vec<ProjectPart>,
+    ) -> Result<(), ProjectError> {
+        let payload = BomPayload {
+            parts: parts
+                .iter()
+                .map(|p| BomLinePayload {
+                    part: p.part(),
+                    quantity: p.quantity(),
+                })
+                .collect(),
+        };
+        let response = self
+            .client
+            .client
+            .put(self.client.url(&format!("/v1/projects/{}/parts", project)))
+            .json(&payload)
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        match success_or_missing(response).await? {
+            true => Ok(()),
+            false => Err(ProjectError::MissingProject),
+        }
+    }
+
+    async fn report_progress(
+        &self,
+        project: ProjectId,
+        part: PartId,
+        printed: u32,
+    ) -> Result<(), ProjectError> {
+        let response = self
+            .client
+            .client
+            .post(
+                self.client
+                    .url(&format!("/v1/projects/{}/progress", project)),
+            )
+            .json(&ProgressPayload { part, printed })
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        match success_or_missing(response).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(ProjectError::MissingProject),
+            Err(e) => Err(project_error(e)),
+        }
+    }
+
+    async fn duplicate_project(
+        &self,
+        project: ProjectId,
+        name: Option<Name>,
+    ) -> Result<ProjectId, ProjectError> {
+        let response = self
+            .client
+            .client
+            .post(
+                self.client
+                    .url(&format!("/v1/projects/{}/duplicate", project)),
+            )
+            .json(&CopyProjectPayload {
+                name: name.map(|n| n.to_string()),
+                multiplier: None,
+            })
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        created_project(response).await
+    }
+
+    async fn mark_template(&self, project: ProjectId, template: bool) -> Result<(), ProjectError> {
+        let response = self
+            .client
+            .client
+            .put(
+                self.client
+                    .url(&format!("/v1/projects/{}/template", project)),
+            )
+            .json(&TemplatePayload { template })
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        match success_or_missing(response).await? {
+            true => Ok(()),
+            false => Err(ProjectError::MissingProject),
+        }
+    }
+
+    async fn instantiate_template(
+        &self,
+        template: ProjectId,
+        multiplier: Multiplier,
+        name: Option<Name>,
+    ) -> Result<ProjectId, ProjectError> {
+        let response = self
+            .client
+            .client
+            .post(
+                self.client
+                    .url(&format!("/v1/projects/{}/instantiate", template)),
+            )
+            .json(&CopyProjectPayload {
+                name: name.map(|n| n.to_string()),
+                multiplier: Some(multiplier.value()),
+            })
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        created_project(response).await
+    }
+
+    async fn list_outdated(
+        &self,
+        part: Option<PartId>,
+    ) -> Result<Vec<OutdatedProject>, ProjectError> {
+        let mut request = self
+            .client
+            .client
+            .get(self.client.url("/v1/projects/outdated"));
+        if let Some(part) = part {
+            request = request.query(&[("part", part.to_string())]);
+        }
+        let response = request.send().await.map_err(anyhow::Error::from)?;
+        Ok(json(response).await?)
+    }
+
+    async fn upgrade_parts(
+        &self,
+        project: ProjectId,
+        part: Option<PartId>,
+    ) -> Result<(), ProjectError> {
+        let response = self
+            .client
+            .client
+            .post(
+                self.client
+                    .url(&format!("/v1/projects/{}/upgrade", project)),
+            )
+            .json(&UpgradePayload { part })
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        match success_or_missing(response).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(ProjectError::MissingProject),
+            Err(e) => Err(project_error(e)),
+        }
+    }
+
+    async fn part_demand(&self, grouping: DemandGrouping) -> Result<Vec<PartDemand>, ProjectError> {
+        let response = self
+            .client
+            .client
+            .get(self.client.url("/v1/projects/demand"))
+            .query(&[("group_by", grouping.to_string())])
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        Ok(json(response).await?)
+    }
+
+    async fn license_report(&self, project: ProjectId) -> Result<LicenseReport, ProjectError> {
+        let response = self
+            .client
+            .client
+            .get(
+                self.client
+                    .url(&format!("/v1/projects/{}/licenses", project)),
+            )
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        json_or_missing(response)
+            .await?
+            .ok_or(ProjectError::MissingProject)
+    }
+}
+
+/// Reads the identifier of the created project.
+async fn created_project(response: reqwest::Response) -> Result<ProjectId, ProjectError> {
+    match json_or_missing::<CreatedPayload>(response).await {
+        Ok(Some(created)) => Ok(created.id),
+        Ok(None) => Err(ProjectError::MissingProject),
+        Err(e) => Err(project_error(e)),
+    }
+}
+
+/// Translates rejected requests into validation errors.
+fn project_error(e: anyhow::Error) -> ProjectError {
+    match e.downcast::<RemoteError>() {
+        Ok(RemoteError::Rejected(errors)) => ProjectError::ValidationFailed(errors),
+        Ok(e) => ProjectError::GeneralError(e.into()),
+        Err(e) => ProjectError::GeneralError(e),
+    }
+}
+
+#[async_trait]
+impl BomImporter for RestProjectsService {
+    async fn import_csv(
+        &self,
+        project_id: ProjectId,
+        csv: &str,
+        options: BomImportOptions,
+    ) -> Result<BomImportReport, BomImportError> {
+        let response = self
+            .client
+            .client
+            .post(
+                self.client
+                    .url(&format!("/v1/projects/{}/parts/import", project_id)),
+            )
+            .query(&[
+                ("dry_run", options.dry_run),
+                ("register_missing", options.register_missing),
+            ])
+            .header("Content-Type", "text/csv")
+            .body(csv.to_string())
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        match json_or_missing(response).await {
+            Ok(Some(report)) => Ok(report),
+            Ok(None) => Err(BomImportError::MissingProject),
+            Err(e) => match e.downcast::<RemoteError>() {
+                Ok(RemoteError::Rejected(errors)) => Err(BomImportError::InvalidBom(errors)),
+                Ok(e) => Err(BomImportError::GeneralError(e.into())),
+                Err(e) => Err(BomImportError::GeneralError(e)),
+            },
+        }
+    }
+}
+
+/// The server responds with 404 to both missing projects and missing revisions.
+/// Errors of revision lookups are reported as missing revisions.
+#[async_trait]
+impl BomRevisionService for RestProjectsService {
+    async fn list_revisions(
+        &self,
+        project: ProjectId,
+    ) -> Result<Vec<BomRevision>, BomRevisionError> {
+        let response = self
+            .client
+            .client
+            .get(
+                self.client
+                    .url(&format!("/v1/projects/{}/revisions", project)),
+            )
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        json_or_missing(response)
+            .await?
+            .ok_or(BomRevisionError::MissingProject)
+    }
+
+    async fn diff_revisions(
+        &self,
+        project: ProjectId,
+        from: RevisionNumber,
+        to: RevisionNumber,
+    ) -> Result<BomDiff, BomRevisionError> {
+        let response = self
+            .client
+            .client
+            .get(
+                self.client
+                    .url(&format!("/v1/projects/{}/revisions/diff", project)),
+            )
+            .query(&[("from", from), ("to", to)])
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        json_or_missing(response)
+            .await?
+            .ok_or(BomRevisionError::MissingRevision)
+    }
+
+    async fn restore_revision(
+        &self,
+        project: ProjectId,
+        revision: RevisionNumber,
+    ) -> Result<(), BomRevisionError> {
+        let response = self
+            .client
+            .client
+            .post(self.client.url(&format!(
+                "/v1/projects/{}/revisions/{}/restore",
+                project, revision
+            )))
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        match success_or_missing(response).await? {
+            true => Ok(()),
+            false => Err(BomRevisionError::MissingRevision),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct RegisterProjectPayload {
+    name: String,
+}
+
+#[derive(Debug, Serialize)]
+struct CopyProjectPayload {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    multiplier: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+struct TemplatePayload {
+    template: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreatedPayload {
+    id: ProjectId,
+}
+
+#[derive(Debug, Serialize)]
+struct BomPayload {
+    parts: Vec<BomLinePayload>,
+}
+
+#[derive(Debug, Serialize)]
+struct BomLinePayload {
+    part: PartId,
+    quantity: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct ProgressPayload {
+    part: PartId,
+    printed: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct UpgradePayload {
+    part: Option<PartId>,
+}
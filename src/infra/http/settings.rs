@@ -0,0 +1,48 @@
+//! Settings service over REST API.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+
+use super::{json_or_missing, success_or_missing, RestClient};
+use crate::settings::app::service::SettingsService;
+use crate::settings::domain::costs::{CostSettings, RawCostSettings};
+use crate::shared::validation::error::describe;
+
+#[derive(Debug, Clone)]
+pub struct RestSettingsService {
+    client: RestClient,
+}
+
+impl RestSettingsService {
+    pub fn new(client: RestClient) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl SettingsService for RestSettingsService {
+    async fn cost_settings(&self) -> anyhow::Result<Option<CostSettings>> {
+        let response = self
+            .client
+            .client
+            .get(self.client.url("/v1/settings/costs"))
+            .send()
+            .await?;
+        let raw: Option<RawCostSettings> = json_or_missing(response).await?;
+        raw.map(CostSettings::parse)
+            .transpose()
+            .map_err(|errors| anyhow!("invalid cost settings: {}", describe(&errors)))
+    }
+
+    async fn configure_costs(&self, settings: CostSettings) -> anyhow::Result<()> {
+        let response = self
+            .client
+            .client
+            .put(self.client.url("/v1/settings/costs"))
+            .json(&RawCostSettings::from(&settings))
+            .send()
+            .await?;
+        success_or_missing(response).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+//! In-memory implementation of the activity repository.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::activity::domain::entry::ActivityEntry;
+use crate::activity::domain::repository::ActivityRepository;
+use crate::shared::pagination::{Page, PageRequest};
+
+use super::MemoryStorage;
+
+pub struct InMemoryActivityRepository {
+    storage: Arc<MemoryStorage>,
+}
+
+impl InMemoryActivityRepository {
+    pub fn new(storage: Arc<MemoryStorage>) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl ActivityRepository for InMemoryActivityRepository {
+    async fn append(&self, entries: Vec<ActivityEntry>) -> anyhow::Result<()> {
+        let mut activity = self.storage.activity();
+        for mut entry in entries {
+            entry.seq = activity.len() as i64 + 1;
+            activity.push(entry);
+        }
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        entity_id: Option<Uuid>,
+        page: PageRequest,
+    ) -> anyhow::Result<Page<ActivityEntry>> {
+        let activity = self.storage.activity();
+        let matching: Vec<&ActivityEntry> = activity
+            .iter()
+            .rev()
+            .filter(|e| entity_id.is_none_or(|id| e.entity_id == id))
+            .collect();
+        let items = matching
+            .iter()
+            .skip(usize::try_from(page.offset()).unwrap_or(usize::MAX))
+            .take(page.per_page() as usize)
+            .map(|e| (*e).clone())
+            .collect();
+        Ok(Page::new(items, page, matching.len() as u64))
+    }
+}
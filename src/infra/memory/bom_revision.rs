@@ -0,0 +1,62 @@
+//! In-memory implementation of the BOM revisions repository.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::projects::domain::project::ProjectId;
+use crate::projects::domain::repository::BomRevisionRepository;
+use crate::projects::domain::revision::{BomRevision, RevisionNumber};
+
+use super::MemoryStorage;
+
+pub struct InMemoryBomRevisionRepository {
+    storage: Arc<MemoryStorage>,
+}
+
+impl InMemoryBomRevisionRepository {
+    pub fn new(storage: Arc<MemoryStorage>) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl BomRevisionRepository for InMemoryBomRevisionRepository {
+    async fn append(&self, mut revision: BomRevision) -> anyhow::Result<RevisionNumber> {
+        let mut revisions = self.storage.bom_revisions();
+        revision.revision = revisions
+            .iter()
+            .filter(|r| r.project == revision.project)
+            .map(|r| r.revision)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let number = revision.revision;
+        revisions.push(revision);
+        Ok(number)
+    }
+
+    async fn list(&self, project: ProjectId) -> anyhow::Result<Vec<BomRevision>> {
+        Ok(self
+            .storage
+            .bom_revisions()
+            .iter()
+            .rev()
+            .filter(|r| r.project == project)
+            .cloned()
+            .collect())
+    }
+
+    async fn find(
+        &self,
+        project: ProjectId,
+        revision: RevisionNumber,
+    ) -> anyhow::Result<Option<BomRevision>> {
+        Ok(self
+            .storage
+            .bom_revisions()
+            .iter()
+            .find(|r| r.project == project && r.revision == revision)
+            .cloned())
+    }
+}
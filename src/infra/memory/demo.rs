@@ -0,0 +1,89 @@
+//! Sample data for the demo mode.
+
+use crate::infra::services::Services;
+use crate::inventory::domain::name::Name as PartName;
+use crate::projects::domain::name::Name as ProjectName;
+use crate::projects::domain::project::ProjectPart;
+
+/// BOM line as `(part name, quantity, printed)`.
+type SampleLine = (&'static str, u32, u32);
+
+/// Sample projects with their BOMs.
+const PROJECTS: &[(&str, &[SampleLine])] = &[
+    (
+        "Desk organizer",
+        &[
+            ("Drawer enclosure", 2, 2),
+            ("Drawer", 8, 5),
+            ("Drawer handle", 8, 0),
+        ],
+    ),
+    (
+        "Multiboard wall",
+        &[
+            ("Multiboard tile", 12, 4),
+            ("Multiboard peg", 40, 40),
+            ("Bin, small", 6, 0),
+        ],
+    ),
+    ("Filament dryer box", &[("Spool holder", 2, 0)]),
+];
+
+/// Populates empty services with sample parts and projects.
+pub async fn seed(services: &Services) -> anyhow::Result<()> {
+    for (project_name, bom) in PROJECTS {
+        let project = services
+            .projects
+            .register_project(ProjectName::parse(*project_name)?)
+            .await?;
+        let mut part_ids = Vec::with_capacity(bom.len());
+        for (part_name, _, _) in bom.iter() {
+            let part = services
+                .inventory
+                .register_part(PartName::parse(*part_name)?)
+                .await?;
+            part_ids.push(part);
+        }
+        let lines = part_ids
+            .iter()
+            .zip(bom.iter())
+            .map(|(part, (_, quantity, _))| ProjectPart::new(*part, *quantity))
+            .collect();
+        services.projects.set_project_bom(project, lines).await?;
+        for (part, (_, _, printed)) in part_ids.into_iter().zip(bom.iter()) {
+            if *printed > 0 {
+                services
+                    .projects
+                    .report_progress(project, part, *printed)
+                    .await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn seed_sample_projects() {
+        let services = Services::in_memory();
+
+        seed(&services).await.unwrap();
+
+        let projects = services.projects.list_projects().await.unwrap();
+        assert_eq!(projects.len(), PROJECTS.len());
+        let organizer = projects
+            .iter()
+            .find(|p| p.name() == "Desk organizer")
+            .unwrap();
+        let organizer = services
+            .projects
+            .view_project(organizer.id())
+            .await
+            .unwrap();
+        assert_eq!(organizer.parts()[1].printed(), 5);
+    }
+}
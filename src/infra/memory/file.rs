@@ -0,0 +1,53 @@
+//! In-memory implementation of the part file repository.
+
+use std::sync::Arc;
+
+use anyhow::bail;
+use async_trait::async_trait;
+
+use crate::inventory::domain::file::{PartFile, PartFileId};
+use crate::inventory::domain::file_repository::PartFileRepository;
+use crate::inventory::domain::part::PartId;
+
+use super::MemoryStorage;
+
+pub struct InMemoryPartFileRepository {
+    storage: Arc<MemoryStorage>,
+}
+
+impl InMemoryPartFileRepository {
+    pub fn new(storage: Arc<MemoryStorage>) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl PartFileRepository for InMemoryPartFileRepository {
+    async fn insert(&self, file: PartFile) -> anyhow::Result<()> {
+        let mut files = self.storage.files();
+        if files.iter().any(|f| f.id == file.id) {
+            bail!("file {} already exists", file.id);
+        }
+        files.push(file);
+        Ok(())
+    }
+
+    async fn find_by_id(&self, part: PartId, id: PartFileId) -> anyhow::Result<Option<PartFile>> {
+        Ok(self
+            .storage
+            .files()
+            .iter()
+            .find(|f| f.part == part && f.id == id)
+            .cloned())
+    }
+
+    async fn list_by_part(&self, part: PartId) -> anyhow::Result<Vec<PartFile>> {
+        Ok(self
+            .storage
+            .files()
+            .iter()
+            .filter(|f| f.part == part)
+            .cloned()
+            .collect())
+    }
+}
@@ -0,0 +1,65 @@
+//! In-memory implementation of the content-addressed file store.
+
+use std::io::Cursor;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sha2::{Digest as _, Sha256};
+use tokio::io::AsyncReadExt;
+
+use crate::inventory::domain::file::{Digest, FileContent, StoredFile, Upload};
+use crate::inventory::domain::file_repository::FileStore;
+use crate::inventory::domain::thumbnail::ThumbnailFormat;
+
+use super::MemoryStorage;
+
+pub struct InMemoryFileStore {
+    storage: Arc<MemoryStorage>,
+}
+
+impl InMemoryFileStore {
+    pub fn new(storage: Arc<MemoryStorage>) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl FileStore for InMemoryFileStore {
+    async fn store(&self, content: Upload<'_>) -> anyhow::Result<StoredFile> {
+        let mut bytes = Vec::new();
+        content.read_to_end(&mut bytes).await?;
+        let sha256 = Digest::from_bytes(&Sha256::digest(&bytes));
+        let size = bytes.len() as u64;
+        self.storage
+            .file_content()
+            .entry(sha256.clone())
+            .or_insert(bytes);
+        Ok(StoredFile { sha256, size })
+    }
+
+    async fn open(&self, sha256: &Digest) -> anyhow::Result<Option<FileContent>> {
+        let content = self.storage.file_content().get(sha256).cloned();
+        Ok(content.map(|bytes| Box::pin(Cursor::new(bytes)) as FileContent))
+    }
+
+    async fn load_thumbnail(
+        &self,
+        sha256: &Digest,
+        format: ThumbnailFormat,
+    ) -> anyhow::Result<Option<Vec<u8>>> {
+        let key = (sha256.clone(), format);
+        Ok(self.storage.thumbnails().get(&key).cloned())
+    }
+
+    async fn store_thumbnail(
+        &self,
+        sha256: &Digest,
+        format: ThumbnailFormat,
+        image: &[u8],
+    ) -> anyhow::Result<()> {
+        self.storage
+            .thumbnails()
+            .insert((sha256.clone(), format), image.to_vec());
+        Ok(())
+    }
+}
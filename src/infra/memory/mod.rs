@@ -0,0 +1,133 @@
+//! In-memory storage adapters.
+//! Repositories keep data in shared maps guarded by mutexes and lose it when the process stops.
+//! Useful for service tests and for the demo mode of the server.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::activity::domain::entry::ActivityEntry;
+use crate::filament::domain::spool::{Consumption, Spool, SpoolId};
+use crate::inventory::domain::file::{Digest, PartFile};
+use crate::inventory::domain::part::{Part, PartId};
+use crate::inventory::domain::revision::PartRevision;
+use crate::inventory::domain::thumbnail::ThumbnailFormat;
+use crate::projects::domain::project::{Project, ProjectId};
+use crate::projects::domain::revision::BomRevision;
+use crate::settings::domain::costs::CostSettings;
+
+pub mod activity;
+pub mod bom_revision;
+pub mod demo;
+pub mod file;
+pub mod file_store;
+pub mod part;
+pub mod part_revision;
+pub mod project;
+pub mod project_view;
+pub mod settings;
+pub mod spool;
+pub mod unit_of_work;
+
+/// Storage shared by in-memory repositories.
+/// Project views join projects with parts, so all repositories must use the same storage.
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    projects: Mutex<HashMap<ProjectId, Project>>,
+    parts: Mutex<HashMap<PartId, Part>>,
+    part_revisions: Mutex<Vec<PartRevision>>,
+    activity: Mutex<Vec<ActivityEntry>>,
+    bom_revisions: Mutex<Vec<BomRevision>>,
+    spools: Mutex<HashMap<SpoolId, Spool>>,
+    consumption: Mutex<Vec<Consumption>>,
+    cost_settings: Mutex<Option<CostSettings>>,
+    files: Mutex<Vec<PartFile>>,
+    /// Content of stored files by their digest. Not part of units of work, like a file system.
+    file_content: Mutex<HashMap<Digest, Vec<u8>>>,
+    /// Thumbnails rendered from stored files, cached like files on a file system.
+    thumbnails: Mutex<HashMap<(Digest, ThumbnailFormat), Vec<u8>>>,
+    /// Held by the active unit of work, so units of work run one after another.
+    units_of_work: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Copies stored data into independent storage.
+    fn snapshot(&self) -> Arc<Self> {
+        Arc::new(Self {
+            projects: Mutex::new(self.projects().clone()),
+            parts: Mutex::new(self.parts().clone()),
+            part_revisions: Mutex::new(self.part_revisions().clone()),
+            activity: Mutex::new(self.activity().clone()),
+            bom_revisions: Mutex::new(self.bom_revisions().clone()),
+            spools: Mutex::new(self.spools().clone()),
+            consumption: Mutex::new(self.consumption().clone()),
+            cost_settings: Mutex::new(*self.cost_settings()),
+            files: Mutex::new(self.files().clone()),
+            file_content: Mutex::default(),
+            thumbnails: Mutex::default(),
+            units_of_work: Arc::default(),
+        })
+    }
+
+    /// Replaces stored data with data of the snapshot.
+    fn restore(&self, snapshot: &Self) {
+        *self.projects() = snapshot.projects().clone();
+        *self.parts() = snapshot.parts().clone();
+        *self.part_revisions() = snapshot.part_revisions().clone();
+        *self.activity() = snapshot.activity().clone();
+        *self.bom_revisions() = snapshot.bom_revisions().clone();
+        *self.spools() = snapshot.spools().clone();
+        *self.consumption() = snapshot.consumption().clone();
+        *self.cost_settings() = *snapshot.cost_settings();
+        *self.files() = snapshot.files().clone();
+    }
+
+    fn projects(&self) -> MutexGuard<'_, HashMap<ProjectId, Project>> {
+        self.projects.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn parts(&self) -> MutexGuard<'_, HashMap<PartId, Part>> {
+        self.parts.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn part_revisions(&self) -> MutexGuard<'_, Vec<PartRevision>> {
+        self.part_revisions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn activity(&self) -> MutexGuard<'_, Vec<ActivityEntry>> {
+        self.activity.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn bom_revisions(&self) -> MutexGuard<'_, Vec<BomRevision>> {
+        self.bom_revisions.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn spools(&self) -> MutexGuard<'_, HashMap<SpoolId, Spool>> {
+        self.spools.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn consumption(&self) -> MutexGuard<'_, Vec<Consumption>> {
+        self.consumption.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn cost_settings(&self) -> MutexGuard<'_, Option<CostSettings>> {
+        self.cost_settings.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn files(&self) -> MutexGuard<'_, Vec<PartFile>> {
+        self.files.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn file_content(&self) -> MutexGuard<'_, HashMap<Digest, Vec<u8>>> {
+        self.file_content.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn thumbnails(&self) -> MutexGuard<'_, HashMap<(Digest, ThumbnailFormat), Vec<u8>>> {
+        self.thumbnails.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
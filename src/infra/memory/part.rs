@@ -0,0 +1,71 @@
+//! In-memory implementation of the part repository.
+
+use std::sync::Arc;
+
+use anyhow::bail;
+use async_trait::async_trait;
+
+use crate::inventory::domain::classification::PartFilter;
+use crate::inventory::domain::name::Name;
+use crate::inventory::domain::part::{Part, PartId};
+use crate::inventory::domain::part_repository::PartRepository;
+
+use super::MemoryStorage;
+
+#[derive(Debug)]
+pub struct InMemoryPartRepository {
+    storage: Arc<MemoryStorage>,
+}
+
+impl InMemoryPartRepository {
+    pub fn new(storage: Arc<MemoryStorage>) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl PartRepository for InMemoryPartRepository {
+    async fn insert(&self, part: Part) -> anyhow::Result<()> {
+        let mut parts = self.storage.parts();
+        if parts.contains_key(&part.id()) {
+            bail!("part {} already exists", part.id());
+        }
+        parts.insert(part.id(), part);
+        Ok(())
+    }
+
+    async fn update(&self, part: Part) -> anyhow::Result<()> {
+        let mut parts = self.storage.parts();
+        match parts.get_mut(&part.id()) {
+            Some(stored) => *stored = part,
+            None => bail!("part {} does not exist", part.id()),
+        }
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: PartId) -> anyhow::Result<Option<Part>> {
+        Ok(self.storage.parts().get(&id).cloned())
+    }
+
+    async fn find_by_name(&self, name: &Name) -> anyhow::Result<Option<Part>> {
+        let name = name.as_ref().to_lowercase();
+        let parts = self.storage.parts();
+        let found = parts
+            .values()
+            .filter(|p| p.name().as_ref().to_lowercase() == name)
+            .min_by_key(|p| p.id());
+        Ok(found.cloned())
+    }
+
+    async fn list(&self, filter: &PartFilter) -> anyhow::Result<Vec<Part>> {
+        let mut parts: Vec<Part> = self
+            .storage
+            .parts()
+            .values()
+            .filter(|part| filter.matches(part))
+            .cloned()
+            .collect();
+        parts.sort_by_cached_key(|p| p.name().as_ref().to_lowercase());
+        Ok(parts)
+    }
+}
@@ -0,0 +1,41 @@
+//! In-memory implementation of the part revisions repository.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::inventory::domain::part::PartId;
+use crate::inventory::domain::part_repository::PartRevisionRepository;
+use crate::inventory::domain::revision::PartRevision;
+
+use super::MemoryStorage;
+
+pub struct InMemoryPartRevisionRepository {
+    storage: Arc<MemoryStorage>,
+}
+
+impl InMemoryPartRevisionRepository {
+    pub fn new(storage: Arc<MemoryStorage>) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl PartRevisionRepository for InMemoryPartRevisionRepository {
+    async fn append(&self, revision: PartRevision) -> anyhow::Result<()> {
+        self.storage.part_revisions().push(revision);
+        Ok(())
+    }
+
+    async fn list(&self, part: PartId) -> anyhow::Result<Vec<PartRevision>> {
+        let mut revisions: Vec<PartRevision> = self
+            .storage
+            .part_revisions()
+            .iter()
+            .filter(|r| r.part == part)
+            .cloned()
+            .collect();
+        revisions.sort_by_key(|r| std::cmp::Reverse(r.revision));
+        Ok(revisions)
+    }
+}
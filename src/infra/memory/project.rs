@@ -0,0 +1,53 @@
+//! In-memory implementation of the project repository.
+
+use std::sync::Arc;
+
+use anyhow::bail;
+use async_trait::async_trait;
+
+use crate::projects::domain::project::{Project, ProjectId};
+use crate::projects::domain::repository::ProjectRepository;
+
+use super::MemoryStorage;
+
+pub struct InMemoryProjectsRepository {
+    storage: Arc<MemoryStorage>,
+}
+
+impl InMemoryProjectsRepository {
+    pub fn new(storage: Arc<MemoryStorage>) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl ProjectRepository for InMemoryProjectsRepository {
+    async fn create(&self, project: Project) -> anyhow::Result<ProjectId> {
+        let id = project.id();
+        check_pinned(&project)?;
+        self.storage.projects().insert(id, project);
+        Ok(id)
+    }
+
+    async fn find_by_id(&self, id: ProjectId) -> anyhow::Result<Option<Project>> {
+        Ok(self.storage.projects().get(&id).cloned())
+    }
+
+    async fn update(&self, project: Project) -> anyhow::Result<()> {
+        check_pinned(&project)?;
+        let mut projects = self.storage.projects();
+        match projects.get_mut(&project.id()) {
+            Some(stored) => *stored = project,
+            None => bail!("project {} does not exist", project.id()),
+        }
+        Ok(())
+    }
+}
+
+/// Stored BOM lines keep the revision pinned by the project service.
+fn check_pinned(project: &Project) -> anyhow::Result<()> {
+    if let Some(part) = project.unpinned_parts().first() {
+        bail!("BOM line of part {} is not pinned to a revision", part);
+    }
+    Ok(())
+}
@@ -0,0 +1,153 @@
+//! In-memory implementation of the project view repository.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::projects::domain::project::ProjectId;
+use crate::projects::view::demand::{demand, DemandGrouping, PartDemand};
+use crate::projects::view::license::{LicenseReport, PartLicense};
+use crate::projects::view::outdated::{OutdatedPart, OutdatedProject};
+use crate::projects::view::project::{BomVariant, ProjectListItem, ProjectPart, ProjectView};
+use crate::projects::view::repository::ProjectViewRepository;
+use crate::projects::view::summary::ProjectSummary;
+
+use super::MemoryStorage;
+
+pub struct InMemoryProjectViewRepository {
+    storage: Arc<MemoryStorage>,
+}
+
+impl InMemoryProjectViewRepository {
+    pub fn new(storage: Arc<MemoryStorage>) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl ProjectViewRepository for InMemoryProjectViewRepository {
+    async fn get_view_by_id(&self, id: ProjectId) -> anyhow::Result<Option<ProjectView>> {
+        let Some(project) = self.storage.projects().get(&id).cloned() else {
+            return Ok(None);
+        };
+        let parts = self.storage.parts();
+        let revisions = self.storage.part_revisions();
+        let bom = project
+            .parts()
+            .iter()
+            .filter_map(|line| {
+                let part = parts.get(&line.part())?;
+                let revision = line.revision().unwrap_or(part.revision());
+                // Estimates of replaced revisions are kept with the revision.
+                let metadata = revisions
+                    .iter()
+                    .find(|r| r.part == part.id() && r.revision == revision)
+                    .map_or(part.metadata(), |r| &r.metadata);
+                let variant = part.variant().and_then(|variant| {
+                    let parent = parts.get(&variant.parent)?;
+                    Some(BomVariant {
+                        parent_id: parent.id(),
+                        parent_name: parent.name().to_string(),
+                        color: variant.attributes.color.as_ref().map(|c| c.to_string()),
+                        size: variant.attributes.size.as_ref().map(|s| s.to_string()),
+                    })
+                });
+                let view = ProjectPart::new(part.id(), part.name().to_string(), line.quantity())
+                    .with_printed(line.printed())
+                    .with_revision(revision, part.revision())
+                    .with_category(
+                        part.classification()
+                            .category
+                            .as_ref()
+                            .map(|c| c.to_string())
+                            .as_deref(),
+                    )
+                    .with_variant(variant)
+                    .with_estimates(
+                        metadata.material.map(|m| m.as_str()),
+                        metadata.color.as_ref().map(|c| c.as_ref()),
+                        metadata.grams.map(|g| g.value()),
+                        metadata.minutes.map(|m| m.value()),
+                    );
+                Some(view)
+            })
+            .collect::<Vec<_>>();
+        let summary = ProjectSummary::of(&bom);
+        Ok(Some(
+            ProjectView::new(project.id(), project.name().to_string(), bom)
+                .with_template(project.is_template())
+                .with_summary(summary),
+        ))
+    }
+
+    async fn list_projects(&self) -> anyhow::Result<Vec<ProjectListItem>> {
+        let mut projects: Vec<_> = self.storage.projects().values().cloned().collect();
+        projects.sort_by_key(|p| std::cmp::Reverse((p.created_at(), p.id())));
+        let items = projects
+            .into_iter()
+            .map(|p| {
+                ProjectListItem::new(p.id(), p.name().to_string(), p.parts().len() as u32)
+                    .with_template(p.is_template())
+            })
+            .collect();
+        Ok(items)
+    }
+
+    async fn list_outdated(&self) -> anyhow::Result<Vec<OutdatedProject>> {
+        let mut projects: Vec<_> = self.storage.projects().values().cloned().collect();
+        projects.sort_by_key(|p| std::cmp::Reverse((p.created_at(), p.id())));
+        let parts = self.storage.parts();
+        let outdated = projects
+            .into_iter()
+            .filter_map(|project| {
+                let mut lines: Vec<OutdatedPart> = project
+                    .parts()
+                    .iter()
+                    .filter_map(|line| {
+                        let part = parts.get(&line.part())?;
+                        let revision = line.revision()?;
+                        (revision < part.revision()).then(|| OutdatedPart {
+                            part_id: part.id(),
+                            name: part.name().to_string(),
+                            revision,
+                            latest_revision: part.revision(),
+                        })
+                    })
+                    .collect();
+                lines.sort_by_key(|line| line.name.to_lowercase());
+                (!lines.is_empty()).then(|| OutdatedProject {
+                    id: project.id(),
+                    name: project.name().to_string(),
+                    parts: lines,
+                })
+            })
+            .collect();
+        Ok(outdated)
+    }
+
+    async fn part_demand(&self, grouping: DemandGrouping) -> anyhow::Result<Vec<PartDemand>> {
+        let ids: Vec<ProjectId> = self.storage.projects().keys().copied().collect();
+        let mut projects = vec![];
+        for id in ids {
+            projects.extend(self.get_view_by_id(id).await?);
+        }
+        Ok(demand(&projects, grouping))
+    }
+
+    async fn get_licenses_by_id(&self, id: ProjectId) -> anyhow::Result<Option<LicenseReport>> {
+        let Some(project) = self.storage.projects().get(&id).cloned() else {
+            return Ok(None);
+        };
+        let parts = self.storage.parts();
+        let licenses = project
+            .parts()
+            .iter()
+            .filter_map(|line| parts.get(&line.part()).map(PartLicense::from))
+            .collect();
+        Ok(Some(LicenseReport::new(
+            project.id(),
+            project.name().to_string(),
+            licenses,
+        )))
+    }
+}
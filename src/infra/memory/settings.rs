@@ -0,0 +1,32 @@
+//! In-memory implementation of the settings repository.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::settings::domain::costs::CostSettings;
+use crate::settings::domain::repository::SettingsRepository;
+
+use super::MemoryStorage;
+
+pub struct InMemorySettingsRepository {
+    storage: Arc<MemoryStorage>,
+}
+
+impl InMemorySettingsRepository {
+    pub fn new(storage: Arc<MemoryStorage>) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl SettingsRepository for InMemorySettingsRepository {
+    async fn cost_settings(&self) -> anyhow::Result<Option<CostSettings>> {
+        Ok(*self.storage.cost_settings())
+    }
+
+    async fn save_cost_settings(&self, settings: CostSettings) -> anyhow::Result<()> {
+        *self.storage.cost_settings() = Some(settings);
+        Ok(())
+    }
+}
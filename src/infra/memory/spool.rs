@@ -0,0 +1,74 @@
+//! In-memory implementation of the spool repository.
+
+use std::sync::Arc;
+
+use anyhow::bail;
+use async_trait::async_trait;
+
+use crate::filament::domain::repository::SpoolRepository;
+use crate::filament::domain::spool::{Consumption, Spool, SpoolId};
+use crate::projects::domain::project::ProjectId;
+
+use super::MemoryStorage;
+
+pub struct InMemorySpoolRepository {
+    storage: Arc<MemoryStorage>,
+}
+
+impl InMemorySpoolRepository {
+    pub fn new(storage: Arc<MemoryStorage>) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl SpoolRepository for InMemorySpoolRepository {
+    async fn insert(&self, spool: Spool) -> anyhow::Result<()> {
+        let mut spools = self.storage.spools();
+        if spools.contains_key(&spool.id()) {
+            bail!("spool {} already exists", spool.id());
+        }
+        spools.insert(spool.id(), spool);
+        Ok(())
+    }
+
+    async fn update(&self, spool: Spool) -> anyhow::Result<()> {
+        let mut spools = self.storage.spools();
+        match spools.get_mut(&spool.id()) {
+            Some(stored) => *stored = spool,
+            None => bail!("spool {} does not exist", spool.id()),
+        }
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: SpoolId) -> anyhow::Result<Option<Spool>> {
+        Ok(self.storage.spools().get(&id).cloned())
+    }
+
+    async fn list_all(&self) -> anyhow::Result<Vec<Spool>> {
+        let mut spools: Vec<Spool> = self.storage.spools().values().cloned().collect();
+        spools.sort_by_key(|s| {
+            (
+                s.material().as_str(),
+                s.color().as_ref().to_lowercase(),
+                s.id(),
+            )
+        });
+        Ok(spools)
+    }
+
+    async fn record_consumption(&self, consumption: Consumption) -> anyhow::Result<()> {
+        self.storage.consumption().push(consumption);
+        Ok(())
+    }
+
+    async fn list_consumption(&self, project: ProjectId) -> anyhow::Result<Vec<Consumption>> {
+        Ok(self
+            .storage
+            .consumption()
+            .iter()
+            .filter(|c| c.project == project)
+            .cloned()
+            .collect())
+    }
+}
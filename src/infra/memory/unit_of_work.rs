@@ -0,0 +1,136 @@
+//! In-memory unit of work.
+//! Changes are made on a snapshot of the storage and copied back on commit.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::OwnedMutexGuard;
+
+use crate::activity::domain::repository::ActivityRepository;
+use crate::app::unit_of_work::{UnitOfWork, UnitOfWorkFactory};
+use crate::filament::domain::repository::SpoolRepository;
+use crate::inventory::domain::file_repository::PartFileRepository;
+use crate::inventory::domain::part_repository::{PartRepository, PartRevisionRepository};
+use crate::projects::domain::repository::{BomRevisionRepository, ProjectRepository};
+
+use super::activity::InMemoryActivityRepository;
+use super::bom_revision::InMemoryBomRevisionRepository;
+use super::file::InMemoryPartFileRepository;
+use super::part::InMemoryPartRepository;
+use super::part_revision::InMemoryPartRevisionRepository;
+use super::project::InMemoryProjectsRepository;
+use super::spool::InMemorySpoolRepository;
+use super::MemoryStorage;
+
+pub struct InMemoryUnitOfWork {
+    storage: Arc<MemoryStorage>,
+    snapshot: Arc<MemoryStorage>,
+    _lock: OwnedMutexGuard<()>,
+}
+
+#[async_trait]
+impl UnitOfWork for InMemoryUnitOfWork {
+    fn projects(&self) -> Arc<dyn ProjectRepository> {
+        Arc::new(InMemoryProjectsRepository::new(Arc::clone(&self.snapshot)))
+    }
+
+    fn parts(&self) -> Arc<dyn PartRepository> {
+        Arc::new(InMemoryPartRepository::new(Arc::clone(&self.snapshot)))
+    }
+
+    fn activity(&self) -> Arc<dyn ActivityRepository> {
+        Arc::new(InMemoryActivityRepository::new(Arc::clone(&self.snapshot)))
+    }
+
+    fn bom_revisions(&self) -> Arc<dyn BomRevisionRepository> {
+        Arc::new(InMemoryBomRevisionRepository::new(Arc::clone(
+            &self.snapshot,
+        )))
+    }
+
+    fn spools(&self) -> Arc<dyn SpoolRepository> {
+        Arc::new(InMemorySpoolRepository::new(Arc::clone(&self.snapshot)))
+    }
+
+    fn files(&self) -> Arc<dyn PartFileRepository> {
+        Arc::new(InMemoryPartFileRepository::new(Arc::clone(&self.snapshot)))
+    }
+
+    fn part_revisions(&self) -> Arc<dyn PartRevisionRepository> {
+        Arc::new(InMemoryPartRevisionRepository::new(Arc::clone(
+            &self.snapshot,
+        )))
+    }
+
+    async fn commit(self: Box<Self>) -> anyhow::Result<()> {
+        self.storage.restore(&self.snapshot);
+        Ok(())
+    }
+
+    async fn rollback(self: Box<Self>) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+pub struct InMemoryUnitOfWorkFactory {
+    storage: Arc<MemoryStorage>,
+}
+
+impl InMemoryUnitOfWorkFactory {
+    pub fn new(storage: Arc<MemoryStorage>) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl UnitOfWorkFactory for InMemoryUnitOfWorkFactory {
+    async fn begin(&self) -> anyhow::Result<Box<dyn UnitOfWork>> {
+        let lock = Arc::clone(&self.storage.units_of_work).lock_owned().await;
+        Ok(Box::new(InMemoryUnitOfWork {
+            storage: Arc::clone(&self.storage),
+            snapshot: self.storage.snapshot(),
+            _lock: lock,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::inventory::domain::name::Name;
+    use crate::inventory::domain::part::Part;
+
+    #[tokio::test]
+    async fn apply_changes_on_commit() {
+        let storage = MemoryStorage::new();
+        let units = InMemoryUnitOfWorkFactory::new(Arc::clone(&storage));
+        let part = Part::new(Name::parse("Drawer").unwrap());
+        let id = part.id();
+
+        let uow = units.begin().await.unwrap();
+        uow.parts().insert(part).await.unwrap();
+        assert!(
+            storage.parts().is_empty(),
+            "changes must not be visible before commit"
+        );
+        uow.commit().await.unwrap();
+
+        assert!(storage.parts().contains_key(&id));
+    }
+
+    #[tokio::test]
+    async fn discard_changes_on_drop() {
+        let storage = MemoryStorage::new();
+        let units = InMemoryUnitOfWorkFactory::new(Arc::clone(&storage));
+
+        let uow = units.begin().await.unwrap();
+        uow.parts()
+            .insert(Part::new(Name::parse("Drawer").unwrap()))
+            .await
+            .unwrap();
+        drop(uow);
+
+        assert!(storage.parts().is_empty());
+    }
+}
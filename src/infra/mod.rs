@@ -1,2 +1,8 @@
+pub mod backup;
+pub mod files;
+pub mod http;
+#[cfg(any(test, feature = "memory"))]
+pub mod memory;
+pub mod services;
 pub mod sqlx;
 pub mod tracing;
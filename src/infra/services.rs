@@ -0,0 +1,233 @@
+//! Wires application services with the infrastructure they run on.
+//! Every printtables interface (server, TUI, CLI) builds its services here, either on top of the
+//! local database or as a client of a remote server.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use sqlx::{migrate, SqlitePool};
+
+use crate::activity::app::service::{ActivityService, DefaultActivityService};
+use crate::app::events::EventBus;
+use crate::app::unit_of_work::{DispatchingUnitOfWorkFactory, UnitOfWorkFactory};
+use crate::filament::app::service::{DefaultFilamentService, FilamentService};
+use crate::infra::files::{file_store_dir, FsFileStore};
+use crate::infra::http::activity::RestActivityService;
+use crate::infra::http::filament::RestFilamentService;
+use crate::infra::http::files::RestPartFileService;
+use crate::infra::http::inventory::RestInventoryService;
+use crate::infra::http::packing::RestPackingService;
+use crate::infra::http::projects::RestProjectsService;
+use crate::infra::http::settings::RestSettingsService;
+use crate::infra::http::RestClient;
+use crate::infra::sqlx::activity::SqlxActivityRepository;
+use crate::infra::sqlx::file::SqlxPartFileRepository;
+use crate::infra::sqlx::part::SqlxPartRepository;
+use crate::infra::sqlx::project_view::SqlxProjectViewRepository;
+use crate::infra::sqlx::settings::SqlxSettingsRepository;
+use crate::infra::sqlx::spool::SqlxSpoolRepository;
+use crate::infra::sqlx::unit_of_work::SqlxUnitOfWorkFactory;
+use crate::infra::sqlx::{database_pool, database_url};
+use crate::inventory::app::files::{DefaultPartFileService, PartFileService};
+use crate::inventory::app::service::{DefaultInventoryService, InventoryService};
+use crate::projects::app::completion::CompletionNotifier;
+use crate::projects::app::import::{BomImporter, DefaultBomImporter};
+use crate::projects::app::model_import::{DefaultModelImporter, ModelImporter};
+use crate::projects::app::packing::{DefaultPackingService, PackingService};
+use crate::projects::app::revisions::{BomRevisionService, DefaultBomRevisionService};
+use crate::projects::app::service::{DefaultProjectService, ProjectsService};
+use crate::settings::app::service::{DefaultSettingsService, SettingsService};
+
+/// Application services shared by printtables interfaces.
+#[derive(Clone)]
+pub struct Services {
+    pub projects: Arc<dyn ProjectsService>,
+    pub inventory: Arc<dyn InventoryService>,
+    pub files: Arc<dyn PartFileService>,
+    pub bom_importer: Arc<dyn BomImporter>,
+    pub model_importer: Arc<dyn ModelImporter>,
+    pub revisions: Arc<dyn BomRevisionService>,
+    pub packing: Arc<dyn PackingService>,
+    pub activity: Arc<dyn ActivityService>,
+    pub filament: Arc<dyn FilamentService>,
+    pub settings: Arc<dyn SettingsService>,
+    /// Committed domain events. Only services running on local storage dispatch events.
+    pub events: EventBus,
+}
+
+impl Services {
+    /// Builds services backed by the database configured by the `DATABASE_URL` variable and the
+    /// file store next to it.
+    pub async fn local() -> anyhow::Result<Self> {
+        let files_dir = file_store_dir(&database_url())?;
+        Self::sqlite(database_pool().await?, files_dir).await
+    }
+
+    /// Builds services backed by the SQLite database and part files kept in the `files_dir`.
+    /// Runs pending database migrations first.
+    pub async fn sqlite(db_pool: SqlitePool, files_dir: PathBuf) -> anyhow::Result<Self> {
+        migrate!("./migrations").run(&db_pool).await?;
+
+        let events = EventBus::default();
+        let units: Arc<dyn UnitOfWorkFactory> = Arc::new(DispatchingUnitOfWorkFactory::new(
+            Arc::new(SqlxUnitOfWorkFactory::new(db_pool.clone())),
+            events.clone(),
+        ));
+
+        let settings_repo = Arc::new(SqlxSettingsRepository::new(db_pool.clone()));
+        let settings = Arc::new(DefaultSettingsService::new(settings_repo.clone()));
+
+        let project_view_repo = Arc::new(SqlxProjectViewRepository::new(db_pool.clone()));
+        let projects: Arc<dyn ProjectsService> = Arc::new(DefaultProjectService::new(
+            Arc::clone(&units),
+            project_view_repo.clone(),
+            settings_repo,
+        ));
+        events.subscribe(Arc::new(CompletionNotifier::new(project_view_repo)));
+
+        let parts_repo = Arc::new(SqlxPartRepository::new(db_pool.clone()));
+        let inventory: Arc<dyn InventoryService> = Arc::new(DefaultInventoryService::new(
+            Arc::clone(&units),
+            parts_repo.clone(),
+        ));
+        let files = Arc::new(DefaultPartFileService::new(
+            Arc::clone(&units),
+            Arc::new(SqlxPartFileRepository::new(db_pool.clone())),
+            parts_repo.clone(),
+            Arc::new(FsFileStore::new(files_dir)),
+        ));
+        let packing = Arc::new(DefaultPackingService::new(
+            Arc::clone(&projects),
+            parts_repo,
+        ));
+
+        let bom_importer = Arc::new(DefaultBomImporter::new(
+            Arc::clone(&projects),
+            Arc::clone(&inventory),
+            Arc::clone(&units),
+        ));
+        let model_importer = Arc::new(DefaultModelImporter::new(Arc::clone(&units)));
+        let filament = Arc::new(DefaultFilamentService::new(
+            Arc::clone(&units),
+            Arc::new(SqlxSpoolRepository::new(db_pool.clone())),
+            Arc::clone(&projects),
+        ));
+        let revisions = Arc::new(DefaultBomRevisionService::new(units));
+
+        let activity = Arc::new(DefaultActivityService::new(Arc::new(
+            SqlxActivityRepository::new(db_pool),
+        )));
+
+        Ok(Self {
+            projects,
+            inventory,
+            files,
+            bom_importer,
+            model_importer,
+            revisions,
+            packing,
+            activity,
+            filament,
+            settings,
+            events,
+        })
+    }
+
+    /// Builds services keeping all data in memory.
+    #[cfg(any(test, feature = "memory"))]
+    pub fn in_memory() -> Self {
+        use crate::infra::memory::activity::InMemoryActivityRepository;
+        use crate::infra::memory::file::InMemoryPartFileRepository;
+        use crate::infra::memory::file_store::InMemoryFileStore;
+        use crate::infra::memory::part::InMemoryPartRepository;
+        use crate::infra::memory::project_view::InMemoryProjectViewRepository;
+        use crate::infra::memory::settings::InMemorySettingsRepository;
+        use crate::infra::memory::spool::InMemorySpoolRepository;
+        use crate::infra::memory::unit_of_work::InMemoryUnitOfWorkFactory;
+        use crate::infra::memory::MemoryStorage;
+
+        let storage = MemoryStorage::new();
+        let events = EventBus::default();
+        let units: Arc<dyn UnitOfWorkFactory> = Arc::new(DispatchingUnitOfWorkFactory::new(
+            Arc::new(InMemoryUnitOfWorkFactory::new(Arc::clone(&storage))),
+            events.clone(),
+        ));
+        let settings_repo = Arc::new(InMemorySettingsRepository::new(Arc::clone(&storage)));
+        let settings = Arc::new(DefaultSettingsService::new(settings_repo.clone()));
+        let project_view_repo = Arc::new(InMemoryProjectViewRepository::new(Arc::clone(&storage)));
+        let projects: Arc<dyn ProjectsService> = Arc::new(DefaultProjectService::new(
+            Arc::clone(&units),
+            project_view_repo.clone(),
+            settings_repo,
+        ));
+        events.subscribe(Arc::new(CompletionNotifier::new(project_view_repo)));
+        let parts_repo = Arc::new(InMemoryPartRepository::new(Arc::clone(&storage)));
+        let inventory: Arc<dyn InventoryService> = Arc::new(DefaultInventoryService::new(
+            Arc::clone(&units),
+            parts_repo.clone(),
+        ));
+        let files = Arc::new(DefaultPartFileService::new(
+            Arc::clone(&units),
+            Arc::new(InMemoryPartFileRepository::new(Arc::clone(&storage))),
+            parts_repo.clone(),
+            Arc::new(InMemoryFileStore::new(Arc::clone(&storage))),
+        ));
+        let packing = Arc::new(DefaultPackingService::new(
+            Arc::clone(&projects),
+            parts_repo,
+        ));
+        let bom_importer = Arc::new(DefaultBomImporter::new(
+            Arc::clone(&projects),
+            Arc::clone(&inventory),
+            Arc::clone(&units),
+        ));
+        let model_importer = Arc::new(DefaultModelImporter::new(Arc::clone(&units)));
+        let filament = Arc::new(DefaultFilamentService::new(
+            Arc::clone(&units),
+            Arc::new(InMemorySpoolRepository::new(Arc::clone(&storage))),
+            Arc::clone(&projects),
+        ));
+        let revisions = Arc::new(DefaultBomRevisionService::new(units));
+        let activity = Arc::new(DefaultActivityService::new(Arc::new(
+            InMemoryActivityRepository::new(storage),
+        )));
+        Self {
+            projects,
+            inventory,
+            files,
+            bom_importer,
+            model_importer,
+            revisions,
+            packing,
+            activity,
+            filament,
+            settings,
+            events,
+        }
+    }
+
+    /// Builds services calling the REST API of a running printtables server.
+    pub fn remote(server_url: &str) -> anyhow::Result<Self> {
+        let client = RestClient::new(server_url)?;
+        let projects = Arc::new(RestProjectsService::new(client.clone()));
+        let inventory = Arc::new(RestInventoryService::new(client.clone()));
+        let files = Arc::new(RestPartFileService::new(client.clone()));
+        let activity = Arc::new(RestActivityService::new(client.clone()));
+        let filament = Arc::new(RestFilamentService::new(client.clone()));
+        let packing = Arc::new(RestPackingService::new(client.clone()));
+        let settings = Arc::new(RestSettingsService::new(client));
+        Ok(Self {
+            projects: projects.clone(),
+            inventory: inventory.clone(),
+            files,
+            bom_importer: projects.clone(),
+            model_importer: inventory,
+            revisions: projects,
+            packing,
+            activity,
+            filament,
+            settings,
+            events: EventBus::default(),
+        })
+    }
+}
@@ -0,0 +1,117 @@
+//! Provides implementation of the activity repository.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::activity::domain::entry::{ActivityEntry, EntityKind};
+use crate::activity::domain::repository::ActivityRepository;
+use crate::shared::pagination::{Page, PageRequest};
+
+use super::unit_of_work::SqlxExecutor;
+
+pub struct SqlxActivityRepository {
+    executor: SqlxExecutor,
+}
+
+impl SqlxActivityRepository {
+    pub fn new(executor: impl Into<SqlxExecutor>) -> Self {
+        Self {
+            executor: executor.into(),
+        }
+    }
+}
+
+struct ActivityRecord {
+    seq: i64,
+    occurred_at: DateTime<Utc>,
+    actor: String,
+    event: String,
+    entity: String,
+    entity_id: Uuid,
+    changes: String,
+}
+
+#[async_trait]
+impl ActivityRepository for SqlxActivityRepository {
+    async fn append(&self, entries: Vec<ActivityEntry>) -> anyhow::Result<()> {
+        let mut conn = self.executor.acquire().await?;
+        for entry in entries {
+            let entity = entry.entity.as_str();
+            let changes = entry.changes.to_string();
+            sqlx::query!(
+                r#"
+            insert into activity (occurred_at, actor, event, entity, entity_id, changes)
+            values (?, ?, ?, ?, ?, ?)
+            "#,
+                entry.occurred_at,
+                entry.actor,
+                entry.event,
+                entity,
+                entry.entity_id,
+                changes
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        entity_id: Option<Uuid>,
+        page: PageRequest,
+    ) -> anyhow::Result<Page<ActivityEntry>> {
+        let mut conn = self.executor.acquire().await?;
+        let limit = page.per_page();
+        let offset = i64::try_from(page.offset())?;
+        let records = sqlx::query_as!(
+            ActivityRecord,
+            r#"
+            select seq, occurred_at as "occurred_at: DateTime<Utc>", actor, event, entity,
+                entity_id as "entity_id: Uuid", changes
+            from activity
+            where ?1 is null or entity_id = ?1
+            order by seq desc
+            limit ?2 offset ?3
+            "#,
+            entity_id,
+            limit,
+            offset
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+        let total = sqlx::query_scalar!(
+            r#"
+            select count(*) as "total: i64"
+            from activity
+            where ?1 is null or entity_id = ?1
+            "#,
+            entity_id
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        let entries = records
+            .into_iter()
+            .map(ActivityEntry::try_from)
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Page::new(entries, page, total as u64))
+    }
+}
+
+impl TryFrom<ActivityRecord> for ActivityEntry {
+    type Error = anyhow::Error;
+
+    fn try_from(value: ActivityRecord) -> Result<Self, Self::Error> {
+        Ok(ActivityEntry {
+            seq: value.seq,
+            occurred_at: value.occurred_at,
+            actor: value.actor,
+            event: value.event,
+            entity: EntityKind::try_from(value.entity.as_str())?,
+            entity_id: value.entity_id,
+            changes: serde_json::from_str(&value.changes)?,
+        })
+    }
+}
@@ -0,0 +1,144 @@
+//! Provides implementation of the BOM revisions repository.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::projects::domain::project::ProjectId;
+use crate::projects::domain::repository::BomRevisionRepository;
+use crate::projects::domain::revision::{BomLine, BomRevision, RevisionNumber};
+
+use super::unit_of_work::{SqlxConnection, SqlxExecutor};
+
+pub struct SqlxBomRevisionRepository {
+    executor: SqlxExecutor,
+}
+
+impl SqlxBomRevisionRepository {
+    pub fn new(executor: impl Into<SqlxExecutor>) -> Self {
+        Self {
+            executor: executor.into(),
+        }
+    }
+}
+
+struct RevisionRecord {
+    project_id: Uuid,
+    revision: u32,
+    created_at: DateTime<Utc>,
+    actor: String,
+}
+
+#[async_trait]
+impl BomRevisionRepository for SqlxBomRevisionRepository {
+    async fn append(&self, revision: BomRevision) -> anyhow::Result<RevisionNumber> {
+        let mut conn = self.executor.acquire().await?;
+        let number = sqlx::query_scalar!(
+            r#"
+            insert into bom_revision (project_id, revision, created_at, actor)
+            select ?1, coalesce(max(revision), 0) + 1, ?2, ?3
+            from bom_revision
+            where project_id = ?1
+            returning revision as "revision: u32"
+            "#,
+            revision.project,
+            revision.created_at,
+            revision.actor
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+        for (position, line) in revision.parts.iter().enumerate() {
+            let position = position as u32;
+            sqlx::query!(
+                r#"
+            insert into bom_revision_line (project_id, revision, position, part_id, quantity)
+            values (?, ?, ?, ?, ?)
+            "#,
+                revision.project,
+                number,
+                position,
+                line.part_id,
+                line.quantity
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+        Ok(number)
+    }
+
+    async fn list(&self, project: ProjectId) -> anyhow::Result<Vec<BomRevision>> {
+        let mut conn = self.executor.acquire().await?;
+        let records = sqlx::query_as!(
+            RevisionRecord,
+            r#"
+            select project_id as "project_id: Uuid", revision as "revision: u32",
+                created_at as "created_at: DateTime<Utc>", actor
+            from bom_revision
+            where project_id = ?
+            order by revision desc
+            "#,
+            project
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        let mut revisions = Vec::with_capacity(records.len());
+        for record in records {
+            revisions.push(hydrate(&mut conn, record).await?);
+        }
+        Ok(revisions)
+    }
+
+    async fn find(
+        &self,
+        project: ProjectId,
+        revision: RevisionNumber,
+    ) -> anyhow::Result<Option<BomRevision>> {
+        let mut conn = self.executor.acquire().await?;
+        let record = sqlx::query_as!(
+            RevisionRecord,
+            r#"
+            select project_id as "project_id: Uuid", revision as "revision: u32",
+                created_at as "created_at: DateTime<Utc>", actor
+            from bom_revision
+            where project_id = ? and revision = ?
+            "#,
+            project,
+            revision
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        match record {
+            Some(record) => Ok(Some(hydrate(&mut conn, record).await?)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Loads BOM lines of the revision.
+async fn hydrate(
+    conn: &mut SqlxConnection<'_>,
+    record: RevisionRecord,
+) -> anyhow::Result<BomRevision> {
+    let parts = sqlx::query_as!(
+        BomLine,
+        r#"
+        select part_id as "part_id: Uuid", quantity as "quantity: u32"
+        from bom_revision_line
+        where project_id = ? and revision = ?
+        order by position
+        "#,
+        record.project_id,
+        record.revision
+    )
+    .fetch_all(&mut **conn)
+    .await?;
+    Ok(BomRevision {
+        project: record.project_id,
+        revision: record.revision,
+        created_at: record.created_at,
+        actor: record.actor,
+        parts,
+    })
+}
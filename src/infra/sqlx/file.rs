@@ -0,0 +1,124 @@
+//! Provides implementation of the part file repository.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::inventory::domain::file::{Digest, FileName, PartFile, PartFileId};
+use crate::inventory::domain::file_repository::PartFileRepository;
+use crate::inventory::domain::part::PartId;
+
+use super::unit_of_work::SqlxExecutor;
+
+pub struct SqlxPartFileRepository {
+    executor: SqlxExecutor,
+}
+
+impl SqlxPartFileRepository {
+    pub fn new(executor: impl Into<SqlxExecutor>) -> Self {
+        Self {
+            executor: executor.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl PartFileRepository for SqlxPartFileRepository {
+    async fn insert(&self, file: PartFile) -> anyhow::Result<()> {
+        let mut conn = self.executor.acquire().await?;
+        let file_name = file.file_name.as_ref();
+        let format = file.format.to_string();
+        let sha256 = file.sha256.as_ref();
+        let size = i64::try_from(file.size)?;
+        sqlx::query!(
+            r#"
+        insert into part_file (id, part_id, revision, file_name, format, sha256, size,
+            uploaded_at)
+        values (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            file.id,
+            file.part,
+            file.revision,
+            file_name,
+            format,
+            sha256,
+            size,
+            file.uploaded_at
+        )
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+
+    async fn find_by_id(&self, part: PartId, id: PartFileId) -> anyhow::Result<Option<PartFile>> {
+        let mut conn = self.executor.acquire().await?;
+        let record = sqlx::query_as!(
+            PartFileRecord,
+            r#"
+            select id as "id: Uuid", part_id as "part_id: Uuid", revision as "revision: u32",
+                file_name, sha256, size, uploaded_at as "uploaded_at: DateTime<Utc>"
+            from part_file
+            where part_id = ? and id = ?
+            "#,
+            part,
+            id
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        record.map(PartFile::try_from).transpose()
+    }
+
+    async fn list_by_part(&self, part: PartId) -> anyhow::Result<Vec<PartFile>> {
+        let mut conn = self.executor.acquire().await?;
+        let records = sqlx::query_as!(
+            PartFileRecord,
+            r#"
+            select id as "id: Uuid", part_id as "part_id: Uuid", revision as "revision: u32",
+                file_name, sha256, size, uploaded_at as "uploaded_at: DateTime<Utc>"
+            from part_file
+            where part_id = ?
+            order by uploaded_at, id
+            "#,
+            part
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        records.into_iter().map(PartFile::try_from).collect()
+    }
+}
+
+/// The format column duplicates the file name extension for queries outside the application.
+#[derive(Debug)]
+struct PartFileRecord {
+    id: Uuid,
+    part_id: Uuid,
+    revision: u32,
+    file_name: String,
+    sha256: String,
+    size: i64,
+    uploaded_at: DateTime<Utc>,
+}
+
+impl TryFrom<PartFileRecord> for PartFile {
+    type Error = anyhow::Error;
+
+    fn try_from(value: PartFileRecord) -> Result<Self, Self::Error> {
+        let file_name = FileName::parse(value.file_name)
+            .map_err(|e| anyhow!("invalid file {}: {}", value.id, e))?;
+        let sha256 =
+            Digest::parse(value.sha256).map_err(|e| anyhow!("invalid file {}: {}", value.id, e))?;
+        Ok(PartFile {
+            id: value.id,
+            part: value.part_id,
+            revision: value.revision,
+            format: file_name.format(),
+            file_name,
+            sha256,
+            size: u64::try_from(value.size)?,
+            uploaded_at: value.uploaded_at,
+        })
+    }
+}
@@ -1,3 +1,25 @@
+use sqlx::SqlitePool;
+
+pub mod activity;
+pub mod bom_revision;
+pub mod file;
 pub mod part;
+pub mod part_revision;
 pub mod project;
 pub mod project_view;
+pub mod settings;
+pub mod spool;
+pub mod unit_of_work;
+
+/// URL of the database configured by the `DATABASE_URL` variable.
+/// Falls back to the `dev.db` file in the working directory.
+pub fn database_url() -> String {
+    dotenvy::var("DATABASE_URL").unwrap_or_else(|_| "sqlite:dev.db".to_string())
+}
+
+/// Connects to the database at [`database_url`].
+pub async fn database_pool() -> anyhow::Result<SqlitePool> {
+    SqlitePool::connect(&database_url())
+        .await
+        .map_err(anyhow::Error::new)
+}
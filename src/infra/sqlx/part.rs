@@ -1,56 +1,135 @@
+use anyhow::{anyhow, bail};
 use async_trait::async_trait;
-use sqlx::{Error, SqlitePool};
+use sqlx::{Error, SqliteConnection};
 use uuid::Uuid;
 
+use crate::inventory::domain::classification::{Classification, PartFilter, RawClassification};
+use crate::inventory::domain::geometry::Geometry;
+use crate::inventory::domain::metadata::{PartMetadata, RawDimensions, RawPartMetadata};
 use crate::inventory::domain::name::Name;
 use crate::inventory::domain::part::{Part, PartId};
 use crate::inventory::domain::part_repository::PartRepository;
+use crate::inventory::domain::source::{PartSource, RawPartSource};
+use crate::inventory::domain::variant::{RawVariantAttributes, Variant, VariantAttributes};
+use crate::shared::validation::error::describe;
+
+use super::unit_of_work::SqlxExecutor;
 
 #[derive(Debug)]
 pub struct SqlxPartRepository {
-    pool: SqlitePool,
+    executor: SqlxExecutor,
 }
 
 impl SqlxPartRepository {
-    pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+    pub fn new(executor: impl Into<SqlxExecutor>) -> Self {
+        Self {
+            executor: executor.into(),
+        }
     }
 }
 
 #[async_trait]
 impl PartRepository for SqlxPartRepository {
     async fn insert(&self, part: Part) -> anyhow::Result<()> {
+        let mut conn = self.executor.acquire().await?;
         let record = PartRecord::from(part);
         sqlx::query!(
             r#"
-        insert into part (id, name)
-        values (?, ?)
+        insert into part (id, name, revision, material, color, grams, minutes, size_x, size_y,
+            size_z, notes, geometry, category, variant_of, variant_color, variant_size, source_url,
+            designer, license, commercial_use)
+        values (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
             record.id,
-            record.name
+            record.name,
+            record.revision,
+            record.material,
+            record.color,
+            record.grams,
+            record.minutes,
+            record.size_x,
+            record.size_y,
+            record.size_z,
+            record.notes,
+            record.geometry,
+            record.category,
+            record.variant_of,
+            record.variant_color,
+            record.variant_size,
+            record.source_url,
+            record.designer,
+            record.license,
+            record.commercial_use
+        )
+        .execute(&mut *conn)
+        .await?;
+        save_tags(&mut conn, record.id, &record.tag_list()).await
+    }
+
+    async fn update(&self, part: Part) -> anyhow::Result<()> {
+        let mut conn = self.executor.acquire().await?;
+        let record = PartRecord::from(part);
+        let updated = sqlx::query!(
+            r#"
+        update part
+        set name = ?, revision = ?, material = ?, color = ?, grams = ?, minutes = ?,
+            size_x = ?, size_y = ?, size_z = ?, notes = ?, geometry = ?, category = ?,
+            variant_of = ?, variant_color = ?, variant_size = ?, source_url = ?, designer = ?,
+            license = ?, commercial_use = ?
+        where id = ?
+            "#,
+            record.name,
+            record.revision,
+            record.material,
+            record.color,
+            record.grams,
+            record.minutes,
+            record.size_x,
+            record.size_y,
+            record.size_z,
+            record.notes,
+            record.geometry,
+            record.category,
+            record.variant_of,
+            record.variant_color,
+            record.variant_size,
+            record.source_url,
+            record.designer,
+            record.license,
+            record.commercial_use,
+            record.id
         )
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await?;
-        Ok(())
+        if updated.rows_affected() == 0 {
+            bail!("part {} does not exist", record.id);
+        }
+        save_tags(&mut conn, record.id, &record.tag_list()).await
     }
 
     async fn find_by_id(&self, id: PartId) -> anyhow::Result<Option<Part>> {
+        let mut conn = self.executor.acquire().await?;
         let result = sqlx::query_as!(
             PartRecord,
             r#"
-            select id as "id: Uuid", name 
-            from part 
+            select id as "id: Uuid", name, revision as "revision: u32", material, color, grams,
+                minutes as "minutes: u32", size_x, size_y, size_z, notes, geometry, category,
+                variant_of as "variant_of: Uuid", variant_color, variant_size, source_url, designer,
+                license, commercial_use,
+                (select group_concat(tag, ',') from part_tag where part_tag.part_id = part.id)
+                    as "tags?: String"
+            from part
             where id = ?
             "#,
             id
         )
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *conn)
         .await;
 
         // TODO: extract helper function to hanle missing items.
         match result {
             Ok(record) => {
-                let part = Part::try_from(record).map_err(anyhow::Error::from)?;
+                let part = Part::try_from(record)?;
                 Ok(Some(part))
             }
             Err(e) => match e {
@@ -59,28 +138,208 @@ impl PartRepository for SqlxPartRepository {
             },
         }
     }
+
+    async fn find_by_name(&self, name: &Name) -> anyhow::Result<Option<Part>> {
+        let mut conn = self.executor.acquire().await?;
+        let name = name.as_ref();
+        let record = sqlx::query_as!(
+            PartRecord,
+            r#"
+            select id as "id: Uuid", name, revision as "revision: u32", material, color, grams,
+                minutes as "minutes: u32", size_x, size_y, size_z, notes, geometry, category,
+                variant_of as "variant_of: Uuid", variant_color, variant_size, source_url, designer,
+                license, commercial_use,
+                (select group_concat(tag, ',') from part_tag where part_tag.part_id = part.id)
+                    as "tags?: String"
+            from part
+            where name = ? collate nocase
+            order by id
+            limit 1
+            "#,
+            name
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        match record {
+            Some(record) => Ok(Some(Part::try_from(record)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Categories are stored as paths, so subcategories start with the category and a separator.
+    async fn list(&self, filter: &PartFilter) -> anyhow::Result<Vec<Part>> {
+        let mut conn = self.executor.acquire().await?;
+        let tag = filter.tag.as_ref().map(|t| t.to_string());
+        let category = filter.category.as_ref().map(|c| c.to_string());
+        let records = sqlx::query_as!(
+            PartRecord,
+            r#"
+            select id as "id: Uuid", name, revision as "revision: u32", material, color, grams,
+                minutes as "minutes: u32", size_x, size_y, size_z, notes, geometry, category,
+                variant_of as "variant_of: Uuid", variant_color, variant_size, source_url, designer,
+                license, commercial_use,
+                (select group_concat(tag, ',') from part_tag where part_tag.part_id = part.id)
+                    as "tags?: String"
+            from part
+            where (?1 is null or exists (
+                    select 1 from part_tag where part_tag.part_id = part.id and part_tag.tag = ?1
+                ))
+                and (?2 is null or category = ?2 collate nocase
+                    or substr(category, 1, length(?2) + 3) collate nocase = ?2 || ' › ')
+                and (?3 is null or variant_of = ?3)
+            order by name collate nocase
+            "#,
+            tag,
+            category,
+            filter.variant_of
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        records.into_iter().map(Part::try_from).collect()
+    }
+}
+
+/// Replaces tags of the part.
+async fn save_tags(conn: &mut SqliteConnection, id: PartId, tags: &[String]) -> anyhow::Result<()> {
+    sqlx::query!("delete from part_tag where part_id = ?", id)
+        .execute(&mut *conn)
+        .await?;
+    for tag in tags {
+        sqlx::query!("insert into part_tag (part_id, tag) values (?, ?)", id, tag)
+            .execute(&mut *conn)
+            .await?;
+    }
+    Ok(())
 }
 
 #[derive(Debug)]
 struct PartRecord {
     id: Uuid,
     name: String,
+    revision: u32,
+    material: Option<String>,
+    color: Option<String>,
+    grams: Option<f64>,
+    minutes: Option<u32>,
+    size_x: Option<f64>,
+    size_y: Option<f64>,
+    size_z: Option<f64>,
+    notes: Option<String>,
+    /// Geometry as JSON.
+    geometry: Option<String>,
+    category: Option<String>,
+    /// Parent part of a variant.
+    variant_of: Option<Uuid>,
+    variant_color: Option<String>,
+    variant_size: Option<String>,
+    source_url: Option<String>,
+    designer: Option<String>,
+    license: Option<String>,
+    commercial_use: Option<bool>,
+    /// Tags separated by commas, which tags cannot contain.
+    tags: Option<String>,
+}
+
+impl PartRecord {
+    fn tag_list(&self) -> Vec<String> {
+        self.tags
+            .as_deref()
+            .map(|tags| tags.split(',').map(str::to_string).collect())
+            .unwrap_or_default()
+    }
 }
 
 impl TryFrom<PartRecord> for Part {
     type Error = anyhow::Error;
 
     fn try_from(value: PartRecord) -> Result<Self, Self::Error> {
+        let tags = value.tag_list();
         let name = Name::try_from(value.name).map_err(anyhow::Error::from)?;
-        Ok(Part::hydrate(value.id, name))
+        let dimensions = match (value.size_x, value.size_y, value.size_z) {
+            (Some(x), Some(y), Some(z)) => Some(RawDimensions { x, y, z }),
+            _ => None,
+        };
+        let metadata = PartMetadata::parse(RawPartMetadata {
+            material: value.material,
+            color: value.color,
+            grams: value.grams,
+            minutes: value.minutes,
+            dimensions,
+            notes: value.notes,
+        })
+        .map_err(|errors| anyhow!("invalid part {}: {}", value.id, describe(&errors)))?;
+        let geometry = value
+            .geometry
+            .map(|g| serde_json::from_str::<Geometry>(&g))
+            .transpose()?;
+        let classification = Classification::parse(RawClassification {
+            tags,
+            category: value.category,
+        })
+        .map_err(|errors| anyhow!("invalid part {}: {}", value.id, describe(&errors)))?;
+        let variant = value
+            .variant_of
+            .map(|parent| {
+                VariantAttributes::parse(RawVariantAttributes {
+                    color: value.variant_color,
+                    size: value.variant_size,
+                })
+                .map(|attributes| Variant { parent, attributes })
+            })
+            .transpose()
+            .map_err(|errors| anyhow!("invalid part {}: {}", value.id, describe(&errors)))?;
+        let source = PartSource::parse(RawPartSource {
+            source_url: value.source_url,
+            designer: value.designer,
+            license: value.license,
+            commercial_use: value.commercial_use,
+        })
+        .map_err(|errors| anyhow!("invalid part {}: {}", value.id, describe(&errors)))?;
+        Ok(Part::hydrate(value.id, name)
+            .with_revision(value.revision)
+            .with_metadata(metadata)
+            .with_classification(classification)
+            .with_source(source)
+            .with_geometry(geometry)
+            .with_variant(variant))
     }
 }
 
 impl From<Part> for PartRecord {
     fn from(value: Part) -> Self {
+        let metadata = RawPartMetadata::from(value.metadata());
+        let geometry = value
+            .geometry()
+            .map(|g| serde_json::to_string(g).expect("geometry is serializable"));
+        let classification = RawClassification::from(value.classification());
+        let source = RawPartSource::from(value.source());
+        let variant = value
+            .variant()
+            .map(|v| (v.parent, RawVariantAttributes::from(&v.attributes)));
         Self {
             id: value.id(),
             name: value.name().to_string(),
+            revision: value.revision(),
+            material: metadata.material,
+            color: metadata.color,
+            grams: metadata.grams,
+            minutes: metadata.minutes,
+            size_x: metadata.dimensions.map(|d| d.x),
+            size_y: metadata.dimensions.map(|d| d.y),
+            size_z: metadata.dimensions.map(|d| d.z),
+            notes: metadata.notes,
+            geometry,
+            category: classification.category,
+            tags: Some(classification.tags.join(",")).filter(|tags| !tags.is_empty()),
+            variant_of: variant.as_ref().map(|(parent, _)| *parent),
+            variant_color: variant.as_ref().and_then(|(_, v)| v.color.clone()),
+            variant_size: variant.and_then(|(_, v)| v.size),
+            source_url: source.source_url,
+            designer: source.designer,
+            license: source.license,
+            commercial_use: source.commercial_use,
         }
     }
 }
@@ -0,0 +1,101 @@
+//! Provides implementation of the part revisions repository.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::inventory::domain::metadata::{PartMetadata, RawPartMetadata};
+use crate::inventory::domain::part::PartId;
+use crate::inventory::domain::part_repository::PartRevisionRepository;
+use crate::inventory::domain::revision::PartRevision;
+use crate::shared::validation::error::describe;
+
+use super::unit_of_work::SqlxExecutor;
+
+pub struct SqlxPartRevisionRepository {
+    executor: SqlxExecutor,
+}
+
+impl SqlxPartRevisionRepository {
+    pub fn new(executor: impl Into<SqlxExecutor>) -> Self {
+        Self {
+            executor: executor.into(),
+        }
+    }
+}
+
+struct PartRevisionRecord {
+    part_id: Uuid,
+    revision: u32,
+    metadata: String,
+    replaced_at: DateTime<Utc>,
+}
+
+#[async_trait]
+impl PartRevisionRepository for SqlxPartRevisionRepository {
+    async fn append(&self, revision: PartRevision) -> anyhow::Result<()> {
+        let mut conn = self.executor.acquire().await?;
+        let metadata = serde_json::to_string(&RawPartMetadata::from(&revision.metadata))?;
+        let replaced_at = revision.replaced_at.ok_or_else(|| {
+            anyhow!(
+                "latest revision of part {} is kept with the part",
+                revision.part
+            )
+        })?;
+        sqlx::query!(
+            r#"
+            insert into part_revision (part_id, revision, metadata, replaced_at)
+            values (?, ?, ?, ?)
+            "#,
+            revision.part,
+            revision.revision,
+            metadata,
+            replaced_at
+        )
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+
+    async fn list(&self, part: PartId) -> anyhow::Result<Vec<PartRevision>> {
+        let mut conn = self.executor.acquire().await?;
+        let records = sqlx::query_as!(
+            PartRevisionRecord,
+            r#"
+            select part_id as "part_id: Uuid", revision as "revision: u32", metadata,
+                replaced_at as "replaced_at: DateTime<Utc>"
+            from part_revision
+            where part_id = ?
+            order by revision desc
+            "#,
+            part
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        records.into_iter().map(PartRevision::try_from).collect()
+    }
+}
+
+impl TryFrom<PartRevisionRecord> for PartRevision {
+    type Error = anyhow::Error;
+
+    fn try_from(value: PartRevisionRecord) -> Result<Self, Self::Error> {
+        let raw: RawPartMetadata = serde_json::from_str(&value.metadata)?;
+        let metadata = PartMetadata::parse(raw).map_err(|errors| {
+            anyhow!(
+                "invalid revision {} of part {}: {}",
+                value.revision,
+                value.part_id,
+                describe(&errors)
+            )
+        })?;
+        Ok(PartRevision {
+            part: value.part_id,
+            revision: value.revision,
+            metadata,
+            replaced_at: Some(value.replaced_at),
+        })
+    }
+}
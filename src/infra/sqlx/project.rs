@@ -4,11 +4,13 @@ use crate::projects::domain::repository::ProjectRepository;
 use anyhow::bail;
 use async_trait::async_trait;
 use chrono::NaiveDateTime;
-use sqlx::SqlitePool;
+use sqlx::{Connection, SqliteConnection};
 use uuid::Uuid;
 
+use super::unit_of_work::SqlxExecutor;
+
 pub struct SqlxProjectRepository {
-    pool: SqlitePool,
+    executor: SqlxExecutor,
 }
 
 #[derive(Debug, Clone)]
@@ -16,44 +18,56 @@ struct ProjectRecord {
     id: Uuid,
     name: String,
     created_at: NaiveDateTime,
+    template: bool,
 }
 
 impl SqlxProjectRepository {
-    pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+    pub fn new(executor: impl Into<SqlxExecutor>) -> Self {
+        Self {
+            executor: executor.into(),
+        }
     }
 }
 
-// TODO: add transactions support
 #[async_trait]
 impl ProjectRepository for SqlxProjectRepository {
+    /// Stores the project with its BOM.
+    /// Outside of a unit of work the insert runs in its own transaction.
     async fn create(&self, project: Project) -> anyhow::Result<ProjectId> {
+        let mut conn = self.executor.acquire().await?;
+        let mut tx = conn.begin().await?;
         let id = project.id();
+        let parts = project.parts().to_vec();
         let record = ProjectRecord::from(project);
         sqlx::query!(
             r#"
-        insert into project (id, name, created_at)
-        values (?, ?, ?)
+        insert into project (id, name, created_at, template)
+        values (?, ?, ?, ?)
             "#,
             record.id,
             record.name,
-            record.created_at
+            record.created_at,
+            record.template
         )
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
+        insert_bom(&mut tx, id, &parts).await?;
+        tx.commit().await?;
         Ok(id)
     }
+
     async fn find_by_id(&self, id: ProjectId) -> anyhow::Result<Option<Project>> {
+        let mut conn = self.executor.acquire().await?;
         let result = sqlx::query_as!(
             ProjectRecord,
             r#"
-        select id as "id: Uuid", name, created_at 
+        select id as "id: Uuid", name, created_at, template
         from project
         where id = ?
             "#,
             id
         )
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *conn)
         .await;
 
         let project_record = match result {
@@ -64,13 +78,14 @@ impl ProjectRepository for SqlxProjectRepository {
 
         let bom_result = sqlx::query!(
             r#"
-        select bom.part_id as "part_id: Uuid", bom.quantity as "quantity: u32"
+        select bom.part_id as "part_id: Uuid", bom.quantity as "quantity: u32", bom.printed as "printed: u32",
+            bom.revision as "revision: u32"
         from bom
         where bom.project_id = ?
         "#,
             id
         )
-        .fetch_all(&self.pool)
+        .fetch_all(&mut *conn)
         .await;
 
         let parts_records = match bom_result {
@@ -80,59 +95,84 @@ impl ProjectRepository for SqlxProjectRepository {
 
         let parts = parts_records
             .into_iter()
-            .map(|p| ProjectPart::new(p.part_id, p.quantity))
+            .map(|p| ProjectPart::full(p.part_id, p.quantity, p.printed).pinned(p.revision))
             .collect();
         let project = Project::full(
             project_record.id,
             Name::try_from(project_record.name).unwrap_or_default(),
             parts,
             project_record.created_at,
-        );
+        )
+        .with_template(project_record.template);
         Ok(Some(project))
     }
 
+    /// Replaces the project and its BOM atomically.
+    /// Outside of a unit of work the update runs in its own transaction.
     async fn update(&self, project: Project) -> anyhow::Result<()> {
-        let mut tx = self.pool.begin().await?;
+        let mut conn = self.executor.acquire().await?;
+        let mut tx = conn.begin().await?;
         let id = project.id();
         let name = project.name().to_string();
-        let _ = sqlx::query!(
+        let template = project.is_template();
+        let updated = sqlx::query!(
             r#"
-        update project set name = ? where id = ?
+        update project set name = ?, template = ? where id = ?
         "#,
-            id,
             name,
+            template,
+            id,
         )
         .execute(&mut *tx)
-        .await;
+        .await?;
+        if updated.rows_affected() == 0 {
+            bail!("project {} does not exist", id);
+        }
 
-        let _ = sqlx::query!(r#"delete from bom where project_id = ?"#, id)
-            .execute(&mut *tx)
-            .await;
-
-        for part in project.parts() {
-            let part_id = part.part();
-            let quantity = part.quantity();
-            let _ = sqlx::query!(
-                r#"
-            insert into bom (project_id, part_id, quantity) values (?, ?, ?)
-            "#,
-                id,
-                part_id,
-                quantity
-            )
+        sqlx::query!(r#"delete from bom where project_id = ?"#, id)
             .execute(&mut *tx)
-            .await;
-        }
+            .await?;
 
+        insert_bom(&mut tx, id, project.parts()).await?;
         tx.commit().await?;
         Ok(())
     }
 }
 
+/// Lines must be pinned to a revision by the project service.
+async fn insert_bom(
+    conn: &mut SqliteConnection,
+    id: ProjectId,
+    parts: &[ProjectPart],
+) -> anyhow::Result<()> {
+    for part in parts {
+        let part_id = part.part();
+        let quantity = part.quantity();
+        let printed = part.printed();
+        let Some(revision) = part.revision() else {
+            bail!("BOM line of part {} is not pinned to a revision", part_id);
+        };
+        sqlx::query!(
+            r#"
+        insert into bom (project_id, part_id, quantity, printed, revision)
+        values (?, ?, ?, ?, ?)
+        "#,
+            id,
+            part_id,
+            quantity,
+            printed,
+            revision
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
 impl From<ProjectRecord> for Project {
     fn from(value: ProjectRecord) -> Self {
         let name = Name::try_from(value.name).unwrap_or_default();
-        Project::full(value.id, name, vec![], value.created_at)
+        Project::full(value.id, name, vec![], value.created_at).with_template(value.template)
     }
 }
 
@@ -142,6 +182,7 @@ impl From<Project> for ProjectRecord {
             id: value.id(),
             name: value.name().to_string(),
             created_at: value.created_at(),
+            template: value.is_template(),
         }
     }
 }
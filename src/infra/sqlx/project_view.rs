@@ -5,8 +5,13 @@ use async_trait::async_trait;
 use sqlx::SqlitePool;
 use uuid::Uuid;
 
+use crate::inventory::domain::source::{PartSource, RawPartSource};
 use crate::projects::domain::project::ProjectId;
-use crate::projects::view::project::ProjectPart;
+use crate::projects::view::demand::{DemandGrouping, PartDemand};
+use crate::projects::view::license::{LicenseReport, PartLicense};
+use crate::projects::view::outdated::{OutdatedPart, OutdatedProject};
+use crate::projects::view::project::{BomVariant, ProjectListItem, ProjectPart};
+use crate::projects::view::summary::{FilamentTotal, ProjectSummary};
 use crate::projects::view::{project::ProjectView, repository::ProjectViewRepository};
 
 pub struct SqlxProjectViewRepository {
@@ -19,12 +24,75 @@ impl SqlxProjectViewRepository {
     }
 }
 
+impl SqlxProjectViewRepository {
+    /// Adds up filament and print time estimates of BOM lines of the project.
+    async fn summarize(&self, id: ProjectId) -> anyhow::Result<ProjectSummary> {
+        let filament = sqlx::query!(
+            r#"
+            with line as (
+                select bom.quantity, max(bom.quantity - bom.printed, 0) as remaining,
+                    case when pinned.part_id is null then part.material else json_extract(pinned.metadata, '$.material') end as material,
+                    case when pinned.part_id is null then part.color else json_extract(pinned.metadata, '$.color') end as color,
+                    case when pinned.part_id is null then part.grams else json_extract(pinned.metadata, '$.grams') end as grams
+                from bom
+                join part on part.id = bom.part_id
+                left join part_revision pinned on pinned.part_id = bom.part_id and pinned.revision = bom.revision
+                where bom.project_id = ?
+            )
+            select material as "material?: String", color as "color?: String",
+                total(grams * quantity) as "grams!: f64", total(grams * remaining) as "remaining_grams!: f64"
+            from line
+            where grams is not null
+            group by material, color
+            order by material, color
+        "#,
+            id
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|record| FilamentTotal {
+            material: record.material,
+            color: record.color,
+            grams: record.grams,
+            remaining_grams: record.remaining_grams,
+        })
+        .collect();
+        let totals = sqlx::query!(
+            r#"
+            with line as (
+                select bom.quantity, max(bom.quantity - bom.printed, 0) as remaining,
+                    case when pinned.part_id is null then part.grams else json_extract(pinned.metadata, '$.grams') end as grams,
+                    case when pinned.part_id is null then part.minutes else json_extract(pinned.metadata, '$.minutes') end as minutes
+                from bom
+                join part on part.id = bom.part_id
+                left join part_revision pinned on pinned.part_id = bom.part_id and pinned.revision = bom.revision
+                where bom.project_id = ?
+            )
+            select coalesce(sum(minutes * quantity), 0) as "print_minutes!: i64",
+                coalesce(sum(minutes * remaining), 0) as "remaining_print_minutes!: i64",
+                count(*) filter (where grams is null or minutes is null) as "missing_estimates!: u32"
+            from line
+        "#,
+            id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(ProjectSummary::new(
+            filament,
+            u64::try_from(totals.print_minutes)?,
+            u64::try_from(totals.remaining_print_minutes)?,
+            totals.missing_estimates,
+        ))
+    }
+}
+
 #[async_trait]
 impl ProjectViewRepository for SqlxProjectViewRepository {
     async fn get_view_by_id(&self, id: ProjectId) -> anyhow::Result<Option<ProjectView>> {
         let project_result = sqlx::query!(
             r#"
-            select id as "id: Uuid", name 
+            select id as "id: Uuid", name, template
             from project
             where id = ?
         "#,
@@ -41,9 +109,19 @@ impl ProjectViewRepository for SqlxProjectViewRepository {
 
         let bom_result = sqlx::query!(
             r#"
-            select bom.part_id as "part_id: Uuid", bom.quantity as "quantity: u32", part.name as part_name 
-            from bom 
+            select bom.part_id as "part_id: Uuid", bom.quantity as "quantity: u32", bom.printed as "printed: u32", part.name as part_name,
+                bom.revision as "revision: u32", part.revision as "latest_revision: u32",
+                case when pinned.part_id is null then part.material else json_extract(pinned.metadata, '$.material') end as "material?: String",
+                case when pinned.part_id is null then part.color else json_extract(pinned.metadata, '$.color') end as "color?: String",
+                case when pinned.part_id is null then part.grams else json_extract(pinned.metadata, '$.grams') end as "grams?: f64",
+                case when pinned.part_id is null then part.minutes else json_extract(pinned.metadata, '$.minutes') end as "minutes?: u32",
+                part.category,
+                parent.id as "parent_id?: Uuid", parent.name as "parent_name?", part.variant_color, part.variant_size
+            from bom
             join part on part.id = bom.part_id
+            -- Estimates of replaced revisions are kept with the revision.
+            left join part_revision pinned on pinned.part_id = bom.part_id and pinned.revision = bom.revision
+            left join part parent on parent.id = part.variant_of
             where bom.project_id = ?
         "#,
             id
@@ -58,9 +136,185 @@ impl ProjectViewRepository for SqlxProjectViewRepository {
 
         let parts = bom_records
             .into_iter()
-            .map(|record| ProjectPart::new(record.part_id, record.part_name, record.quantity))
+            .map(|record| {
+                let variant =
+                    record
+                        .parent_id
+                        .zip(record.parent_name)
+                        .map(|(parent_id, parent_name)| BomVariant {
+                            parent_id,
+                            parent_name,
+                            color: record.variant_color,
+                            size: record.variant_size,
+                        });
+                ProjectPart::new(record.part_id, record.part_name, record.quantity)
+                    .with_printed(record.printed)
+                    .with_revision(record.revision, record.latest_revision)
+                    .with_category(record.category.as_deref())
+                    .with_variant(variant)
+                    .with_estimates(
+                        record.material.as_deref(),
+                        record.color.as_deref(),
+                        record.grams,
+                        record.minutes,
+                    )
+            })
             .collect();
-        let project_view = ProjectView::new(project_record.id, project_record.name, parts);
+        let summary = self.summarize(id).await?;
+        let project_view = ProjectView::new(project_record.id, project_record.name, parts)
+            .with_template(project_record.template)
+            .with_summary(summary);
         Ok(Some(project_view))
     }
+
+    async fn list_projects(&self) -> anyhow::Result<Vec<ProjectListItem>> {
+        let records = sqlx::query!(
+            r#"
+            select project.id as "id: Uuid", project.name, project.template,
+                count(bom.part_id) as "bom_size: u32"
+            from project
+            left join bom on bom.project_id = project.id
+            group by project.id
+            order by project.created_at desc, project.id desc
+        "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let projects = records
+            .into_iter()
+            .map(|record| {
+                ProjectListItem::new(record.id, record.name, record.bom_size)
+                    .with_template(record.template)
+            })
+            .collect();
+        Ok(projects)
+    }
+
+    async fn list_outdated(&self) -> anyhow::Result<Vec<OutdatedProject>> {
+        let records = sqlx::query!(
+            r#"
+            select project.id as "id: Uuid", project.name, bom.part_id as "part_id: Uuid",
+                part.name as part_name, bom.revision as "revision: u32",
+                part.revision as "latest_revision: u32"
+            from bom
+            join project on project.id = bom.project_id
+            join part on part.id = bom.part_id
+            where bom.revision < part.revision
+            order by project.created_at desc, project.id desc, part.name collate nocase
+        "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut projects: Vec<OutdatedProject> = vec![];
+        for record in records {
+            let part = OutdatedPart {
+                part_id: record.part_id,
+                name: record.part_name,
+                revision: record.revision,
+                latest_revision: record.latest_revision,
+            };
+            match projects.last_mut() {
+                Some(project) if project.id == record.id => project.parts.push(part),
+                _ => projects.push(OutdatedProject {
+                    id: record.id,
+                    name: record.name,
+                    parts: vec![part],
+                }),
+            }
+        }
+        Ok(projects)
+    }
+
+    async fn part_demand(&self, grouping: DemandGrouping) -> anyhow::Result<Vec<PartDemand>> {
+        let by_parent = grouping == DemandGrouping::Parent;
+        let records = sqlx::query!(
+            r#"
+            select case when ?1 and parent.id is not null then parent.id else part.id end as "part_id!: Uuid",
+                case when ?1 and parent.id is not null then parent.name else part.name end as "name!: String",
+                case when ?1 or parent.id is null then null else part.variant_color end as "color?: String",
+                case when ?1 or parent.id is null then null else part.variant_size end as "size?: String",
+                sum(bom.quantity) as "quantity!: u32", sum(bom.printed) as "printed!: u32",
+                sum(max(bom.quantity - bom.printed, 0)) as "remaining!: u32",
+                count(distinct bom.project_id) as "projects!: u32"
+            from bom
+            join project on project.id = bom.project_id
+            join part on part.id = bom.part_id
+            left join part parent on parent.id = part.variant_of
+            -- Templates only describe projects to create.
+            where not project.template
+            group by 1
+            order by 2 collate nocase, 1
+        "#,
+            by_parent
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let demand = records
+            .into_iter()
+            .map(|record| PartDemand {
+                part_id: record.part_id,
+                name: record.name,
+                color: record.color,
+                size: record.size,
+                quantity: record.quantity,
+                printed: record.printed,
+                remaining: record.remaining,
+                projects: record.projects,
+            })
+            .collect();
+        Ok(demand)
+    }
+
+    async fn get_licenses_by_id(&self, id: ProjectId) -> anyhow::Result<Option<LicenseReport>> {
+        let project = sqlx::query!(
+            r#"
+            select id as "id: Uuid", name
+            from project
+            where id = ?
+        "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        let Some(project) = project else {
+            return Ok(None);
+        };
+
+        let records = sqlx::query!(
+            r#"
+            select part.id as "part_id: Uuid", part.name, part.source_url, part.designer,
+                part.license, part.commercial_use as "commercial_use?: bool"
+            from bom
+            join part on part.id = bom.part_id
+            where bom.project_id = ?
+        "#,
+            id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        // A part with a source that no longer parses is reported on its own line instead of
+        // failing the whole report.
+        let parts = records
+            .into_iter()
+            .map(|record| {
+                let source = RawPartSource {
+                    source_url: record.source_url,
+                    designer: record.designer,
+                    license: record.license,
+                    commercial_use: record.commercial_use,
+                };
+                match PartSource::parse(source.clone()) {
+                    Ok(parsed) => PartLicense::new(record.part_id, record.name, &parsed),
+                    Err(errors) => {
+                        PartLicense::invalid(record.part_id, record.name, source, errors)
+                    }
+                }
+            })
+            .collect();
+        Ok(Some(LicenseReport::new(project.id, project.name, parts)))
+    }
 }
@@ -0,0 +1,58 @@
+//! Provides implementation of the settings repository.
+//! Settings are stored as JSON documents under their keys.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+
+use crate::settings::domain::costs::{CostSettings, RawCostSettings};
+use crate::settings::domain::repository::SettingsRepository;
+use crate::shared::validation::error::describe;
+
+use super::unit_of_work::SqlxExecutor;
+
+const COSTS_KEY: &str = "costs";
+
+pub struct SqlxSettingsRepository {
+    executor: SqlxExecutor,
+}
+
+impl SqlxSettingsRepository {
+    pub fn new(executor: impl Into<SqlxExecutor>) -> Self {
+        Self {
+            executor: executor.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl SettingsRepository for SqlxSettingsRepository {
+    async fn cost_settings(&self) -> anyhow::Result<Option<CostSettings>> {
+        let mut conn = self.executor.acquire().await?;
+        let value = sqlx::query_scalar!("select value from setting where key = ?", COSTS_KEY)
+            .fetch_optional(&mut *conn)
+            .await?;
+        let Some(value) = value else {
+            return Ok(None);
+        };
+        let raw: RawCostSettings = serde_json::from_str(&value)?;
+        CostSettings::parse(raw)
+            .map(Some)
+            .map_err(|errors| anyhow!("invalid cost settings: {}", describe(&errors)))
+    }
+
+    async fn save_cost_settings(&self, settings: CostSettings) -> anyhow::Result<()> {
+        let mut conn = self.executor.acquire().await?;
+        let value = serde_json::to_string(&RawCostSettings::from(&settings))?;
+        sqlx::query!(
+            r#"
+        insert into setting (key, value) values (?, ?)
+        on conflict (key) do update set value = excluded.value
+            "#,
+            COSTS_KEY,
+            value
+        )
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,188 @@
+//! Provides implementation of the spool repository.
+
+use anyhow::{anyhow, bail};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::filament::domain::repository::SpoolRepository;
+use crate::filament::domain::spool::{Consumption, RawSpool, Spool, SpoolId};
+use crate::projects::domain::project::ProjectId;
+use crate::shared::validation::error::describe;
+
+use super::unit_of_work::SqlxExecutor;
+
+pub struct SqlxSpoolRepository {
+    executor: SqlxExecutor,
+}
+
+impl SqlxSpoolRepository {
+    pub fn new(executor: impl Into<SqlxExecutor>) -> Self {
+        Self {
+            executor: executor.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl SpoolRepository for SqlxSpoolRepository {
+    async fn insert(&self, spool: Spool) -> anyhow::Result<()> {
+        let mut conn = self.executor.acquire().await?;
+        let record = SpoolRecord::from(&spool);
+        sqlx::query!(
+            r#"
+        insert into spool (id, material, color, brand, initial_grams, remaining_grams, cost)
+        values (?, ?, ?, ?, ?, ?, ?)
+            "#,
+            record.id,
+            record.material,
+            record.color,
+            record.brand,
+            record.initial_grams,
+            record.remaining_grams,
+            record.cost
+        )
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+
+    async fn update(&self, spool: Spool) -> anyhow::Result<()> {
+        let mut conn = self.executor.acquire().await?;
+        let record = SpoolRecord::from(&spool);
+        let updated = sqlx::query!(
+            r#"
+        update spool
+        set material = ?, color = ?, brand = ?, initial_grams = ?, remaining_grams = ?, cost = ?
+        where id = ?
+            "#,
+            record.material,
+            record.color,
+            record.brand,
+            record.initial_grams,
+            record.remaining_grams,
+            record.cost,
+            record.id
+        )
+        .execute(&mut *conn)
+        .await?;
+        if updated.rows_affected() == 0 {
+            bail!("spool {} does not exist", record.id);
+        }
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: SpoolId) -> anyhow::Result<Option<Spool>> {
+        let mut conn = self.executor.acquire().await?;
+        let record = sqlx::query_as!(
+            SpoolRecord,
+            r#"
+            select id as "id: Uuid", material, color, brand, initial_grams, remaining_grams, cost
+            from spool
+            where id = ?
+            "#,
+            id
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        record.map(Spool::try_from).transpose()
+    }
+
+    async fn list_all(&self) -> anyhow::Result<Vec<Spool>> {
+        let mut conn = self.executor.acquire().await?;
+        let records = sqlx::query_as!(
+            SpoolRecord,
+            r#"
+            select id as "id: Uuid", material, color, brand, initial_grams, remaining_grams, cost
+            from spool
+            order by material, color collate nocase, id
+            "#
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        records.into_iter().map(Spool::try_from).collect()
+    }
+
+    async fn record_consumption(&self, consumption: Consumption) -> anyhow::Result<()> {
+        let mut conn = self.executor.acquire().await?;
+        sqlx::query!(
+            r#"
+        insert into spool_consumption (spool_id, project_id, grams, consumed_at, actor)
+        values (?, ?, ?, ?, ?)
+            "#,
+            consumption.spool,
+            consumption.project,
+            consumption.grams,
+            consumption.consumed_at,
+            consumption.actor
+        )
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+
+    async fn list_consumption(&self, project: ProjectId) -> anyhow::Result<Vec<Consumption>> {
+        let mut conn = self.executor.acquire().await?;
+        let consumption = sqlx::query_as!(
+            Consumption,
+            r#"
+            select spool_id as "spool: Uuid", project_id as "project: Uuid", grams,
+                consumed_at as "consumed_at: DateTime<Utc>", actor
+            from spool_consumption
+            where project_id = ?
+            order by seq
+            "#,
+            project
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+        Ok(consumption)
+    }
+}
+
+#[derive(Debug)]
+struct SpoolRecord {
+    id: Uuid,
+    material: String,
+    color: String,
+    brand: Option<String>,
+    initial_grams: f64,
+    remaining_grams: f64,
+    cost: Option<f64>,
+}
+
+impl TryFrom<SpoolRecord> for Spool {
+    type Error = anyhow::Error;
+
+    fn try_from(value: SpoolRecord) -> Result<Self, Self::Error> {
+        Spool::parse(
+            value.id,
+            RawSpool {
+                material: value.material,
+                color: value.color,
+                brand: value.brand,
+                initial_grams: value.initial_grams,
+                remaining_grams: Some(value.remaining_grams),
+                cost: value.cost,
+            },
+        )
+        .map_err(|errors| anyhow!("invalid spool {}: {}", value.id, describe(&errors)))
+    }
+}
+
+impl From<&Spool> for SpoolRecord {
+    fn from(value: &Spool) -> Self {
+        let raw = RawSpool::from(value);
+        Self {
+            id: value.id(),
+            material: raw.material,
+            color: raw.color,
+            brand: raw.brand,
+            initial_grams: raw.initial_grams,
+            remaining_grams: value.remaining_grams(),
+            cost: raw.cost,
+        }
+    }
+}
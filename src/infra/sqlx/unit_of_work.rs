@@ -0,0 +1,158 @@
+//! SQLite transactions shared by repositories of a unit of work.
+
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use sqlx::pool::PoolConnection;
+use sqlx::{Sqlite, SqliteConnection, SqlitePool, Transaction};
+use tokio::sync::{Mutex, MutexGuard};
+
+use crate::activity::domain::repository::ActivityRepository;
+use crate::app::unit_of_work::{UnitOfWork, UnitOfWorkFactory};
+use crate::filament::domain::repository::SpoolRepository;
+use crate::inventory::domain::file_repository::PartFileRepository;
+use crate::inventory::domain::part_repository::{PartRepository, PartRevisionRepository};
+use crate::projects::domain::repository::{BomRevisionRepository, ProjectRepository};
+
+use super::activity::SqlxActivityRepository;
+use super::bom_revision::SqlxBomRevisionRepository;
+use super::file::SqlxPartFileRepository;
+use super::part::SqlxPartRepository;
+use super::part_revision::SqlxPartRevisionRepository;
+use super::project::SqlxProjectRepository;
+use super::spool::SqlxSpoolRepository;
+
+type SharedTransaction = Arc<Mutex<Option<Transaction<'static, Sqlite>>>>;
+
+/// Source of database connections for repositories.
+/// Repositories either take a connection from the pool for every operation or run all
+/// operations in the transaction of a unit of work.
+#[derive(Debug, Clone)]
+pub enum SqlxExecutor {
+    Pool(SqlitePool),
+    Transaction(SharedTransaction),
+}
+
+/// Connection acquired from [`SqlxExecutor`].
+pub enum SqlxConnection<'a> {
+    Pooled(PoolConnection<Sqlite>),
+    Transaction(MutexGuard<'a, Option<Transaction<'static, Sqlite>>>),
+}
+
+impl SqlxExecutor {
+    pub async fn acquire(&self) -> anyhow::Result<SqlxConnection<'_>> {
+        match self {
+            SqlxExecutor::Pool(pool) => Ok(SqlxConnection::Pooled(pool.acquire().await?)),
+            SqlxExecutor::Transaction(tx) => {
+                let guard = tx.lock().await;
+                if guard.is_none() {
+                    return Err(anyhow!("unit of work is already finished"));
+                }
+                Ok(SqlxConnection::Transaction(guard))
+            }
+        }
+    }
+}
+
+impl From<SqlitePool> for SqlxExecutor {
+    fn from(value: SqlitePool) -> Self {
+        SqlxExecutor::Pool(value)
+    }
+}
+
+impl Deref for SqlxConnection<'_> {
+    type Target = SqliteConnection;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            SqlxConnection::Pooled(connection) => connection,
+            SqlxConnection::Transaction(tx) => tx.as_ref().expect("acquired finished transaction"),
+        }
+    }
+}
+
+impl DerefMut for SqlxConnection<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            SqlxConnection::Pooled(connection) => connection,
+            SqlxConnection::Transaction(tx) => tx.as_mut().expect("acquired finished transaction"),
+        }
+    }
+}
+
+pub struct SqlxUnitOfWork {
+    tx: SharedTransaction,
+}
+
+#[async_trait]
+impl UnitOfWork for SqlxUnitOfWork {
+    fn projects(&self) -> Arc<dyn ProjectRepository> {
+        Arc::new(SqlxProjectRepository::new(self.executor()))
+    }
+
+    fn parts(&self) -> Arc<dyn PartRepository> {
+        Arc::new(SqlxPartRepository::new(self.executor()))
+    }
+
+    fn activity(&self) -> Arc<dyn ActivityRepository> {
+        Arc::new(SqlxActivityRepository::new(self.executor()))
+    }
+
+    fn bom_revisions(&self) -> Arc<dyn BomRevisionRepository> {
+        Arc::new(SqlxBomRevisionRepository::new(self.executor()))
+    }
+
+    fn spools(&self) -> Arc<dyn SpoolRepository> {
+        Arc::new(SqlxSpoolRepository::new(self.executor()))
+    }
+
+    fn files(&self) -> Arc<dyn PartFileRepository> {
+        Arc::new(SqlxPartFileRepository::new(self.executor()))
+    }
+
+    fn part_revisions(&self) -> Arc<dyn PartRevisionRepository> {
+        Arc::new(SqlxPartRevisionRepository::new(self.executor()))
+    }
+
+    async fn commit(self: Box<Self>) -> anyhow::Result<()> {
+        match self.tx.lock().await.take() {
+            Some(tx) => Ok(tx.commit().await?),
+            None => Err(anyhow!("unit of work is already finished")),
+        }
+    }
+
+    async fn rollback(self: Box<Self>) -> anyhow::Result<()> {
+        match self.tx.lock().await.take() {
+            Some(tx) => Ok(tx.rollback().await?),
+            None => Err(anyhow!("unit of work is already finished")),
+        }
+    }
+}
+
+impl SqlxUnitOfWork {
+    fn executor(&self) -> SqlxExecutor {
+        SqlxExecutor::Transaction(Arc::clone(&self.tx))
+    }
+}
+
+pub struct SqlxUnitOfWorkFactory {
+    pool: SqlitePool,
+}
+
+impl SqlxUnitOfWorkFactory {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UnitOfWorkFactory for SqlxUnitOfWorkFactory {
+    async fn begin(&self) -> anyhow::Result<Box<dyn UnitOfWork>> {
+        let tx = self.pool.begin().await?;
+        Ok(Box::new(SqlxUnitOfWork {
+            tx: Arc::new(Mutex::new(Some(tx))),
+        }))
+    }
+}
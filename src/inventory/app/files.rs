@@ -0,0 +1,405 @@
+//! Defines application service for model files attached to parts.
+
+use std::io::BufReader;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use thiserror::Error;
+use tokio::io::AsyncReadExt;
+use tokio_util::io::SyncIoBridge;
+use tracing::info;
+
+use crate::app::unit_of_work::UnitOfWorkFactory;
+use crate::inventory::domain::file::{
+    Digest, FileContent, FileFormat, FileName, PartFile, PartFileId, StoredFile, Upload,
+};
+use crate::inventory::domain::file_repository::{FileStore, PartFileRepository};
+use crate::inventory::domain::gcode::{self, SlicerEstimates};
+use crate::inventory::domain::geometry::Geometry;
+use crate::inventory::domain::part::{Part, PartId};
+use crate::inventory::domain::part_repository::PartRepository;
+use crate::inventory::domain::stl;
+use crate::inventory::domain::thumbnail::{self, ThumbnailFormat};
+use crate::shared::validation::error::ValidationError;
+
+#[derive(Debug, Error)]
+pub enum PartFileError {
+    #[error("requested part not found")]
+    MissingPart,
+    #[error("requested file not found")]
+    MissingFile,
+    /// The file content is not a valid model or G-code.
+    #[error("invalid file")]
+    ValidationFailed(Vec<ValidationError>),
+    #[error("general error")]
+    GeneralError(#[from] anyhow::Error),
+}
+
+#[async_trait]
+pub trait PartFileService: Send + Sync {
+    /// Stores the content and attaches it to the latest revision of the part under the file
+    /// name.
+    /// STL meshes are measured and their geometry is kept with the part.
+    /// Print time and filament weight estimated by the slicer are read from G-code and kept
+    /// with the part. G-code of plates is not supported, see [`gcode`].
+    async fn attach_file(
+        &self,
+        part: PartId,
+        file_name: FileName,
+        content: Upload<'_>,
+    ) -> Result<PartFile, PartFileError>;
+
+    /// Lists files of the part, the oldest upload first.
+    async fn list_files(&self, part: PartId) -> Result<Vec<PartFile>, PartFileError>;
+
+    /// Opens content of the file for reading.
+    async fn open_file(
+        &self,
+        part: PartId,
+        id: PartFileId,
+    ) -> Result<(PartFile, FileContent), PartFileError>;
+
+    /// Renders a thumbnail of the STL mesh attached to the part last.
+    /// Parts without STL files have no thumbnail and are reported as missing files.
+    async fn thumbnail(
+        &self,
+        part: PartId,
+        format: ThumbnailFormat,
+    ) -> Result<Vec<u8>, PartFileError>;
+}
+
+/// Keeps file content in the file store and file metadata in the repository.
+pub struct DefaultPartFileService {
+    units: Arc<dyn UnitOfWorkFactory>,
+    files_repo: Arc<dyn PartFileRepository>,
+    parts_repo: Arc<dyn PartRepository>,
+    store: Arc<dyn FileStore>,
+}
+
+impl DefaultPartFileService {
+    pub fn new(
+        units: Arc<dyn UnitOfWorkFactory>,
+        files_repo: Arc<dyn PartFileRepository>,
+        parts_repo: Arc<dyn PartRepository>,
+        store: Arc<dyn FileStore>,
+    ) -> Self {
+        Self {
+            units,
+            files_repo,
+            parts_repo,
+            store,
+        }
+    }
+
+    async fn find_part(&self, part: PartId) -> Result<Part, PartFileError> {
+        self.parts_repo
+            .find_by_id(part)
+            .await?
+            .ok_or(PartFileError::MissingPart)
+    }
+
+    /// Reads what the file tells about the part. Meshes are measured while streamed from the
+    /// store, G-code is read whole as PrusaSlicer writes estimates at its end.
+    async fn analyze(
+        &self,
+        format: FileFormat,
+        stored: &StoredFile,
+    ) -> Result<Option<FileAnalysis>, PartFileError> {
+        let sha256 = &stored.sha256;
+        let analysis = match format {
+            FileFormat::Stl => {
+                let content = SyncIoBridge::new(self.open(sha256).await?);
+                let size = stored.size;
+                tokio::task::spawn_blocking(move || stl::analyze(BufReader::new(content), size))
+                    .await
+                    .map_err(anyhow::Error::from)?
+                    .map(FileAnalysis::Mesh)
+            }
+            FileFormat::Gcode | FileFormat::Bgcode => {
+                gcode::estimates(&self.read(sha256).await?).map(FileAnalysis::Slice)
+            }
+            FileFormat::ThreeMf | FileFormat::Step => return Ok(None),
+        };
+        analysis
+            .map(Some)
+            .map_err(|e| PartFileError::ValidationFailed(vec![e]))
+    }
+
+    async fn open(&self, sha256: &Digest) -> Result<FileContent, PartFileError> {
+        Ok(self
+            .store
+            .open(sha256)
+            .await?
+            .ok_or_else(|| anyhow!("content {} is missing in the store", sha256))?)
+    }
+
+    async fn read(&self, sha256: &Digest) -> Result<Vec<u8>, PartFileError> {
+        let mut content = self.open(sha256).await?;
+        let mut bytes = Vec::new();
+        content
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(anyhow::Error::from)?;
+        Ok(bytes)
+    }
+}
+
+/// Part properties learned from an attached file.
+enum FileAnalysis {
+    Mesh(Geometry),
+    Slice(SlicerEstimates),
+}
+
+impl FileAnalysis {
+    fn apply(self, part: &mut Part) {
+        match self {
+            FileAnalysis::Mesh(geometry) => part.measure(geometry),
+            FileAnalysis::Slice(estimates) => part.estimate(estimates),
+        }
+    }
+}
+
+#[async_trait]
+impl PartFileService for DefaultPartFileService {
+    /// Content is kept in the store even if attaching fails. Identical uploads reuse it.
+    async fn attach_file(
+        &self,
+        part: PartId,
+        file_name: FileName,
+        content: Upload<'_>,
+    ) -> Result<PartFile, PartFileError> {
+        // Rejects uploads to missing parts before their content is stored.
+        self.find_part(part).await?;
+        let stored = self.store.store(content).await?;
+        let analysis = self.analyze(file_name.format(), &stored).await?;
+        let uow = self.units.begin().await?;
+        let parts = uow.parts();
+        let mut current = parts
+            .find_by_id(part)
+            .await?
+            .ok_or(PartFileError::MissingPart)?;
+        // The file belongs to the revision current when it is recorded, even if the part was
+        // revised while the content was being stored.
+        let file = PartFile::new(part, current.revision(), file_name, stored);
+        if let Some(analysis) = analysis {
+            analysis.apply(&mut current);
+            parts.update(current).await?;
+        }
+        uow.files().insert(file.clone()).await?;
+        uow.commit().await?;
+        info!(
+            "file {} attached to part {} with id {}",
+            file.file_name, part, file.id
+        );
+        Ok(file)
+    }
+
+    async fn list_files(&self, part: PartId) -> Result<Vec<PartFile>, PartFileError> {
+        self.find_part(part).await?;
+        Ok(self.files_repo.list_by_part(part).await?)
+    }
+
+    async fn open_file(
+        &self,
+        part: PartId,
+        id: PartFileId,
+    ) -> Result<(PartFile, FileContent), PartFileError> {
+        let file = self
+            .files_repo
+            .find_by_id(part, id)
+            .await?
+            .ok_or(PartFileError::MissingFile)?;
+        let content = self
+            .store
+            .open(&file.sha256)
+            .await?
+            .ok_or_else(|| anyhow!("content of file {} is missing in the store", id))?;
+        Ok((file, content))
+    }
+
+    /// Thumbnails are cached in the file store, so every mesh is rendered once per format.
+    /// Rendering runs on a blocking thread, as large meshes take a while.
+    async fn thumbnail(
+        &self,
+        part: PartId,
+        format: ThumbnailFormat,
+    ) -> Result<Vec<u8>, PartFileError> {
+        self.find_part(part).await?;
+        let mesh = self
+            .files_repo
+            .list_by_part(part)
+            .await?
+            .into_iter()
+            .rfind(|file| file.format == FileFormat::Stl)
+            .ok_or(PartFileError::MissingFile)?;
+        if let Some(image) = self.store.load_thumbnail(&mesh.sha256, format).await? {
+            return Ok(image);
+        }
+        let content = self.read(&mesh.sha256).await?;
+        let image = tokio::task::spawn_blocking(move || {
+            stl::triangles(&content).map(|triangles| thumbnail::render(&triangles, format))
+        })
+        .await
+        .map_err(anyhow::Error::from)?
+        .map_err(|e| PartFileError::ValidationFailed(vec![e]))?;
+        self.store
+            .store_thumbnail(&mesh.sha256, format, &image)
+            .await?;
+        info!("rendered {} thumbnail of file {}", format, mesh.id);
+        Ok(image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::Cursor;
+
+    use tokio::io::AsyncReadExt;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::infra::memory::file::InMemoryPartFileRepository;
+    use crate::infra::memory::file_store::InMemoryFileStore;
+    use crate::infra::memory::part::InMemoryPartRepository;
+    use crate::infra::memory::unit_of_work::InMemoryUnitOfWorkFactory;
+    use crate::infra::memory::MemoryStorage;
+    use crate::inventory::app::service::{DefaultInventoryService, InventoryService};
+    use crate::inventory::domain::geometry::tests::cuboid;
+    use crate::inventory::domain::name::Name;
+    use crate::inventory::domain::stl::tests::ascii;
+
+    async fn service_with_part() -> (DefaultPartFileService, PartId, Arc<InMemoryPartRepository>) {
+        let storage = MemoryStorage::new();
+        let units = Arc::new(InMemoryUnitOfWorkFactory::new(Arc::clone(&storage)));
+        let parts_repo = Arc::new(InMemoryPartRepository::new(Arc::clone(&storage)));
+        let part = DefaultInventoryService::new(units.clone(), parts_repo.clone())
+            .register_part(Name::parse("Drawer").unwrap())
+            .await
+            .unwrap();
+        let service = DefaultPartFileService::new(
+            units,
+            Arc::new(InMemoryPartFileRepository::new(Arc::clone(&storage))),
+            parts_repo.clone(),
+            Arc::new(InMemoryFileStore::new(storage)),
+        );
+        (service, part, parts_repo)
+    }
+
+    #[tokio::test]
+    async fn attach_and_read_file() {
+        let (service, part, _) = service_with_part().await;
+
+        let file = service
+            .attach_file(
+                part,
+                FileName::parse("drawer.step").unwrap(),
+                &mut Cursor::new(b"solid"),
+            )
+            .await
+            .unwrap();
+
+        let (opened, mut content) = service.open_file(part, file.id).await.unwrap();
+        let mut stored = String::new();
+        content.read_to_string(&mut stored).await.unwrap();
+        assert_eq!(opened, file);
+        assert_eq!(stored, "solid");
+        assert_eq!(service.list_files(part).await.unwrap(), vec![file]);
+    }
+
+    #[tokio::test]
+    async fn reject_files_of_missing_part() {
+        let (service, _, _) = service_with_part().await;
+
+        let result = service
+            .attach_file(
+                Uuid::now_v7(),
+                FileName::parse("drawer.stl").unwrap(),
+                &mut Cursor::new(b"solid"),
+            )
+            .await;
+
+        assert!(matches!(result, Err(PartFileError::MissingPart)));
+    }
+
+    #[tokio::test]
+    async fn measure_attached_stl() {
+        let (service, part, parts_repo) = service_with_part().await;
+        let mesh = ascii(&cuboid([0.0; 3], [40.0, 20.0, 10.0]));
+
+        service
+            .attach_file(
+                part,
+                FileName::parse("drawer.stl").unwrap(),
+                &mut Cursor::new(mesh.into_bytes()),
+            )
+            .await
+            .unwrap();
+
+        let measured = parts_repo.find_by_id(part).await.unwrap().unwrap();
+        assert_eq!(measured.geometry().unwrap().volume, 8000.0);
+        assert!(measured.metadata().dimensions.is_some());
+        assert!(measured.metadata().grams.is_some());
+    }
+
+    #[tokio::test]
+    async fn take_estimates_from_gcode() {
+        let (service, part, parts_repo) = service_with_part().await;
+        let gcode = "G28\n; filament used [g] = 12.34\n\
+            ; estimated printing time (normal mode) = 1h 30m\n";
+
+        service
+            .attach_file(
+                part,
+                FileName::parse("drawer_0.2mm_PLA.gcode").unwrap(),
+                &mut Cursor::new(gcode),
+            )
+            .await
+            .unwrap();
+
+        let sliced = parts_repo.find_by_id(part).await.unwrap().unwrap();
+        assert_eq!(sliced.metadata().minutes.unwrap().value(), 90);
+        assert_eq!(sliced.metadata().grams.unwrap().value(), 12.34);
+    }
+
+    #[tokio::test]
+    async fn render_thumbnail_of_latest_mesh() {
+        let (service, part, _) = service_with_part().await;
+        let mesh = ascii(&cuboid([0.0; 3], [40.0, 20.0, 10.0]));
+        let missing = service.thumbnail(part, ThumbnailFormat::Svg).await;
+        service
+            .attach_file(
+                part,
+                FileName::parse("drawer.stl").unwrap(),
+                &mut Cursor::new(mesh.into_bytes()),
+            )
+            .await
+            .unwrap();
+
+        let image = service.thumbnail(part, ThumbnailFormat::Svg).await.unwrap();
+
+        assert!(matches!(missing, Err(PartFileError::MissingFile)));
+        assert!(String::from_utf8(image.clone())
+            .unwrap()
+            .starts_with("<svg "));
+        let cached = service.thumbnail(part, ThumbnailFormat::Svg).await.unwrap();
+        assert_eq!(cached, image);
+    }
+
+    #[tokio::test]
+    async fn reject_invalid_stl() {
+        let (service, part, _) = service_with_part().await;
+
+        let result = service
+            .attach_file(
+                part,
+                FileName::parse("drawer.stl").unwrap(),
+                &mut Cursor::new(b"not a mesh"),
+            )
+            .await;
+
+        assert!(matches!(result, Err(PartFileError::ValidationFailed(_))));
+        assert!(service.list_files(part).await.unwrap().is_empty());
+    }
+}
@@ -1 +1,2 @@
+pub mod files;
 pub mod service;
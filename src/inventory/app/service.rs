@@ -4,16 +4,26 @@ use axum::async_trait;
 use thiserror::Error;
 use tracing::info;
 
+use crate::app::unit_of_work::UnitOfWorkFactory;
 use crate::inventory::domain::{
+    classification::{Classification, PartFilter},
+    metadata::PartMetadata,
     name::Name,
     part::{Part, PartId},
     part_repository::PartRepository,
+    revision::{PartRevision, PartRevisionNumber},
+    source::PartSource,
+    variant::{check_parent, Variant, VariantAttributes},
 };
+use crate::shared::validation::error::ValidationError;
 
 #[derive(Debug, Error)]
 pub enum InventoryError {
     #[error("requested part not found")]
     MissingPart,
+    /// Requested change violates part rules.
+    #[error("invalid part change")]
+    ValidationFailed(Vec<ValidationError>),
     #[error("general error")]
     GeneralError(anyhow::Error),
 }
@@ -24,16 +34,66 @@ pub trait InventoryService: Sync + Send {
     async fn register_part(&self, name: Name) -> Result<PartId, anyhow::Error>;
 
     async fn view_part(&self, id: PartId) -> Result<Part, InventoryError>;
+
+    /// Changes the name of the part.
+    async fn rename_part(&self, id: PartId, name: Name) -> Result<(), InventoryError>;
+
+    /// Replaces printing properties of the part.
+    async fn describe_part(&self, id: PartId, metadata: PartMetadata)
+        -> Result<(), InventoryError>;
+
+    /// Replaces tags and the category of the part.
+    async fn classify_part(
+        &self,
+        id: PartId,
+        classification: Classification,
+    ) -> Result<(), InventoryError>;
+
+    /// Replaces the origin and the license of the part.
+    async fn attribute_part(&self, id: PartId, source: PartSource) -> Result<(), InventoryError>;
+
+    /// Starts a new revision of the part with printing properties of the latest one.
+    /// Files uploaded afterwards belong to the new revision. Returns the new revision number.
+    async fn revise_part(&self, id: PartId) -> Result<PartRevisionNumber, InventoryError>;
+
+    /// Lists revisions of the part, the latest first.
+    async fn list_revisions(&self, id: PartId) -> Result<Vec<PartRevision>, InventoryError>;
+
+    /// Registers a variant of the parent part with printing properties, tags, the category and
+    /// the source of the parent. The variant color replaces the filament color. The name defaults
+    /// to the parent name with variant attributes, e.g., `Bin (red, 3 units)`.
+    async fn register_variant(
+        &self,
+        parent: PartId,
+        name: Option<Name>,
+        attributes: VariantAttributes,
+    ) -> Result<PartId, InventoryError>;
+
+    /// Makes an existing part a variant of another part, or a standalone part again when the
+    /// variant is missing.
+    async fn define_variant(
+        &self,
+        id: PartId,
+        variant: Option<Variant>,
+    ) -> Result<(), InventoryError>;
+
+    /// Looks up a part by its name.
+    /// Returns Ok(None) when the inventory has no part with this name.
+    async fn find_part_by_name(&self, name: &Name) -> Result<Option<Part>, InventoryError>;
+
+    /// Lists parts in the inventory matching the filter.
+    async fn list_parts(&self, filter: &PartFilter) -> Result<Vec<Part>, InventoryError>;
 }
 
-#[derive(Debug)]
+/// Registers parts within units of work and reads them from the parts repository.
 pub struct DefaultInventoryService {
+    units: Arc<dyn UnitOfWorkFactory>,
     parts_repo: Arc<dyn PartRepository>,
 }
 
 impl DefaultInventoryService {
-    pub fn new(parts_repo: Arc<dyn PartRepository>) -> Self {
-        Self { parts_repo }
+    pub fn new(units: Arc<dyn UnitOfWorkFactory>, parts_repo: Arc<dyn PartRepository>) -> Self {
+        Self { units, parts_repo }
     }
 }
 
@@ -42,7 +102,9 @@ impl InventoryService for DefaultInventoryService {
     async fn register_part(&self, name: Name) -> Result<PartId, anyhow::Error> {
         let new_part = Part::new(name.clone());
         let id = new_part.id();
-        self.parts_repo.insert(new_part).await?;
+        let uow = self.units.begin().await?;
+        uow.parts().insert(new_part).await?;
+        uow.commit().await?;
         info!("part {} registered with id {}", &name, id);
         Ok(id)
     }
@@ -55,4 +117,469 @@ impl InventoryService for DefaultInventoryService {
             Err(e) => Err(InventoryError::GeneralError(e)),
         }
     }
+
+    async fn rename_part(&self, id: PartId, name: Name) -> Result<(), InventoryError> {
+        let uow = self
+            .units
+            .begin()
+            .await
+            .map_err(InventoryError::GeneralError)?;
+        let mut part = uow
+            .parts()
+            .find_by_id(id)
+            .await
+            .map_err(InventoryError::GeneralError)?
+            .ok_or(InventoryError::MissingPart)?;
+        part.rename(name);
+        uow.parts()
+            .update(part)
+            .await
+            .map_err(InventoryError::GeneralError)?;
+        uow.commit().await.map_err(InventoryError::GeneralError)?;
+        info!("part {} renamed", id);
+        Ok(())
+    }
+
+    async fn describe_part(
+        &self,
+        id: PartId,
+        metadata: PartMetadata,
+    ) -> Result<(), InventoryError> {
+        let uow = self
+            .units
+            .begin()
+            .await
+            .map_err(InventoryError::GeneralError)?;
+        let mut part = uow
+            .parts()
+            .find_by_id(id)
+            .await
+            .map_err(InventoryError::GeneralError)?
+            .ok_or(InventoryError::MissingPart)?;
+        part.describe(metadata);
+        uow.parts()
+            .update(part)
+            .await
+            .map_err(InventoryError::GeneralError)?;
+        uow.commit().await.map_err(InventoryError::GeneralError)?;
+        info!("part {} described", id);
+        Ok(())
+    }
+
+    async fn classify_part(
+        &self,
+        id: PartId,
+        classification: Classification,
+    ) -> Result<(), InventoryError> {
+        let uow = self
+            .units
+            .begin()
+            .await
+            .map_err(InventoryError::GeneralError)?;
+        let mut part = uow
+            .parts()
+            .find_by_id(id)
+            .await
+            .map_err(InventoryError::GeneralError)?
+            .ok_or(InventoryError::MissingPart)?;
+        part.classify(classification);
+        uow.parts()
+            .update(part)
+            .await
+            .map_err(InventoryError::GeneralError)?;
+        uow.commit().await.map_err(InventoryError::GeneralError)?;
+        info!("part {} classified", id);
+        Ok(())
+    }
+
+    async fn attribute_part(&self, id: PartId, source: PartSource) -> Result<(), InventoryError> {
+        let uow = self
+            .units
+            .begin()
+            .await
+            .map_err(InventoryError::GeneralError)?;
+        let mut part = uow
+            .parts()
+            .find_by_id(id)
+            .await
+            .map_err(InventoryError::GeneralError)?
+            .ok_or(InventoryError::MissingPart)?;
+        part.attribute(source);
+        uow.parts()
+            .update(part)
+            .await
+            .map_err(InventoryError::GeneralError)?;
+        uow.commit().await.map_err(InventoryError::GeneralError)?;
+        info!("part {} attributed", id);
+        Ok(())
+    }
+
+    async fn revise_part(&self, id: PartId) -> Result<PartRevisionNumber, InventoryError> {
+        let uow = self
+            .units
+            .begin()
+            .await
+            .map_err(InventoryError::GeneralError)?;
+        let mut part = uow
+            .parts()
+            .find_by_id(id)
+            .await
+            .map_err(InventoryError::GeneralError)?
+            .ok_or(InventoryError::MissingPart)?;
+        let replaced = part.revise();
+        let revision = part.revision();
+        uow.part_revisions()
+            .append(replaced)
+            .await
+            .map_err(InventoryError::GeneralError)?;
+        uow.parts()
+            .update(part)
+            .await
+            .map_err(InventoryError::GeneralError)?;
+        uow.commit().await.map_err(InventoryError::GeneralError)?;
+        info!("part {} revised to revision {}", id, revision);
+        Ok(revision)
+    }
+
+    async fn list_revisions(&self, id: PartId) -> Result<Vec<PartRevision>, InventoryError> {
+        let uow = self
+            .units
+            .begin()
+            .await
+            .map_err(InventoryError::GeneralError)?;
+        let part = uow
+            .parts()
+            .find_by_id(id)
+            .await
+            .map_err(InventoryError::GeneralError)?
+            .ok_or(InventoryError::MissingPart)?;
+        let replaced = uow
+            .part_revisions()
+            .list(id)
+            .await
+            .map_err(InventoryError::GeneralError)?;
+        Ok(std::iter::once(part.latest_revision())
+            .chain(replaced)
+            .collect())
+    }
+
+    async fn register_variant(
+        &self,
+        parent: PartId,
+        name: Option<Name>,
+        attributes: VariantAttributes,
+    ) -> Result<PartId, InventoryError> {
+        let uow = self
+            .units
+            .begin()
+            .await
+            .map_err(InventoryError::GeneralError)?;
+        let parent = uow
+            .parts()
+            .find_by_id(parent)
+            .await
+            .map_err(InventoryError::GeneralError)?
+            .ok_or(InventoryError::MissingPart)?;
+        let name = match name {
+            Some(name) => name,
+            None => Name::parse(format!("{} ({})", parent.name(), attributes))
+                .map_err(|e| InventoryError::ValidationFailed(vec![e]))?,
+        };
+        let mut variant = Part::new(name);
+        check_parent(variant.id(), &parent, false)
+            .map_err(|e| InventoryError::ValidationFailed(vec![e]))?;
+        let mut metadata = parent.metadata().clone();
+        metadata.color = attributes.color.clone().or(metadata.color);
+        variant.describe(metadata);
+        variant.classify(parent.classification().clone());
+        variant.attribute(parent.source().clone());
+        variant.define_variant(Some(Variant {
+            parent: parent.id(),
+            attributes,
+        }));
+        let id = variant.id();
+        uow.parts()
+            .insert(variant)
+            .await
+            .map_err(InventoryError::GeneralError)?;
+        uow.commit().await.map_err(InventoryError::GeneralError)?;
+        info!("variant {} of part {} registered", id, parent.id());
+        Ok(id)
+    }
+
+    async fn define_variant(
+        &self,
+        id: PartId,
+        variant: Option<Variant>,
+    ) -> Result<(), InventoryError> {
+        let uow = self
+            .units
+            .begin()
+            .await
+            .map_err(InventoryError::GeneralError)?;
+        let mut part = uow
+            .parts()
+            .find_by_id(id)
+            .await
+            .map_err(InventoryError::GeneralError)?
+            .ok_or(InventoryError::MissingPart)?;
+        if let Some(variant) = &variant {
+            let parent = uow
+                .parts()
+                .find_by_id(variant.parent)
+                .await
+                .map_err(InventoryError::GeneralError)?
+                .ok_or(InventoryError::MissingPart)?;
+            let filter = PartFilter {
+                variant_of: Some(id),
+                ..PartFilter::default()
+            };
+            let has_variants = !uow
+                .parts()
+                .list(&filter)
+                .await
+                .map_err(InventoryError::GeneralError)?
+                .is_empty();
+            check_parent(id, &parent, has_variants)
+                .map_err(|e| InventoryError::ValidationFailed(vec![e]))?;
+        }
+        part.define_variant(variant);
+        uow.parts()
+            .update(part)
+            .await
+            .map_err(InventoryError::GeneralError)?;
+        uow.commit().await.map_err(InventoryError::GeneralError)?;
+        info!("variant of part {} defined", id);
+        Ok(())
+    }
+
+    async fn find_part_by_name(&self, name: &Name) -> Result<Option<Part>, InventoryError> {
+        self.parts_repo
+            .find_by_name(name)
+            .await
+            .map_err(InventoryError::GeneralError)
+    }
+
+    async fn list_parts(&self, filter: &PartFilter) -> Result<Vec<Part>, InventoryError> {
+        self.parts_repo
+            .list(filter)
+            .await
+            .map_err(InventoryError::GeneralError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::infra::memory::part::InMemoryPartRepository;
+    use crate::infra::memory::unit_of_work::InMemoryUnitOfWorkFactory;
+    use crate::infra::memory::MemoryStorage;
+    use crate::inventory::domain::classification::{Category, RawClassification, Tag};
+    use crate::inventory::domain::metadata::{Grams, Material};
+    use crate::inventory::domain::variant::RawVariantAttributes;
+
+    fn service() -> DefaultInventoryService {
+        let storage = MemoryStorage::new();
+        DefaultInventoryService::new(
+            Arc::new(InMemoryUnitOfWorkFactory::new(Arc::clone(&storage))),
+            Arc::new(InMemoryPartRepository::new(storage)),
+        )
+    }
+
+    #[tokio::test]
+    async fn register_and_view_part() {
+        let service = service();
+
+        let id = service
+            .register_part(Name::parse("Drawer").unwrap())
+            .await
+            .unwrap();
+
+        let part = service.view_part(id).await.unwrap();
+        assert_eq!(part.name().as_ref(), "Drawer");
+    }
+
+    #[tokio::test]
+    async fn describe_part() {
+        let service = service();
+        let id = service
+            .register_part(Name::parse("Drawer").unwrap())
+            .await
+            .unwrap();
+        let metadata = PartMetadata {
+            material: Some(Material::Petg),
+            grams: Some(Grams::parse(42.5).unwrap()),
+            ..PartMetadata::default()
+        };
+
+        service.describe_part(id, metadata.clone()).await.unwrap();
+
+        let part = service.view_part(id).await.unwrap();
+        assert_eq!(part.metadata(), &metadata);
+    }
+
+    #[tokio::test]
+    async fn report_missing_part() {
+        let result = service().view_part(Uuid::now_v7()).await;
+
+        assert!(matches!(result, Err(InventoryError::MissingPart)));
+    }
+
+    #[tokio::test]
+    async fn find_part_by_name_ignoring_case() {
+        let service = service();
+        let id = service
+            .register_part(Name::parse("Drawer handle").unwrap())
+            .await
+            .unwrap();
+
+        let found = service
+            .find_part_by_name(&Name::parse("drawer HANDLE").unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(found.map(|p| p.id()), Some(id));
+    }
+
+    #[tokio::test]
+    async fn list_parts_by_name_ignoring_case() {
+        let service = service();
+        for name in ["Lid", "bin", "Drawer"] {
+            service
+                .register_part(Name::parse(name).unwrap())
+                .await
+                .unwrap();
+        }
+
+        let parts = service.list_parts(&PartFilter::default()).await.unwrap();
+
+        let names: Vec<&str> = parts.iter().map(|p| p.name().as_ref()).collect();
+        assert_eq!(names, vec!["bin", "Drawer", "Lid"]);
+    }
+
+    #[tokio::test]
+    async fn filter_parts_by_tag_and_category() {
+        let service = service();
+        let classify = |category: &str, tags: &[&str]| {
+            Classification::parse(RawClassification {
+                category: Some(category.to_string()),
+                tags: tags.iter().map(|t| t.to_string()).collect(),
+            })
+            .unwrap()
+        };
+        for (name, category, tags) in [
+            (
+                "Bin",
+                "Storage › Gridfinity › Bins",
+                &["gridfinity", "bin"][..],
+            ),
+            ("Baseplate", "Storage › Gridfinity", &["gridfinity"][..]),
+            ("Hook", "Workshop › Wall", &["bin"][..]),
+        ] {
+            let id = service
+                .register_part(Name::parse(name).unwrap())
+                .await
+                .unwrap();
+            service
+                .classify_part(id, classify(category, tags))
+                .await
+                .unwrap();
+        }
+
+        let filter = PartFilter {
+            tag: Some(Tag::parse("Gridfinity").unwrap()),
+            category: Some(Category::parse("storage").unwrap()),
+            ..PartFilter::default()
+        };
+        let parts = service.list_parts(&filter).await.unwrap();
+
+        let names: Vec<&str> = parts.iter().map(|p| p.name().as_ref()).collect();
+        assert_eq!(names, vec!["Baseplate", "Bin"]);
+        let filter = PartFilter {
+            tag: Some(Tag::parse("bin").unwrap()),
+            ..PartFilter::default()
+        };
+        assert_eq!(service.list_parts(&filter).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn keep_metadata_of_replaced_revisions() {
+        let service = service();
+        let id = service
+            .register_part(Name::parse("Hinge").unwrap())
+            .await
+            .unwrap();
+        let grams = |value| PartMetadata {
+            grams: Some(Grams::parse(value).unwrap()),
+            ..PartMetadata::default()
+        };
+        service.describe_part(id, grams(12.0)).await.unwrap();
+
+        let revision = service.revise_part(id).await.unwrap();
+        service.describe_part(id, grams(15.0)).await.unwrap();
+
+        assert_eq!(revision, 2);
+        let revisions = service.list_revisions(id).await.unwrap();
+        let grams: Vec<(u32, f64)> = revisions
+            .iter()
+            .map(|r| (r.revision, r.metadata.grams.unwrap().value()))
+            .collect();
+        assert_eq!(grams, vec![(2, 15.0), (1, 12.0)]);
+        assert!(revisions[0].replaced_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn register_variants_of_part() {
+        let service = service();
+        let bin = service
+            .register_part(Name::parse("Bin").unwrap())
+            .await
+            .unwrap();
+        service
+            .describe_part(
+                bin,
+                PartMetadata {
+                    material: Some(Material::Pla),
+                    ..PartMetadata::default()
+                },
+            )
+            .await
+            .unwrap();
+        let attributes = |color: &str| {
+            VariantAttributes::parse(RawVariantAttributes {
+                color: Some(color.to_string()),
+                size: Some("3 units".to_string()),
+            })
+            .unwrap()
+        };
+
+        let red_bin = service
+            .register_variant(bin, None, attributes("red"))
+            .await
+            .unwrap();
+        let nested = service
+            .register_variant(red_bin, None, attributes("blue"))
+            .await;
+
+        let part = service.view_part(red_bin).await.unwrap();
+        assert_eq!(part.name().as_ref(), "Bin (red, 3 units)");
+        assert_eq!(part.metadata().material, Some(Material::Pla));
+        assert_eq!(part.metadata().color.as_ref().unwrap().as_ref(), "red");
+        assert_eq!(part.variant().unwrap().parent, bin);
+        assert!(matches!(nested, Err(InventoryError::ValidationFailed(_))));
+        let filter = PartFilter {
+            variant_of: Some(bin),
+            ..PartFilter::default()
+        };
+        assert_eq!(service.list_parts(&filter).await.unwrap().len(), 1);
+        let parent = Variant {
+            parent: red_bin,
+            attributes: attributes("blue"),
+        };
+        let result = service.define_variant(bin, Some(parent)).await;
+        assert!(matches!(result, Err(InventoryError::ValidationFailed(_))));
+    }
 }
@@ -0,0 +1,293 @@
+//! Tags and categories organizing parts.
+//!
+//! A part has any number of tags and at most one category. Categories form a hierarchy written
+//! as a path from the top, e.g., `Storage › Gridfinity › Bins`. Parts of a category also belong
+//! to all categories above it.
+
+use std::collections::BTreeSet;
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::shared::validation::error::ValidationError;
+use crate::shared::validation::validator::CollectingValidator;
+
+use super::part::{Part, PartId};
+
+/// Separator of category levels used to display and store categories.
+pub const CATEGORY_SEPARATOR: &str = " › ";
+const MAX_CATEGORY_DEPTH: usize = 5;
+
+/// Tags and the category of a part.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Classification {
+    pub category: Option<Category>,
+    /// Tags ordered alphabetically.
+    pub tags: BTreeSet<Tag>,
+}
+
+/// Unvalidated tags and category, e.g., from request payloads or storage.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RawClassification {
+    pub category: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl Classification {
+    /// Validates the category and all tags, reporting every invalid one.
+    /// A blank category is treated as missing. Repeated tags are kept once.
+    pub fn parse(raw: RawClassification) -> Result<Self, Vec<ValidationError>> {
+        let mut validator = CollectingValidator::default();
+        let category = raw
+            .category
+            .filter(|c| !c.trim().is_empty())
+            .map(|c| validator.parse_string::<Category>(c));
+        let tags: Vec<Result<Tag, ValidationError>> = raw
+            .tags
+            .into_iter()
+            .map(|t| validator.parse_string::<Tag>(t))
+            .collect();
+        if validator.has_errors() {
+            return Err(validator.into_errors());
+        }
+        Ok(Self {
+            category: category.transpose().unwrap(),
+            tags: tags.into_iter().map(Result::unwrap).collect(),
+        })
+    }
+}
+
+impl From<&Classification> for RawClassification {
+    fn from(value: &Classification) -> Self {
+        Self {
+            category: value.category.as_ref().map(|c| c.to_string()),
+            tags: value.tags.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+}
+
+/// Short label of a part, e.g., `gridfinity` or `wall-mount`.
+/// Tags are lowercase, up to 50 bytes long, and made of letters, digits, spaces, `-` and `_`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(transparent)]
+pub struct Tag(String);
+
+impl Tag {
+    /// Lowercases the tag, so tags differing only in case are the same.
+    pub fn parse(value: impl Into<String>) -> Result<Self, ValidationError> {
+        let value = value.into();
+        let tag = value.trim().to_lowercase();
+        let attr = "tags";
+        if tag.is_empty() {
+            return Err(ValidationError::new(
+                attr,
+                "part.tag.too-short",
+                "tag is too short",
+            ));
+        }
+        if tag.len() > 50 {
+            return Err(ValidationError::new(
+                attr,
+                "part.tag.too-long",
+                "tag is too long",
+            ));
+        }
+        if !tag
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_'))
+        {
+            return Err(ValidationError::new(
+                attr,
+                "part.tag.invalid-character",
+                "tag can contain only letters, digits, spaces, - and _",
+            ));
+        }
+        Ok(Self(tag))
+    }
+}
+
+impl TryFrom<String> for Tag {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl AsRef<str> for Tag {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for Tag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Path of category names from the top of the hierarchy, e.g., `Storage › Gridfinity › Bins`.
+/// Levels may be separated by `›`, `>` or `/`. Categories are up to 5 levels deep and every
+/// level name is up to 50 bytes long.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(into = "String")]
+pub struct Category(Vec<String>);
+
+impl Category {
+    pub fn parse(value: impl Into<String>) -> Result<Self, ValidationError> {
+        let value = value.into();
+        let attr = "category";
+        let levels: Vec<String> = value
+            .split(['›', '>', '/'])
+            .map(|level| level.trim().to_string())
+            .collect();
+        if levels.iter().any(|level| level.is_empty()) {
+            return Err(ValidationError::new(
+                attr,
+                "part.category.too-short",
+                "category names cannot be empty",
+            ));
+        }
+        if levels.iter().any(|level| level.len() > 50) {
+            return Err(ValidationError::new(
+                attr,
+                "part.category.too-long",
+                "category name is too long",
+            ));
+        }
+        if levels.len() > MAX_CATEGORY_DEPTH {
+            return Err(ValidationError::new(
+                attr,
+                "part.category.too-deep",
+                "categories can be up to 5 levels deep",
+            ));
+        }
+        Ok(Self(levels))
+    }
+
+    /// Names of category levels, starting from the top.
+    pub fn levels(&self) -> &[String] {
+        &self.0
+    }
+
+    /// Checks whether the category is this one or lies below it, ignoring case.
+    pub fn contains(&self, other: &Category) -> bool {
+        self.0.len() <= other.0.len()
+            && self
+                .0
+                .iter()
+                .zip(&other.0)
+                .all(|(a, b)| a.eq_ignore_ascii_case(b))
+    }
+}
+
+impl TryFrom<String> for Category {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl From<Category> for String {
+    fn from(value: Category) -> Self {
+        value.to_string()
+    }
+}
+
+impl Display for Category {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.join(CATEGORY_SEPARATOR))
+    }
+}
+
+/// Narrows part listings down. Parts must match every given criterion.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PartFilter {
+    pub tag: Option<Tag>,
+    /// Matches parts of the category and of categories below it.
+    pub category: Option<Category>,
+    /// Matches variants of the part.
+    pub variant_of: Option<PartId>,
+}
+
+impl PartFilter {
+    pub fn matches(&self, part: &Part) -> bool {
+        let classification = part.classification();
+        let tagged = self
+            .tag
+            .as_ref()
+            .is_none_or(|tag| classification.tags.contains(tag));
+        let categorized = self.category.as_ref().is_none_or(|category| {
+            classification
+                .category
+                .as_ref()
+                .is_some_and(|c| category.contains(c))
+        });
+        let variant = self
+            .variant_of
+            .is_none_or(|parent| part.variant().is_some_and(|v| v.parent == parent));
+        tagged && categorized && variant
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn normalize_tags() {
+        let classification = Classification::parse(RawClassification {
+            category: Some(" ".to_string()),
+            tags: vec!["Gridfinity".to_string(), " gridfinity ".to_string()],
+        })
+        .unwrap();
+
+        assert_eq!(classification.category, None);
+        let tags: Vec<&str> = classification.tags.iter().map(|t| t.as_ref()).collect();
+        assert_eq!(tags, vec!["gridfinity"]);
+    }
+
+    #[test]
+    fn report_every_invalid_value() {
+        let errors = Classification::parse(RawClassification {
+            category: Some("Storage › › Bins".to_string()),
+            tags: vec!["bins, lids".to_string(), "".to_string()],
+        })
+        .unwrap_err();
+
+        let codes: Vec<&str> = errors.iter().map(|e| e.code()).collect();
+        assert_eq!(
+            codes,
+            vec![
+                "part.category.too-short",
+                "part.tag.invalid-character",
+                "part.tag.too-short"
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_category_path() {
+        let category = Category::parse("Storage/Gridfinity > Bins").unwrap();
+
+        assert_eq!(category.to_string(), "Storage › Gridfinity › Bins");
+        assert_eq!(
+            Category::parse("a/b/c/d/e/f").unwrap_err().code(),
+            "part.category.too-deep"
+        );
+    }
+
+    #[test]
+    fn contain_subcategories() {
+        let storage = Category::parse("Storage").unwrap();
+        let bins = Category::parse("storage › Gridfinity › Bins").unwrap();
+
+        assert!(storage.contains(&bins));
+        assert!(bins.contains(&bins));
+        assert!(!bins.contains(&storage));
+        assert!(!Category::parse("Stor").unwrap().contains(&bins));
+    }
+}
@@ -0,0 +1,51 @@
+//! Events recorded by the part aggregate.
+
+use super::classification::Classification;
+use super::metadata::PartMetadata;
+use super::part::PartId;
+use super::revision::PartRevisionNumber;
+use super::source::PartSource;
+use super::variant::Variant;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PartEvent {
+    PartRegistered {
+        part: PartId,
+        name: String,
+    },
+    PartRenamed {
+        part: PartId,
+        from: String,
+        to: String,
+    },
+    /// Printing properties of the part were replaced.
+    PartDescribed {
+        part: PartId,
+        previous: PartMetadata,
+        metadata: PartMetadata,
+    },
+    /// Tags and the category of the part were replaced.
+    PartClassified {
+        part: PartId,
+        previous: Classification,
+        classification: Classification,
+    },
+    /// The origin and the license of the part were replaced.
+    PartAttributed {
+        part: PartId,
+        previous: PartSource,
+        source: PartSource,
+    },
+    /// A new revision of the part was started. `revision` is the number of the new revision.
+    PartRevised {
+        part: PartId,
+        revision: PartRevisionNumber,
+    },
+    /// The part became a variant of another part, its attributes changed, or it became
+    /// a standalone part again.
+    VariantDefined {
+        part: PartId,
+        previous: Option<Variant>,
+        variant: Option<Variant>,
+    },
+}
@@ -0,0 +1,303 @@
+//! Model files attached to parts, e.g., STL meshes, STEP sources or sliced G-code.
+//!
+//! File content lives in a content-addressed
+//! [`FileStore`](super::file_repository::FileStore) and is identified by its SHA-256 digest,
+//! so identical uploads are stored once. Parts keep metadata of attached files.
+
+use std::fmt::Display;
+use std::pin::Pin;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncRead;
+use uuid::Uuid;
+
+use crate::shared::validation::error::ValidationError;
+
+use super::part::PartId;
+use super::revision::PartRevisionNumber;
+
+pub type PartFileId = Uuid;
+
+/// Readable content of a stored file.
+pub type FileContent = Pin<Box<dyn AsyncRead + Send>>;
+
+/// Content of an uploaded file, read to the end once.
+pub type Upload<'a> = &'a mut (dyn AsyncRead + Send + Unpin);
+
+/// Metadata of a file attached to a part.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartFile {
+    pub id: PartFileId,
+    pub part: PartId,
+    /// Revision of the part the file belongs to.
+    pub revision: PartRevisionNumber,
+    pub file_name: FileName,
+    pub format: FileFormat,
+    pub sha256: Digest,
+    /// Size of the file in bytes.
+    pub size: u64,
+    pub uploaded_at: DateTime<Utc>,
+}
+
+impl PartFile {
+    /// Attaches stored content to the revision of the part.
+    /// The format follows the file name extension.
+    pub fn new(
+        part: PartId,
+        revision: PartRevisionNumber,
+        file_name: FileName,
+        stored: StoredFile,
+    ) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            part,
+            revision,
+            format: file_name.format(),
+            file_name,
+            sha256: stored.sha256,
+            size: stored.size,
+            uploaded_at: Utc::now(),
+        }
+    }
+}
+
+/// Formats of model files accepted by the inventory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileFormat {
+    Stl,
+    #[serde(rename = "3mf")]
+    ThreeMf,
+    Step,
+    Gcode,
+    /// Binary G-code of PrusaSlicer.
+    Bgcode,
+}
+
+impl FileFormat {
+    /// Detects the format by the file name extension, ignoring case.
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        let (_, extension) = file_name.rsplit_once('.')?;
+        match extension.to_ascii_lowercase().as_str() {
+            "stl" => Some(FileFormat::Stl),
+            "3mf" => Some(FileFormat::ThreeMf),
+            "step" | "stp" => Some(FileFormat::Step),
+            "gcode" => Some(FileFormat::Gcode),
+            "bgcode" => Some(FileFormat::Bgcode),
+            _ => None,
+        }
+    }
+
+    /// Media type used when the file is downloaded.
+    pub fn media_type(&self) -> &'static str {
+        match self {
+            FileFormat::Stl => "model/stl",
+            FileFormat::ThreeMf => "model/3mf",
+            FileFormat::Step => "model/step",
+            FileFormat::Gcode => "text/x.gcode",
+            FileFormat::Bgcode => "application/x.bgcode",
+        }
+    }
+}
+
+impl Display for FileFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let format = match self {
+            FileFormat::Stl => "stl",
+            FileFormat::ThreeMf => "3mf",
+            FileFormat::Step => "step",
+            FileFormat::Gcode => "gcode",
+            FileFormat::Bgcode => "bgcode",
+        };
+        write!(f, "{}", format)
+    }
+}
+
+/// Name of an attached file without directories.
+/// Name cannot be empty, it's max length is 255 bytes and it must have an extension of a
+/// supported [`FileFormat`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct FileName(String);
+
+impl FileName {
+    /// Directories of the uploaded path are dropped, so `parts/lid.stl` becomes `lid.stl`.
+    pub fn parse(value: impl Into<String>) -> Result<Self, ValidationError> {
+        let value = value.into();
+        let attr = "file_name";
+        let name = value.rsplit(['/', '\\']).next().unwrap_or_default().trim();
+        if name.is_empty() {
+            return Err(ValidationError::new(
+                attr,
+                "file.name.too-short",
+                "file name is too short",
+            ));
+        }
+        if name.len() > 255 {
+            return Err(ValidationError::new(
+                attr,
+                "file.name.too-long",
+                "file name is too long",
+            ));
+        }
+        if FileFormat::from_file_name(name).is_none() {
+            return Err(ValidationError::new(
+                attr,
+                "file.format.unsupported",
+                "only STL, 3MF, STEP and G-code files are supported",
+            ));
+        }
+        Ok(Self(name.to_string()))
+    }
+
+    pub fn format(&self) -> FileFormat {
+        FileFormat::from_file_name(&self.0).expect("file name is validated on parse")
+    }
+}
+
+impl TryFrom<String> for FileName {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl From<FileName> for String {
+    fn from(value: FileName) -> Self {
+        value.0
+    }
+}
+
+impl AsRef<str> for FileName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for FileName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// SHA-256 digest of file content as 64 lowercase hexadecimal digits.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Digest(String);
+
+impl Digest {
+    pub fn parse(value: impl Into<String>) -> Result<Self, ValidationError> {
+        let value = value.into();
+        if value.len() != 64
+            || !value
+                .bytes()
+                .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+        {
+            return Err(ValidationError::new(
+                "sha256",
+                "file.sha256.invalid",
+                "digest must have 64 lowercase hexadecimal digits",
+            ));
+        }
+        Ok(Self(value))
+    }
+
+    /// Formats raw digest bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        Self(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+    }
+}
+
+impl TryFrom<String> for Digest {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl From<Digest> for String {
+    fn from(value: Digest) -> Self {
+        value.0
+    }
+}
+
+impl AsRef<str> for Digest {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for Digest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Content saved in the file store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredFile {
+    pub sha256: Digest,
+    pub size: u64,
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn detect_format_by_extension() {
+        let formats: Vec<FileFormat> = [
+            "lid.STL",
+            "box.3mf",
+            "hinge.step",
+            "hinge.stp",
+            "lid.bgcode",
+        ]
+        .into_iter()
+        .map(|name| FileName::parse(name).unwrap().format())
+        .collect();
+
+        assert_eq!(
+            formats,
+            vec![
+                FileFormat::Stl,
+                FileFormat::ThreeMf,
+                FileFormat::Step,
+                FileFormat::Step,
+                FileFormat::Bgcode
+            ]
+        );
+    }
+
+    #[test]
+    fn drop_directories_from_file_name() {
+        let name = FileName::parse("C:\\models\\drawer/lid.stl").unwrap();
+
+        assert_eq!(name.as_ref(), "lid.stl");
+    }
+
+    #[test]
+    fn reject_unsupported_format() {
+        let error = FileName::parse("lid.obj").unwrap_err();
+
+        assert_eq!(error.code(), "file.format.unsupported");
+    }
+
+    #[test]
+    fn reject_empty_file_name() {
+        let error = FileName::parse("models/").unwrap_err();
+
+        assert_eq!(error.code(), "file.name.too-short");
+    }
+
+    #[test]
+    fn format_digest_as_hex() {
+        let digest = Digest::from_bytes(&[0x0a; 32]);
+
+        assert_eq!(Digest::parse(digest.to_string()).unwrap(), digest);
+    }
+}
@@ -0,0 +1,46 @@
+//! The repository traits to interact with files attached to parts.
+
+use async_trait::async_trait;
+
+use super::file::{Digest, FileContent, PartFile, PartFileId, StoredFile, Upload};
+use super::part::PartId;
+use super::thumbnail::ThumbnailFormat;
+
+/// Content-addressed storage of file content.
+/// This trait must be implemented in the infrastructure layer.
+#[async_trait]
+pub trait FileStore: Send + Sync {
+    /// Saves the content read to the end. Content stored before is kept as is.
+    async fn store(&self, content: Upload<'_>) -> anyhow::Result<StoredFile>;
+
+    /// The implementation must return Ok(None) if the content is not stored.
+    async fn open(&self, sha256: &Digest) -> anyhow::Result<Option<FileContent>>;
+
+    /// Returns the thumbnail cached for the content, or Ok(None) if it was not rendered yet.
+    async fn load_thumbnail(
+        &self,
+        sha256: &Digest,
+        format: ThumbnailFormat,
+    ) -> anyhow::Result<Option<Vec<u8>>>;
+
+    /// Caches the thumbnail rendered from the content, replacing one cached before.
+    async fn store_thumbnail(
+        &self,
+        sha256: &Digest,
+        format: ThumbnailFormat,
+        image: &[u8],
+    ) -> anyhow::Result<()>;
+}
+
+/// Repository of part file metadata.
+/// This trait must be implemented in the infrastructure layer.
+#[async_trait]
+pub trait PartFileRepository: Send + Sync {
+    async fn insert(&self, file: PartFile) -> anyhow::Result<()>;
+
+    /// The implementation must return Ok(None) if the part has no file with this ID.
+    async fn find_by_id(&self, part: PartId, id: PartFileId) -> anyhow::Result<Option<PartFile>>;
+
+    /// Lists files of the part, the oldest upload first.
+    async fn list_by_part(&self, part: PartId) -> anyhow::Result<Vec<PartFile>>;
+}
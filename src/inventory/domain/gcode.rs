@@ -0,0 +1,357 @@
+//! Reads print estimates that slicers write into G-code.
+//!
+//! PrusaSlicer and OrcaSlicer write estimated time and filament weight as comments, e.g.,
+//! `; estimated printing time (normal mode) = 1h 2m 3s` and `; filament used [g] = 12.34`.
+//! Cura writes the time in seconds, `;TIME:3723`, and only the length of used filament, e.g.,
+//! `;Filament used: 4.21m`, so the weight is calculated for 1.75 mm filament.
+//! Binary G-code of PrusaSlicer keeps the same values in metadata blocks.
+//!
+//! Estimates are attached to parts only. Plates are proposed on the fly from the project BOM
+//! and never stored, so G-code of a whole plate has nothing to be attached to and would
+//! overstate the estimates of a single part. Such G-code should be sliced per part instead.
+
+use std::f64::consts::PI;
+use std::io::Read;
+
+use flate2::read::ZlibDecoder;
+
+use crate::shared::validation::error::ValidationError;
+
+use super::metadata::{Grams, Material, Minutes};
+
+/// Diameter of filament assumed when only its length is known.
+const FILAMENT_DIAMETER: f64 = 1.75;
+/// Magic bytes starting binary G-code.
+const BGCODE_MAGIC: &[u8] = b"GCDE";
+
+/// Estimates of printing the G-code.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SlicerEstimates {
+    /// Print time in seconds.
+    pub seconds: Option<u64>,
+    /// Filament weight in grams.
+    pub grams: Option<f64>,
+    /// Filament length in millimeters.
+    pub filament_length: Option<f64>,
+}
+
+impl SlicerEstimates {
+    /// Print time rounded up to whole minutes.
+    pub fn minutes(&self) -> Option<Minutes> {
+        let minutes = self.seconds?.div_ceil(60);
+        Minutes::parse(u32::try_from(minutes).ok()?).ok()
+    }
+
+    /// Filament weight reported by the slicer or calculated from the length of filament,
+    /// assuming PLA when the material is not known.
+    pub fn grams(&self, material: Option<Material>) -> Option<Grams> {
+        let grams = self.grams.or_else(|| {
+            let radius = FILAMENT_DIAMETER / 2.0;
+            let volume = PI * radius * radius * self.filament_length?;
+            let density = material.unwrap_or(Material::Pla).density();
+            Some((volume / 1000.0 * density * 100.0).round() / 100.0)
+        })?;
+        Grams::parse(grams).ok()
+    }
+}
+
+/// Reads estimates from text or binary G-code.
+/// Rejects G-code without time or filament estimates.
+pub fn estimates(content: &[u8]) -> Result<SlicerEstimates, ValidationError> {
+    let mut reader = EstimatesReader::default();
+    if content.starts_with(BGCODE_MAGIC) {
+        read_binary(content, &mut reader)?;
+    } else {
+        for line in content.split(|b| *b == b'\n') {
+            if let Some(comment) = line.strip_prefix(b";") {
+                reader.comment(&String::from_utf8_lossy(comment));
+            }
+        }
+    }
+    let estimates = reader.finish();
+    if estimates.minutes().is_none() && estimates.grams(None).is_none() {
+        return Err(ValidationError::new(
+            "file",
+            "gcode.estimates.missing",
+            "G-code has no print time or filament estimates of a supported slicer",
+        ));
+    }
+    Ok(estimates)
+}
+
+/// Collects values of known keys. Totals are preferred over values per extruder.
+#[derive(Debug, Default)]
+struct EstimatesReader {
+    seconds: Option<u64>,
+    total_grams: Option<f64>,
+    grams: Option<f64>,
+    filament_length: Option<f64>,
+}
+
+impl EstimatesReader {
+    /// Reads a comment without the leading `;`. OrcaSlicer puts several values on a line,
+    /// e.g., `model printing time: 1h 2m; total estimated time: 1h 10m`.
+    fn comment(&mut self, comment: &str) {
+        for entry in comment.split(';') {
+            if let Some((key, value)) = entry.split_once(['=', ':']) {
+                self.value(&key.trim().to_ascii_lowercase(), value.trim());
+            }
+        }
+    }
+
+    fn value(&mut self, key: &str, value: &str) {
+        match key {
+            "estimated printing time (normal mode)" | "total estimated time" => {
+                self.seconds = self.seconds.or(duration(value));
+            }
+            "time" => self.seconds = self.seconds.or(value.parse().ok()),
+            "total filament used [g]" | "total filament weight [g]" => {
+                self.total_grams = self.total_grams.or(sum(value, ""));
+            }
+            "filament used [g]" => self.grams = self.grams.or(sum(value, "")),
+            "filament used [mm]" => self.filament_length = self.filament_length.or(sum(value, "")),
+            "filament used" => {
+                let meters = sum(value, "m");
+                self.filament_length = self.filament_length.or(meters.map(|m| m * 1000.0));
+            }
+            _ => {}
+        }
+    }
+
+    fn finish(self) -> SlicerEstimates {
+        SlicerEstimates {
+            seconds: self.seconds,
+            grams: self.total_grams.or(self.grams),
+            filament_length: self.filament_length,
+        }
+    }
+}
+
+/// Parses durations like `1d 2h 3m 4s` into seconds.
+/// Durations too long to count in seconds are not estimates worth keeping.
+fn duration(value: &str) -> Option<u64> {
+    let mut seconds: u64 = 0;
+    for part in value.split_whitespace() {
+        let unit = match part.chars().last()? {
+            'd' => 86_400,
+            'h' => 3_600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        let amount: u64 = part[..part.len() - 1].parse().ok()?;
+        seconds = seconds.checked_add(amount.checked_mul(unit)?)?;
+    }
+    (seconds > 0).then_some(seconds)
+}
+
+/// Sums comma-separated values of all extruders, stripping the unit suffix.
+fn sum(value: &str, unit: &str) -> Option<f64> {
+    let mut total = 0.0;
+    for value in value.split(',') {
+        let value = value.trim();
+        let number: f64 = value
+            .strip_suffix(unit)
+            .unwrap_or(value)
+            .trim()
+            .parse()
+            .ok()?;
+        total += number;
+    }
+    (total.is_finite() && total > 0.0).then_some(total)
+}
+
+/// Reads INI metadata blocks of binary G-code. Blocks with G-code and thumbnails are skipped.
+/// Metadata compressed with other methods than deflate is skipped as well.
+fn read_binary(content: &[u8], reader: &mut EstimatesReader) -> Result<(), ValidationError> {
+    let mut input = BinaryInput {
+        content,
+        position: 4,
+    };
+    let _version = input.u32()?;
+    let checksum_size = match input.u16()? {
+        0 => 0,
+        1 => 4,
+        _ => return Err(invalid("unknown checksum type")),
+    };
+    while input.position < content.len() {
+        let block_type = input.u16()?;
+        let compression = input.u16()?;
+        let size = input.u32()? as usize;
+        let stored_size = match compression {
+            0 => size,
+            _ => input.u32()? as usize,
+        };
+        let params_size = match block_type {
+            5 => 6,
+            _ => 2,
+        };
+        input.skip(params_size)?;
+        let data = input.take(stored_size)?;
+        input.skip(checksum_size)?;
+        if !matches!(block_type, 0 | 2 | 3 | 4) {
+            continue;
+        }
+        let text = match compression {
+            0 => String::from_utf8_lossy(data).into_owned(),
+            1 => {
+                let mut text = String::new();
+                ZlibDecoder::new(data)
+                    .take(size as u64)
+                    .read_to_string(&mut text)
+                    .map_err(|e| invalid(e.to_string()))?;
+                text
+            }
+            _ => continue,
+        };
+        for line in text.lines() {
+            reader.comment(line);
+        }
+    }
+    Ok(())
+}
+
+struct BinaryInput<'a> {
+    content: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BinaryInput<'a> {
+    fn take(&mut self, size: usize) -> Result<&'a [u8], ValidationError> {
+        let end = self
+            .position
+            .checked_add(size)
+            .filter(|end| *end <= self.content.len())
+            .ok_or_else(|| invalid("block is truncated"))?;
+        let data = &self.content[self.position..end];
+        self.position = end;
+        Ok(data)
+    }
+
+    fn skip(&mut self, size: usize) -> Result<(), ValidationError> {
+        self.take(size).map(|_| ())
+    }
+
+    fn u16(&mut self) -> Result<u16, ValidationError> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, ValidationError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+}
+
+fn invalid(reason: impl Into<String>) -> ValidationError {
+    ValidationError::new(
+        "file",
+        "gcode.content.invalid",
+        format!("binary G-code cannot be read: {}", reason.into()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::Write;
+
+    use flate2::write::ZlibEncoder;
+    use flate2::Compression;
+
+    use super::*;
+
+    #[test]
+    fn read_prusaslicer_footer() {
+        let gcode =
+            "G1 X10 Y10\n; filament used [mm] = 4210.55\n; filament used [g] = 12.50, 0.50\n\
+            ; total filament used [g] = 13.10\n\
+            ; estimated printing time (normal mode) = 1h 2m 3s\n";
+
+        let estimates = estimates(gcode.as_bytes()).unwrap();
+
+        assert_eq!(estimates.seconds, Some(3723));
+        assert_eq!(estimates.grams, Some(13.1));
+        assert_eq!(estimates.minutes().unwrap().value(), 63);
+    }
+
+    #[test]
+    fn read_orcaslicer_header() {
+        let gcode = "; HEADER_BLOCK_START\n\
+            ; model printing time: 1h 2m 3s; total estimated time: 1d 1h 10m 5s\n\
+            ; total filament weight [g] : 21.34\n; HEADER_BLOCK_END\n";
+
+        let estimates = estimates(gcode.as_bytes()).unwrap();
+
+        assert_eq!(estimates.seconds, Some(90_605));
+        assert_eq!(estimates.grams, Some(21.34));
+    }
+
+    #[test]
+    fn ignore_overflowing_durations() {
+        let gcode = "; estimated printing time (normal mode) = 999999999999999d 1s\n\
+            ; total estimated time = 18446744073709551615s 1s\n;TIME:99999999999\n\
+            ; filament used [g] = 12.5\n";
+
+        let estimates = estimates(gcode.as_bytes()).unwrap();
+
+        assert_eq!(estimates.seconds, Some(99_999_999_999));
+        assert_eq!(estimates.minutes(), None);
+        assert_eq!(estimates.grams, Some(12.5));
+    }
+
+    #[test]
+    fn calculate_weight_of_cura_filament() {
+        let gcode = ";FLAVOR:Marlin\n;TIME:3723\n;Filament used: 4.21m\n;TIME_ELAPSED:12.5\n";
+
+        let estimates = estimates(gcode.as_bytes()).unwrap();
+
+        assert_eq!(estimates.seconds, Some(3723));
+        assert_eq!(estimates.grams, None);
+        assert_eq!(estimates.grams(Some(Material::Pla)).unwrap().value(), 12.56);
+    }
+
+    #[test]
+    fn read_binary_gcode_metadata() {
+        let print_metadata =
+            "filament used [g]=12.34\nestimated printing time (normal mode)=45m 10s\n";
+        let mut compressed = ZlibEncoder::new(Vec::new(), Compression::default());
+        compressed.write_all(print_metadata.as_bytes()).unwrap();
+        let compressed = compressed.finish().unwrap();
+        let mut content = BGCODE_MAGIC.to_vec();
+        content.extend(1_u32.to_le_bytes());
+        content.extend(0_u16.to_le_bytes());
+        // Print metadata compressed with deflate.
+        content.extend(4_u16.to_le_bytes());
+        content.extend(1_u16.to_le_bytes());
+        content.extend((print_metadata.len() as u32).to_le_bytes());
+        content.extend((compressed.len() as u32).to_le_bytes());
+        content.extend(0_u16.to_le_bytes());
+        content.extend(&compressed);
+        // Uncompressed G-code.
+        content.extend(1_u16.to_le_bytes());
+        content.extend(0_u16.to_le_bytes());
+        content.extend(4_u32.to_le_bytes());
+        content.extend(0_u16.to_le_bytes());
+        content.extend(b"G28\n");
+
+        let estimates = estimates(&content).unwrap();
+
+        assert_eq!(estimates.seconds, Some(2710));
+        assert_eq!(estimates.grams, Some(12.34));
+    }
+
+    #[test]
+    fn reject_gcode_without_estimates() {
+        let errors = [
+            estimates(b"G28\nG1 X10\n").unwrap_err(),
+            estimates(b"GCDE\x01\x00").unwrap_err(),
+        ];
+
+        let codes: Vec<&str> = errors.iter().map(|e| e.code()).collect();
+        assert_eq!(
+            codes,
+            vec!["gcode.estimates.missing", "gcode.content.invalid"]
+        );
+    }
+}
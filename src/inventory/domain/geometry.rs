@@ -0,0 +1,174 @@
+//! Geometry of part meshes measured from model files.
+//!
+//! Sizes are in millimeters, so volumes are in mm³ and areas in mm².
+
+use serde::{Deserialize, Serialize};
+
+use crate::shared::rounding::round;
+use crate::shared::validation::error::ValidationError;
+
+use super::metadata::{Dimensions, Grams, Material};
+
+/// Corner of a mesh triangle.
+pub type Vertex = [f64; 3];
+
+/// Measurements of a closed triangle mesh.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Geometry {
+    /// Size of the axis-aligned bounding box.
+    pub bounding_box: [f64; 3],
+    pub triangles: u32,
+    pub volume: f64,
+    pub area: f64,
+}
+
+impl Geometry {
+    /// Measures the mesh. Rejects meshes without triangles.
+    /// Volume is only meaningful for closed meshes; open meshes report a rough value.
+    pub fn measure(
+        triangles: impl IntoIterator<Item = [Vertex; 3]>,
+    ) -> Result<Self, ValidationError> {
+        let mut min = [f64::INFINITY; 3];
+        let mut max = [f64::NEG_INFINITY; 3];
+        let (mut count, mut volume, mut area) = (0_u32, 0.0, 0.0);
+        for [a, b, c] in triangles {
+            for vertex in [a, b, c] {
+                for axis in 0..3 {
+                    min[axis] = min[axis].min(vertex[axis]);
+                    max[axis] = max[axis].max(vertex[axis]);
+                }
+            }
+            // Signed volume of the tetrahedron spanned with the origin.
+            volume += dot(a, cross(b, c)) / 6.0;
+            area += length(cross(sub(b, a), sub(c, a))) / 2.0;
+            count = count.saturating_add(1);
+        }
+        if count == 0 {
+            return Err(ValidationError::new(
+                "file",
+                "mesh.triangles.missing",
+                "model has no triangles",
+            ));
+        }
+        Ok(Self {
+            bounding_box: [0, 1, 2].map(|axis| round(max[axis] - min[axis])),
+            triangles: count,
+            volume: round(volume.abs()),
+            area: round(area),
+        })
+    }
+
+    /// Bounding box as part dimensions. Flat or oversized meshes have no dimensions.
+    pub fn dimensions(&self) -> Option<Dimensions> {
+        let [x, y, z] = self.bounding_box;
+        Dimensions::parse(x, y, z).ok()
+    }
+
+    /// Weight of the mesh printed solid, assuming PLA when the material is not known.
+    /// Infill makes real prints lighter, so this is an upper estimate.
+    /// Meshes lighter than a gram are estimated at the smallest valid weight of 1 gram,
+    /// while meshes without volume have no estimate.
+    pub fn estimated_grams(&self, material: Option<Material>) -> Option<Grams> {
+        if self.volume <= 0.0 {
+            return None;
+        }
+        let density = material.unwrap_or(Material::Pla).density();
+        let grams = (self.volume / 1000.0 * density * 10.0).round() / 10.0;
+        Grams::parse(grams.max(1.0)).ok()
+    }
+}
+
+pub(crate) fn sub(a: Vertex, b: Vertex) -> Vertex {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+pub(crate) fn cross(a: Vertex, b: Vertex) -> Vertex {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+pub(crate) fn dot(a: Vertex, b: Vertex) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn length(a: Vertex) -> f64 {
+    dot(a, a).sqrt()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+
+    use super::*;
+
+    /// Triangles of an axis-aligned box with the corner at `origin`, facing outwards.
+    pub(crate) fn cuboid(origin: Vertex, size: Vertex) -> Vec<[Vertex; 3]> {
+        let [x0, y0, z0] = origin;
+        let [x1, y1, z1] = [x0 + size[0], y0 + size[1], z0 + size[2]];
+        let corners = [
+            [x0, y0, z0],
+            [x1, y0, z0],
+            [x1, y1, z0],
+            [x0, y1, z0],
+            [x0, y0, z1],
+            [x1, y0, z1],
+            [x1, y1, z1],
+            [x0, y1, z1],
+        ];
+        let faces = [
+            [0, 3, 2, 1],
+            [4, 5, 6, 7],
+            [0, 1, 5, 4],
+            [1, 2, 6, 5],
+            [2, 3, 7, 6],
+            [3, 0, 4, 7],
+        ];
+        faces
+            .iter()
+            .flat_map(|[a, b, c, d]| {
+                [
+                    [corners[*a], corners[*b], corners[*c]],
+                    [corners[*a], corners[*c], corners[*d]],
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn measure_box() {
+        let geometry = Geometry::measure(cuboid([5.0, -3.0, 1.0], [20.0, 10.0, 5.0])).unwrap();
+
+        assert_eq!(
+            geometry,
+            Geometry {
+                bounding_box: [20.0, 10.0, 5.0],
+                triangles: 12,
+                volume: 1000.0,
+                area: 700.0,
+            }
+        );
+    }
+
+    #[test]
+    fn estimate_weight_by_material_density() {
+        let geometry = Geometry::measure(cuboid([0.0; 3], [10.0, 10.0, 10.0])).unwrap();
+
+        assert_eq!(geometry.estimated_grams(None).unwrap().value(), 1.2);
+        assert_eq!(
+            geometry
+                .estimated_grams(Some(Material::Petg))
+                .unwrap()
+                .value(),
+            1.3
+        );
+    }
+
+    #[test]
+    fn reject_empty_mesh() {
+        let error = Geometry::measure(vec![]).unwrap_err();
+
+        assert_eq!(error.code(), "mesh.triangles.missing");
+    }
+}
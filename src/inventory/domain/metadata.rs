@@ -0,0 +1,508 @@
+//! Printing properties of a part.
+//!
+//! Values come from slicer estimates and from the designer of the part. Projects use them to
+//! calculate how much filament and time printing takes.
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::shared::validation::error::ValidationError;
+use crate::shared::validation::validator::CollectingValidator;
+
+/// Properties of the part. Every property is optional.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PartMetadata {
+    pub material: Option<Material>,
+    pub color: Option<Color>,
+    /// Estimated filament weight.
+    pub grams: Option<Grams>,
+    /// Estimated print time.
+    pub minutes: Option<Minutes>,
+    /// Bounding box of the part.
+    pub dimensions: Option<Dimensions>,
+    pub notes: Option<Notes>,
+}
+
+/// Unvalidated part properties, e.g., from request payloads or storage.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RawPartMetadata {
+    pub material: Option<String>,
+    pub color: Option<String>,
+    pub grams: Option<f64>,
+    pub minutes: Option<u32>,
+    pub dimensions: Option<RawDimensions>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RawDimensions {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl PartMetadata {
+    /// Validates all properties, reporting every invalid one.
+    /// Blank notes are treated as missing.
+    pub fn parse(raw: RawPartMetadata) -> Result<Self, Vec<ValidationError>> {
+        let mut validator = CollectingValidator::default();
+        let material = raw.material.map(|m| validator.parse_string::<Material>(m));
+        let color = raw.color.map(|c| validator.parse_string::<Color>(c));
+        let grams = raw.grams.map(|g| validator.parse::<Grams, _>(g));
+        let minutes = raw.minutes.map(|m| validator.parse::<Minutes, _>(m));
+        let dimensions = raw
+            .dimensions
+            .map(|d| validator.parse::<Dimensions, _>((d.x, d.y, d.z)));
+        let notes = raw
+            .notes
+            .filter(|n| !n.trim().is_empty())
+            .map(|n| validator.parse_string::<Notes>(n));
+        if validator.has_errors() {
+            return Err(validator.into_errors());
+        }
+        Ok(Self {
+            material: material.transpose().unwrap(),
+            color: color.transpose().unwrap(),
+            grams: grams.transpose().unwrap(),
+            minutes: minutes.transpose().unwrap(),
+            dimensions: dimensions.transpose().unwrap(),
+            notes: notes.transpose().unwrap(),
+        })
+    }
+}
+
+impl From<&PartMetadata> for RawPartMetadata {
+    fn from(value: &PartMetadata) -> Self {
+        Self {
+            material: value.material.map(|m| m.to_string()),
+            color: value.color.as_ref().map(|c| c.to_string()),
+            grams: value.grams.map(|g| g.value()),
+            minutes: value.minutes.map(|m| m.value()),
+            dimensions: value.dimensions.map(|d| RawDimensions {
+                x: d.x(),
+                y: d.y(),
+                z: d.z(),
+            }),
+            notes: value.notes.as_ref().map(|n| n.to_string()),
+        }
+    }
+}
+
+/// Filament material.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "SCREAMING-KEBAB-CASE")]
+pub enum Material {
+    Pla,
+    Petg,
+    Abs,
+    Asa,
+    Tpu,
+    Pa,
+    Pc,
+    Pva,
+    Hips,
+    PlaCf,
+    PetgCf,
+    PaCf,
+}
+
+impl Material {
+    pub const ALL: [Material; 12] = [
+        Material::Pla,
+        Material::Petg,
+        Material::Abs,
+        Material::Asa,
+        Material::Tpu,
+        Material::Pa,
+        Material::Pc,
+        Material::Pva,
+        Material::Hips,
+        Material::PlaCf,
+        Material::PetgCf,
+        Material::PaCf,
+    ];
+
+    /// Parses material name, ignoring case.
+    pub fn parse(value: &str) -> Result<Self, ValidationError> {
+        let value = value.trim();
+        Material::ALL
+            .into_iter()
+            .find(|m| m.as_str().eq_ignore_ascii_case(value))
+            .ok_or_else(|| {
+                ValidationError::new(
+                    "material",
+                    "part.material.unknown",
+                    format!(
+                        "material must be one of {}",
+                        Material::ALL.map(|m| m.as_str()).join(", ")
+                    ),
+                )
+            })
+    }
+
+    /// Typical density of the filament in g/cm³.
+    pub fn density(&self) -> f64 {
+        match self {
+            Material::Pla => 1.24,
+            Material::Petg => 1.27,
+            Material::Abs => 1.04,
+            Material::Asa => 1.07,
+            Material::Tpu => 1.21,
+            Material::Pa => 1.14,
+            Material::Pc => 1.2,
+            Material::Pva => 1.23,
+            Material::Hips => 1.03,
+            Material::PlaCf => 1.29,
+            Material::PetgCf => 1.3,
+            Material::PaCf => 1.2,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Material::Pla => "PLA",
+            Material::Petg => "PETG",
+            Material::Abs => "ABS",
+            Material::Asa => "ASA",
+            Material::Tpu => "TPU",
+            Material::Pa => "PA",
+            Material::Pc => "PC",
+            Material::Pva => "PVA",
+            Material::Hips => "HIPS",
+            Material::PlaCf => "PLA-CF",
+            Material::PetgCf => "PETG-CF",
+            Material::PaCf => "PA-CF",
+        }
+    }
+}
+
+impl TryFrom<String> for Material {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl Display for Material {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Filament color, e.g., `black` or `#ff8800`.
+/// Color cannot be empty and it's max length is 50 bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(transparent)]
+pub struct Color(String);
+
+impl Color {
+    pub fn parse(value: impl Into<String>) -> Result<Self, ValidationError> {
+        let value = value.into();
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            return Err(ValidationError::new(
+                "color",
+                "part.color.too-short",
+                "color is too short",
+            ));
+        }
+        if trimmed.len() > 50 {
+            return Err(ValidationError::new(
+                "color",
+                "part.color.too-long",
+                "color is too long",
+            ));
+        }
+        Ok(Self(trimmed.to_string()))
+    }
+}
+
+impl TryFrom<String> for Color {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl AsRef<str> for Color {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Weight of filament used to print the part.
+/// Weight must be at least 1 gram and must not exceed 100000 grams.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize)]
+#[serde(transparent)]
+pub struct Grams(f64);
+
+impl Grams {
+    pub fn parse(value: f64) -> Result<Self, ValidationError> {
+        if !value.is_finite() || !(1.0..=100_000.0).contains(&value) {
+            return Err(ValidationError::new(
+                "grams",
+                "part.grams.out-of-range",
+                "filament weight must be between 1 and 100000 grams",
+            ));
+        }
+        Ok(Self(value))
+    }
+
+    pub fn value(&self) -> f64 {
+        self.0
+    }
+}
+
+impl TryFrom<f64> for Grams {
+    type Error = ValidationError;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+/// Time to print the part.
+/// Time must be a positive number of minutes not exceeding 100000.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(transparent)]
+pub struct Minutes(u32);
+
+impl Minutes {
+    pub fn parse(value: u32) -> Result<Self, ValidationError> {
+        if value == 0 || value > 100_000 {
+            return Err(ValidationError::new(
+                "minutes",
+                "part.minutes.out-of-range",
+                "print time must be between 1 and 100000 minutes",
+            ));
+        }
+        Ok(Self(value))
+    }
+
+    pub fn value(&self) -> u32 {
+        self.0
+    }
+}
+
+impl TryFrom<u32> for Minutes {
+    type Error = ValidationError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+/// Bounding box of the part in millimeters.
+/// Every side must be positive and not exceed 10000 mm.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Dimensions {
+    x: f64,
+    y: f64,
+    z: f64,
+}
+
+const MAX_SIDE: f64 = 10_000.0;
+
+impl Dimensions {
+    pub fn parse(x: f64, y: f64, z: f64) -> Result<Self, ValidationError> {
+        if [x, y, z]
+            .iter()
+            .any(|side| !side.is_finite() || *side <= 0.0 || *side > MAX_SIDE)
+        {
+            return Err(ValidationError::new(
+                "dimensions",
+                "part.dimensions.out-of-range",
+                "every side must be between 0 and 10000 mm",
+            ));
+        }
+        Ok(Self { x, y, z })
+    }
+
+    pub fn x(&self) -> f64 {
+        self.x
+    }
+
+    pub fn y(&self) -> f64 {
+        self.y
+    }
+
+    pub fn z(&self) -> f64 {
+        self.z
+    }
+}
+
+impl TryFrom<(f64, f64, f64)> for Dimensions {
+    type Error = ValidationError;
+
+    fn try_from((x, y, z): (f64, f64, f64)) -> Result<Self, Self::Error> {
+        Self::parse(x, y, z)
+    }
+}
+
+/// Parses dimensions written as `XxYxZ`, e.g., `120x80x35.5`.
+impl TryFrom<String> for Dimensions {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let sides: Vec<f64> = value
+            .split(['x', 'X', '×'])
+            .map(|side| side.trim().parse::<f64>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| invalid_dimensions())?;
+        match sides[..] {
+            [x, y, z] => Self::parse(x, y, z),
+            _ => Err(invalid_dimensions()),
+        }
+    }
+}
+
+fn invalid_dimensions() -> ValidationError {
+    ValidationError::new(
+        "dimensions",
+        "part.dimensions.invalid-format",
+        "dimensions must be written as XxYxZ, e.g., 120x80x35",
+    )
+}
+
+impl Display for Dimensions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x{}x{}", self.x, self.y, self.z)
+    }
+}
+
+/// Free-form notes, e.g., recommended print settings.
+/// Notes max length is 2000 bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct Notes(String);
+
+impl Notes {
+    pub fn parse(value: impl Into<String>) -> Result<Self, ValidationError> {
+        let value = value.into();
+        let trimmed = value.trim();
+        if trimmed.len() > 2000 {
+            return Err(ValidationError::new(
+                "notes",
+                "part.notes.too-long",
+                "notes are too long",
+            ));
+        }
+        Ok(Self(trimmed.to_string()))
+    }
+}
+
+impl TryFrom<String> for Notes {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl Display for Notes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn parse_material_ignoring_case() {
+        assert_eq!(Material::parse(" petg-cf ").unwrap(), Material::PetgCf);
+        assert_eq!(
+            Material::parse("wood").unwrap_err().code(),
+            "part.material.unknown"
+        );
+    }
+
+    #[test]
+    fn parse_dimensions() {
+        let dimensions = Dimensions::try_from("120x80 × 35.5".to_string()).unwrap();
+        assert_eq!(
+            (dimensions.x(), dimensions.y(), dimensions.z()),
+            (120.0, 80.0, 35.5)
+        );
+        assert_eq!(
+            Dimensions::try_from("120x80".to_string())
+                .unwrap_err()
+                .code(),
+            "part.dimensions.invalid-format"
+        );
+        assert_eq!(
+            Dimensions::parse(0.0, 1.0, 1.0).unwrap_err().code(),
+            "part.dimensions.out-of-range"
+        );
+    }
+
+    #[test]
+    fn reject_out_of_range_estimates() {
+        assert!(Grams::parse(12.5).is_ok());
+        assert!(Grams::parse(0.5).is_err());
+        assert!(Grams::parse(f64::NAN).is_err());
+        assert_eq!(
+            Grams::parse(-1.0).unwrap_err().code(),
+            "part.grams.out-of-range"
+        );
+        assert_eq!(
+            Minutes::parse(0).unwrap_err().code(),
+            "part.minutes.out-of-range"
+        );
+    }
+
+    #[test]
+    fn report_all_invalid_properties() {
+        let raw = RawPartMetadata {
+            material: Some("wood".to_string()),
+            color: Some(" ".to_string()),
+            grams: Some(12.0),
+            minutes: Some(0),
+            dimensions: None,
+            notes: Some("  ".to_string()),
+        };
+
+        let errors = PartMetadata::parse(raw).unwrap_err();
+
+        let codes: Vec<&str> = errors.iter().map(|e| e.code()).collect();
+        assert_eq!(
+            codes,
+            vec![
+                "part.material.unknown",
+                "part.color.too-short",
+                "part.minutes.out-of-range"
+            ]
+        );
+    }
+
+    #[test]
+    fn convert_to_raw_and_back() {
+        let raw = RawPartMetadata {
+            material: Some("PLA".to_string()),
+            color: Some("black".to_string()),
+            grams: Some(12.5),
+            minutes: Some(95),
+            dimensions: Some(RawDimensions {
+                x: 10.0,
+                y: 20.0,
+                z: 30.0,
+            }),
+            notes: Some("0.2 mm layers".to_string()),
+        };
+
+        let metadata = PartMetadata::parse(raw.clone()).unwrap();
+
+        assert_eq!(RawPartMetadata::from(&metadata), raw);
+    }
+}
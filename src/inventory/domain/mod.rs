@@ -1,3 +1,17 @@
+pub mod classification;
+pub mod event;
+pub mod file;
+pub mod file_repository;
+pub mod gcode;
+pub mod geometry;
+pub mod metadata;
 pub mod name;
+pub mod packing;
 pub mod part;
 pub mod part_repository;
+pub mod revision;
+pub mod source;
+pub mod stl;
+pub mod three_mf;
+pub mod thumbnail;
+pub mod variant;
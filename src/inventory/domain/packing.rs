@@ -0,0 +1,412 @@
+//! Fits parts on printer beds.
+//!
+//! Parts are packed by their footprint, the X and Y sides of the bounding box, and may be turned
+//! by 90 degrees. Mixed plates are packed in shelves: copies sorted by depth are placed in rows
+//! along the bed width and a new row starts behind the deepest copy of the previous one.
+//! Spacing is kept between copies, but not at bed edges.
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::shared::validation::error::ValidationError;
+
+use super::metadata::Dimensions;
+use super::part::PartId;
+
+/// Printable area of a printer bed in millimeters, e.g., `250x210`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Bed {
+    width: f64,
+    depth: f64,
+}
+
+const MAX_BED_SIDE: f64 = 10_000.0;
+
+impl Bed {
+    pub fn parse(width: f64, depth: f64) -> Result<Self, ValidationError> {
+        if [width, depth]
+            .iter()
+            .any(|side| !side.is_finite() || *side <= 0.0 || *side > MAX_BED_SIDE)
+        {
+            return Err(ValidationError::new(
+                "bed",
+                "packing.bed.out-of-range",
+                "bed sides must be between 0 and 10000 mm",
+            ));
+        }
+        Ok(Self { width, depth })
+    }
+
+    pub fn width(&self) -> f64 {
+        self.width
+    }
+
+    pub fn depth(&self) -> f64 {
+        self.depth
+    }
+
+    /// Turns the footprint so it fits the bed, preferring the longer side along the bed width.
+    /// Returns None when the footprint does not fit either way.
+    pub fn orient(&self, footprint: Footprint) -> Option<Footprint> {
+        let turned = footprint.turned();
+        let (wide, narrow) = if footprint.width >= footprint.depth {
+            (footprint, turned)
+        } else {
+            (turned, footprint)
+        };
+        [wide, narrow].into_iter().find(|f| self.holds(f))
+    }
+
+    /// Lays copies of the footprint out in a grid, turned the way more copies fit.
+    /// Returns None when the footprint does not fit the bed.
+    pub fn grid(&self, footprint: Footprint, spacing: Spacing) -> Option<Grid> {
+        [footprint, footprint.turned()]
+            .into_iter()
+            .filter(|f| self.holds(f))
+            .map(|f| Grid {
+                columns: fitting(self.width, f.width, spacing),
+                rows: fitting(self.depth, f.depth, spacing),
+                rotated: f.rotated,
+            })
+            .max_by_key(|grid| grid.copies())
+    }
+
+    fn holds(&self, footprint: &Footprint) -> bool {
+        footprint.width <= self.width && footprint.depth <= self.depth
+    }
+}
+
+/// Number of sides fitting along the bed side with spacing between them.
+/// Nothing fits when the bed side left is negative, e.g., a shelf filled up to its spacing.
+fn fitting(bed: f64, side: f64, spacing: Spacing) -> u32 {
+    ((bed + spacing.0) / (side + spacing.0)).floor().max(0.0) as u32
+}
+
+/// Parses beds written as `WIDTHxDEPTH`, e.g., `250x210`.
+impl TryFrom<String> for Bed {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let sides: Vec<f64> = value
+            .split(['x', 'X', '×'])
+            .map(|side| side.trim().parse::<f64>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| invalid_bed())?;
+        match sides[..] {
+            [width, depth] => Self::parse(width, depth),
+            _ => Err(invalid_bed()),
+        }
+    }
+}
+
+fn invalid_bed() -> ValidationError {
+    ValidationError::new(
+        "bed",
+        "packing.bed.invalid-format",
+        "bed must be written as WIDTHxDEPTH, e.g., 250x210",
+    )
+}
+
+impl Display for Bed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x{}", self.width, self.depth)
+    }
+}
+
+/// Gap kept between copies on a plate in millimeters.
+/// Spacing must be between 0 and 100 mm.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Spacing(f64);
+
+impl Spacing {
+    pub const DEFAULT: Spacing = Spacing(5.0);
+
+    pub fn parse(value: f64) -> Result<Self, ValidationError> {
+        if !value.is_finite() || !(0.0..=100.0).contains(&value) {
+            return Err(ValidationError::new(
+                "spacing",
+                "packing.spacing.out-of-range",
+                "spacing must be between 0 and 100 mm",
+            ));
+        }
+        Ok(Self(value))
+    }
+
+    pub fn value(&self) -> f64 {
+        self.0
+    }
+}
+
+impl TryFrom<f64> for Spacing {
+    type Error = ValidationError;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl Default for Spacing {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Area a part takes on the bed.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Footprint {
+    pub width: f64,
+    pub depth: f64,
+    /// The part is turned by 90 degrees against its X and Y sides.
+    pub rotated: bool,
+}
+
+impl Footprint {
+    fn turned(self) -> Self {
+        Self {
+            width: self.depth,
+            depth: self.width,
+            rotated: !self.rotated,
+        }
+    }
+}
+
+impl From<Dimensions> for Footprint {
+    fn from(value: Dimensions) -> Self {
+        Self {
+            width: value.x(),
+            depth: value.y(),
+            rotated: false,
+        }
+    }
+}
+
+/// Copies of a single part laid out in columns along the bed width and rows along its depth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Grid {
+    pub columns: u32,
+    pub rows: u32,
+    pub rotated: bool,
+}
+
+impl Grid {
+    /// Tiny footprints on large beds make millions of copies, so the count does not fit u32.
+    pub fn copies(&self) -> u64 {
+        u64::from(self.columns).saturating_mul(u64::from(self.rows))
+    }
+}
+
+/// Copy of a part placed on a plate. Coordinates are of the front left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Placement {
+    pub part: PartId,
+    pub x: f64,
+    pub y: f64,
+    #[serde(flatten)]
+    pub footprint: Footprint,
+}
+
+/// Copies to pack. Footprints must fit the bed, see [`Bed::orient`].
+#[derive(Debug, Clone, Copy)]
+pub struct PackingItem {
+    pub part: PartId,
+    pub footprint: Footprint,
+    pub copies: u32,
+}
+
+/// Packs copies of all items on as few plates as the shelf heuristic manages.
+/// Returns placements of every plate.
+pub fn pack_plates(bed: Bed, spacing: Spacing, items: &[PackingItem]) -> Vec<Vec<Placement>> {
+    let mut items: Vec<&PackingItem> = items
+        .iter()
+        .filter(|item| bed.holds(&item.footprint))
+        .collect();
+    items.sort_by(|a, b| {
+        b.footprint
+            .depth
+            .total_cmp(&a.footprint.depth)
+            .then(b.footprint.width.total_cmp(&a.footprint.width))
+    });
+
+    let mut plates: Vec<ShelfPlate> = vec![];
+    for item in items {
+        // Copies of an item are placed in bulk. Once a plate has no room for the footprint,
+        // later copies cannot fit it either, so every plate is visited once per item.
+        let mut remaining = item.copies;
+        let mut index = 0;
+        while remaining > 0 {
+            if index == plates.len() {
+                plates.push(ShelfPlate::default());
+            }
+            remaining -= plates[index].place(item, remaining, bed, spacing);
+            index += 1;
+        }
+    }
+    plates.into_iter().map(|plate| plate.placements).collect()
+}
+
+#[derive(Debug, Default)]
+struct ShelfPlate {
+    shelves: Vec<Shelf>,
+    placements: Vec<Placement>,
+}
+
+impl ShelfPlate {
+    /// Places up to `copies` copies of the item on shelves with room for them, then on new
+    /// shelves behind the others. Returns the number of copies placed.
+    fn place(&mut self, item: &PackingItem, copies: u32, bed: Bed, spacing: Spacing) -> u32 {
+        let footprint = item.footprint;
+        let gap = spacing.value();
+        let mut placed = 0;
+        let mut index = 0;
+        while placed < copies {
+            if index == self.shelves.len() {
+                let y = self
+                    .shelves
+                    .last()
+                    .map(|shelf| shelf.y + shelf.depth + gap)
+                    .unwrap_or_default();
+                if y + footprint.depth > bed.depth {
+                    break;
+                }
+                self.shelves.push(Shelf {
+                    y,
+                    depth: footprint.depth,
+                    used: 0.0,
+                });
+            }
+            let shelf = &mut self.shelves[index];
+            index += 1;
+            if shelf.depth < footprint.depth {
+                continue;
+            }
+            let room = fitting(bed.width - shelf.used, footprint.width, spacing);
+            for _ in 0..room.min(copies - placed) {
+                self.placements.push(Placement {
+                    part: item.part,
+                    x: shelf.used,
+                    y: shelf.y,
+                    footprint,
+                });
+                shelf.used += footprint.width + gap;
+                placed += 1;
+            }
+        }
+        placed
+    }
+}
+
+/// Row of copies. `used` includes spacing after the last copy.
+#[derive(Debug)]
+struct Shelf {
+    y: f64,
+    depth: f64,
+    used: f64,
+}
+
+#[cfg(test)]
+mod tests {
+
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn footprint(width: f64, depth: f64) -> Footprint {
+        Footprint {
+            width,
+            depth,
+            rotated: false,
+        }
+    }
+
+    #[test]
+    fn parse_bed() {
+        let bed = Bed::try_from("250x210".to_string()).unwrap();
+
+        assert_eq!((bed.width(), bed.depth()), (250.0, 210.0));
+        assert_eq!(
+            Bed::try_from("250".to_string()).unwrap_err().code(),
+            "packing.bed.invalid-format"
+        );
+    }
+
+    #[test]
+    fn turn_footprint_to_fit() {
+        let bed = Bed::parse(250.0, 210.0).unwrap();
+
+        let turned = bed.orient(footprint(100.0, 240.0)).unwrap();
+
+        assert_eq!((turned.width, turned.depth), (240.0, 100.0));
+        assert!(turned.rotated);
+        assert_eq!(bed.orient(footprint(220.0, 220.0)), None);
+    }
+
+    #[test]
+    fn count_copies_on_bed() {
+        let bed = Bed::parse(250.0, 210.0).unwrap();
+
+        let grid = bed
+            .grid(footprint(40.0, 60.0), Spacing::parse(5.0).unwrap())
+            .unwrap();
+
+        // 5 columns of 40 mm with 4 gaps take 220 mm, 3 rows of 60 mm take 190 mm.
+        assert_eq!(grid.copies(), 15);
+        assert!(!grid.rotated);
+    }
+
+    #[test]
+    fn count_copies_of_tiny_footprints() {
+        let bed = Bed::parse(10_000.0, 10_000.0).unwrap();
+
+        let grid = bed
+            .grid(footprint(0.01, 0.01), Spacing::parse(0.0).unwrap())
+            .unwrap();
+
+        assert_eq!(grid.copies(), 1_000_000_000_000);
+    }
+
+    #[test]
+    fn pack_many_copies_in_bulk() {
+        let bed = Bed::parse(100.0, 100.0).unwrap();
+        let items = [PackingItem {
+            part: Uuid::now_v7(),
+            footprint: footprint(10.0, 10.0),
+            copies: 100_000,
+        }];
+
+        let plates = pack_plates(bed, Spacing::parse(0.0).unwrap(), &items);
+
+        assert_eq!(plates.len(), 1000);
+        assert!(plates.iter().all(|plate| plate.len() == 100));
+    }
+
+    #[test]
+    fn pack_mixed_plates() {
+        let bed = Bed::parse(100.0, 100.0).unwrap();
+        let (large, small) = (Uuid::now_v7(), Uuid::now_v7());
+        let items = [
+            PackingItem {
+                part: small,
+                footprint: footprint(20.0, 20.0),
+                copies: 6,
+            },
+            PackingItem {
+                part: large,
+                footprint: footprint(60.0, 60.0),
+                copies: 2,
+            },
+        ];
+
+        let plates = pack_plates(bed, Spacing::parse(0.0).unwrap(), &items);
+
+        assert_eq!(plates.len(), 2);
+        // Small copies fill the space next to and behind the large part of the first plate.
+        assert_eq!(plates[0].len(), 7);
+        assert_eq!(plates[1].len(), 1);
+        assert!(plates
+            .iter()
+            .flatten()
+            .all(|p| p.x + p.footprint.width <= 100.0 && p.y + p.footprint.depth <= 100.0));
+    }
+}
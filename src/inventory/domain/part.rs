@@ -1,14 +1,33 @@
 //! Part domain entity.
 //! Represents a single printable part that can be added to a project and placed on a table.
 
+use chrono::Utc;
 use uuid::Uuid;
 
+use super::classification::Classification;
+use super::event::PartEvent;
+use super::gcode::SlicerEstimates;
+use super::geometry::Geometry;
+use super::metadata::PartMetadata;
 use super::name::Name;
+use super::revision::{PartRevision, PartRevisionNumber, FIRST_REVISION};
+use super::source::PartSource;
+use super::variant::Variant;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Part {
     id: PartId,
     name: Name,
+    /// The latest revision. Metadata and geometry belong to it.
+    revision: PartRevisionNumber,
+    metadata: PartMetadata,
+    classification: Classification,
+    source: PartSource,
+    /// Measured from the last attached mesh.
+    geometry: Option<Geometry>,
+    /// Set when the part is a variant of another part.
+    variant: Option<Variant>,
+    events: Vec<PartEvent>,
 }
 
 pub type PartId = Uuid;
@@ -16,13 +35,173 @@ pub type PartId = Uuid;
 impl Part {
     /// To use only with the database deserializers.
     pub fn hydrate(id: PartId, name: Name) -> Self {
-        Self { id, name }
+        Self {
+            id,
+            name,
+            revision: FIRST_REVISION,
+            metadata: PartMetadata::default(),
+            classification: Classification::default(),
+            source: PartSource::default(),
+            geometry: None,
+            variant: None,
+            events: vec![],
+        }
+    }
+
+    /// Restores the latest revision number of a stored part.
+    pub fn with_revision(self, revision: PartRevisionNumber) -> Self {
+        Self { revision, ..self }
+    }
+
+    /// Restores printing properties of a stored part.
+    pub fn with_metadata(self, metadata: PartMetadata) -> Self {
+        Self { metadata, ..self }
+    }
+
+    /// Restores tags and the category of a stored part.
+    pub fn with_classification(self, classification: Classification) -> Self {
+        Self {
+            classification,
+            ..self
+        }
+    }
+
+    /// Restores the origin and the license of a stored part.
+    pub fn with_source(self, source: PartSource) -> Self {
+        Self { source, ..self }
+    }
+
+    /// Restores measured geometry of a stored part.
+    pub fn with_geometry(self, geometry: Option<Geometry>) -> Self {
+        Self { geometry, ..self }
+    }
+
+    /// Restores the parent part and attributes of a stored variant.
+    pub fn with_variant(self, variant: Option<Variant>) -> Self {
+        Self { variant, ..self }
     }
 
     /// Create a new named part.
     pub fn new(name: Name) -> Self {
         let id = Uuid::now_v7();
-        Part::hydrate(id, name)
+        let mut part = Part::hydrate(id, name);
+        part.events.push(PartEvent::PartRegistered {
+            part: id,
+            name: part.name.to_string(),
+        });
+        part
+    }
+
+    /// Changes the part name. Renaming to the same name is not recorded as a change.
+    pub fn rename(&mut self, name: Name) {
+        if name.as_ref() == self.name.as_ref() {
+            return;
+        }
+        let from = std::mem::replace(&mut self.name, name);
+        self.events.push(PartEvent::PartRenamed {
+            part: self.id,
+            from: from.to_string(),
+            to: self.name.to_string(),
+        });
+    }
+
+    /// Replaces printing properties of the part. Unchanged properties are not recorded as a change.
+    pub fn describe(&mut self, metadata: PartMetadata) {
+        if metadata == self.metadata {
+            return;
+        }
+        let previous = std::mem::replace(&mut self.metadata, metadata);
+        self.events.push(PartEvent::PartDescribed {
+            part: self.id,
+            previous,
+            metadata: self.metadata.clone(),
+        });
+    }
+
+    /// Replaces tags and the category of the part. Unchanged values are not recorded as a change.
+    pub fn classify(&mut self, classification: Classification) {
+        if classification == self.classification {
+            return;
+        }
+        let previous = std::mem::replace(&mut self.classification, classification);
+        self.events.push(PartEvent::PartClassified {
+            part: self.id,
+            previous,
+            classification: self.classification.clone(),
+        });
+    }
+
+    /// Replaces the origin and the license of the part. Unchanged values are not recorded as
+    /// a change.
+    pub fn attribute(&mut self, source: PartSource) {
+        if source == self.source {
+            return;
+        }
+        let previous = std::mem::replace(&mut self.source, source);
+        self.events.push(PartEvent::PartAttributed {
+            part: self.id,
+            previous,
+            source: self.source.clone(),
+        });
+    }
+
+    /// Makes the part a variant of another part, or a standalone part again without a variant.
+    /// Rules of variants are checked with [`super::variant::check_parent`] before.
+    /// An unchanged variant is not recorded as a change.
+    pub fn define_variant(&mut self, variant: Option<Variant>) {
+        if variant == self.variant {
+            return;
+        }
+        let previous = std::mem::replace(&mut self.variant, variant);
+        self.events.push(PartEvent::VariantDefined {
+            part: self.id,
+            previous,
+            variant: self.variant.clone(),
+        });
+    }
+
+    /// Keeps geometry measured from a mesh of the part.
+    /// Missing dimensions are taken from the bounding box and missing weight is estimated from
+    /// the volume, so slicer estimates and values set by hand are never replaced.
+    pub fn measure(&mut self, geometry: Geometry) {
+        self.geometry = Some(geometry);
+        let mut metadata = self.metadata.clone();
+        metadata.dimensions = metadata.dimensions.or(geometry.dimensions());
+        metadata.grams = metadata
+            .grams
+            .or(geometry.estimated_grams(metadata.material));
+        self.describe(metadata);
+    }
+
+    /// Takes print time and filament weight estimated by a slicer.
+    /// Slicer estimates replace values set by hand or estimated from a mesh.
+    pub fn estimate(&mut self, estimates: SlicerEstimates) {
+        let mut metadata = self.metadata.clone();
+        metadata.minutes = estimates.minutes().or(metadata.minutes);
+        metadata.grams = estimates.grams(metadata.material).or(metadata.grams);
+        self.describe(metadata);
+    }
+
+    /// Starts a new revision of the part and returns the replaced one to keep in history.
+    /// The new revision starts with printing properties of the replaced one.
+    pub fn revise(&mut self) -> PartRevision {
+        let replaced = PartRevision {
+            part: self.id,
+            revision: self.revision,
+            metadata: self.metadata.clone(),
+            replaced_at: Some(Utc::now()),
+        };
+        self.revision += 1;
+        self.events.push(PartEvent::PartRevised {
+            part: self.id,
+            revision: self.revision,
+        });
+        replaced
+    }
+
+    /// Removes events recorded since the part was loaded or created.
+    pub fn take_events(&mut self) -> Vec<PartEvent> {
+        std::mem::take(&mut self.events)
     }
 
     pub fn id(&self) -> PartId {
@@ -32,4 +211,147 @@ impl Part {
     pub fn name(&self) -> &Name {
         &self.name
     }
+
+    pub fn revision(&self) -> PartRevisionNumber {
+        self.revision
+    }
+
+    /// The latest revision with current printing properties.
+    pub fn latest_revision(&self) -> PartRevision {
+        PartRevision {
+            part: self.id,
+            revision: self.revision,
+            metadata: self.metadata.clone(),
+            replaced_at: None,
+        }
+    }
+
+    pub fn metadata(&self) -> &PartMetadata {
+        &self.metadata
+    }
+
+    pub fn classification(&self) -> &Classification {
+        &self.classification
+    }
+
+    pub fn source(&self) -> &PartSource {
+        &self.source
+    }
+
+    pub fn geometry(&self) -> Option<&Geometry> {
+        self.geometry.as_ref()
+    }
+
+    pub fn variant(&self) -> Option<&Variant> {
+        self.variant.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::inventory::domain::geometry::tests::cuboid;
+    use crate::inventory::domain::metadata::{Grams, Material, Minutes};
+
+    #[test]
+    fn record_rename() {
+        let mut part = Part::hydrate(Uuid::now_v7(), Name::parse("Drawer").unwrap());
+
+        part.rename(Name::parse("Drawer").unwrap());
+        part.rename(Name::parse("Drawer, small").unwrap());
+
+        assert_eq!(part.name().as_ref(), "Drawer, small");
+        assert_eq!(
+            part.take_events(),
+            vec![PartEvent::PartRenamed {
+                part: part.id(),
+                from: "Drawer".to_string(),
+                to: "Drawer, small".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn record_metadata_changes() {
+        let mut part = Part::hydrate(Uuid::now_v7(), Name::parse("Drawer").unwrap());
+        let metadata = PartMetadata {
+            material: Some(Material::Petg),
+            ..PartMetadata::default()
+        };
+
+        part.describe(metadata.clone());
+        part.describe(metadata.clone());
+
+        assert_eq!(part.metadata(), &metadata);
+        assert_eq!(
+            part.take_events(),
+            vec![PartEvent::PartDescribed {
+                part: part.id(),
+                previous: PartMetadata::default(),
+                metadata,
+            }]
+        );
+    }
+
+    #[test]
+    fn fill_missing_metadata_from_geometry() {
+        let mut part = Part::hydrate(Uuid::now_v7(), Name::parse("Drawer").unwrap());
+        part.describe(PartMetadata {
+            grams: Some(Grams::parse(42.0).unwrap()),
+            ..PartMetadata::default()
+        });
+        let geometry = Geometry::measure(cuboid([0.0; 3], [40.0, 20.0, 10.0])).unwrap();
+
+        part.measure(geometry);
+
+        assert_eq!(part.geometry(), Some(&geometry));
+        assert_eq!(part.metadata().dimensions, geometry.dimensions());
+        assert_eq!(part.metadata().grams.unwrap().value(), 42.0);
+    }
+
+    #[test]
+    fn replace_estimates_with_slicer_values() {
+        let mut part = Part::hydrate(Uuid::now_v7(), Name::parse("Drawer").unwrap());
+        part.describe(PartMetadata {
+            grams: Some(Grams::parse(42.0).unwrap()),
+            minutes: Some(Minutes::parse(90).unwrap()),
+            ..PartMetadata::default()
+        });
+
+        part.estimate(SlicerEstimates {
+            seconds: Some(3723),
+            grams: None,
+            filament_length: None,
+        });
+
+        assert_eq!(part.metadata().minutes.unwrap().value(), 63);
+        assert_eq!(part.metadata().grams.unwrap().value(), 42.0);
+    }
+
+    #[test]
+    fn keep_replaced_revision() {
+        let mut part = Part::hydrate(Uuid::now_v7(), Name::parse("Hinge").unwrap());
+        let metadata = PartMetadata {
+            material: Some(Material::Petg),
+            ..PartMetadata::default()
+        };
+        part.describe(metadata.clone());
+        part.take_events();
+
+        let replaced = part.revise();
+
+        assert_eq!(replaced.revision, FIRST_REVISION);
+        assert_eq!(replaced.metadata, metadata);
+        assert!(replaced.replaced_at.is_some());
+        assert_eq!(part.revision(), 2);
+        assert_eq!(part.metadata(), &metadata);
+        assert_eq!(
+            part.take_events(),
+            vec![PartEvent::PartRevised {
+                part: part.id(),
+                revision: 2,
+            }]
+        );
+    }
 }
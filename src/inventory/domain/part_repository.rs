@@ -3,12 +3,35 @@ use std::fmt::Debug;
 
 use async_trait::async_trait;
 
+use super::classification::PartFilter;
+use super::name::Name;
 use super::part::{Part, PartId};
+use super::revision::PartRevision;
 
 #[async_trait]
 pub trait PartRepository: Debug + Send + Sync {
     /// Inserts a new part into the storage.
     async fn insert(&self, part: Part) -> anyhow::Result<()>;
 
+    /// Saves changes of an existing part.
+    async fn update(&self, part: Part) -> anyhow::Result<()>;
+
     async fn find_by_id(&self, id: PartId) -> anyhow::Result<Option<Part>>;
+
+    /// Finds a part by its name. Names are compared case-insensitively.
+    /// The implementation must return Ok(None) if there is no part with this name.
+    async fn find_by_name(&self, name: &Name) -> anyhow::Result<Option<Part>>;
+
+    /// Lists parts matching the filter ordered by name.
+    async fn list(&self, filter: &PartFilter) -> anyhow::Result<Vec<Part>>;
+}
+
+/// Repository of replaced part revisions. Revisions are never changed once stored.
+/// The latest revision of every part lives with the part itself.
+#[async_trait]
+pub trait PartRevisionRepository: Send + Sync {
+    async fn append(&self, revision: PartRevision) -> anyhow::Result<()>;
+
+    /// Lists replaced revisions of the part, the most recent first.
+    async fn list(&self, part: PartId) -> anyhow::Result<Vec<PartRevision>>;
 }
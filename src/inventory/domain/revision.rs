@@ -0,0 +1,28 @@
+//! Revisions of parts.
+//!
+//! Designers revise models, e.g., a hinge gets thicker walls in its second revision. Every
+//! revision has its own files and printing properties. Project BOMs pin revisions of their
+//! parts, so projects printed with an older revision keep it until they are upgraded.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::metadata::PartMetadata;
+use super::part::PartId;
+
+/// Consecutive number of the part revision, starting with [`FIRST_REVISION`].
+pub type PartRevisionNumber = u32;
+
+/// Revision of newly registered parts.
+pub const FIRST_REVISION: PartRevisionNumber = 1;
+
+/// Printing properties of the part as they were in the revision.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PartRevision {
+    pub part: PartId,
+    pub revision: PartRevisionNumber,
+    #[serde(flatten)]
+    pub metadata: PartMetadata,
+    /// When the next revision replaced this one. Missing for the latest revision.
+    pub replaced_at: Option<DateTime<Utc>>,
+}
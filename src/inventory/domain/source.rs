@@ -0,0 +1,414 @@
+//! Where parts come from and how they may be used.
+//!
+//! Models downloaded from Printables, Thingiverse or MakerWorld come with a designer and a
+//! license. Licenses are written as SPDX identifiers, e.g., `CC-BY-NC-SA-4.0`, and tell whether
+//! prints may be sold. Designers sometimes allow selling prints beyond their license, so the
+//! commercial use flag set by hand wins over the license.
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::shared::validation::error::ValidationError;
+use crate::shared::validation::validator::CollectingValidator;
+
+/// Licenses besides the Creative Commons ones with a flag telling whether they allow commercial
+/// use. Deprecated SPDX identifiers, e.g., `GPL-3.0`, are still used on model sites.
+const LICENSES: &[(&str, bool)] = &[
+    ("CC0-1.0", true),
+    ("CC-PDDC", true),
+    ("Unlicense", true),
+    ("WTFPL", true),
+    ("0BSD", true),
+    ("MIT", true),
+    ("ISC", true),
+    ("Zlib", true),
+    ("BSL-1.0", true),
+    ("BSD-2-Clause", true),
+    ("BSD-3-Clause", true),
+    ("Apache-2.0", true),
+    ("Artistic-2.0", true),
+    ("MPL-1.1", true),
+    ("MPL-2.0", true),
+    ("EUPL-1.2", true),
+    ("LGPL-2.1", true),
+    ("LGPL-2.1-only", true),
+    ("LGPL-2.1-or-later", true),
+    ("LGPL-3.0", true),
+    ("LGPL-3.0-only", true),
+    ("LGPL-3.0-or-later", true),
+    ("GPL-2.0", true),
+    ("GPL-2.0-only", true),
+    ("GPL-2.0-or-later", true),
+    ("GPL-3.0", true),
+    ("GPL-3.0-only", true),
+    ("GPL-3.0-or-later", true),
+    ("AGPL-3.0", true),
+    ("AGPL-3.0-only", true),
+    ("AGPL-3.0-or-later", true),
+    ("CERN-OHL-1.1", true),
+    ("CERN-OHL-1.2", true),
+    ("CERN-OHL-P-2.0", true),
+    ("CERN-OHL-W-2.0", true),
+    ("CERN-OHL-S-2.0", true),
+    ("TAPR-OHL-1.0", true),
+    ("SHL-0.51", true),
+];
+
+/// License elements and versions of Creative Commons licenses, e.g., `CC-BY-NC-SA-4.0`.
+const CC_ELEMENTS: &[&str] = &["BY", "BY-SA", "BY-ND", "BY-NC", "BY-NC-SA", "BY-NC-ND"];
+const CC_VERSIONS: &[&str] = &["1.0", "2.0", "2.5", "3.0", "4.0"];
+
+/// Prefix of SPDX references to licenses outside the SPDX list, e.g., `LicenseRef-Printables`.
+const LICENSE_REF: &str = "LicenseRef-";
+
+/// Origin and license of a part. Every property is optional.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PartSource {
+    pub source_url: Option<SourceUrl>,
+    pub designer: Option<Designer>,
+    pub license: Option<License>,
+    /// Set by hand when the designer allows or forbids selling prints regardless of the license.
+    pub commercial_use: Option<bool>,
+}
+
+/// Unvalidated source properties, e.g., from request payloads or storage.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RawPartSource {
+    pub source_url: Option<String>,
+    pub designer: Option<String>,
+    pub license: Option<String>,
+    pub commercial_use: Option<bool>,
+}
+
+impl PartSource {
+    /// Validates all properties, reporting every invalid one.
+    /// Blank properties are treated as missing.
+    pub fn parse(raw: RawPartSource) -> Result<Self, Vec<ValidationError>> {
+        let mut validator = CollectingValidator::default();
+        let source_url = raw
+            .source_url
+            .filter(|u| !u.trim().is_empty())
+            .map(|u| validator.parse_string::<SourceUrl>(u));
+        let designer = raw
+            .designer
+            .filter(|d| !d.trim().is_empty())
+            .map(|d| validator.parse_string::<Designer>(d));
+        let license = raw
+            .license
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| validator.parse_string::<License>(l));
+        if validator.has_errors() {
+            return Err(validator.into_errors());
+        }
+        Ok(Self {
+            source_url: source_url.transpose().unwrap(),
+            designer: designer.transpose().unwrap(),
+            license: license.transpose().unwrap(),
+            commercial_use: raw.commercial_use,
+        })
+    }
+
+    /// Tells whether prints of the part may be sold. The flag set by hand wins over the license.
+    /// Returns None when neither the flag nor the license is known.
+    pub fn allows_commercial_use(&self) -> Option<bool> {
+        self.commercial_use.or(self
+            .license
+            .as_ref()
+            .and_then(|l| l.allows_commercial_use()))
+    }
+}
+
+impl From<&PartSource> for RawPartSource {
+    fn from(value: &PartSource) -> Self {
+        Self {
+            source_url: value.source_url.as_ref().map(|u| u.to_string()),
+            designer: value.designer.as_ref().map(|d| d.to_string()),
+            license: value.license.as_ref().map(|l| l.to_string()),
+            commercial_use: value.commercial_use,
+        }
+    }
+}
+
+/// Web page of the model, e.g., `https://www.printables.com/model/1234`.
+/// Only `http` and `https` links up to 2000 bytes long are accepted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct SourceUrl(String);
+
+impl SourceUrl {
+    pub fn parse(value: impl Into<String>) -> Result<Self, ValidationError> {
+        let value = value.into();
+        let url = value.trim();
+        let attr = "source_url";
+        if url.len() > 2000 {
+            return Err(ValidationError::new(
+                attr,
+                "part.source-url.too-long",
+                "source URL is too long",
+            ));
+        }
+        let host = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+            .map(|rest| rest.split(['/', '?', '#']).next().unwrap_or_default());
+        if host.is_none_or(str::is_empty) || url.chars().any(char::is_whitespace) {
+            return Err(ValidationError::new(
+                attr,
+                "part.source-url.invalid",
+                "source URL must be an http or https link",
+            ));
+        }
+        Ok(Self(url.to_string()))
+    }
+}
+
+impl TryFrom<String> for SourceUrl {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl Display for SourceUrl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Name or user name of the designer of the model.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct Designer(String);
+
+impl Designer {
+    pub fn parse(value: impl Into<String>) -> Result<Self, ValidationError> {
+        let value = value.into();
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            return Err(ValidationError::new(
+                "designer",
+                "part.designer.too-short",
+                "designer is too short",
+            ));
+        }
+        if trimmed.len() > 100 {
+            return Err(ValidationError::new(
+                "designer",
+                "part.designer.too-long",
+                "designer is too long",
+            ));
+        }
+        Ok(Self(trimmed.to_string()))
+    }
+}
+
+impl TryFrom<String> for Designer {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl Display for Designer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// SPDX identifier of a Creative Commons or open source license, e.g., `CC-BY-SA-4.0`.
+/// Identifiers are matched ignoring case, and spaces may replace dashes, so `cc by-nc 4.0`
+/// is `CC-BY-NC-4.0`. Creative Commons licenses of every version are accepted, including ports
+/// to jurisdictions, e.g., `CC-BY-SA-3.0-DE`. Custom licenses are referenced as `LicenseRef-`
+/// identifiers, and whether they allow commercial use is unknown.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(into = "String")]
+pub struct License {
+    id: String,
+    commercial_use: Option<bool>,
+}
+
+impl License {
+    pub fn parse(value: impl Into<String>) -> Result<Self, ValidationError> {
+        let value = value.into();
+        let normalized = value.split_whitespace().collect::<Vec<_>>().join("-");
+        Self::listed(&normalized)
+            .or_else(|| Self::creative_commons(&normalized))
+            .or_else(|| Self::reference(&normalized))
+            .ok_or_else(|| {
+                ValidationError::new(
+                    "license",
+                    "part.license.unknown",
+                    "license must be an SPDX identifier or a LicenseRef- of a custom license",
+                )
+            })
+    }
+
+    fn listed(id: &str) -> Option<Self> {
+        LICENSES
+            .iter()
+            .find(|(listed, _)| listed.eq_ignore_ascii_case(id))
+            .map(|(listed, commercial_use)| Self {
+                id: listed.to_string(),
+                commercial_use: Some(*commercial_use),
+            })
+    }
+
+    /// Reads `CC-ELEMENTS-VERSION` identifiers with an optional jurisdiction, e.g., `-DE`.
+    fn creative_commons(id: &str) -> Option<Self> {
+        let id = id.to_ascii_uppercase();
+        let parts: Vec<&str> = id.split('-').collect();
+        let version = parts.iter().position(|p| CC_VERSIONS.contains(p))?;
+        let elements = parts.get(1..version)?.join("-");
+        let jurisdiction = &parts[version + 1..];
+        let ported = match jurisdiction {
+            [] => true,
+            [code] => {
+                (2..=3).contains(&code.len()) && code.chars().all(|c| c.is_ascii_alphabetic())
+            }
+            _ => false,
+        };
+        (parts[0] == "CC" && CC_ELEMENTS.contains(&elements.as_str()) && ported).then(|| Self {
+            commercial_use: Some(!elements.contains("NC")),
+            id,
+        })
+    }
+
+    fn reference(id: &str) -> Option<Self> {
+        let name = id
+            .get(..LICENSE_REF.len())
+            .filter(|prefix| prefix.eq_ignore_ascii_case(LICENSE_REF))
+            .map(|_| &id[LICENSE_REF.len()..])?;
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-');
+        valid.then(|| Self {
+            id: format!("{}{}", LICENSE_REF, name),
+            commercial_use: None,
+        })
+    }
+
+    /// Creative Commons licenses with the NonCommercial clause forbid selling prints.
+    /// Returns None for custom licenses.
+    pub fn allows_commercial_use(&self) -> Option<bool> {
+        self.commercial_use
+    }
+}
+
+impl TryFrom<String> for License {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl From<License> for String {
+    fn from(value: License) -> Self {
+        value.to_string()
+    }
+}
+
+impl AsRef<str> for License {
+    fn as_ref(&self) -> &str {
+        &self.id
+    }
+}
+
+impl Display for License {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn normalize_license_identifiers() {
+        let license = License::parse(" cc by-nc-sa 4.0 ").unwrap();
+
+        assert_eq!(license.to_string(), "CC-BY-NC-SA-4.0");
+        assert_eq!(license.allows_commercial_use(), Some(false));
+        assert_eq!(
+            License::parse("mit").unwrap().allows_commercial_use(),
+            Some(true)
+        );
+        assert_eq!(
+            License::parse("Standard Digital File License")
+                .unwrap_err()
+                .code(),
+            "part.license.unknown"
+        );
+    }
+
+    #[test]
+    fn accept_creative_commons_family_and_custom_licenses() {
+        let commercial_use = |id: &str| License::parse(id).unwrap().allows_commercial_use();
+
+        assert_eq!(commercial_use("CC-BY-2.0"), Some(true));
+        assert_eq!(commercial_use("CC-BY-SA-2.0"), Some(true));
+        assert_eq!(commercial_use("cc-by-nc-2.5"), Some(false));
+        assert_eq!(commercial_use("CC-BY-NC-ND-1.0"), Some(false));
+        assert_eq!(commercial_use("Unlicense"), Some(true));
+        assert_eq!(commercial_use("LicenseRef-Printables-SDFL"), None);
+        assert_eq!(
+            License::parse("cc by-sa 3.0 de").unwrap().to_string(),
+            "CC-BY-SA-3.0-DE"
+        );
+        for id in [
+            "CC-BY-NC-5.0",
+            "CC-NC-4.0",
+            "CC-BY-4.0-GERMANY",
+            "LicenseRef-",
+        ] {
+            assert!(License::parse(id).is_err(), "{} must be rejected", id);
+        }
+    }
+
+    #[test]
+    fn prefer_commercial_flag_over_license() {
+        let source = |license: Option<&str>, commercial_use| {
+            PartSource::parse(RawPartSource {
+                license: license.map(str::to_string),
+                commercial_use,
+                ..RawPartSource::default()
+            })
+            .unwrap()
+        };
+
+        assert_eq!(
+            source(Some("CC-BY-NC-4.0"), None).allows_commercial_use(),
+            Some(false)
+        );
+        assert_eq!(
+            source(Some("CC-BY-NC-4.0"), Some(true)).allows_commercial_use(),
+            Some(true)
+        );
+        assert_eq!(source(None, None).allows_commercial_use(), None);
+    }
+
+    #[test]
+    fn report_every_invalid_value() {
+        let errors = PartSource::parse(RawPartSource {
+            source_url: Some("printables.com/model/1234".to_string()),
+            designer: Some(" ".to_string()),
+            license: Some("CC-BY-NC".to_string()),
+            commercial_use: None,
+        })
+        .unwrap_err();
+
+        let codes: Vec<&str> = errors.iter().map(|e| e.code()).collect();
+        assert_eq!(
+            codes,
+            vec!["part.source-url.invalid", "part.license.unknown"]
+        );
+        assert!(SourceUrl::parse("https://www.printables.com/model/1234").is_ok());
+        assert!(SourceUrl::parse("https:///model").is_err());
+    }
+}
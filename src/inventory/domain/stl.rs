@@ -0,0 +1,251 @@
+//! Reads triangle meshes from STL files.
+//!
+//! Binary STL has an 80 byte header, the number of triangles and 50 bytes per triangle.
+//! ASCII STL starts with `solid` and lists `vertex x y z` lines, three per facet.
+//! Normals are ignored, they are recalculated from vertices when needed.
+
+use std::io::{BufRead, Read};
+
+use crate::shared::validation::error::ValidationError;
+
+use super::geometry::{Geometry, Vertex};
+
+const HEADER_LEN: usize = 80;
+const TRIANGLE_LEN: usize = 50;
+
+/// Triangles read one at a time, so meshes are never held in memory whole.
+type TriangleReader<'a> = Box<dyn Iterator<Item = Result<[Vertex; 3], ValidationError>> + 'a>;
+
+/// Measures the mesh of an STL file of the given size, detecting binary and ASCII encoding.
+/// Triangles are measured as they are read.
+pub fn analyze(content: impl BufRead, size: u64) -> Result<Geometry, ValidationError> {
+    let mut error = None;
+    let triangles = read_triangles(content, size)?.map_while(|triangle| match triangle {
+        Ok(triangle) => Some(triangle),
+        Err(e) => {
+            error = Some(e);
+            None
+        }
+    });
+    let geometry = Geometry::measure(triangles);
+    match error {
+        Some(e) => Err(e),
+        None => geometry,
+    }
+}
+
+/// Reads triangles of an STL file, detecting binary and ASCII encoding.
+pub fn triangles(content: &[u8]) -> Result<Vec<[Vertex; 3]>, ValidationError> {
+    read_triangles(content, content.len() as u64)?.collect()
+}
+
+/// Binary files are recognized by the size matching their triangle count.
+/// ASCII files starting with `solid` hardly ever match by accident.
+fn read_triangles<'a>(
+    mut content: impl BufRead + 'a,
+    size: u64,
+) -> Result<TriangleReader<'a>, ValidationError> {
+    let mut header = [0_u8; HEADER_LEN + 4];
+    let read = read_up_to(&mut content, &mut header)?;
+    if read == header.len() {
+        let count = u32::from_le_bytes(header[HEADER_LEN..].try_into().unwrap());
+        let body = size.checked_sub(header.len() as u64);
+        if body == Some(u64::from(count) * TRIANGLE_LEN as u64) {
+            return Ok(Box::new(
+                (0..count).map(move |_| binary_triangle(&mut content)),
+            ));
+        }
+    }
+    if header[..read].trim_ascii_start().starts_with(b"solid") {
+        let content = std::io::Cursor::new(header[..read].to_vec()).chain(content);
+        return Ok(Box::new(AsciiTriangles {
+            lines: content.lines(),
+        }));
+    }
+    Err(invalid_stl("file is neither binary nor ASCII STL"))
+}
+
+/// Fills the buffer unless the content ends first. Returns the number of bytes read.
+fn read_up_to(content: &mut impl Read, buffer: &mut [u8]) -> Result<usize, ValidationError> {
+    let mut read = 0;
+    while read < buffer.len() {
+        match content.read(&mut buffer[read..]) {
+            Ok(0) => break,
+            Ok(n) => read += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(_) => return Err(invalid_stl("file cannot be read")),
+        }
+    }
+    Ok(read)
+}
+
+fn binary_triangle(content: &mut impl Read) -> Result<[Vertex; 3], ValidationError> {
+    let mut triangle = [0_u8; TRIANGLE_LEN];
+    content
+        .read_exact(&mut triangle)
+        .map_err(|_| invalid_stl("file ends before its last triangle"))?;
+    // Skips the normal, then reads three vertices of three little-endian f32 each.
+    let value = |index: usize| {
+        let offset = 12 + index * 4;
+        f64::from(f32::from_le_bytes(
+            triangle[offset..offset + 4].try_into().unwrap(),
+        ))
+    };
+    let vertices = [0, 1, 2].map(|vertex| [0, 1, 2].map(|axis| value(vertex * 3 + axis)));
+    if !vertices.iter().flatten().all(|c| c.is_finite()) {
+        return Err(invalid_coordinates());
+    }
+    Ok(vertices)
+}
+
+/// Collects `vertex` lines of ASCII STL into triangles.
+struct AsciiTriangles<R> {
+    lines: std::io::Lines<R>,
+}
+
+impl<R: BufRead> Iterator for AsciiTriangles<R> {
+    type Item = Result<[Vertex; 3], ValidationError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut vertices = Vec::with_capacity(3);
+        while vertices.len() < 3 {
+            let line = match self.lines.next() {
+                Some(Ok(line)) => line,
+                Some(Err(_)) => return Some(Err(invalid_stl("file is not text"))),
+                None if vertices.is_empty() => return None,
+                None => return Some(Err(invalid_stl("facets must have three vertices"))),
+            };
+            match ascii_vertex(&line) {
+                Ok(Some(vertex)) => vertices.push(vertex),
+                Ok(None) => continue,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        Some(Ok([vertices[0], vertices[1], vertices[2]]))
+    }
+}
+
+/// Reads coordinates of a `vertex` line. Other lines have no vertex.
+fn ascii_vertex(line: &str) -> Result<Option<Vertex>, ValidationError> {
+    let mut tokens = line.split_whitespace();
+    if tokens.next() != Some("vertex") {
+        return Ok(None);
+    }
+    let coordinates: Vec<f64> = tokens
+        .map(|t| t.parse::<f64>())
+        .collect::<Result<_, _>>()
+        .map_err(|_| invalid_coordinates())?;
+    match coordinates[..] {
+        [x, y, z] if x.is_finite() && y.is_finite() && z.is_finite() => Ok(Some([x, y, z])),
+        [_, _, _] => Err(invalid_coordinates()),
+        _ => Err(invalid_stl("vertex must have three coordinates")),
+    }
+}
+
+fn invalid_stl(message: &str) -> ValidationError {
+    ValidationError::new("file", "stl.content.invalid", message)
+}
+
+/// Coordinates must be finite numbers, NaN or infinity would spoil the geometry.
+fn invalid_coordinates() -> ValidationError {
+    invalid_stl("vertex has invalid coordinates")
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+
+    use super::*;
+    use crate::inventory::domain::geometry::tests::cuboid;
+
+    fn measure(content: &[u8]) -> Result<Geometry, ValidationError> {
+        analyze(content, content.len() as u64)
+    }
+
+    fn binary(triangles: &[[Vertex; 3]]) -> Vec<u8> {
+        let mut content = vec![b' '; HEADER_LEN];
+        content[..5].copy_from_slice(b"solid");
+        content.extend((triangles.len() as u32).to_le_bytes());
+        for triangle in triangles {
+            content.extend([0_u8; 12]);
+            for value in triangle.iter().flatten() {
+                content.extend((*value as f32).to_le_bytes());
+            }
+            content.extend([0_u8; 2]);
+        }
+        content
+    }
+
+    pub(crate) fn ascii(triangles: &[[Vertex; 3]]) -> String {
+        let mut content = String::from("solid cube\n");
+        for triangle in triangles {
+            content.push_str("  facet normal 0 0 0\n    outer loop\n");
+            for [x, y, z] in triangle {
+                content.push_str(&format!("      vertex {:e} {} {}\n", x, y, z));
+            }
+            content.push_str("    endloop\n  endfacet\n");
+        }
+        content.push_str("endsolid cube\n");
+        content
+    }
+
+    #[test]
+    fn analyze_binary_stl() {
+        let triangles = cuboid([0.0; 3], [40.0, 20.0, 10.0]);
+
+        let geometry = measure(&binary(&triangles)).unwrap();
+
+        assert_eq!(geometry.bounding_box, [40.0, 20.0, 10.0]);
+        assert_eq!(geometry.triangles, 12);
+        assert_eq!(geometry.volume, 8000.0);
+    }
+
+    #[test]
+    fn analyze_ascii_stl() {
+        let triangles = cuboid([0.0; 3], [40.0, 20.0, 10.0]);
+
+        let geometry = measure(ascii(&triangles).as_bytes()).unwrap();
+
+        assert_eq!(geometry.triangles, 12);
+        assert_eq!(geometry.area, 2800.0);
+    }
+
+    #[test]
+    fn reject_non_finite_binary_coordinates() {
+        let mut triangles = cuboid([0.0; 3], [40.0, 20.0, 10.0]);
+        triangles[3][1][2] = f64::NAN;
+        triangles[7][0][0] = f64::INFINITY;
+
+        let error = measure(&binary(&triangles)).unwrap_err();
+
+        assert_eq!(error.code(), "stl.content.invalid");
+        assert_eq!(error.message(), "vertex has invalid coordinates");
+    }
+
+    #[test]
+    fn reject_binary_stl_ending_early() {
+        let content = binary(&cuboid([0.0; 3], [40.0, 20.0, 10.0]));
+
+        let error = analyze(&content[..content.len() - 10], content.len() as u64).unwrap_err();
+
+        assert_eq!(error.message(), "file ends before its last triangle");
+    }
+
+    #[test]
+    fn reject_malformed_stl() {
+        let errors = [
+            measure(b"not a mesh").unwrap_err(),
+            measure(b"solid x\n vertex 1 2\n endsolid").unwrap_err(),
+            measure(b"solid empty\nendsolid empty\n").unwrap_err(),
+        ];
+
+        let codes: Vec<&str> = errors.iter().map(|e| e.code()).collect();
+        assert_eq!(
+            codes,
+            vec![
+                "stl.content.invalid",
+                "stl.content.invalid",
+                "mesh.triangles.missing"
+            ]
+        );
+    }
+}
@@ -0,0 +1,227 @@
+//! Reads objects of 3MF packages, e.g., models downloaded from Printables or MakerWorld.
+//!
+//! A 3MF file is a zip package. The root model, named in `_rels/.rels`, defines objects in
+//! `resources` and places them on plates with `build` items. Slicers keep the name of every
+//! object in the `name` attribute. Objects used only as components of other objects are not
+//! placed on plates and are not listed.
+
+use std::io::{Cursor, Read};
+
+use zip::ZipArchive;
+
+use crate::shared::validation::error::ValidationError;
+
+/// Root model used when the package does not name one.
+const DEFAULT_MODEL: &str = "3D/3dmodel.model";
+/// Relationship type of the root model.
+const MODEL_RELATIONSHIP: &str = "http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel";
+/// Largest root model read, as packages may compress well beyond their size.
+const MAX_MODEL_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Object placed on the build plates of a 3MF package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelObject {
+    /// Identifier of the object within the package.
+    pub id: String,
+    /// Name given to the object by the slicer. Empty when the object is not named.
+    pub name: String,
+    /// Number of copies placed on all plates.
+    pub count: u32,
+}
+
+/// Lists objects placed on the build plates, in the order they are defined.
+pub fn objects(content: &[u8]) -> Result<Vec<ModelObject>, ValidationError> {
+    let mut package = ZipArchive::new(Cursor::new(content)).map_err(|e| invalid(e.to_string()))?;
+    let model_path = match read_entry(&mut package, "_rels/.rels")? {
+        Some(rels) => root_model_path(&rels)?,
+        None => DEFAULT_MODEL.to_string(),
+    };
+    let model = read_entry(&mut package, &model_path)?.ok_or_else(|| {
+        ValidationError::new(
+            "file",
+            "3mf.model.missing",
+            format!("package has no model at {}", model_path),
+        )
+    })?;
+    placed_objects(&model)
+}
+
+fn read_entry(
+    package: &mut ZipArchive<Cursor<&[u8]>>,
+    path: &str,
+) -> Result<Option<String>, ValidationError> {
+    let entry = match package.by_name(path) {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(invalid(e.to_string())),
+    };
+    let mut text = String::new();
+    entry
+        .take(MAX_MODEL_SIZE)
+        .read_to_string(&mut text)
+        .map_err(|e| invalid(format!("{} cannot be read: {}", path, e)))?;
+    Ok(Some(text))
+}
+
+/// Finds the root model in package relationships. Targets are absolute within the package.
+fn root_model_path(rels: &str) -> Result<String, ValidationError> {
+    let document = roxmltree::Document::parse(rels).map_err(|e| invalid(e.to_string()))?;
+    let target = document
+        .descendants()
+        .filter(|node| node.has_tag_name("Relationship"))
+        .find(|node| node.attribute("Type") == Some(MODEL_RELATIONSHIP))
+        .and_then(|node| node.attribute("Target"))
+        .unwrap_or(DEFAULT_MODEL);
+    Ok(target.trim_start_matches('/').to_string())
+}
+
+fn placed_objects(model: &str) -> Result<Vec<ModelObject>, ValidationError> {
+    let document = roxmltree::Document::parse(model).map_err(|e| invalid(e.to_string()))?;
+    let mut objects: Vec<ModelObject> = document
+        .descendants()
+        .filter(|node| node.tag_name().name() == "object")
+        .filter(|node| node.attribute("type").unwrap_or("model") == "model")
+        .filter_map(|node| {
+            Some(ModelObject {
+                id: node.attribute("id")?.to_string(),
+                name: node.attribute("name").unwrap_or_default().to_string(),
+                count: 0,
+            })
+        })
+        .collect();
+    let items = document
+        .descendants()
+        .filter(|node| node.tag_name().name() == "item")
+        .filter(|node| {
+            node.parent()
+                .is_some_and(|p| p.tag_name().name() == "build")
+        })
+        .filter_map(|node| node.attribute("objectid"));
+    for id in items {
+        if let Some(object) = objects.iter_mut().find(|o| o.id == id) {
+            object.count = object.count.saturating_add(1);
+        }
+    }
+    objects.retain(|o| o.count > 0);
+    if objects.is_empty() {
+        return Err(ValidationError::new(
+            "file",
+            "3mf.objects.missing",
+            "model has no objects placed on plates",
+        ));
+    }
+    Ok(objects)
+}
+
+fn invalid(reason: impl Into<String>) -> ValidationError {
+    ValidationError::new(
+        "file",
+        "3mf.content.invalid",
+        format!("file is not a valid 3MF package: {}", reason.into()),
+    )
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+
+    use std::io::Write;
+
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    use super::*;
+
+    /// Packages a root model with the given objects and build items at the default location.
+    pub(crate) fn package(objects: &[(&str, &str)], items: &[&str]) -> Vec<u8> {
+        let mut model = String::from(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+  <resources>
+"#,
+        );
+        for (id, name) in objects {
+            model.push_str(&format!(
+                "    <object id=\"{}\" name=\"{}\" type=\"model\"><mesh/></object>\n",
+                id, name
+            ));
+        }
+        model.push_str("  </resources>\n  <build>\n");
+        for id in items {
+            model.push_str(&format!("    <item objectid=\"{}\"/>\n", id));
+        }
+        model.push_str("  </build>\n</model>\n");
+
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        writer
+            .start_file(DEFAULT_MODEL, SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(model.as_bytes()).unwrap();
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn count_objects_placed_on_plates() {
+        let content = package(
+            &[("1", "Bin"), ("2", "Lid"), ("3", "Unused")],
+            &["2", "1", "1"],
+        );
+
+        let objects = objects(&content).unwrap();
+
+        assert_eq!(
+            objects,
+            vec![
+                ModelObject {
+                    id: "1".to_string(),
+                    name: "Bin".to_string(),
+                    count: 2
+                },
+                ModelObject {
+                    id: "2".to_string(),
+                    name: "Lid".to_string(),
+                    count: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn read_model_named_in_relationships() {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        writer
+            .start_file("_rels/.rels", SimpleFileOptions::default())
+            .unwrap();
+        writer
+            .write_all(
+                br#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Target="/3D/box.model" Id="rel0" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel"/>
+</Relationships>"#,
+            )
+            .unwrap();
+        writer
+            .start_file("3D/box.model", SimpleFileOptions::default())
+            .unwrap();
+        writer
+            .write_all(
+                br#"<model><resources><object id="7" name="Box"/></resources>
+<build><item objectid="7"/></build></model>"#,
+            )
+            .unwrap();
+        let content = writer.finish().unwrap().into_inner();
+
+        let objects = objects(&content).unwrap();
+
+        assert_eq!(objects[0].name, "Box");
+    }
+
+    #[test]
+    fn reject_invalid_packages() {
+        let errors = [
+            objects(b"solid cube").unwrap_err(),
+            objects(&package(&[("1", "Bin")], &[])).unwrap_err(),
+        ];
+
+        let codes: Vec<&str> = errors.iter().map(|e| e.code()).collect();
+        assert_eq!(codes, vec!["3mf.content.invalid", "3mf.objects.missing"]);
+    }
+}
@@ -0,0 +1,325 @@
+//! Renders thumbnails of triangle meshes on the CPU.
+//!
+//! Meshes are drawn in an orthographic isometric projection, viewed from the front right and
+//! above, and scaled to fill the image. Every triangle is filled with a single shade of the
+//! part color that depends on its angle to the light, so faces of a box look different.
+//! PNG images are rasterized with a depth buffer. SVG images list triangles as polygons drawn
+//! from the farthest to the nearest, so they grow with the number of triangles of the mesh.
+
+use std::f64::consts::FRAC_1_SQRT_2;
+use std::fmt::Display;
+use std::io::Write;
+
+use flate2::write::ZlibEncoder;
+use flate2::{Compression, Crc};
+use serde::{Deserialize, Serialize};
+
+use crate::shared::validation::error::ValidationError;
+
+use super::geometry::{cross, dot, sub, Vertex};
+
+/// Width and height of thumbnails in pixels.
+pub const THUMBNAIL_SIZE: u32 = 256;
+/// Empty border around the mesh in pixels.
+const MARGIN: f64 = 8.0;
+/// Screen axes and the direction to the viewer of the isometric projection.
+const RIGHT: Vertex = [FRAC_1_SQRT_2, FRAC_1_SQRT_2, 0.0];
+const UP: Vertex = [-0.4082482904638631, 0.4082482904638631, 0.8164965809277261];
+const TOWARDS_VIEWER: Vertex = [0.5773502691896258, -0.5773502691896258, 0.5773502691896258];
+/// Direction to the light, tilted from the viewer so that visible sides of a box differ.
+const LIGHT: Vertex = [0.4319342127906801, -0.2591605276744081, 0.8638684255813602];
+/// Share of light reaching triangles turned away from the light.
+const AMBIENT: f64 = 0.3;
+const COLOR: [u8; 3] = [242, 140, 40];
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Image formats of thumbnails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThumbnailFormat {
+    #[default]
+    Png,
+    Svg,
+}
+
+impl ThumbnailFormat {
+    /// Parses `png` or `svg`, ignoring case.
+    pub fn parse(value: &str) -> Result<Self, ValidationError> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "png" => Ok(ThumbnailFormat::Png),
+            "svg" => Ok(ThumbnailFormat::Svg),
+            _ => Err(ValidationError::new(
+                "format",
+                "thumbnail.format.unsupported",
+                "thumbnail format must be png or svg",
+            )),
+        }
+    }
+
+    pub fn media_type(&self) -> &'static str {
+        match self {
+            ThumbnailFormat::Png => "image/png",
+            ThumbnailFormat::Svg => "image/svg+xml",
+        }
+    }
+}
+
+impl TryFrom<String> for ThumbnailFormat {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl Display for ThumbnailFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let format = match self {
+            ThumbnailFormat::Png => "png",
+            ThumbnailFormat::Svg => "svg",
+        };
+        write!(f, "{}", format)
+    }
+}
+
+/// Renders the mesh into an image of [`THUMBNAIL_SIZE`] pixels with transparent background.
+pub fn render(triangles: &[[Vertex; 3]], format: ThumbnailFormat) -> Vec<u8> {
+    let faces = project(triangles, f64::from(THUMBNAIL_SIZE));
+    match format {
+        ThumbnailFormat::Png => png(THUMBNAIL_SIZE, &rasterize(&faces, THUMBNAIL_SIZE)),
+        ThumbnailFormat::Svg => svg(faces, THUMBNAIL_SIZE).into_bytes(),
+    }
+}
+
+/// Triangle in image coordinates, with y growing downwards.
+#[derive(Debug, Clone, Copy)]
+struct Face {
+    points: [[f64; 2]; 3],
+    /// Distance towards the viewer of every point.
+    depths: [f64; 3],
+    color: [u8; 3],
+}
+
+impl Face {
+    fn depth(&self) -> f64 {
+        self.depths.iter().sum::<f64>() / 3.0
+    }
+}
+
+/// Projects triangles and fits them into the image. Degenerate triangles are dropped.
+fn project(triangles: &[[Vertex; 3]], size: f64) -> Vec<Face> {
+    let projected: Vec<([[f64; 2]; 3], [f64; 3], f64)> = triangles
+        .iter()
+        .filter_map(|[a, b, c]| {
+            let normal = cross(sub(*b, *a), sub(*c, *a));
+            let length = dot(normal, normal).sqrt();
+            if length == 0.0 || !length.is_finite() {
+                return None;
+            }
+            let light = dot(normal, LIGHT).abs() / length;
+            let shade = AMBIENT + (1.0 - AMBIENT) * light;
+            let points = [a, b, c].map(|v| [dot(*v, RIGHT), dot(*v, UP)]);
+            let depths = [a, b, c].map(|v| dot(*v, TOWARDS_VIEWER));
+            Some((points, depths, shade))
+        })
+        .collect();
+
+    let (mut min, mut max) = ([f64::INFINITY; 2], [f64::NEG_INFINITY; 2]);
+    for [x, y] in projected.iter().flat_map(|(points, _, _)| points) {
+        min = [min[0].min(*x), min[1].min(*y)];
+        max = [max[0].max(*x), max[1].max(*y)];
+    }
+    let extent = [max[0] - min[0], max[1] - min[1]];
+    let available = size - 2.0 * MARGIN;
+    let scale = match extent[0].max(extent[1]) {
+        longest if longest > 0.0 => available / longest,
+        _ => 1.0,
+    };
+    let offset = extent.map(|side| MARGIN + (available - side * scale) / 2.0);
+
+    projected
+        .into_iter()
+        .map(|(points, depths, shade)| Face {
+            points: points.map(|[x, y]| {
+                [
+                    offset[0] + (x - min[0]) * scale,
+                    offset[1] + (max[1] - y) * scale,
+                ]
+            }),
+            depths,
+            color: COLOR.map(|channel| (f64::from(channel) * shade).round() as u8),
+        })
+        .collect()
+}
+
+/// Fills pixels whose centers lie in triangles, keeping the nearest triangle of every pixel.
+/// Returns RGBA pixels row by row.
+fn rasterize(faces: &[Face], size: u32) -> Vec<u8> {
+    let size = size as usize;
+    let mut pixels = vec![0_u8; size * size * 4];
+    let mut depths = vec![f64::NEG_INFINITY; size * size];
+    for face in faces {
+        let [p0, p1, p2] = face.points;
+        let area = edge(p0, p1, p2);
+        if area.abs() < f64::EPSILON {
+            continue;
+        }
+        let bounds = |axis: usize| {
+            let low = p0[axis].min(p1[axis]).min(p2[axis]).floor().max(0.0) as usize;
+            let high = p0[axis].max(p1[axis]).max(p2[axis]).ceil().max(0.0) as usize;
+            low..high.min(size)
+        };
+        for y in bounds(1) {
+            for x in bounds(0) {
+                let point = [x as f64 + 0.5, y as f64 + 0.5];
+                let weights = [
+                    edge(p1, p2, point),
+                    edge(p2, p0, point),
+                    edge(p0, p1, point),
+                ];
+                if weights.iter().any(|w| w * area.signum() < 0.0) {
+                    continue;
+                }
+                let depth = (0..3).map(|i| weights[i] * face.depths[i]).sum::<f64>() / area;
+                let index = y * size + x;
+                if depth > depths[index] {
+                    depths[index] = depth;
+                    let [r, g, b] = face.color;
+                    pixels[index * 4..index * 4 + 4].copy_from_slice(&[r, g, b, 255]);
+                }
+            }
+        }
+    }
+    pixels
+}
+
+/// Encodes RGBA pixels as a PNG image without filtering.
+fn png(size: u32, pixels: &[u8]) -> Vec<u8> {
+    let row = size as usize * 4;
+    let mut data = ZlibEncoder::new(Vec::new(), Compression::default());
+    for line in pixels.chunks_exact(row) {
+        data.write_all(&[0])
+            .and_then(|_| data.write_all(line))
+            .expect("writing to memory does not fail");
+    }
+    let data = data.finish().expect("writing to memory does not fail");
+
+    let mut header = Vec::with_capacity(13);
+    header.extend(size.to_be_bytes());
+    header.extend(size.to_be_bytes());
+    // 8 bits per channel of RGBA, default compression and filtering, no interlacing.
+    header.extend([8, 6, 0, 0, 0]);
+
+    let mut image = PNG_SIGNATURE.to_vec();
+    for (kind, content) in [(b"IHDR", &header), (b"IDAT", &data), (b"IEND", &vec![])] {
+        image.extend((content.len() as u32).to_be_bytes());
+        image.extend(kind);
+        image.extend(content);
+        let mut crc = Crc::new();
+        crc.update(kind);
+        crc.update(content);
+        image.extend(crc.sum().to_be_bytes());
+    }
+    image
+}
+
+/// Draws triangles from the farthest. Outlines of the fill color hide seams between triangles.
+fn svg(mut faces: Vec<Face>, size: u32) -> String {
+    faces.sort_by(|a, b| a.depth().total_cmp(&b.depth()));
+    let mut image = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{size}\" height=\"{size}\" \
+        viewBox=\"0 0 {size} {size}\">\n"
+    );
+    for face in faces {
+        let [r, g, b] = face.color;
+        let points: Vec<String> = face
+            .points
+            .iter()
+            .map(|[x, y]| format!("{:.1},{:.1}", x, y))
+            .collect();
+        image.push_str(&format!(
+            "<polygon points=\"{}\" fill=\"#{r:02x}{g:02x}{b:02x}\" \
+            stroke=\"#{r:02x}{g:02x}{b:02x}\" stroke-width=\"0.5\" stroke-linejoin=\"round\"/>\n",
+            points.join(" ")
+        ));
+    }
+    image.push_str("</svg>\n");
+    image
+}
+
+/// Twice the signed area of the triangle.
+fn edge(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::Read;
+
+    use flate2::read::ZlibDecoder;
+
+    use super::*;
+    use crate::inventory::domain::geometry::tests::cuboid;
+
+    /// Decodes RGBA pixels of an image encoded by [`png`].
+    fn decode(image: &[u8]) -> Vec<u8> {
+        let length = u32::from_be_bytes(image[33..37].try_into().unwrap()) as usize;
+        let mut data = Vec::new();
+        ZlibDecoder::new(&image[41..41 + length])
+            .read_to_end(&mut data)
+            .unwrap();
+        let row = THUMBNAIL_SIZE as usize * 4;
+        data.chunks_exact(row + 1)
+            .flat_map(|line| line[1..].to_vec())
+            .collect()
+    }
+
+    #[test]
+    fn render_png() {
+        let image = render(&cuboid([0.0; 3], [40.0, 20.0, 10.0]), ThumbnailFormat::Png);
+
+        assert!(image.starts_with(PNG_SIGNATURE));
+        assert_eq!(&image[16..24], &[0, 0, 1, 0, 0, 0, 1, 0]);
+        let pixels = decode(&image);
+        let pixel = |x: usize, y: usize| {
+            let index = (y * THUMBNAIL_SIZE as usize + x) * 4;
+            pixels[index..index + 4].to_vec()
+        };
+        assert_eq!(pixel(0, 0), vec![0, 0, 0, 0]);
+        assert_eq!(pixel(128, 128)[3], 255);
+    }
+
+    #[test]
+    fn shade_sides_differently() {
+        let faces = project(&cuboid([0.0; 3], [10.0, 10.0, 10.0]), 256.0);
+
+        let mut colors: Vec<[u8; 3]> = faces.iter().map(|f| f.color).collect();
+        colors.sort();
+        colors.dedup();
+        assert_eq!(colors.len(), 3);
+        assert!(faces
+            .iter()
+            .flat_map(|f| f.points)
+            .all(|[x, y]| (MARGIN..=256.0 - MARGIN).contains(&x.round())
+                && (MARGIN..=256.0 - MARGIN).contains(&y.round())));
+    }
+
+    #[test]
+    fn render_svg() {
+        let image = render(&cuboid([0.0; 3], [10.0, 10.0, 10.0]), ThumbnailFormat::Svg);
+
+        let image = String::from_utf8(image).unwrap();
+        assert!(image.starts_with("<svg "));
+        assert_eq!(image.matches("<polygon ").count(), 12);
+    }
+
+    #[test]
+    fn parse_format() {
+        assert_eq!(ThumbnailFormat::parse("SVG").unwrap(), ThumbnailFormat::Svg);
+        assert_eq!(
+            ThumbnailFormat::parse("jpeg").unwrap_err().code(),
+            "thumbnail.format.unsupported"
+        );
+    }
+}
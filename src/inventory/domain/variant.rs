@@ -0,0 +1,227 @@
+//! Variants of parts.
+//!
+//! The same model is often printed in several colors or sizes, e.g., a bin in six colors and
+//! three heights. Every variant is a part of its own, so projects list the exact variant they
+//! need, and the variant points to its parent part to keep variants together. Variants cannot
+//! have variants of their own.
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::shared::validation::error::ValidationError;
+use crate::shared::validation::validator::CollectingValidator;
+
+use super::metadata::Color;
+use super::part::{Part, PartId};
+
+/// Link of a variant to its parent part.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Variant {
+    pub parent: PartId,
+    #[serde(flatten)]
+    pub attributes: VariantAttributes,
+}
+
+/// What distinguishes the variant from other variants of the parent part.
+/// At least one attribute is set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VariantAttributes {
+    pub color: Option<Color>,
+    pub size: Option<Size>,
+}
+
+/// Unvalidated variant attributes, e.g., from request payloads or storage.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RawVariantAttributes {
+    pub color: Option<String>,
+    pub size: Option<String>,
+}
+
+impl VariantAttributes {
+    /// Validates both attributes, reporting every invalid one.
+    /// Blank attributes are treated as missing.
+    pub fn parse(raw: RawVariantAttributes) -> Result<Self, Vec<ValidationError>> {
+        let mut validator = CollectingValidator::default();
+        let color = raw
+            .color
+            .filter(|c| !c.trim().is_empty())
+            .map(|c| validator.parse_string::<Color>(c));
+        let size = raw
+            .size
+            .filter(|s| !s.trim().is_empty())
+            .map(|s| validator.parse_string::<Size>(s));
+        if color.is_none() && size.is_none() {
+            validator.report(ValidationError::new(
+                "color",
+                "variant.attributes.missing",
+                "variant needs a color or a size",
+            ));
+        }
+        if validator.has_errors() {
+            return Err(validator.into_errors());
+        }
+        Ok(Self {
+            color: color.transpose().unwrap(),
+            size: size.transpose().unwrap(),
+        })
+    }
+}
+
+impl From<&VariantAttributes> for RawVariantAttributes {
+    fn from(value: &VariantAttributes) -> Self {
+        Self {
+            color: value.color.as_ref().map(|c| c.to_string()),
+            size: value.size.as_ref().map(|s| s.to_string()),
+        }
+    }
+}
+
+/// Lists set attributes separated by commas, e.g., `red, 3 units`.
+impl Display for VariantAttributes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let attributes: Vec<&str> = [
+            self.color.as_ref().map(|c| c.as_ref()),
+            self.size.as_ref().map(|s| s.as_ref()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        write!(f, "{}", attributes.join(", "))
+    }
+}
+
+/// Size of the variant as the designer calls it, e.g., `3 units` or `M`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(transparent)]
+pub struct Size(String);
+
+impl Size {
+    pub fn parse(value: impl Into<String>) -> Result<Self, ValidationError> {
+        let value = value.into();
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            return Err(ValidationError::new(
+                "size",
+                "variant.size.too-short",
+                "size is too short",
+            ));
+        }
+        if trimmed.len() > 50 {
+            return Err(ValidationError::new(
+                "size",
+                "variant.size.too-long",
+                "size is too long",
+            ));
+        }
+        Ok(Self(trimmed.to_string()))
+    }
+}
+
+impl TryFrom<String> for Size {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl AsRef<str> for Size {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for Size {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Checks whether the part can become a variant of the parent.
+/// Parts with variants of their own stay parents, so `has_variants` tells whether any part
+/// is a variant of this one.
+pub fn check_parent(
+    part: PartId,
+    parent: &Part,
+    has_variants: bool,
+) -> Result<(), ValidationError> {
+    if parent.id() == part {
+        return Err(ValidationError::new(
+            "parent",
+            "variant.parent.same-part",
+            "part cannot be a variant of itself",
+        ));
+    }
+    if parent.variant().is_some() {
+        return Err(ValidationError::new(
+            "parent",
+            "variant.parent.is-variant",
+            "variants cannot have variants",
+        ));
+    }
+    if has_variants {
+        return Err(ValidationError::new(
+            "parent",
+            "variant.part.has-variants",
+            "part with variants cannot become a variant",
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::inventory::domain::name::Name;
+
+    #[test]
+    fn require_color_or_size() {
+        let errors = VariantAttributes::parse(RawVariantAttributes {
+            color: Some(" ".to_string()),
+            size: None,
+        })
+        .unwrap_err();
+
+        assert_eq!(errors[0].code(), "variant.attributes.missing");
+        let attributes = VariantAttributes::parse(RawVariantAttributes {
+            color: Some("red".to_string()),
+            size: Some(" 3 units ".to_string()),
+        })
+        .unwrap();
+        assert_eq!(attributes.to_string(), "red, 3 units");
+    }
+
+    #[test]
+    fn keep_variants_one_level_deep() {
+        let bin = Part::hydrate(Uuid::now_v7(), Name::parse("Bin").unwrap());
+        let red_bin = Part::hydrate(Uuid::now_v7(), Name::parse("Bin, red").unwrap()).with_variant(
+            Some(Variant {
+                parent: bin.id(),
+                attributes: VariantAttributes::parse(RawVariantAttributes {
+                    color: Some("red".to_string()),
+                    size: None,
+                })
+                .unwrap(),
+            }),
+        );
+        let code = |result: Result<(), ValidationError>| result.unwrap_err().code().to_string();
+
+        assert!(check_parent(Uuid::now_v7(), &bin, false).is_ok());
+        assert_eq!(
+            code(check_parent(bin.id(), &bin, false)),
+            "variant.parent.same-part"
+        );
+        assert_eq!(
+            code(check_parent(Uuid::now_v7(), &red_bin, false)),
+            "variant.parent.is-variant"
+        );
+        assert_eq!(
+            code(check_parent(Uuid::now_v7(), &bin, true)),
+            "variant.part.has-variants"
+        );
+    }
+}
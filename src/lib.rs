@@ -1,14 +1,28 @@
 //! Printtables is a project to keep track of multipart 3D-printing projects.
 //!
 //! The structure loosely follow the hexagonal architecture principles and DDD.
-//! The application contains two subdomains: inventory and projects.
+//! The application contains three subdomains: inventory, projects and filament.
 //!
-//! Inventory keeps reusable parts that can be printed.
+//! Inventory keeps reusable parts that can be printed, along with their model files.
 //!
 //! Projects is concerned with organising those parts into printable plans and keeping track of
 //! plans completion.
+//!
+//! Filament keeps spools on the shelf and warns when projects need more than the shelf holds.
+//!
+//! Settings keep shared configuration, like prices used to calculate costs of parts and projects.
+//!
+//! Activity records the history of changes made to inventory and projects.
+//!
+//! The application layer coordinates changes spanning multiple subdomains.
+pub mod activity;
+pub mod app;
+pub mod cli;
+pub mod filament;
 pub mod infra;
 pub mod inventory;
 pub mod projects;
 pub mod server;
+pub mod settings;
 pub mod shared;
+pub mod tui;
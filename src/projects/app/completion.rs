@@ -0,0 +1,77 @@
+//! Announces projects completed by reported progress.
+//!
+//! The notifier subscribes to committed domain events, so it announces completions reported by
+//! any interface, e.g., a slicer hook calling the CLI while the server is running.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::info;
+
+use crate::app::events::{DomainEvent, EventSubscriber};
+use crate::projects::domain::event::ProjectEvent;
+use crate::projects::view::project::ProjectView;
+use crate::projects::view::repository::ProjectViewRepository;
+
+/// Logs a notification when the last part of a project BOM is printed.
+pub struct CompletionNotifier {
+    views: Arc<dyn ProjectViewRepository>,
+}
+
+impl CompletionNotifier {
+    pub fn new(views: Arc<dyn ProjectViewRepository>) -> Self {
+        Self { views }
+    }
+}
+
+#[async_trait]
+impl EventSubscriber for CompletionNotifier {
+    /// Progress cannot exceed BOM quantities, so every completion is announced once.
+    async fn on_event(&self, event: &DomainEvent) -> anyhow::Result<()> {
+        let DomainEvent::Project(ProjectEvent::ProgressReported { project, .. }) = event else {
+            return Ok(());
+        };
+        if let Some(view) = self.views.get_view_by_id(*project).await? {
+            if is_complete(&view) {
+                info!(
+                    "project {} \"{}\" is complete, all {} BOM lines are printed",
+                    view.id(),
+                    view.name(),
+                    view.bom_size()
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether every part of a non-empty BOM is printed.
+fn is_complete(project: &ProjectView) -> bool {
+    !project.parts().is_empty() && project.parts().iter().all(|p| p.remaining() == 0)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::projects::view::project::ProjectPart;
+
+    fn project(printed: &[(u32, u32)]) -> ProjectView {
+        let parts = printed
+            .iter()
+            .map(|&(quantity, printed)| {
+                ProjectPart::new(Uuid::now_v7(), "Bin".to_string(), quantity).with_printed(printed)
+            })
+            .collect();
+        ProjectView::new(Uuid::now_v7(), "Organizers".to_string(), parts)
+    }
+
+    #[test]
+    fn complete_when_every_part_is_printed() {
+        assert!(is_complete(&project(&[(9, 9), (5, 5)])));
+        assert!(!is_complete(&project(&[(9, 9), (5, 4)])));
+        assert!(!is_complete(&project(&[])));
+    }
+}
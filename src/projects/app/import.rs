@@ -0,0 +1,463 @@
+//! Imports project BOM from spreadsheets.
+//!
+//! The import accepts CSV with `part name,quantity` rows, so exported BOMs with progress columns
+//! are imported back. Part names are matched against the inventory. Unknown parts are either reported as errors or registered in the inventory.
+//! Registered parts and the new BOM are stored in a single unit of work.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::info;
+
+use crate::app::unit_of_work::UnitOfWorkFactory;
+use crate::inventory::app::service::{InventoryError, InventoryService};
+use crate::inventory::domain::name::Name;
+use crate::inventory::domain::part::{Part, PartId};
+use crate::inventory::domain::part_repository::PartRepository;
+use crate::projects::app::service::{latest_revisions, ProjectError, ProjectsService};
+use crate::projects::domain::project::{ProjectId, ProjectPart};
+use crate::projects::domain::quantity::Quantity;
+use crate::projects::view::project::ProjectPart as BomViewPart;
+use crate::shared::validation::error::ValidationError;
+use crate::shared::validation::validator::CollectingValidator;
+
+/// Controls how the import treats the payload.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BomImportOptions {
+    /// Report changes without modifying the project or the inventory.
+    pub dry_run: bool,
+    /// Register parts missing in the inventory instead of rejecting the import.
+    pub register_missing: bool,
+}
+
+#[derive(Debug, Error)]
+pub enum BomImportError {
+    #[error("requested project does not exist")]
+    MissingProject,
+    #[error("imported BOM is invalid")]
+    InvalidBom(Vec<ValidationError>),
+    #[error("error importing BOM")]
+    GeneralError(#[from] anyhow::Error),
+}
+
+/// Outcome of the import, including what was changed in the project BOM.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BomImportReport {
+    pub dry_run: bool,
+    pub rows: Vec<ImportedRow>,
+    pub changes: Vec<BomChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedRow {
+    pub row: usize,
+    pub name: String,
+    pub quantity: u32,
+    pub part_id: Option<PartId>,
+    pub status: RowStatus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RowStatus {
+    /// The row refers to a part already present in the inventory.
+    Matched,
+    /// The part was registered in the inventory during the import.
+    Registered,
+    /// The part will be registered when the import is committed.
+    WillRegister,
+}
+
+/// Change of a single BOM line.
+/// Added lines have zero `before` quantity, removed lines have zero `after` quantity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BomChange {
+    pub part_id: Option<PartId>,
+    pub name: String,
+    pub before: u32,
+    pub after: u32,
+}
+
+#[async_trait]
+pub trait BomImporter: Send + Sync {
+    /// Replaces the BOM of the project with parts listed in CSV.
+    async fn import_csv(
+        &self,
+        project_id: ProjectId,
+        csv: &str,
+        options: BomImportOptions,
+    ) -> Result<BomImportReport, BomImportError>;
+}
+
+pub struct DefaultBomImporter {
+    projects: Arc<dyn ProjectsService>,
+    inventory: Arc<dyn InventoryService>,
+    units: Arc<dyn UnitOfWorkFactory>,
+}
+
+impl DefaultBomImporter {
+    pub fn new(
+        projects: Arc<dyn ProjectsService>,
+        inventory: Arc<dyn InventoryService>,
+        units: Arc<dyn UnitOfWorkFactory>,
+    ) -> Self {
+        Self {
+            projects,
+            inventory,
+            units,
+        }
+    }
+}
+
+#[async_trait]
+impl BomImporter for DefaultBomImporter {
+    async fn import_csv(
+        &self,
+        project_id: ProjectId,
+        csv: &str,
+        options: BomImportOptions,
+    ) -> Result<BomImportReport, BomImportError> {
+        let project = self.projects.view_project(project_id).await?;
+
+        let mut validator = CollectingValidator::default();
+        let lines = parse_bom_csv(csv, &mut validator);
+
+        let mut rows = Vec::with_capacity(lines.len());
+        let mut missing = vec![];
+        for line in lines {
+            let part = self
+                .inventory
+                .find_part_by_name(&line.name)
+                .await
+                .map_err(inventory_error)?;
+            let (part_id, status) = match part {
+                Some(part) => (Some(part.id()), RowStatus::Matched),
+                None if options.register_missing => (None, RowStatus::WillRegister),
+                None => {
+                    validator.report(ValidationError::new(
+                        format!("rows[{}].name", line.row),
+                        "bom.part.missing",
+                        format!("part {} is not registered in the inventory", line.name),
+                    ));
+                    (None, RowStatus::WillRegister)
+                }
+            };
+            if part_id.is_none() {
+                missing.push(rows.len());
+            }
+            rows.push(ImportedRow {
+                row: line.row,
+                name: line.name.to_string(),
+                quantity: line.quantity.value(),
+                part_id,
+                status,
+            });
+        }
+
+        if validator.has_errors() {
+            return Err(BomImportError::InvalidBom(validator.into_errors()));
+        }
+
+        if options.dry_run {
+            let changes = bom_changes(project.parts(), &rows);
+            return Ok(BomImportReport {
+                dry_run: true,
+                rows,
+                changes,
+            });
+        }
+
+        let uow = self.units.begin().await?;
+        register_missing_parts(uow.parts().as_ref(), &mut rows, &missing).await?;
+        let changes = bom_changes(project.parts(), &rows);
+
+        let bom = merge_rows(&rows)
+            .into_iter()
+            .filter_map(|(part_id, _, quantity)| part_id.map(|id| ProjectPart::new(id, quantity)))
+            .collect();
+        let mut project = uow
+            .projects()
+            .find_by_id(project_id)
+            .await?
+            .ok_or(BomImportError::MissingProject)?;
+        project
+            .define_parts(bom)
+            .map_err(|e| BomImportError::InvalidBom(vec![e]))?;
+        let latest = latest_revisions(uow.parts().as_ref(), &project).await?;
+        project
+            .pin_revisions(&latest)
+            .map_err(|e| BomImportError::InvalidBom(vec![e]))?;
+        uow.projects().update(project).await?;
+        uow.commit().await?;
+        info!(
+            "imported {} BOM rows into project {}",
+            rows.len(),
+            project_id
+        );
+
+        Ok(BomImportReport {
+            dry_run: false,
+            rows,
+            changes,
+        })
+    }
+}
+
+/// Registers parts for rows without a matching inventory part.
+/// Rows with the same name share the newly registered part.
+async fn register_missing_parts(
+    parts: &dyn PartRepository,
+    rows: &mut [ImportedRow],
+    missing: &[usize],
+) -> Result<(), BomImportError> {
+    let mut registered: HashMap<String, PartId> = HashMap::new();
+    for &index in missing {
+        let row = &mut rows[index];
+        let key = row.name.to_lowercase();
+        let part_id = match registered.get(&key) {
+            Some(id) => *id,
+            None => {
+                let name = Name::try_from(row.name.as_str()).map_err(anyhow::Error::from)?;
+                let part = Part::new(name);
+                let id = part.id();
+                parts.insert(part).await?;
+                registered.insert(key, id);
+                id
+            }
+        };
+        row.part_id = Some(part_id);
+        row.status = RowStatus::Registered;
+    }
+    Ok(())
+}
+
+/// Sums quantities of rows referring to the same part, keeping the order of first occurrence.
+fn merge_rows(rows: &[ImportedRow]) -> Vec<(Option<PartId>, String, u32)> {
+    let mut merged: Vec<(Option<PartId>, String, u32)> = vec![];
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for row in rows {
+        let key = row.name.to_lowercase();
+        match positions.get(&key) {
+            Some(&position) => merged[position].2 += row.quantity,
+            None => {
+                positions.insert(key, merged.len());
+                merged.push((row.part_id, row.name.clone(), row.quantity));
+            }
+        }
+    }
+    merged
+}
+
+fn bom_changes(current: &[BomViewPart], rows: &[ImportedRow]) -> Vec<BomChange> {
+    let imported = merge_rows(rows);
+    let mut changes = vec![];
+    for (part_id, name, quantity) in &imported {
+        let before = part_id
+            .and_then(|id| current.iter().find(|p| p.id() == id))
+            .map(|p| p.quantity())
+            .unwrap_or_default();
+        if before != *quantity {
+            changes.push(BomChange {
+                part_id: *part_id,
+                name: name.clone(),
+                before,
+                after: *quantity,
+            });
+        }
+    }
+    for part in current {
+        let kept = imported.iter().any(|(id, _, _)| *id == Some(part.id()));
+        if !kept {
+            changes.push(BomChange {
+                part_id: Some(part.id()),
+                name: part.name().to_string(),
+                before: part.quantity(),
+                after: 0,
+            });
+        }
+    }
+    changes
+}
+
+impl From<ProjectError> for BomImportError {
+    fn from(value: ProjectError) -> Self {
+        match value {
+            ProjectError::MissingProject => BomImportError::MissingProject,
+            ProjectError::ValidationFailed(errors) => BomImportError::InvalidBom(errors),
+            ProjectError::GeneralError(e) => BomImportError::GeneralError(e),
+        }
+    }
+}
+
+fn inventory_error(e: InventoryError) -> BomImportError {
+    match e {
+        InventoryError::GeneralError(e) => BomImportError::GeneralError(e),
+        e => BomImportError::GeneralError(anyhow::Error::from(e)),
+    }
+}
+
+/// Parsed and validated line of BOM.
+#[derive(Debug)]
+struct BomLine {
+    row: usize,
+    name: Name,
+    quantity: Quantity,
+}
+
+/// Parses CSV rows. Columns after the quantity, e.g., `printed` and `remaining` of exported
+/// BOMs, are ignored.
+/// The first row is treated as a header when its quantity column reads
+/// `quantity`. Empty rows are skipped. Invalid rows are reported to the validator.
+/// Rows are numbered after the line they start on.
+fn parse_bom_csv(csv: &str, validator: &mut CollectingValidator) -> Vec<BomLine> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(csv.as_bytes());
+    let mut lines = vec![];
+    let mut first_record = true;
+    for (index, result) in reader.records().enumerate() {
+        let record = match result {
+            Ok(record) => record,
+            Err(e) => {
+                let row = e.position().map_or(index + 1, |p| line_of(csv, p));
+                validator.report(ValidationError::new(
+                    format!("rows[{}]", row),
+                    "bom.row.malformed",
+                    format!("row cannot be read: {}", e),
+                ));
+                continue;
+            }
+        };
+        let row = record.position().map_or(index + 1, |p| line_of(csv, p));
+        let path = format!("rows[{}]", row);
+        if record.iter().all(str::is_empty) {
+            continue;
+        }
+        let is_first_record = std::mem::replace(&mut first_record, false);
+        let header = record
+            .get(1)
+            .is_some_and(|q| q.eq_ignore_ascii_case("quantity"));
+        if is_first_record && header {
+            continue;
+        }
+        if record.len() < 2 {
+            validator.report(ValidationError::new(
+                path,
+                "bom.row.columns",
+                "row must contain part name and quantity",
+            ));
+            continue;
+        }
+        let name = validator.parse_string_at::<Name>(&path, &record[0]);
+        let quantity = validator.parse_string_at::<Quantity>(&path, &record[1]);
+        if let (Ok(name), Ok(quantity)) = (name, quantity) {
+            lines.push(BomLine {
+                row,
+                name,
+                quantity,
+            });
+        }
+    }
+    lines
+}
+
+/// Numbers lines of the input from 1. The reader does not count empty lines and places
+/// records after them, so lines are counted up to the first character of the record.
+fn line_of(csv: &str, position: &csv::Position) -> usize {
+    let offset = usize::try_from(position.byte()).map_or(csv.len(), |o| o.min(csv.len()));
+    let skipped = csv[offset..]
+        .bytes()
+        .take_while(|b| *b == b'\n' || *b == b'\r')
+        .filter(|b| *b == b'\n')
+        .count();
+    csv[..offset].matches('\n').count() + skipped + 1
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn parse_exported_bom() {
+        let mut validator = CollectingValidator::default();
+        let lines = parse_bom_csv(
+            "part name,quantity,printed,remaining\nDrawer,9,3,6\n",
+            &mut validator,
+        );
+
+        assert!(!validator.has_errors());
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].quantity.value(), 9);
+    }
+
+    #[test]
+    fn parse_rows_with_header() {
+        let mut validator = CollectingValidator::default();
+        let lines = parse_bom_csv(
+            "part name,quantity\nBin,5\n\n\"Drawer, large\", 9\n",
+            &mut validator,
+        );
+
+        assert!(!validator.has_errors());
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1].name.as_ref(), "Drawer, large");
+        assert_eq!(lines[1].quantity.value(), 9);
+        assert_eq!(lines[1].row, 4);
+    }
+
+    #[test]
+    fn parse_quoted_values_spanning_lines() {
+        let mut validator = CollectingValidator::default();
+        let lines = parse_bom_csv("\"Bin,\nsmall\",5\nLid,2\n", &mut validator);
+
+        assert!(!validator.has_errors());
+        assert_eq!(lines[0].name.as_ref(), "Bin,\nsmall");
+        assert_eq!(lines[1].row, 3);
+    }
+
+    #[test]
+    fn report_invalid_rows() {
+        let mut validator = CollectingValidator::default();
+        let lines = parse_bom_csv("Bin,five\n,2\nStopper\nInsert,1", &mut validator);
+
+        assert_eq!(lines.len(), 1);
+        let errors = validator.into_errors();
+        let attributes: Vec<&str> = errors.iter().map(|e| e.attribute()).collect();
+        assert_eq!(
+            attributes,
+            vec!["rows[1].quantity", "rows[2].name", "rows[3]"]
+        );
+    }
+
+    #[test]
+    fn skip_only_first_header() {
+        let mut validator = CollectingValidator::default();
+        let lines = parse_bom_csv("Bin,five\nname,quantity\nLid,2\n", &mut validator);
+
+        assert_eq!(lines.len(), 1);
+        let errors = validator.into_errors();
+        let attributes: Vec<&str> = errors.iter().map(|e| e.attribute()).collect();
+        assert_eq!(attributes, vec!["rows[1].quantity", "rows[2].quantity"]);
+    }
+
+    #[test]
+    fn merge_duplicate_rows() {
+        let row = |name: &str, quantity| ImportedRow {
+            row: 1,
+            name: name.to_string(),
+            quantity,
+            part_id: None,
+            status: RowStatus::WillRegister,
+        };
+        let merged = merge_rows(&[row("Bin", 2), row("Drawer", 1), row("bin", 3)]);
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].2, 5);
+    }
+}
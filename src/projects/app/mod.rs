@@ -1,2 +1,7 @@
 //! Coordinate main project use cases
+pub mod completion;
+pub mod import;
+pub mod model_import;
+pub mod packing;
+pub mod revisions;
 pub mod service;
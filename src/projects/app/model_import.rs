@@ -0,0 +1,316 @@
+//! Imports parts from 3MF model packages.
+//!
+//! Every object placed on the plates becomes an inventory part named after the object. Objects
+//! named like parts already in the inventory use those parts. Objects that cannot be imported,
+//! e.g., objects without a name, are reported with their errors while the rest is imported.
+//! Optionally, a new project is created with a BOM counting copies of every object.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::info;
+
+use crate::app::unit_of_work::UnitOfWorkFactory;
+use crate::inventory::domain::name::Name;
+use crate::inventory::domain::part::{Part, PartId};
+use crate::inventory::domain::three_mf;
+use crate::projects::app::service::latest_revisions;
+use crate::projects::domain::name::Name as ProjectName;
+use crate::projects::domain::project::{Project, ProjectId, ProjectPart};
+use crate::projects::domain::quantity::Quantity;
+use crate::shared::validation::error::ValidationError;
+use crate::shared::validation::validator::CollectingValidator;
+
+/// Controls what the import creates besides parts.
+#[derive(Debug, Clone, Default)]
+pub struct ModelImportOptions {
+    /// Name of the project created with the BOM of imported objects.
+    pub project: Option<ProjectName>,
+}
+
+#[derive(Debug, Error)]
+pub enum ModelImportError {
+    /// The package cannot be read at all.
+    #[error("imported model is invalid")]
+    InvalidModel(Vec<ValidationError>),
+    #[error("error importing model")]
+    GeneralError(#[from] anyhow::Error),
+}
+
+/// Outcome of the import for every object placed on the plates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelImportReport {
+    /// Project created with the BOM of imported objects.
+    pub project_id: Option<ProjectId>,
+    pub objects: Vec<ImportedObject>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedObject {
+    /// Identifier of the object within the package.
+    pub object: String,
+    pub name: String,
+    /// Number of copies placed on all plates.
+    pub count: u32,
+    pub part_id: Option<PartId>,
+    pub status: ObjectStatus,
+    /// Reasons the object was rejected.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<ValidationError>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ObjectStatus {
+    /// The object is named like a part already present in the inventory.
+    Matched,
+    /// The part was registered in the inventory during the import.
+    Registered,
+    /// The object was not imported.
+    Rejected,
+}
+
+#[async_trait]
+pub trait ModelImporter: Send + Sync {
+    /// Registers parts for objects of the 3MF package.
+    async fn import_3mf(
+        &self,
+        content: &[u8],
+        options: ModelImportOptions,
+    ) -> Result<ModelImportReport, ModelImportError>;
+}
+
+pub struct DefaultModelImporter {
+    units: Arc<dyn UnitOfWorkFactory>,
+}
+
+impl DefaultModelImporter {
+    pub fn new(units: Arc<dyn UnitOfWorkFactory>) -> Self {
+        Self { units }
+    }
+}
+
+#[async_trait]
+impl ModelImporter for DefaultModelImporter {
+    /// Parts and the project are stored in a single unit of work.
+    /// The project is created only when at least one object is imported.
+    async fn import_3mf(
+        &self,
+        content: &[u8],
+        options: ModelImportOptions,
+    ) -> Result<ModelImportReport, ModelImportError> {
+        let placed =
+            three_mf::objects(content).map_err(|e| ModelImportError::InvalidModel(vec![e]))?;
+
+        let uow = self.units.begin().await?;
+        let parts = uow.parts();
+        let mut registered: HashMap<String, PartId> = HashMap::new();
+        let mut objects = Vec::with_capacity(placed.len());
+        for (index, object) in placed.into_iter().enumerate() {
+            let mut imported = ImportedObject {
+                object: object.id,
+                name: object.name.trim().to_string(),
+                count: object.count,
+                part_id: None,
+                status: ObjectStatus::Rejected,
+                errors: vec![],
+            };
+            let name = match Name::parse(object.name) {
+                Ok(name) => name,
+                Err(e) => {
+                    imported
+                        .errors
+                        .push(e.nested(&format!("objects[{}]", index)));
+                    objects.push(imported);
+                    continue;
+                }
+            };
+            let key = imported.name.to_lowercase();
+            let (part_id, status) = match registered.get(&key) {
+                Some(&id) => (id, ObjectStatus::Registered),
+                None => match parts.find_by_name(&name).await? {
+                    Some(part) => (part.id(), ObjectStatus::Matched),
+                    None => {
+                        let part = Part::new(name);
+                        let id = part.id();
+                        parts.insert(part).await?;
+                        registered.insert(key, id);
+                        (id, ObjectStatus::Registered)
+                    }
+                },
+            };
+            imported.part_id = Some(part_id);
+            imported.status = status;
+            objects.push(imported);
+        }
+
+        let project_id = match options.project {
+            Some(name) => {
+                let bom = project_bom(&objects).map_err(ModelImportError::InvalidModel)?;
+                if bom.is_empty() {
+                    None
+                } else {
+                    let mut project = Project::new(name);
+                    project
+                        .define_parts(bom)
+                        .map_err(|e| ModelImportError::InvalidModel(vec![e]))?;
+                    let latest = latest_revisions(uow.parts().as_ref(), &project).await?;
+                    project
+                        .pin_revisions(&latest)
+                        .map_err(|e| ModelImportError::InvalidModel(vec![e]))?;
+                    Some(uow.projects().create(project).await?)
+                }
+            }
+            None => None,
+        };
+        uow.commit().await?;
+        info!("imported {} objects from 3MF model", objects.len());
+
+        Ok(ModelImportReport {
+            project_id,
+            objects,
+        })
+    }
+}
+
+/// Sums copies of objects imported as the same part, keeping the order of first occurrence.
+/// Every object of a part with too many copies is reported, like rows of imported CSV files.
+fn project_bom(objects: &[ImportedObject]) -> Result<Vec<ProjectPart>, Vec<ValidationError>> {
+    let mut totals: Vec<(PartId, u64, Vec<usize>)> = vec![];
+    for (index, object) in objects.iter().enumerate() {
+        let Some(part_id) = object.part_id else {
+            continue;
+        };
+        match totals.iter_mut().find(|(id, _, _)| *id == part_id) {
+            Some((_, total, indexes)) => {
+                *total += u64::from(object.count);
+                indexes.push(index);
+            }
+            None => totals.push((part_id, u64::from(object.count), vec![index])),
+        }
+    }
+    let mut validator = CollectingValidator::default();
+    let mut bom = Vec::with_capacity(totals.len());
+    for (part, total, indexes) in totals {
+        match Quantity::parse(u32::try_from(total).unwrap_or(u32::MAX)) {
+            Ok(quantity) => bom.push(ProjectPart::new(part, quantity.value())),
+            Err(e) => {
+                for index in indexes {
+                    validator.report(e.clone().nested(&format!("objects[{}]", index)));
+                }
+            }
+        }
+    }
+    if validator.has_errors() {
+        Err(validator.into_errors())
+    } else {
+        Ok(bom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::infra::memory::unit_of_work::InMemoryUnitOfWorkFactory;
+    use crate::infra::memory::MemoryStorage;
+    use crate::inventory::domain::three_mf::tests::package;
+
+    fn importer() -> (DefaultModelImporter, Arc<InMemoryUnitOfWorkFactory>) {
+        let units = Arc::new(InMemoryUnitOfWorkFactory::new(MemoryStorage::new()));
+        (DefaultModelImporter::new(units.clone()), units)
+    }
+
+    #[tokio::test]
+    async fn register_parts_and_project_bom() {
+        let (importer, units) = importer();
+        let content = package(&[("1", "Bin"), ("2", "Lid")], &["1", "2", "1"]);
+
+        let report = importer
+            .import_3mf(
+                &content,
+                ModelImportOptions {
+                    project: Some(ProjectName::parse("Drawers").unwrap()),
+                },
+            )
+            .await
+            .unwrap();
+
+        assert!(report
+            .objects
+            .iter()
+            .all(|o| o.status == ObjectStatus::Registered));
+        let uow = units.begin().await.unwrap();
+        let project = uow
+            .projects()
+            .find_by_id(report.project_id.unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        let quantities: Vec<u32> = project.parts().iter().map(|p| p.quantity()).collect();
+        assert_eq!(quantities, vec![2, 1]);
+        assert_eq!(
+            project.parts()[0].part(),
+            report.objects[0].part_id.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn report_objects_without_names() {
+        let (importer, _) = importer();
+        let content = package(&[("1", "Bin"), ("2", " ")], &["1", "2"]);
+
+        let report = importer
+            .import_3mf(&content, ModelImportOptions::default())
+            .await
+            .unwrap();
+
+        assert_eq!(report.objects[0].status, ObjectStatus::Registered);
+        assert_eq!(report.objects[1].status, ObjectStatus::Rejected);
+        assert_eq!(report.objects[1].errors[0].attribute(), "objects[1].name");
+        assert_eq!(report.project_id, None);
+    }
+
+    #[tokio::test]
+    async fn match_parts_already_in_inventory() {
+        let (importer, _) = importer();
+        let content = package(&[("1", "Bin")], &["1"]);
+        let first = importer
+            .import_3mf(&content, ModelImportOptions::default())
+            .await
+            .unwrap();
+
+        let second = importer
+            .import_3mf(&content, ModelImportOptions::default())
+            .await
+            .unwrap();
+
+        assert_eq!(second.objects[0].status, ObjectStatus::Matched);
+        assert_eq!(second.objects[0].part_id, first.objects[0].part_id);
+    }
+
+    #[test]
+    fn reject_too_many_copies_of_part() {
+        let part = uuid::Uuid::now_v7();
+        let object = |count| ImportedObject {
+            object: "1".to_string(),
+            name: "Bin".to_string(),
+            count,
+            part_id: Some(part),
+            status: ObjectStatus::Registered,
+            errors: vec![],
+        };
+
+        let errors = project_bom(&[object(60_000), object(60_000)]).unwrap_err();
+
+        let attributes: Vec<&str> = errors.iter().map(|e| e.attribute()).collect();
+        assert_eq!(
+            attributes,
+            vec!["objects[0].quantity", "objects[1].quantity"]
+        );
+        assert_eq!(errors[0].code(), "bom.quantity.too-large");
+    }
+}
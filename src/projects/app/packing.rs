@@ -0,0 +1,362 @@
+//! Checks how parts fit on printer beds and proposes plates for projects.
+//!
+//! Parts are placed by the X and Y sides of their dimensions, so parts without dimensions cannot
+//! be packed. Project plates hold copies of BOM parts that are left to print.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::inventory::domain::packing::{
+    pack_plates, Bed, Footprint, Grid, PackingItem, Placement, Spacing,
+};
+use crate::inventory::domain::part::PartId;
+use crate::inventory::domain::part_repository::PartRepository;
+use crate::projects::app::service::{ProjectError, ProjectsService};
+use crate::projects::domain::project::ProjectId;
+use crate::shared::validation::error::ValidationError;
+use crate::shared::validation::validator::CollectingValidator;
+
+/// Printer bed and the gap kept between copies.
+#[derive(Debug, Clone, Copy)]
+pub struct PackingOptions {
+    pub bed: Bed,
+    pub spacing: Spacing,
+}
+
+impl PackingOptions {
+    /// Parses the bed written as `WIDTHxDEPTH`. Spacing defaults to [`Spacing::DEFAULT`].
+    pub fn parse(
+        bed: impl Into<String>,
+        spacing: Option<f64>,
+    ) -> Result<Self, Vec<ValidationError>> {
+        let mut validator = CollectingValidator::default();
+        let bed = validator.parse_string::<Bed>(bed);
+        let spacing = spacing
+            .map(|s| validator.parse::<Spacing, f64>(s))
+            .transpose();
+        match (bed, spacing) {
+            (Ok(bed), Ok(spacing)) => Ok(Self {
+                bed,
+                spacing: spacing.unwrap_or_default(),
+            }),
+            _ => Err(validator.into_errors()),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum PackingError {
+    #[error("requested part does not exist")]
+    MissingPart,
+    #[error("requested project does not exist")]
+    MissingProject,
+    #[error("invalid packing request")]
+    ValidationFailed(Vec<ValidationError>),
+    #[error("error packing parts")]
+    GeneralError(#[from] anyhow::Error),
+}
+
+/// How many copies of a part fit on a single plate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartPacking {
+    pub part_id: PartId,
+    pub bed: Bed,
+    pub spacing: Spacing,
+    pub fits: bool,
+    pub copies_per_plate: u64,
+    /// Layout of copies on the plate. Missing when the part does not fit.
+    pub grid: Option<Grid>,
+}
+
+/// Plates proposed for parts left to print in a project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlatePlan {
+    pub project_id: ProjectId,
+    pub bed: Bed,
+    pub spacing: Spacing,
+    pub plates: Vec<Plate>,
+    /// Parts that cannot be placed on any plate.
+    pub unplaced: Vec<UnplacedPart>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Plate {
+    /// Copies of every part on the plate.
+    pub parts: Vec<PlatePart>,
+    pub placements: Vec<Placement>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlatePart {
+    pub part_id: PartId,
+    pub name: String,
+    pub copies: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnplacedPart {
+    pub part_id: PartId,
+    pub name: String,
+    pub copies: u32,
+    pub reason: UnplacedReason,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UnplacedReason {
+    MissingDimensions,
+    TooLarge,
+}
+
+#[async_trait]
+pub trait PackingService: Send + Sync {
+    /// Checks whether the part fits the bed and how many copies fit on one plate.
+    async fn pack_part(
+        &self,
+        part: PartId,
+        options: PackingOptions,
+    ) -> Result<PartPacking, PackingError>;
+
+    /// Proposes plates mixing parts left to print in the project.
+    async fn plan_plates(
+        &self,
+        project: ProjectId,
+        options: PackingOptions,
+    ) -> Result<PlatePlan, PackingError>;
+}
+
+pub struct DefaultPackingService {
+    projects: Arc<dyn ProjectsService>,
+    parts_repo: Arc<dyn PartRepository>,
+}
+
+impl DefaultPackingService {
+    pub fn new(projects: Arc<dyn ProjectsService>, parts_repo: Arc<dyn PartRepository>) -> Self {
+        Self {
+            projects,
+            parts_repo,
+        }
+    }
+
+    async fn footprint(&self, part: PartId) -> Result<Option<Footprint>, PackingError> {
+        let part = self
+            .parts_repo
+            .find_by_id(part)
+            .await?
+            .ok_or(PackingError::MissingPart)?;
+        Ok(part.metadata().dimensions.map(Footprint::from))
+    }
+}
+
+#[async_trait]
+impl PackingService for DefaultPackingService {
+    async fn pack_part(
+        &self,
+        part: PartId,
+        options: PackingOptions,
+    ) -> Result<PartPacking, PackingError> {
+        let footprint = self.footprint(part).await?.ok_or_else(|| {
+            PackingError::ValidationFailed(vec![ValidationError::new(
+                "dimensions",
+                "packing.dimensions.missing",
+                "part dimensions are needed to fit it on the bed",
+            )])
+        })?;
+        let grid = options.bed.grid(footprint, options.spacing);
+        Ok(PartPacking {
+            part_id: part,
+            bed: options.bed,
+            spacing: options.spacing,
+            fits: grid.is_some(),
+            copies_per_plate: grid.map(|g| g.copies()).unwrap_or_default(),
+            grid,
+        })
+    }
+
+    async fn plan_plates(
+        &self,
+        project: ProjectId,
+        options: PackingOptions,
+    ) -> Result<PlatePlan, PackingError> {
+        let view = self.projects.view_project(project).await?;
+        let mut items = vec![];
+        let mut unplaced = vec![];
+        for line in view.parts().iter().filter(|line| line.remaining() > 0) {
+            let footprint = self.footprint(line.id()).await?;
+            let oriented = footprint.map(|f| options.bed.orient(f));
+            match oriented {
+                Some(Some(footprint)) => items.push(PackingItem {
+                    part: line.id(),
+                    footprint,
+                    copies: line.remaining(),
+                }),
+                _ => unplaced.push(UnplacedPart {
+                    part_id: line.id(),
+                    name: line.name().to_string(),
+                    copies: line.remaining(),
+                    reason: match oriented {
+                        None => UnplacedReason::MissingDimensions,
+                        Some(_) => UnplacedReason::TooLarge,
+                    },
+                }),
+            }
+        }
+
+        let plates = pack_plates(options.bed, options.spacing, &items)
+            .into_iter()
+            .map(|placements| {
+                let mut parts: Vec<PlatePart> = vec![];
+                for placement in &placements {
+                    match parts.iter_mut().find(|p| p.part_id == placement.part) {
+                        Some(part) => part.copies += 1,
+                        None => parts.push(PlatePart {
+                            part_id: placement.part,
+                            name: view
+                                .parts()
+                                .iter()
+                                .find(|line| line.id() == placement.part)
+                                .map(|line| line.name().to_string())
+                                .unwrap_or_default(),
+                            copies: 1,
+                        }),
+                    }
+                }
+                Plate { parts, placements }
+            })
+            .collect();
+
+        Ok(PlatePlan {
+            project_id: project,
+            bed: options.bed,
+            spacing: options.spacing,
+            plates,
+            unplaced,
+        })
+    }
+}
+
+impl From<ProjectError> for PackingError {
+    fn from(value: ProjectError) -> Self {
+        match value {
+            ProjectError::MissingProject => PackingError::MissingProject,
+            ProjectError::ValidationFailed(errors) => PackingError::ValidationFailed(errors),
+            ProjectError::GeneralError(e) => PackingError::GeneralError(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::infra::services::Services;
+    use crate::inventory::domain::metadata::{Dimensions, PartMetadata};
+    use crate::inventory::domain::name::Name;
+    use crate::projects::domain::name::Name as ProjectName;
+    use crate::projects::domain::project::ProjectPart;
+
+    async fn part(services: &Services, name: &str, size: Option<(f64, f64)>) -> PartId {
+        let id = services
+            .inventory
+            .register_part(Name::parse(name).unwrap())
+            .await
+            .unwrap();
+        if let Some((x, y)) = size {
+            let metadata = PartMetadata {
+                dimensions: Some(Dimensions::parse(x, y, 10.0).unwrap()),
+                ..PartMetadata::default()
+            };
+            services
+                .inventory
+                .describe_part(id, metadata)
+                .await
+                .unwrap();
+        }
+        id
+    }
+
+    #[tokio::test]
+    async fn count_copies_of_part_on_bed() {
+        let services = Services::in_memory();
+        let bin = part(&services, "Bin", Some((40.0, 60.0))).await;
+        let options = PackingOptions::parse("250x210", Some(5.0)).unwrap();
+
+        let packing = services.packing.pack_part(bin, options).await.unwrap();
+
+        assert!(packing.fits);
+        assert_eq!(packing.copies_per_plate, 15);
+    }
+
+    #[tokio::test]
+    async fn require_part_dimensions() {
+        let services = Services::in_memory();
+        let bin = part(&services, "Bin", None).await;
+        let options = PackingOptions::parse("250x210", None).unwrap();
+
+        let result = services.packing.pack_part(bin, options).await;
+
+        assert!(matches!(result, Err(PackingError::ValidationFailed(_))));
+    }
+
+    #[tokio::test]
+    async fn plan_plates_for_parts_left_to_print() {
+        let services = Services::in_memory();
+        let bin = part(&services, "Bin", Some((100.0, 100.0))).await;
+        let lid = part(&services, "Lid", Some((50.0, 50.0))).await;
+        let shelf = part(&services, "Shelf", Some((300.0, 100.0))).await;
+        let handle = part(&services, "Handle", None).await;
+        let project = services
+            .projects
+            .register_project(ProjectName::parse("Drawers").unwrap())
+            .await
+            .unwrap();
+        services
+            .projects
+            .set_project_bom(
+                project,
+                vec![
+                    ProjectPart::new(bin, 5),
+                    ProjectPart::new(lid, 4),
+                    ProjectPart::new(shelf, 1),
+                    ProjectPart::new(handle, 2),
+                ],
+            )
+            .await
+            .unwrap();
+        services
+            .projects
+            .report_progress(project, bin, 1)
+            .await
+            .unwrap();
+        let options = PackingOptions::parse("210x210", Some(0.0)).unwrap();
+
+        let plan = services
+            .packing
+            .plan_plates(project, options)
+            .await
+            .unwrap();
+
+        let copies: u32 = plan.plates.iter().map(|p| p.placements.len() as u32).sum();
+        assert_eq!(copies, 8, "4 bins left to print and 4 lids");
+        assert_eq!(plan.plates.len(), 2);
+        let reasons: Vec<UnplacedReason> = plan.unplaced.iter().map(|u| u.reason).collect();
+        assert_eq!(
+            reasons,
+            vec![UnplacedReason::TooLarge, UnplacedReason::MissingDimensions]
+        );
+    }
+
+    #[test]
+    fn reject_invalid_options() {
+        let errors = PackingOptions::parse("wide", Some(-1.0)).unwrap_err();
+
+        let codes: Vec<&str> = errors.iter().map(|e| e.code()).collect();
+        assert_eq!(
+            codes,
+            vec!["packing.bed.invalid-format", "packing.spacing.out-of-range"]
+        );
+    }
+}
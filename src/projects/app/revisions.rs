@@ -0,0 +1,264 @@
+//! Keeps revisions of project BOMs and restores previous ones.
+//!
+//! Revisions are recorded from `BomDefined` events when a unit of work commits, so every BOM
+//! change is kept no matter which service made it.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use tracing::info;
+
+use crate::app::actor::Actor;
+use crate::app::events::DomainEvent;
+use crate::app::unit_of_work::UnitOfWorkFactory;
+use crate::projects::app::service::latest_revisions;
+use crate::projects::domain::event::ProjectEvent;
+use crate::projects::domain::project::ProjectId;
+use crate::projects::domain::revision::{BomDiff, BomLine, BomRevision, RevisionNumber};
+
+#[derive(Debug, Error)]
+pub enum BomRevisionError {
+    #[error("requested project does not exist")]
+    MissingProject,
+    #[error("requested BOM revision does not exist")]
+    MissingRevision,
+    #[error("error processing BOM revisions")]
+    GeneralError(#[from] anyhow::Error),
+}
+
+#[async_trait]
+pub trait BomRevisionService: Send + Sync {
+    /// Lists BOM revisions of the project, the most recent first.
+    async fn list_revisions(
+        &self,
+        project: ProjectId,
+    ) -> Result<Vec<BomRevision>, BomRevisionError>;
+
+    /// Compares BOMs of two revisions of the project.
+    async fn diff_revisions(
+        &self,
+        project: ProjectId,
+        from: RevisionNumber,
+        to: RevisionNumber,
+    ) -> Result<BomDiff, BomRevisionError>;
+
+    /// Replaces the project BOM with the BOM of the revision.
+    /// Progress of parts remaining in the BOM is preserved. The restored BOM becomes a new
+    /// revision, so the restore can be undone as well.
+    async fn restore_revision(
+        &self,
+        project: ProjectId,
+        revision: RevisionNumber,
+    ) -> Result<(), BomRevisionError>;
+}
+
+/// Describes BOM changes among committed events as revisions made by the actor.
+/// Events leaving parts and quantities unchanged do not produce revisions.
+pub fn revisions(events: &[DomainEvent], actor: &Actor, at: DateTime<Utc>) -> Vec<BomRevision> {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            DomainEvent::Project(ProjectEvent::BomDefined {
+                project,
+                previous,
+                parts,
+            }) => {
+                let parts = BomLine::of(parts);
+                (!BomLine::same_bom(&BomLine::of(previous), &parts)).then(|| BomRevision {
+                    project: *project,
+                    revision: 0,
+                    created_at: at,
+                    actor: actor.to_string(),
+                    parts,
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+pub struct DefaultBomRevisionService {
+    units: Arc<dyn UnitOfWorkFactory>,
+}
+
+impl DefaultBomRevisionService {
+    pub fn new(units: Arc<dyn UnitOfWorkFactory>) -> Self {
+        Self { units }
+    }
+}
+
+#[async_trait]
+impl BomRevisionService for DefaultBomRevisionService {
+    async fn list_revisions(
+        &self,
+        project: ProjectId,
+    ) -> Result<Vec<BomRevision>, BomRevisionError> {
+        let uow = self.units.begin().await?;
+        if uow.projects().find_by_id(project).await?.is_none() {
+            return Err(BomRevisionError::MissingProject);
+        }
+        Ok(uow.bom_revisions().list(project).await?)
+    }
+
+    async fn diff_revisions(
+        &self,
+        project: ProjectId,
+        from: RevisionNumber,
+        to: RevisionNumber,
+    ) -> Result<BomDiff, BomRevisionError> {
+        let uow = self.units.begin().await?;
+        if uow.projects().find_by_id(project).await?.is_none() {
+            return Err(BomRevisionError::MissingProject);
+        }
+        let revisions = uow.bom_revisions();
+        let from = revisions
+            .find(project, from)
+            .await?
+            .ok_or(BomRevisionError::MissingRevision)?;
+        let to = revisions
+            .find(project, to)
+            .await?
+            .ok_or(BomRevisionError::MissingRevision)?;
+        Ok(from.diff(&to))
+    }
+
+    async fn restore_revision(
+        &self,
+        project_id: ProjectId,
+        revision: RevisionNumber,
+    ) -> Result<(), BomRevisionError> {
+        let uow = self.units.begin().await?;
+        let mut project = uow
+            .projects()
+            .find_by_id(project_id)
+            .await?
+            .ok_or(BomRevisionError::MissingProject)?;
+        let restored = uow
+            .bom_revisions()
+            .find(project_id, revision)
+            .await?
+            .ok_or(BomRevisionError::MissingRevision)?;
+        project
+            .define_parts(restored.project_parts())
+            .map_err(|e| anyhow::anyhow!("cannot restore BOM revision: {}", e.message()))?;
+        let latest = latest_revisions(uow.parts().as_ref(), &project).await?;
+        project
+            .pin_revisions(&latest)
+            .map_err(|e| anyhow::anyhow!("cannot restore BOM revision: {}", e.message()))?;
+        uow.projects().update(project).await?;
+        uow.commit().await?;
+        info!(
+            "BOM revision {} restored for project {}",
+            revision, project_id
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::infra::services::Services;
+    use crate::inventory::domain::name::Name as PartName;
+    use crate::projects::domain::name::Name;
+    use crate::projects::domain::project::ProjectPart;
+
+    #[tokio::test]
+    async fn restore_previous_bom() {
+        let services = Services::in_memory();
+        let (bin, drawer) = (
+            services
+                .inventory
+                .register_part(PartName::parse("Bin").unwrap())
+                .await
+                .unwrap(),
+            services
+                .inventory
+                .register_part(PartName::parse("Drawer").unwrap())
+                .await
+                .unwrap(),
+        );
+        let project = services
+            .projects
+            .register_project(Name::parse("Desk organizer").unwrap())
+            .await
+            .unwrap();
+        services
+            .projects
+            .set_project_bom(
+                project,
+                vec![ProjectPart::new(bin, 5), ProjectPart::new(drawer, 9)],
+            )
+            .await
+            .unwrap();
+        services
+            .projects
+            .report_progress(project, drawer, 2)
+            .await
+            .unwrap();
+        services
+            .projects
+            .set_project_bom(project, vec![ProjectPart::new(drawer, 1)])
+            .await
+            .unwrap();
+
+        services
+            .revisions
+            .restore_revision(project, 1)
+            .await
+            .unwrap();
+
+        let view = services.projects.view_project(project).await.unwrap();
+        assert_eq!(view.bom_size(), 2);
+        let revisions = services.revisions.list_revisions(project).await.unwrap();
+        let numbers: Vec<RevisionNumber> = revisions.iter().map(|r| r.revision).collect();
+        assert_eq!(numbers, vec![3, 2, 1]);
+        assert_eq!(revisions[0].parts, revisions[2].parts);
+        let diff = services
+            .revisions
+            .diff_revisions(project, 2, 3)
+            .await
+            .unwrap();
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.changed[0].to, 9);
+    }
+
+    #[tokio::test]
+    async fn skip_unchanged_bom() {
+        let services = Services::in_memory();
+        let project = services
+            .projects
+            .register_project(Name::parse("Desk organizer").unwrap())
+            .await
+            .unwrap();
+
+        services
+            .projects
+            .set_project_bom(project, vec![])
+            .await
+            .unwrap();
+
+        let revisions = services.revisions.list_revisions(project).await.unwrap();
+        assert!(revisions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn report_missing_revision() {
+        let services = Services::in_memory();
+        let project = services
+            .projects
+            .register_project(Name::parse("Desk organizer").unwrap())
+            .await
+            .unwrap();
+
+        let restored = services.revisions.restore_revision(project, 1).await;
+        assert!(matches!(restored, Err(BomRevisionError::MissingRevision)));
+        let listed = services.revisions.list_revisions(Uuid::now_v7()).await;
+        assert!(matches!(listed, Err(BomRevisionError::MissingProject)));
+    }
+}
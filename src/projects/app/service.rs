@@ -1,15 +1,25 @@
 //! Defines main application service for Projects.
 
 use async_trait::async_trait;
+use std::collections::HashMap;
 use std::sync::Arc;
 use thiserror::Error;
 use tracing::{error, info};
 
+use crate::app::unit_of_work::UnitOfWorkFactory;
+use crate::inventory::domain::part::PartId;
+use crate::inventory::domain::part_repository::PartRepository;
+use crate::inventory::domain::revision::PartRevisionNumber;
+use crate::projects::domain::multiplier::Multiplier;
 use crate::projects::domain::name::Name;
 use crate::projects::domain::project::{Project, ProjectId, ProjectPart};
-use crate::projects::domain::repository::ProjectRepository;
-use crate::projects::view::project::ProjectView;
+use crate::projects::view::demand::{DemandGrouping, PartDemand};
+use crate::projects::view::license::LicenseReport;
+use crate::projects::view::outdated::OutdatedProject;
+use crate::projects::view::project::{ProjectListItem, ProjectView};
 use crate::projects::view::repository::ProjectViewRepository;
+use crate::settings::domain::repository::SettingsRepository;
+use crate::shared::validation::error::ValidationError;
 
 /// Typical errors happening during project processing.
 #[derive(Debug, Error)]
@@ -17,6 +27,9 @@ pub enum ProjectError {
     /// Requested project cannot be found or it was deleted.
     #[error("requested project does not exist")]
     MissingProject,
+    /// Requested change violates project rules.
+    #[error("invalid project change")]
+    ValidationFailed(Vec<ValidationError>),
     /// Wrapper error for errors reported by downstream components.
     #[error("error procesing project")]
     GeneralError(#[from] anyhow::Error),
@@ -30,36 +43,96 @@ pub trait ProjectsService: Send + Sync {
     /// View the project with identifier id.
     async fn view_project(&self, id: ProjectId) -> Result<ProjectView, ProjectError>;
 
+    /// List all registered projects.
+    async fn list_projects(&self) -> Result<Vec<ProjectListItem>, ProjectError>;
+
     async fn set_project_bom(
         &self,
         project: ProjectId,
         parts: Vec<ProjectPart>,
     ) -> Result<(), ProjectError>;
+
+    /// Records the number of parts printed since the last report.
+    async fn report_progress(
+        &self,
+        project: ProjectId,
+        part: PartId,
+        printed: u32,
+    ) -> Result<(), ProjectError>;
+
+    /// Copies the project with its BOM into a new project.
+    /// The copy is named after the project with the ` (copy)` suffix unless the name is given.
+    async fn duplicate_project(
+        &self,
+        project: ProjectId,
+        name: Option<Name>,
+    ) -> Result<ProjectId, ProjectError>;
+
+    /// Marks the project as a template or turns it back into a regular project.
+    async fn mark_template(&self, project: ProjectId, template: bool) -> Result<(), ProjectError>;
+
+    /// Creates a new project from the template with BOM quantities multiplied by `multiplier`.
+    /// The project is named after the template with the `×multiplier` suffix unless the name is
+    /// given.
+    async fn instantiate_template(
+        &self,
+        template: ProjectId,
+        multiplier: Multiplier,
+        name: Option<Name>,
+    ) -> Result<ProjectId, ProjectError>;
+
+    /// Lists projects with BOM lines pinned to older revisions of their parts.
+    /// When `part` is given, only projects using an older revision of the part are listed.
+    async fn list_outdated(
+        &self,
+        part: Option<PartId>,
+    ) -> Result<Vec<OutdatedProject>, ProjectError>;
+
+    /// Pins the latest revisions of parts in the project BOM.
+    /// When `part` is given, only the line of the part is upgraded.
+    async fn upgrade_parts(
+        &self,
+        project: ProjectId,
+        part: Option<PartId>,
+    ) -> Result<(), ProjectError>;
+
+    /// Adds up parts needed by all regular projects, per variant or per parent part.
+    async fn part_demand(&self, grouping: DemandGrouping) -> Result<Vec<PartDemand>, ProjectError>;
+
+    /// Lists licenses of parts in the project BOM and flags parts forbidding commercial use.
+    async fn license_report(&self, project: ProjectId) -> Result<LicenseReport, ProjectError>;
 }
 
-pub struct DefaultProjectService<R: ProjectRepository, V: ProjectViewRepository> {
-    projects_repo: Arc<R>,
+/// Changes projects within units of work and reads them from the view repository.
+/// Project views include costs once prices are configured in settings.
+pub struct DefaultProjectService<V: ProjectViewRepository> {
+    units: Arc<dyn UnitOfWorkFactory>,
     view_repo: Arc<V>,
+    settings_repo: Arc<dyn SettingsRepository>,
 }
 
-impl<R: ProjectRepository, V: ProjectViewRepository> DefaultProjectService<R, V> {
-    pub fn new(repo: Arc<R>, view_repo: Arc<V>) -> Self {
+impl<V: ProjectViewRepository> DefaultProjectService<V> {
+    pub fn new(
+        units: Arc<dyn UnitOfWorkFactory>,
+        view_repo: Arc<V>,
+        settings_repo: Arc<dyn SettingsRepository>,
+    ) -> Self {
         Self {
-            projects_repo: repo,
+            units,
             view_repo,
+            settings_repo,
         }
     }
 }
 
 #[async_trait]
-impl<R: ProjectRepository, V: ProjectViewRepository> ProjectsService
-    for DefaultProjectService<R, V>
-{
+impl<V: ProjectViewRepository> ProjectsService for DefaultProjectService<V> {
     async fn register_project(&self, name: Name) -> Result<ProjectId, ProjectError> {
         // TODO: check if it's possible to avoid cloning here.
         let new_project = Project::new(name.clone());
-        let repo = &self.projects_repo;
-        let project_id = repo.create(new_project).await?;
+        let uow = self.units.begin().await?;
+        let project_id = uow.projects().create(new_project).await?;
+        uow.commit().await?;
         info!("created project {} with ID {}", name, &project_id);
         Ok(project_id)
     }
@@ -73,19 +146,28 @@ impl<R: ProjectRepository, V: ProjectViewRepository> ProjectsService
         if project.is_err() {
             error!("project retrieval failed: {:?}", project);
         }
-        match project? {
-            Some(p) => Ok(p),
-            None => Err(ProjectError::MissingProject),
+        let project = project?.ok_or(ProjectError::MissingProject)?;
+        match self.settings_repo.cost_settings().await? {
+            Some(settings) => Ok(project.with_costs(&settings)),
+            None => Ok(project),
         }
     }
 
+    async fn list_projects(&self) -> Result<Vec<ProjectListItem>, ProjectError> {
+        self.view_repo
+            .list_projects()
+            .await
+            .map_err(ProjectError::GeneralError)
+    }
+
     async fn set_project_bom(
         &self,
         project_id: ProjectId,
         parts: Vec<ProjectPart>,
     ) -> Result<(), ProjectError> {
-        let project = self
-            .projects_repo
+        let uow = self.units.begin().await?;
+        let project = uow
+            .projects()
             .find_by_id(project_id)
             .await
             .map_err(ProjectError::GeneralError)?;
@@ -94,11 +176,328 @@ impl<R: ProjectRepository, V: ProjectViewRepository> ProjectsService
             None => return Err(ProjectError::MissingProject),
         };
 
-        project.define_parts(parts);
+        project
+            .define_parts(parts)
+            .map_err(|e| ProjectError::ValidationFailed(vec![e]))?;
+        let latest = latest_revisions(uow.parts().as_ref(), &project).await?;
+        project
+            .pin_revisions(&latest)
+            .map_err(|e| ProjectError::ValidationFailed(vec![e]))?;
 
-        self.projects_repo
+        uow.projects()
             .update(project)
             .await
-            .map_err(ProjectError::GeneralError)
+            .map_err(ProjectError::GeneralError)?;
+        uow.commit().await.map_err(ProjectError::GeneralError)
+    }
+
+    async fn report_progress(
+        &self,
+        project_id: ProjectId,
+        part: PartId,
+        printed: u32,
+    ) -> Result<(), ProjectError> {
+        let uow = self.units.begin().await?;
+        let project = uow
+            .projects()
+            .find_by_id(project_id)
+            .await
+            .map_err(ProjectError::GeneralError)?;
+        let mut project = match project {
+            Some(p) => p,
+            None => return Err(ProjectError::MissingProject),
+        };
+
+        project
+            .report_progress(part, printed)
+            .map_err(|e| ProjectError::ValidationFailed(vec![e]))?;
+
+        uow.projects()
+            .update(project)
+            .await
+            .map_err(ProjectError::GeneralError)?;
+        uow.commit().await?;
+        info!(
+            "{} parts {} printed for project {}",
+            printed, part, project_id
+        );
+        Ok(())
+    }
+
+    async fn duplicate_project(
+        &self,
+        project_id: ProjectId,
+        name: Option<Name>,
+    ) -> Result<ProjectId, ProjectError> {
+        let uow = self.units.begin().await?;
+        let project = uow
+            .projects()
+            .find_by_id(project_id)
+            .await?
+            .ok_or(ProjectError::MissingProject)?;
+        let name = name.unwrap_or_else(|| project.name().with_suffix(" (copy)"));
+        let copy = project.duplicate(name);
+        let copy_id = uow.projects().create(copy).await?;
+        uow.commit().await?;
+        info!("project {} duplicated as {}", project_id, copy_id);
+        Ok(copy_id)
+    }
+
+    async fn mark_template(
+        &self,
+        project_id: ProjectId,
+        template: bool,
+    ) -> Result<(), ProjectError> {
+        let uow = self.units.begin().await?;
+        let mut project = uow
+            .projects()
+            .find_by_id(project_id)
+            .await?
+            .ok_or(ProjectError::MissingProject)?;
+        project.mark_as_template(template);
+        uow.projects().update(project).await?;
+        uow.commit().await?;
+        Ok(())
+    }
+
+    async fn instantiate_template(
+        &self,
+        template_id: ProjectId,
+        multiplier: Multiplier,
+        name: Option<Name>,
+    ) -> Result<ProjectId, ProjectError> {
+        let uow = self.units.begin().await?;
+        let template = uow
+            .projects()
+            .find_by_id(template_id)
+            .await?
+            .ok_or(ProjectError::MissingProject)?;
+        let name =
+            name.unwrap_or_else(|| template.name().with_suffix(&format!(" ×{}", multiplier)));
+        let mut project = template
+            .instantiate(name, multiplier)
+            .map_err(|e| ProjectError::ValidationFailed(vec![e]))?;
+        let latest = latest_revisions(uow.parts().as_ref(), &project).await?;
+        project
+            .pin_revisions(&latest)
+            .map_err(|e| ProjectError::ValidationFailed(vec![e]))?;
+        let project_id = uow.projects().create(project).await?;
+        uow.commit().await?;
+        info!(
+            "project {} created from template {} ×{}",
+            project_id, template_id, multiplier
+        );
+        Ok(project_id)
+    }
+
+    async fn list_outdated(
+        &self,
+        part: Option<PartId>,
+    ) -> Result<Vec<OutdatedProject>, ProjectError> {
+        let projects = self.view_repo.list_outdated().await?;
+        Ok(match part {
+            Some(part) => projects
+                .into_iter()
+                .filter_map(|project| project.of_part(part))
+                .collect(),
+            None => projects,
+        })
+    }
+
+    async fn upgrade_parts(
+        &self,
+        project_id: ProjectId,
+        part: Option<PartId>,
+    ) -> Result<(), ProjectError> {
+        let uow = self.units.begin().await?;
+        let mut project = uow
+            .projects()
+            .find_by_id(project_id)
+            .await?
+            .ok_or(ProjectError::MissingProject)?;
+        let lines: Vec<PartId> = project.parts().iter().map(|line| line.part()).collect();
+        let upgraded = match part {
+            Some(part) if !lines.contains(&part) => {
+                return Err(ProjectError::ValidationFailed(vec![ValidationError::new(
+                    "part",
+                    "upgrade.part.not-in-bom",
+                    "the part is not in the project BOM",
+                )]))
+            }
+            Some(part) => vec![part],
+            None => lines,
+        };
+        for id in upgraded {
+            if let Some(latest) = uow.parts().find_by_id(id).await? {
+                project.upgrade_part(id, latest.revision());
+            }
+        }
+        uow.projects().update(project).await?;
+        uow.commit().await?;
+        info!("parts of project {} upgraded", project_id);
+        Ok(())
+    }
+
+    async fn part_demand(&self, grouping: DemandGrouping) -> Result<Vec<PartDemand>, ProjectError> {
+        Ok(self.view_repo.part_demand(grouping).await?)
+    }
+
+    async fn license_report(&self, project_id: ProjectId) -> Result<LicenseReport, ProjectError> {
+        self.view_repo
+            .get_licenses_by_id(project_id)
+            .await?
+            .ok_or(ProjectError::MissingProject)
+    }
+}
+
+/// Looks up the latest revisions of parts of BOM lines not pinned yet.
+/// Parts missing in the inventory are left out, so pinning their lines fails.
+pub(crate) async fn latest_revisions(
+    parts: &dyn PartRepository,
+    project: &Project,
+) -> anyhow::Result<HashMap<PartId, PartRevisionNumber>> {
+    let mut revisions = HashMap::new();
+    for id in project.unpinned_parts() {
+        if let Some(part) = parts.find_by_id(id).await? {
+            revisions.insert(id, part.revision());
+        }
+    }
+    Ok(revisions)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::infra::memory::project_view::InMemoryProjectViewRepository;
+    use crate::infra::memory::settings::InMemorySettingsRepository;
+    use crate::infra::memory::unit_of_work::InMemoryUnitOfWorkFactory;
+    use crate::infra::memory::MemoryStorage;
+    use crate::infra::services::Services;
+    use crate::inventory::domain::name::Name as PartName;
+
+    fn service() -> impl ProjectsService {
+        let storage = MemoryStorage::new();
+        DefaultProjectService::new(
+            Arc::new(InMemoryUnitOfWorkFactory::new(Arc::clone(&storage))),
+            Arc::new(InMemoryProjectViewRepository::new(Arc::clone(&storage))),
+            Arc::new(InMemorySettingsRepository::new(storage)),
+        )
+    }
+
+    #[tokio::test]
+    async fn register_and_view_project() {
+        let service = service();
+
+        let id = service
+            .register_project(Name::parse("Desk organizer").unwrap())
+            .await
+            .unwrap();
+
+        let project = service.view_project(id).await.unwrap();
+        assert_eq!(project.name(), "Desk organizer");
+        assert_eq!(project.bom_size(), 0);
+    }
+
+    #[tokio::test]
+    async fn report_missing_project() {
+        let service = service();
+
+        let view = service.view_project(Uuid::now_v7()).await;
+        assert!(matches!(view, Err(ProjectError::MissingProject)));
+
+        let bom = service.set_project_bom(Uuid::now_v7(), vec![]).await;
+        assert!(matches!(bom, Err(ProjectError::MissingProject)));
+    }
+
+    #[tokio::test]
+    async fn keep_progress_when_bom_changes() {
+        let services = Services::in_memory();
+        let part = services
+            .inventory
+            .register_part(PartName::parse("Drawer").unwrap())
+            .await
+            .unwrap();
+        let project = services
+            .projects
+            .register_project(Name::parse("Desk organizer").unwrap())
+            .await
+            .unwrap();
+        services
+            .projects
+            .set_project_bom(project, vec![ProjectPart::new(part, 8)])
+            .await
+            .unwrap();
+
+        services
+            .projects
+            .report_progress(project, part, 3)
+            .await
+            .unwrap();
+        services
+            .projects
+            .set_project_bom(project, vec![ProjectPart::new(part, 10)])
+            .await
+            .unwrap();
+
+        let view = services.projects.view_project(project).await.unwrap();
+        assert_eq!(view.parts()[0].quantity(), 10);
+        assert_eq!(view.parts()[0].printed(), 3);
+    }
+
+    #[tokio::test]
+    async fn upgrade_outdated_parts() {
+        let services = Services::in_memory();
+        let hinge = services
+            .inventory
+            .register_part(PartName::parse("Hinge").unwrap())
+            .await
+            .unwrap();
+        let project = services
+            .projects
+            .register_project(Name::parse("Cabinet").unwrap())
+            .await
+            .unwrap();
+        services
+            .projects
+            .set_project_bom(project, vec![ProjectPart::new(hinge, 4)])
+            .await
+            .unwrap();
+        services.inventory.revise_part(hinge).await.unwrap();
+
+        let outdated = services.projects.list_outdated(Some(hinge)).await.unwrap();
+        assert_eq!(outdated.len(), 1);
+        assert_eq!(outdated[0].parts[0].revision, 1);
+        assert_eq!(outdated[0].parts[0].latest_revision, 2);
+
+        services
+            .projects
+            .upgrade_parts(project, None)
+            .await
+            .unwrap();
+
+        let view = services.projects.view_project(project).await.unwrap();
+        assert_eq!(view.parts()[0].revision(), 2);
+        assert!(services
+            .projects
+            .list_outdated(None)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn reject_progress_of_part_outside_bom() {
+        let service = service();
+        let project = service
+            .register_project(Name::parse("Desk organizer").unwrap())
+            .await
+            .unwrap();
+
+        let result = service.report_progress(project, Uuid::now_v7(), 1).await;
+
+        assert!(matches!(result, Err(ProjectError::ValidationFailed(errors)) if errors.len() == 1));
     }
 }
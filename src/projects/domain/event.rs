@@ -0,0 +1,39 @@
+//! Events recorded by the project aggregate.
+
+use crate::inventory::domain::part::PartId;
+use crate::inventory::domain::revision::PartRevisionNumber;
+
+use super::project::{ProjectId, ProjectPart};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProjectEvent {
+    ProjectRegistered {
+        project: ProjectId,
+        name: String,
+    },
+    /// The project BOM was replaced. Carries the complete BOM before and after the change.
+    BomDefined {
+        project: ProjectId,
+        previous: Vec<ProjectPart>,
+        parts: Vec<ProjectPart>,
+    },
+    /// The project was marked as a template or turned back into a regular project.
+    TemplateMarked {
+        project: ProjectId,
+        template: bool,
+    },
+    /// Parts were printed. `total` is the number of printed parts after the report.
+    ProgressReported {
+        project: ProjectId,
+        part: PartId,
+        printed: u32,
+        total: u32,
+    },
+    /// The BOM line of the part was pinned to a newer revision of the part.
+    PartUpgraded {
+        project: ProjectId,
+        part: PartId,
+        from: PartRevisionNumber,
+        to: PartRevisionNumber,
+    },
+}
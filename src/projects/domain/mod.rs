@@ -1,3 +1,7 @@
+pub mod event;
+pub mod multiplier;
 pub mod name;
 pub mod project;
+pub mod quantity;
 pub mod repository;
+pub mod revision;
@@ -0,0 +1,67 @@
+use std::fmt::Display;
+
+use crate::shared::validation::error::ValidationError;
+
+/// Factor applied to BOM quantities of a template when a project is created from it.
+/// Multiplier must be a positive number not exceeding 1000.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Multiplier(u32);
+
+const MAX_MULTIPLIER: u32 = 1000;
+
+impl Multiplier {
+    pub fn parse(value: u32) -> Result<Self, ValidationError> {
+        if value == 0 || value > MAX_MULTIPLIER {
+            return Err(ValidationError::new(
+                "multiplier",
+                "template.multiplier.out-of-range",
+                "multiplier must be between 1 and 1000",
+            ));
+        }
+        Ok(Self(value))
+    }
+
+    pub fn value(&self) -> u32 {
+        self.0
+    }
+}
+
+impl TryFrom<u32> for Multiplier {
+    type Error = ValidationError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl Default for Multiplier {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+impl Display for Multiplier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn accept_multiplier_in_range() {
+        assert_eq!(Multiplier::try_from(12).unwrap().value(), 12);
+        assert!(Multiplier::try_from(MAX_MULTIPLIER).is_ok());
+    }
+
+    #[test]
+    fn reject_multiplier_out_of_range() {
+        for value in [0, MAX_MULTIPLIER + 1] {
+            let err = Multiplier::try_from(value).unwrap_err();
+            assert_eq!(err.code(), "template.multiplier.out-of-range");
+        }
+    }
+}
@@ -7,6 +7,8 @@ use crate::shared::validation::error::ValidationError;
 #[derive(Debug, Clone)]
 pub struct Name(String);
 
+const MAX_LENGTH: usize = 200;
+
 impl Name {
     pub fn parse(s: impl Into<String>) -> Result<Self, ValidationError> {
         let value = s.into();
@@ -19,7 +21,7 @@ impl Name {
                 "project name is too short",
             ));
         }
-        if trimmed.len() > 200 {
+        if trimmed.len() > MAX_LENGTH {
             return Err(ValidationError::new(
                 attr,
                 "project.name.too-long",
@@ -28,6 +30,17 @@ impl Name {
         }
         Ok(Self(trimmed.to_string()))
     }
+
+    /// Appends the suffix, e.g., ` (copy)`, shortening the name to stay within the length limit.
+    pub fn with_suffix(&self, suffix: &str) -> Self {
+        let mut base = self.0.as_str();
+        while base.len() + suffix.len() > MAX_LENGTH {
+            let mut chars = base.chars();
+            chars.next_back();
+            base = chars.as_str();
+        }
+        Self::parse(format!("{}{}", base, suffix)).unwrap_or_else(|_| self.clone())
+    }
 }
 
 impl TryFrom<String> for Name {
@@ -94,6 +107,20 @@ mod tests {
         assert!(err.is_err());
     }
 
+    #[test]
+    fn shorten_name_with_suffix() {
+        let name = Name::parse("é".repeat(100)).unwrap();
+
+        let copy = name.with_suffix(" (copy)");
+
+        assert!(copy.as_ref().len() <= MAX_LENGTH);
+        assert!(copy.as_ref().ends_with("é (copy)"));
+        assert_eq!(
+            Name::parse("Bins").unwrap().with_suffix(" (copy)").as_ref(),
+            "Bins (copy)"
+        );
+    }
+
     #[test]
     fn reject_long_name() {
         let err = Name::try_from("a".repeat(201));
@@ -1,8 +1,14 @@
+use std::collections::HashMap;
 use std::vec;
 
 use crate::inventory::domain::part::PartId;
+use crate::inventory::domain::revision::PartRevisionNumber;
+use crate::shared::validation::error::ValidationError;
 
+use super::event::ProjectEvent;
+use super::multiplier::Multiplier;
 use super::name::Name;
+use super::quantity::Quantity;
 use chrono::{Local, NaiveDateTime};
 use uuid::Uuid;
 
@@ -15,19 +21,33 @@ pub struct Project {
     name: Name,
     parts: Vec<ProjectPart>,
     created_at: NaiveDateTime,
+    /// Templates are blueprints of projects created with [`Project::instantiate`].
+    template: bool,
+    events: Vec<ProjectEvent>,
 }
 
-#[derive(Debug, Clone)]
+/// BOM line of the project.
+/// Keeps the required quantity of the part and the number of parts printed so far.
+#[derive(Debug, Clone, PartialEq)]
 pub struct ProjectPart {
     part: PartId,
     quantity: u32,
+    printed: u32,
+    /// Pinned revision of the part. Lines added without a revision must be pinned with
+    /// [`Project::pin_revisions`] before the project is stored.
+    revision: Option<PartRevisionNumber>,
 }
 
 impl Project {
     pub fn new(name: Name) -> Self {
         let id = Uuid::now_v7();
         let created_at = Local::now().naive_local();
-        Project::full(id, name, vec![], created_at)
+        let mut project = Project::full(id, name, vec![], created_at);
+        project.events.push(ProjectEvent::ProjectRegistered {
+            project: id,
+            name: project.name.to_string(),
+        });
+        project
     }
 
     pub fn full(
@@ -41,8 +61,15 @@ impl Project {
             name,
             parts,
             created_at,
+            template: false,
+            events: vec![],
         }
     }
+
+    /// Restores the template flag of a stored project.
+    pub fn with_template(self, template: bool) -> Self {
+        Self { template, ..self }
+    }
 }
 
 impl Project {
@@ -61,18 +88,224 @@ impl Project {
     pub fn created_at(&self) -> NaiveDateTime {
         self.created_at
     }
+
+    pub fn is_template(&self) -> bool {
+        self.template
+    }
+
+    /// Removes events recorded since the project was loaded or created.
+    pub fn take_events(&mut self) -> Vec<ProjectEvent> {
+        std::mem::take(&mut self.events)
+    }
 }
 
 impl Project {
     /// Specify BOM for the project.
-    pub fn define_parts(&mut self, parts: Vec<ProjectPart>) {
-        self.parts = parts;
+    /// Progress and pinned revisions of parts remaining in the BOM are preserved.
+    /// Every part may be listed only once.
+    pub fn define_parts(&mut self, parts: Vec<ProjectPart>) -> Result<(), ValidationError> {
+        for (index, line) in parts.iter().enumerate() {
+            if parts[..index].iter().any(|p| p.part == line.part) {
+                return Err(ValidationError::new(
+                    format!("parts[{}].part", index),
+                    "bom.part.duplicate",
+                    format!("part {} is listed more than once", line.part),
+                ));
+            }
+        }
+        self.replace_parts(parts);
+        Ok(())
+    }
+
+    /// Replaces BOM lines, keeping progress and pinned revisions of remaining parts.
+    fn replace_parts(&mut self, parts: Vec<ProjectPart>) {
+        let parts: Vec<ProjectPart> = parts
+            .into_iter()
+            .map(|mut line| {
+                if let Some(existing) = self.parts.iter().find(|p| p.part == line.part) {
+                    line.printed = existing.printed;
+                    line.revision = line.revision.or(existing.revision);
+                }
+                line
+            })
+            .collect();
+        let previous = std::mem::replace(&mut self.parts, parts);
+        self.events.push(ProjectEvent::BomDefined {
+            project: self.id,
+            previous,
+            parts: self.parts.clone(),
+        });
+    }
+
+    /// Records printed parts. The part must be a part of the project BOM and no more parts may be
+    /// printed than its quantity.
+    pub fn report_progress(&mut self, part: PartId, printed: u32) -> Result<(), ValidationError> {
+        let line = self
+            .parts
+            .iter_mut()
+            .find(|p| p.part == part)
+            .ok_or_else(|| {
+                ValidationError::new(
+                    "part",
+                    "progress.part.not-in-bom",
+                    "the part is not in the project BOM",
+                )
+            })?;
+        let total = line
+            .printed
+            .checked_add(printed)
+            .filter(|total| printed > 0 && *total <= line.quantity)
+            .ok_or_else(|| {
+                ValidationError::new(
+                    "printed",
+                    "progress.printed.out-of-range",
+                    "printed parts must be more than 0 and no more than the parts left to print",
+                )
+            })?;
+        line.printed = total;
+        self.events.push(ProjectEvent::ProgressReported {
+            project: self.id,
+            part,
+            printed,
+            total,
+        });
+        Ok(())
+    }
+}
+
+impl Project {
+    /// Pins BOM lines added without a revision to the given revisions of their parts,
+    /// typically the latest ones. Lines of parts missing in `revisions` are rejected, so the BOM
+    /// cannot refer to parts missing in the inventory.
+    pub fn pin_revisions(
+        &mut self,
+        revisions: &HashMap<PartId, PartRevisionNumber>,
+    ) -> Result<(), ValidationError> {
+        for (index, line) in self.parts.iter_mut().enumerate() {
+            if line.revision.is_some() {
+                continue;
+            }
+            let revision = revisions.get(&line.part).ok_or_else(|| {
+                ValidationError::new(
+                    format!("parts[{}].part", index),
+                    "bom.part.missing",
+                    format!("part {} is not registered in the inventory", line.part),
+                )
+            })?;
+            line.revision = Some(*revision);
+        }
+        Ok(())
+    }
+
+    /// Parts of BOM lines not pinned to a revision yet.
+    pub fn unpinned_parts(&self) -> Vec<PartId> {
+        self.parts
+            .iter()
+            .filter(|line| line.revision.is_none())
+            .map(|line| line.part)
+            .collect()
+    }
+
+    /// Pins the latest revision of the part in its BOM line.
+    /// Parts missing in the BOM, pinned to the latest revision or not pinned yet are left as
+    /// they are.
+    pub fn upgrade_part(&mut self, part: PartId, latest: PartRevisionNumber) {
+        let Some(line) = self.parts.iter_mut().find(|p| p.part == part) else {
+            return;
+        };
+        if let Some(from) = line.revision.filter(|from| *from < latest) {
+            line.revision = Some(latest);
+            self.events.push(ProjectEvent::PartUpgraded {
+                project: self.id,
+                part,
+                from,
+                to: latest,
+            });
+        }
+    }
+}
+
+impl Project {
+    /// Marks the project as a template or turns it back into a regular project.
+    pub fn mark_as_template(&mut self, template: bool) {
+        if self.template == template {
+            return;
+        }
+        self.template = template;
+        self.events.push(ProjectEvent::TemplateMarked {
+            project: self.id,
+            template,
+        });
+    }
+
+    /// Creates a new project with the BOM and the template flag of this project.
+    /// Pinned revisions are copied, progress is not.
+    pub fn duplicate(&self, name: Name) -> Project {
+        let mut copy = Project::new(name);
+        if !self.parts.is_empty() {
+            let parts = self
+                .parts
+                .iter()
+                .map(|p| ProjectPart {
+                    revision: p.revision,
+                    ..ProjectPart::new(p.part, p.quantity)
+                })
+                .collect();
+            copy.replace_parts(parts);
+        }
+        copy.mark_as_template(self.template);
+        copy
+    }
+
+    /// Creates a regular project from the template, multiplying quantities of all BOM lines.
+    pub fn instantiate(
+        &self,
+        name: Name,
+        multiplier: Multiplier,
+    ) -> Result<Project, ValidationError> {
+        if !self.template {
+            return Err(ValidationError::new(
+                "template",
+                "project.template.required",
+                "the project is not a template",
+            ));
+        }
+        let parts = self
+            .parts
+            .iter()
+            .map(|p| {
+                let quantity = p.quantity.saturating_mul(multiplier.value());
+                Quantity::parse(quantity).map(|q| ProjectPart::new(p.part, q.value()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut project = Project::new(name);
+        if !parts.is_empty() {
+            project.replace_parts(parts);
+        }
+        Ok(project)
     }
 }
 
 impl ProjectPart {
     pub fn new(part: PartId, quantity: u32) -> Self {
-        Self { part, quantity }
+        Self::full(part, quantity, 0)
+    }
+
+    pub fn full(part: PartId, quantity: u32, printed: u32) -> Self {
+        Self {
+            part,
+            quantity,
+            printed,
+            revision: None,
+        }
+    }
+
+    /// Pins the revision of the part.
+    pub fn pinned(self, revision: PartRevisionNumber) -> Self {
+        Self {
+            revision: Some(revision),
+            ..self
+        }
     }
 }
 
@@ -84,4 +317,226 @@ impl ProjectPart {
     pub fn quantity(&self) -> u32 {
         self.quantity
     }
+
+    pub fn printed(&self) -> u32 {
+        self.printed
+    }
+
+    pub fn revision(&self) -> Option<PartRevisionNumber> {
+        self.revision
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn keep_progress_when_bom_is_redefined() {
+        let (bin, drawer) = (Uuid::now_v7(), Uuid::now_v7());
+        let mut project = Project::new(Name::default());
+        project
+            .define_parts(vec![ProjectPart::new(bin, 5)])
+            .unwrap();
+        project.report_progress(bin, 2).unwrap();
+
+        project
+            .define_parts(vec![ProjectPart::new(bin, 6), ProjectPart::new(drawer, 9)])
+            .unwrap();
+
+        assert_eq!(project.parts()[0].printed(), 2);
+        assert_eq!(project.parts()[1].printed(), 0);
+    }
+
+    #[test]
+    fn reject_duplicate_bom_lines() {
+        let bin = Uuid::now_v7();
+        let mut project = Project::new(Name::default());
+
+        let error = project
+            .define_parts(vec![ProjectPart::new(bin, 5), ProjectPart::new(bin, 3)])
+            .unwrap_err();
+
+        assert_eq!(error.code(), "bom.part.duplicate");
+        assert_eq!(error.attribute(), "parts[1].part");
+        assert!(project.parts().is_empty());
+    }
+
+    #[test]
+    fn record_project_events() {
+        let part = Uuid::now_v7();
+        let mut project = Project::new(Name::default());
+        project
+            .define_parts(vec![ProjectPart::new(part, 5)])
+            .unwrap();
+
+        let events = project.take_events();
+
+        assert_eq!(
+            events,
+            vec![
+                ProjectEvent::ProjectRegistered {
+                    project: project.id(),
+                    name: project.name().to_string(),
+                },
+                ProjectEvent::BomDefined {
+                    project: project.id(),
+                    previous: vec![],
+                    parts: vec![ProjectPart::new(part, 5)],
+                },
+            ]
+        );
+        assert!(project.take_events().is_empty());
+    }
+
+    #[test]
+    fn duplicate_bom_without_progress() {
+        let part = Uuid::now_v7();
+        let mut project = Project::new(Name::default());
+        project
+            .define_parts(vec![ProjectPart::new(part, 5)])
+            .unwrap();
+        project.report_progress(part, 2).unwrap();
+        project.mark_as_template(true);
+
+        let copy = project.duplicate(Name::parse("Copy").unwrap());
+
+        assert_ne!(copy.id(), project.id());
+        assert_eq!(copy.parts(), &[ProjectPart::new(part, 5)]);
+        assert!(copy.is_template());
+    }
+
+    #[test]
+    fn instantiate_template_with_multiplier() {
+        let part = Uuid::now_v7();
+        let mut template = Project::new(Name::default());
+        template
+            .define_parts(vec![ProjectPart::new(part, 3)])
+            .unwrap();
+        template.mark_as_template(true);
+
+        let project = template
+            .instantiate(Name::default(), Multiplier::parse(12).unwrap())
+            .unwrap();
+
+        assert_eq!(project.parts(), &[ProjectPart::new(part, 36)]);
+        assert!(!project.is_template());
+    }
+
+    #[test]
+    fn reject_instantiating_regular_project() {
+        let project = Project::new(Name::default());
+
+        let err = project
+            .instantiate(Name::default(), Multiplier::default())
+            .unwrap_err();
+
+        assert_eq!(err.code(), "project.template.required");
+    }
+
+    #[test]
+    fn reject_too_large_quantities() {
+        let mut template = Project::new(Name::default());
+        template
+            .define_parts(vec![ProjectPart::new(Uuid::now_v7(), 1000)])
+            .unwrap();
+        template.mark_as_template(true);
+
+        let err = template
+            .instantiate(Name::default(), Multiplier::parse(1000).unwrap())
+            .unwrap_err();
+
+        assert_eq!(err.code(), "bom.quantity.too-large");
+    }
+
+    #[test]
+    fn keep_pinned_revisions() {
+        let (hinge, lid) = (Uuid::now_v7(), Uuid::now_v7());
+        let mut project = Project::new(Name::default());
+        project
+            .define_parts(vec![ProjectPart::new(hinge, 2).pinned(1)])
+            .unwrap();
+
+        project
+            .define_parts(vec![ProjectPart::new(hinge, 4), ProjectPart::new(lid, 1)])
+            .unwrap();
+
+        assert_eq!(project.parts()[0].revision(), Some(1));
+        assert_eq!(project.parts()[1].revision(), None);
+        assert_eq!(
+            project.duplicate(Name::default()).parts()[0].revision(),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn pin_new_lines_to_given_revisions() {
+        let (hinge, lid) = (Uuid::now_v7(), Uuid::now_v7());
+        let mut project = Project::new(Name::default());
+        project
+            .define_parts(vec![
+                ProjectPart::new(hinge, 2).pinned(1),
+                ProjectPart::new(lid, 1),
+            ])
+            .unwrap();
+        assert_eq!(project.unpinned_parts(), vec![lid]);
+
+        let err = project.pin_revisions(&HashMap::new()).unwrap_err();
+        assert_eq!(err.code(), "bom.part.missing");
+        project
+            .pin_revisions(&HashMap::from([(hinge, 3), (lid, 2)]))
+            .unwrap();
+
+        let revisions: Vec<_> = project.parts().iter().map(|p| p.revision()).collect();
+        assert_eq!(revisions, vec![Some(1), Some(2)]);
+    }
+
+    #[test]
+    fn upgrade_outdated_parts() {
+        let part = Uuid::now_v7();
+        let mut project = Project::new(Name::default());
+        project
+            .define_parts(vec![ProjectPart::new(part, 2).pinned(1)])
+            .unwrap();
+        project.take_events();
+
+        project.upgrade_part(part, 3);
+        project.upgrade_part(part, 3);
+        project.upgrade_part(part, 2);
+
+        assert_eq!(project.parts()[0].revision(), Some(3));
+        assert_eq!(
+            project.take_events(),
+            vec![ProjectEvent::PartUpgraded {
+                project: project.id(),
+                part,
+                from: 1,
+                to: 3,
+            }]
+        );
+    }
+
+    #[test]
+    fn reject_progress_of_unknown_part() {
+        let mut project = Project::new(Name::default());
+        let err = project.report_progress(Uuid::now_v7(), 1).unwrap_err();
+        assert_eq!(err.code(), "progress.part.not-in-bom");
+    }
+
+    #[test]
+    fn reject_progress_out_of_range() {
+        let part = Uuid::now_v7();
+        let mut project = Project::new(Name::default());
+        project
+            .define_parts(vec![ProjectPart::new(part, 5)])
+            .unwrap();
+        project.report_progress(part, 4).unwrap();
+
+        for printed in [0, 2, u32::MAX] {
+            let err = project.report_progress(part, printed).unwrap_err();
+            assert_eq!(err.code(), "progress.printed.out-of-range");
+        }
+        assert_eq!(project.parts()[0].printed(), 4);
+    }
 }
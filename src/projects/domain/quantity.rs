@@ -0,0 +1,100 @@
+use std::fmt::Display;
+
+use crate::shared::validation::error::ValidationError;
+
+/// Number of identical parts required by a project.
+/// Quantity must be a positive number not exceeding 100000.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quantity(u32);
+
+const MAX_QUANTITY: u32 = 100_000;
+
+impl Quantity {
+    pub fn parse(value: u32) -> Result<Self, ValidationError> {
+        let attr = "quantity";
+        if value == 0 {
+            return Err(ValidationError::new(
+                attr,
+                "bom.quantity.too-small",
+                "quantity must be greater than zero",
+            ));
+        }
+        if value > MAX_QUANTITY {
+            return Err(ValidationError::new(
+                attr,
+                "bom.quantity.too-large",
+                "quantity is too large",
+            ));
+        }
+        Ok(Self(value))
+    }
+
+    pub fn value(&self) -> u32 {
+        self.0
+    }
+}
+
+impl TryFrom<u32> for Quantity {
+    type Error = ValidationError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl TryFrom<String> for Quantity {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let number = value.trim().parse::<u32>().map_err(|_| {
+            ValidationError::new(
+                "quantity",
+                "bom.quantity.not-a-number",
+                "quantity must be a positive whole number",
+            )
+        })?;
+        Self::parse(number)
+    }
+}
+
+impl Display for Quantity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn accept_positive_quantity() {
+        let ok = Quantity::try_from(" 12 ".to_string());
+        assert_eq!(ok.unwrap().value(), 12);
+    }
+
+    #[test]
+    fn reject_zero_quantity() {
+        let err = Quantity::try_from(0);
+        assert_eq!(err.unwrap_err().code(), "bom.quantity.too-small");
+    }
+
+    #[test]
+    fn reject_huge_quantity() {
+        let err = Quantity::try_from(MAX_QUANTITY + 1);
+        assert_eq!(err.unwrap_err().code(), "bom.quantity.too-large");
+    }
+
+    #[test]
+    fn reject_non_numeric_quantity() {
+        let err = Quantity::try_from("two".to_string());
+        assert_eq!(err.unwrap_err().code(), "bom.quantity.not-a-number");
+    }
+
+    #[test]
+    fn reject_negative_quantity() {
+        let err = Quantity::try_from("-3".to_string());
+        assert!(err.is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 
 use super::project::{Project, ProjectId};
+use super::revision::{BomRevision, RevisionNumber};
 
 /// Repository for manipulating persistent projects.
 /// This trait must be implemented in the infrastructure layer.
@@ -17,3 +18,23 @@ pub trait ProjectRepository: Send + Sync {
     /// Save updated project in storage.
     async fn update(&self, project: Project) -> anyhow::Result<()>;
 }
+
+/// Repository of BOM revisions. Revisions are never changed once stored.
+/// This trait must be implemented in the infrastructure layer.
+#[async_trait]
+pub trait BomRevisionRepository: Send + Sync {
+    /// Stores the revision as the next revision of its project.
+    /// The `revision` number is ignored. Returns the number assigned by the repository.
+    async fn append(&self, revision: BomRevision) -> anyhow::Result<RevisionNumber>;
+
+    /// Lists revisions of the project, the most recent first.
+    async fn list(&self, project: ProjectId) -> anyhow::Result<Vec<BomRevision>>;
+
+    /// Finds a single revision of the project.
+    /// The implementation must return Ok(None) if the revision is not found.
+    async fn find(
+        &self,
+        project: ProjectId,
+        revision: RevisionNumber,
+    ) -> anyhow::Result<Option<BomRevision>>;
+}
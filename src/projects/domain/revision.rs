@@ -0,0 +1,169 @@
+//! Revisions of the project BOM.
+//!
+//! Every change of the BOM is kept as a numbered revision, so a replaced BOM can be compared
+//! with later ones and restored.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::inventory::domain::part::PartId;
+
+use super::project::{ProjectId, ProjectPart};
+
+pub type RevisionNumber = u32;
+
+/// BOM of the project as defined at some point of time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BomRevision {
+    pub project: ProjectId,
+    /// Consecutive number of the revision within the project, assigned when the revision is stored.
+    pub revision: RevisionNumber,
+    pub created_at: DateTime<Utc>,
+    pub actor: String,
+    pub parts: Vec<BomLine>,
+}
+
+/// Part and its required quantity. Revisions do not track printing progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BomLine {
+    pub part_id: PartId,
+    pub quantity: u32,
+}
+
+/// Differences between BOMs of two revisions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BomDiff {
+    pub from: RevisionNumber,
+    pub to: RevisionNumber,
+    /// Lines present only in the `to` revision.
+    pub added: Vec<BomLine>,
+    /// Lines present only in the `from` revision.
+    pub removed: Vec<BomLine>,
+    /// Lines present in both revisions with different quantities.
+    pub changed: Vec<QuantityChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuantityChange {
+    pub part_id: PartId,
+    pub from: u32,
+    pub to: u32,
+}
+
+impl BomRevision {
+    /// Describes how the BOM changed from this revision to the `to` revision.
+    pub fn diff(&self, to: &BomRevision) -> BomDiff {
+        let find =
+            |parts: &[BomLine], part_id| parts.iter().find(|l| l.part_id == part_id).copied();
+        let added = to
+            .parts
+            .iter()
+            .filter(|l| find(&self.parts, l.part_id).is_none())
+            .copied()
+            .collect();
+        let mut removed = vec![];
+        let mut changed = vec![];
+        for line in &self.parts {
+            match find(&to.parts, line.part_id) {
+                None => removed.push(*line),
+                Some(other) if other.quantity != line.quantity => changed.push(QuantityChange {
+                    part_id: line.part_id,
+                    from: line.quantity,
+                    to: other.quantity,
+                }),
+                Some(_) => {}
+            }
+        }
+        BomDiff {
+            from: self.revision,
+            to: to.revision,
+            added,
+            removed,
+            changed,
+        }
+    }
+
+    /// BOM lines of the revision, without any progress.
+    pub fn project_parts(&self) -> Vec<ProjectPart> {
+        self.parts
+            .iter()
+            .map(|l| ProjectPart::new(l.part_id, l.quantity))
+            .collect()
+    }
+}
+
+impl BomLine {
+    /// Takes parts and quantities of the project BOM.
+    pub fn of(parts: &[ProjectPart]) -> Vec<BomLine> {
+        parts
+            .iter()
+            .map(|p| BomLine {
+                part_id: p.part(),
+                quantity: p.quantity(),
+            })
+            .collect()
+    }
+
+    /// Checks if both BOMs require the same quantities of the same parts, in any order.
+    pub fn same_bom(left: &[BomLine], right: &[BomLine]) -> bool {
+        let mut left = left.to_vec();
+        let mut right = right.to_vec();
+        left.sort_by_key(|l| l.part_id);
+        right.sort_by_key(|l| l.part_id);
+        left == right
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn revision(revision: RevisionNumber, parts: Vec<BomLine>) -> BomRevision {
+        BomRevision {
+            project: Uuid::nil(),
+            revision,
+            created_at: Utc::now(),
+            actor: "alice".to_string(),
+            parts,
+        }
+    }
+
+    fn line(part_id: PartId, quantity: u32) -> BomLine {
+        BomLine { part_id, quantity }
+    }
+
+    #[test]
+    fn diff_revisions() {
+        let (bin, drawer, lid) = (Uuid::now_v7(), Uuid::now_v7(), Uuid::now_v7());
+        let from = revision(1, vec![line(bin, 5), line(drawer, 9)]);
+        let to = revision(2, vec![line(drawer, 12), line(lid, 1)]);
+
+        let diff = from.diff(&to);
+
+        assert_eq!((diff.from, diff.to), (1, 2));
+        assert_eq!(diff.added, vec![line(lid, 1)]);
+        assert_eq!(diff.removed, vec![line(bin, 5)]);
+        assert_eq!(
+            diff.changed,
+            vec![QuantityChange {
+                part_id: drawer,
+                from: 9,
+                to: 12
+            }]
+        );
+    }
+
+    #[test]
+    fn compare_boms_in_any_order() {
+        let (bin, drawer) = (Uuid::now_v7(), Uuid::now_v7());
+
+        assert!(BomLine::same_bom(
+            &[line(bin, 5), line(drawer, 9)],
+            &[line(drawer, 9), line(bin, 5)]
+        ));
+        assert!(!BomLine::same_bom(&[line(bin, 5)], &[line(bin, 6)]));
+    }
+}
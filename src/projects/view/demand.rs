@@ -0,0 +1,181 @@
+//! Report of parts needed by all projects.
+//!
+//! Demand adds up BOM lines of regular projects, so it shows how many copies of every part
+//! are still to print. Variants are reported one by one, or added up under their parent part.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::inventory::domain::part::PartId;
+use crate::shared::validation::error::ValidationError;
+
+use super::project::ProjectView;
+
+/// How the demand of variants is reported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DemandGrouping {
+    /// Reports every variant on its own.
+    #[default]
+    Variant,
+    /// Adds up variants of the same parent part.
+    Parent,
+}
+
+impl DemandGrouping {
+    pub fn parse(value: &str) -> Result<Self, ValidationError> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "variant" => Ok(DemandGrouping::Variant),
+            "parent" => Ok(DemandGrouping::Parent),
+            _ => Err(ValidationError::new(
+                "group_by",
+                "demand.group-by.unsupported",
+                "demand can be grouped only by variant or parent",
+            )),
+        }
+    }
+}
+
+impl Display for DemandGrouping {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DemandGrouping::Variant => write!(f, "variant"),
+            DemandGrouping::Parent => write!(f, "parent"),
+        }
+    }
+}
+
+/// Copies of the part or of variants of the parent part needed by projects.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartDemand {
+    pub part_id: PartId,
+    pub name: String,
+    /// Attributes of the variant. Missing for parent parts and parts without variants.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<String>,
+    pub quantity: u32,
+    pub printed: u32,
+    pub remaining: u32,
+    /// Number of projects using the part.
+    pub projects: u32,
+}
+
+/// Adds up BOM lines of the projects, ordered by part name ignoring case.
+/// Templates only describe projects to create, so their lines are skipped.
+pub fn demand(projects: &[ProjectView], grouping: DemandGrouping) -> Vec<PartDemand> {
+    let mut demand: Vec<PartDemand> = vec![];
+    let mut rows: HashMap<PartId, usize> = HashMap::new();
+    for project in projects.iter().filter(|p| !p.is_template()) {
+        let mut counted: HashSet<PartId> = HashSet::new();
+        for line in project.parts() {
+            let variant = line.variant();
+            let (part_id, name, color, size) = match (grouping, variant) {
+                (DemandGrouping::Parent, Some(variant)) => {
+                    (variant.parent_id, variant.parent_name.as_str(), None, None)
+                }
+                (_, variant) => (
+                    line.id(),
+                    line.name(),
+                    variant.and_then(|v| v.color.clone()),
+                    variant.and_then(|v| v.size.clone()),
+                ),
+            };
+            let index = *rows.entry(part_id).or_insert_with(|| {
+                demand.push(PartDemand {
+                    part_id,
+                    name: name.to_string(),
+                    color,
+                    size,
+                    quantity: 0,
+                    printed: 0,
+                    remaining: 0,
+                    projects: 0,
+                });
+                demand.len() - 1
+            });
+            let row = &mut demand[index];
+            row.quantity += line.quantity();
+            row.printed += line.printed();
+            row.remaining += line.remaining();
+            if counted.insert(part_id) {
+                row.projects += 1;
+            }
+        }
+    }
+    demand.sort_by_key(|d| d.name.to_lowercase());
+    demand
+}
+
+#[cfg(test)]
+mod tests {
+
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::projects::view::project::{BomVariant, ProjectPart};
+
+    #[test]
+    fn add_up_variants_under_parent() {
+        let bin = Uuid::now_v7();
+        let red_bin = Uuid::now_v7();
+        let blue_bin = Uuid::now_v7();
+        let variant = |id, name: &str, color: &str, quantity| {
+            ProjectPart::new(id, name.to_string(), quantity).with_variant(Some(BomVariant {
+                parent_id: bin,
+                parent_name: "Bin".to_string(),
+                color: Some(color.to_string()),
+                size: None,
+            }))
+        };
+        let handle = ProjectPart::new(Uuid::now_v7(), "Handle".to_string(), 4);
+        let desk = ProjectView::new(
+            Uuid::now_v7(),
+            "Desk".to_string(),
+            vec![
+                variant(red_bin, "Bin (red)", "red", 3).with_printed(1),
+                variant(blue_bin, "Bin (blue)", "blue", 2),
+                handle.clone(),
+            ],
+        );
+        let garage = ProjectView::new(
+            Uuid::now_v7(),
+            "Garage".to_string(),
+            vec![variant(red_bin, "Bin (red)", "red", 5)],
+        );
+        let template =
+            ProjectView::new(Uuid::now_v7(), "Kit".to_string(), vec![handle]).with_template(true);
+        let projects = [desk, garage, template];
+
+        let by_variant = demand(&projects, DemandGrouping::Variant);
+        let by_parent = demand(&projects, DemandGrouping::Parent);
+
+        let rows: Vec<(&str, Option<&str>, u32, u32)> = by_variant
+            .iter()
+            .map(|d| (d.name.as_str(), d.color.as_deref(), d.remaining, d.projects))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("Bin (blue)", Some("blue"), 2, 1),
+                ("Bin (red)", Some("red"), 7, 2),
+                ("Handle", None, 4, 1),
+            ]
+        );
+        assert_eq!(by_parent[0].part_id, bin);
+        assert_eq!(by_parent[0].name, "Bin");
+        assert_eq!(by_parent[0].color, None);
+        assert_eq!(
+            (
+                by_parent[0].quantity,
+                by_parent[0].printed,
+                by_parent[0].remaining,
+                by_parent[0].projects,
+            ),
+            (10, 1, 9, 2)
+        );
+        assert_eq!(by_parent.len(), 2);
+    }
+}
@@ -0,0 +1,191 @@
+//! Renders project view into text formats suitable for sharing and printing.
+
+use std::fmt::Write;
+use std::str::FromStr;
+
+use super::project::ProjectView;
+
+/// Supported representations of the project view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+    Markdown,
+    Text,
+}
+
+impl ExportFormat {
+    /// Picks the first supported format from the `Accept` header value.
+    /// Quality weights are ignored, the order of media types is respected instead.
+    pub fn from_accept(accept: &str) -> Option<Self> {
+        accept
+            .split(',')
+            .filter_map(|media_type| media_type.split(';').next())
+            .find_map(|media_type| Self::from_media_type(media_type.trim()))
+    }
+
+    fn from_media_type(media_type: &str) -> Option<Self> {
+        match media_type {
+            "application/json" | "*/*" | "application/*" => Some(ExportFormat::Json),
+            "text/csv" => Some(ExportFormat::Csv),
+            "text/markdown" => Some(ExportFormat::Markdown),
+            "text/plain" | "text/*" => Some(ExportFormat::Text),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "application/json",
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Markdown => "text/markdown; charset=utf-8",
+            ExportFormat::Text => "text/plain; charset=utf-8",
+        }
+    }
+
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Markdown => "md",
+            ExportFormat::Text => "txt",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(ExportFormat::Json),
+            "csv" => Ok(ExportFormat::Csv),
+            "md" | "markdown" => Ok(ExportFormat::Markdown),
+            "txt" | "text" => Ok(ExportFormat::Text),
+            other => Err(format!("unsupported export format {}", other)),
+        }
+    }
+}
+
+/// Renders the project in the requested format.
+pub fn render(project: &ProjectView, format: ExportFormat) -> anyhow::Result<String> {
+    match format {
+        ExportFormat::Json => Ok(serde_json::to_string_pretty(project)?),
+        ExportFormat::Csv => render_csv(project),
+        ExportFormat::Markdown => Ok(render_markdown(project)),
+        ExportFormat::Text => Ok(render_text(project)),
+    }
+}
+
+/// Renders BOM and progress as CSV with `part name,quantity,printed,remaining` columns.
+/// The output is accepted by the BOM import, which ignores the progress columns.
+pub fn render_csv(project: &ProjectView) -> anyhow::Result<String> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+    writer.write_record(["part name", "quantity", "printed", "remaining"])?;
+    for part in project.parts() {
+        writer.write_record([
+            part.name(),
+            &part.quantity().to_string(),
+            &part.printed().to_string(),
+            &part.remaining().to_string(),
+        ])?;
+    }
+    let bytes = writer.into_inner()?;
+    Ok(String::from_utf8(bytes)?)
+}
+
+/// Renders project as a Markdown checklist table with progress of every part.
+pub fn render_markdown(project: &ProjectView) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# {}", escape_markdown(project.name()));
+    let _ = writeln!(out);
+    let _ = writeln!(out, "| Part | Quantity | Printed | Remaining |");
+    let _ = writeln!(out, "|------|---------:|--------:|----------:|");
+    for part in project.parts() {
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} | {} |",
+            escape_markdown(part.name()),
+            part.quantity(),
+            part.printed(),
+            part.remaining()
+        );
+    }
+    out
+}
+
+/// Renders project as a plain-text tree.
+///
+/// ```text
+/// Organizers
+/// +-- Drawer: 9
+/// +-- Bin:    5
+/// ```
+pub fn render_text(project: &ProjectView) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "{}", project.name());
+    let width = project
+        .parts()
+        .iter()
+        .map(|p| p.name().chars().count())
+        .max()
+        .unwrap_or_default();
+    for part in project.parts() {
+        let label = format!("{}:", part.name());
+        let _ = writeln!(out, "+-- {:<w$} {}", label, part.quantity(), w = width + 1);
+    }
+    out
+}
+
+fn escape_markdown(value: &str) -> String {
+    value.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::projects::view::project::ProjectPart;
+
+    fn organizers() -> ProjectView {
+        ProjectView::new(
+            Uuid::now_v7(),
+            "Organizers".to_string(),
+            vec![
+                ProjectPart::new(Uuid::now_v7(), "Drawer".to_string(), 9).with_printed(3),
+                ProjectPart::new(Uuid::now_v7(), "Bin, small".to_string(), 5),
+            ],
+        )
+    }
+
+    #[test]
+    fn pick_format_from_accept_header() {
+        let format = ExportFormat::from_accept("text/html, text/markdown;q=0.9, */*;q=0.1");
+        assert_eq!(format, Some(ExportFormat::Markdown));
+        assert_eq!(ExportFormat::from_accept("image/png"), None);
+    }
+
+    #[test]
+    fn render_bom_as_csv() {
+        let csv = render_csv(&organizers()).unwrap();
+        assert_eq!(
+            csv,
+            "part name,quantity,printed,remaining\nDrawer,9,3,6\n\"Bin, small\",5,0,5\n"
+        );
+    }
+
+    #[test]
+    fn render_bom_as_markdown() {
+        let markdown = render_markdown(&organizers());
+        assert!(markdown.starts_with("# Organizers\n"));
+        assert!(markdown.contains("| Drawer | 9 | 3 | 6 |"));
+    }
+
+    #[test]
+    fn render_bom_as_tree() {
+        let text = render_text(&organizers());
+        assert_eq!(text, "Organizers\n+-- Drawer:     9\n+-- Bin, small: 5\n");
+    }
+}
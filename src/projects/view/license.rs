@@ -0,0 +1,179 @@
+//! Report of licenses of parts in the project BOM.
+//!
+//! Prints may be sold only when every part allows commercial use. The report lists the license
+//! of every part and flags parts forbidding commercial use or with an unknown license.
+//! Parts with stored sources that no longer parse are reported with an unknown license.
+
+use serde::{Deserialize, Serialize};
+
+use crate::inventory::domain::part::{Part, PartId};
+use crate::inventory::domain::source::{PartSource, RawPartSource};
+use crate::projects::domain::project::ProjectId;
+use crate::shared::validation::error::ValidationError;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LicenseReport {
+    pub id: ProjectId,
+    pub name: String,
+    /// BOM lines ordered by part name.
+    pub parts: Vec<PartLicense>,
+    /// Set when every part allows commercial use, so prints of the project may be sold.
+    pub commercial_use: bool,
+    /// Number of parts forbidding commercial use.
+    pub non_commercial: u32,
+    /// Number of parts without a license or a commercial use flag, or with an invalid source.
+    pub unknown: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartLicense {
+    pub part_id: PartId,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub designer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// Whether prints of the part may be sold. Missing when unknown.
+    pub commercial_use: Option<bool>,
+    /// Reasons the stored source of the part is invalid.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<ValidationError>,
+}
+
+impl LicenseReport {
+    /// Lists licenses of the parts and counts parts which make the project unsellable.
+    pub fn new(id: ProjectId, name: String, mut parts: Vec<PartLicense>) -> Self {
+        parts.sort_by_key(|p| p.name.to_lowercase());
+        let count = |commercial_use: Option<bool>| {
+            parts
+                .iter()
+                .filter(|p| p.commercial_use == commercial_use)
+                .count() as u32
+        };
+        let non_commercial = count(Some(false));
+        let unknown = count(None);
+        Self {
+            id,
+            name,
+            commercial_use: non_commercial == 0 && unknown == 0,
+            non_commercial,
+            unknown,
+            parts,
+        }
+    }
+}
+
+impl PartLicense {
+    pub fn new(part_id: PartId, name: String, source: &PartSource) -> Self {
+        Self {
+            part_id,
+            name,
+            source_url: source.source_url.as_ref().map(|u| u.to_string()),
+            designer: source.designer.as_ref().map(|d| d.to_string()),
+            license: source.license.as_ref().map(|l| l.to_string()),
+            commercial_use: source.allows_commercial_use(),
+            errors: vec![],
+        }
+    }
+
+    /// Reports the source as stored, with an unknown commercial use.
+    pub fn invalid(
+        part_id: PartId,
+        name: String,
+        source: RawPartSource,
+        errors: Vec<ValidationError>,
+    ) -> Self {
+        Self {
+            part_id,
+            name,
+            source_url: source.source_url,
+            designer: source.designer,
+            license: source.license,
+            commercial_use: None,
+            errors,
+        }
+    }
+}
+
+impl From<&Part> for PartLicense {
+    fn from(part: &Part) -> Self {
+        Self::new(part.id(), part.name().to_string(), part.source())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::inventory::domain::name::Name;
+
+    fn part(name: &str, license: Option<&str>) -> Part {
+        let source = PartSource::parse(RawPartSource {
+            license: license.map(str::to_string),
+            ..RawPartSource::default()
+        })
+        .unwrap();
+        Part::hydrate(Uuid::now_v7(), Name::parse(name).unwrap()).with_source(source)
+    }
+
+    #[test]
+    fn flag_parts_forbidding_commercial_use() {
+        let parts = [
+            part("Lid", Some("CC-BY-4.0")),
+            part("Hinge", None),
+            part("Bin", Some("CC-BY-NC-SA-4.0")),
+        ];
+
+        let report = LicenseReport::new(
+            Uuid::now_v7(),
+            "Drawers".to_string(),
+            parts.iter().map(PartLicense::from).collect(),
+        );
+
+        let lines: Vec<(&str, Option<bool>)> = report
+            .parts
+            .iter()
+            .map(|p| (p.name.as_str(), p.commercial_use))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![("Bin", Some(false)), ("Hinge", None), ("Lid", Some(true))]
+        );
+        assert_eq!((report.non_commercial, report.unknown), (1, 1));
+        assert!(!report.commercial_use);
+        assert!(LicenseReport::new(Uuid::now_v7(), "Empty".to_string(), vec![]).commercial_use);
+    }
+
+    #[test]
+    fn count_invalid_sources_as_unknown() {
+        let source = RawPartSource {
+            license: Some("Standard Digital File License".to_string()),
+            commercial_use: Some(true),
+            ..RawPartSource::default()
+        };
+        let errors = PartSource::parse(source.clone()).unwrap_err();
+
+        let report = LicenseReport::new(
+            Uuid::now_v7(),
+            "Drawers".to_string(),
+            vec![
+                PartLicense::from(&part("Lid", Some("CC-BY-4.0"))),
+                PartLicense::invalid(Uuid::now_v7(), "Bin".to_string(), source, errors),
+            ],
+        );
+
+        let bin = &report.parts[0];
+        assert_eq!(
+            bin.license.as_deref(),
+            Some("Standard Digital File License")
+        );
+        assert_eq!(bin.commercial_use, None);
+        assert!(!bin.errors.is_empty());
+        assert_eq!((report.non_commercial, report.unknown), (0, 1));
+        assert!(!report.commercial_use);
+    }
+}
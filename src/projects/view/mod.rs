@@ -1,2 +1,7 @@
+pub mod demand;
+pub mod export;
+pub mod license;
+pub mod outdated;
 pub mod project;
 pub mod repository;
+pub mod summary;
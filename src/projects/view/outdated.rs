@@ -0,0 +1,37 @@
+//! Report of projects using outdated revisions of parts.
+
+use serde::{Deserialize, Serialize};
+
+use crate::inventory::domain::part::PartId;
+use crate::inventory::domain::revision::PartRevisionNumber;
+use crate::projects::domain::project::ProjectId;
+
+/// Project with BOM lines pinned to older revisions of their parts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutdatedProject {
+    pub id: ProjectId,
+    pub name: String,
+    /// Outdated BOM lines ordered by part name.
+    pub parts: Vec<OutdatedPart>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutdatedPart {
+    pub part_id: PartId,
+    pub name: String,
+    /// Revision pinned by the BOM line.
+    pub revision: PartRevisionNumber,
+    pub latest_revision: PartRevisionNumber,
+}
+
+impl OutdatedProject {
+    /// Keeps only lines of the part. Returns None when the project does not use the part.
+    pub fn of_part(self, part: PartId) -> Option<Self> {
+        let parts: Vec<OutdatedPart> = self
+            .parts
+            .into_iter()
+            .filter(|p| p.part_id == part)
+            .collect();
+        (!parts.is_empty()).then_some(Self { parts, ..self })
+    }
+}
@@ -2,13 +2,63 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::inventory::domain::revision::{PartRevisionNumber, FIRST_REVISION};
+use crate::settings::domain::costs::{Cost, CostSettings};
+use crate::shared::validation::error::ValidationError;
 use crate::{inventory::domain::part::PartId, projects::domain::project::ProjectId};
 
+use super::summary::ProjectSummary;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectView {
     id: ProjectId,
     name: String,
+    #[serde(default)]
+    template: bool,
     bom: Vec<ProjectPart>,
+    #[serde(default)]
+    summary: ProjectSummary,
+    /// BOM lines grouped on request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    groups: Option<Vec<BomGroup>>,
+}
+
+/// BOM lines sharing a category or a parent part, with totals of the lines.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BomGroup {
+    /// Missing for lines of parts without a category and for groups of parent parts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// The parent part of variants in the group, or the part of a line without a variant.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part_id: Option<PartId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub bom: Vec<ProjectPart>,
+    pub summary: ProjectSummary,
+}
+
+/// How BOM lines of the project view are grouped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BomGrouping {
+    /// Groups lines by the full category path of their parts.
+    Category,
+    /// Groups variants under their parent part. Lines without a variant make their own groups.
+    Parent,
+}
+
+impl BomGrouping {
+    pub fn parse(value: &str) -> Result<Self, ValidationError> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "category" => Ok(BomGrouping::Category),
+            "parent" => Ok(BomGrouping::Parent),
+            _ => Err(ValidationError::new(
+                "group_by",
+                "project.group-by.unsupported",
+                "BOM lines can be grouped only by category or parent",
+            )),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,14 +66,146 @@ pub struct ProjectPart {
     part_id: PartId,
     name: String,
     quantity: u32,
+    #[serde(default)]
+    printed: u32,
+    /// Revision of the part pinned by the line.
+    revision: PartRevisionNumber,
+    /// The latest revision of the part. Newer than `revision` when the line is outdated.
+    latest_revision: PartRevisionNumber,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    material: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    color: Option<String>,
+    /// Category path of the part, e.g., `Storage › Gridfinity › Bins`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    category: Option<String>,
+    /// Set when the part is a variant of another part.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    variant: Option<BomVariant>,
+    /// Estimated filament weight of a single part.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    grams: Option<f64>,
+    /// Estimated print time of a single part.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    minutes: Option<u32>,
+    /// Cost of printing a single part. Calculated when prices are configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cost: Option<Cost>,
+}
+
+/// Parent part and attributes of the variant listed on a BOM line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BomVariant {
+    pub parent_id: PartId,
+    pub parent_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<String>,
+}
+
+/// Brief project information for project listings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectListItem {
+    id: ProjectId,
+    name: String,
+    bom_size: u32,
+    #[serde(default)]
+    template: bool,
 }
 
 impl ProjectView {
+    /// Creates the view with empty totals.
     pub fn new(id: ProjectId, name: String, parts: Vec<ProjectPart>) -> Self {
         Self {
             id,
             name,
+            template: false,
+            summary: ProjectSummary::default(),
             bom: parts,
+            groups: None,
+        }
+    }
+
+    pub fn with_template(self, template: bool) -> Self {
+        Self { template, ..self }
+    }
+
+    /// Adds totals of BOM parts added up by the read model.
+    pub fn with_summary(self, summary: ProjectSummary) -> Self {
+        Self { summary, ..self }
+    }
+
+    /// Calculates costs of parts and of the whole project.
+    /// Missing estimates are not charged.
+    pub fn with_costs(mut self, settings: &CostSettings) -> Self {
+        for part in self.bom.iter_mut() {
+            part.cost = Some(settings.cost(
+                part.grams.unwrap_or_default(),
+                f64::from(part.minutes.unwrap_or_default()),
+            ));
+        }
+        self.summary = self.summary.with_costs(settings);
+        self
+    }
+
+    /// Groups BOM lines, keeping their order within groups. Categories are matched ignoring case
+    /// and named after their first line. Groups are ordered by category or by the name of the
+    /// parent part ignoring case, and lines without a category come last.
+    /// Group totals do not include costs, but the lines do when they are calculated.
+    pub fn grouped(self, grouping: BomGrouping) -> Self {
+        let mut groups: Vec<BomGroup> = vec![];
+        match grouping {
+            BomGrouping::Category => {
+                for part in &self.bom {
+                    let category = part.category.as_ref().map(|c| c.to_lowercase());
+                    match groups
+                        .iter_mut()
+                        .find(|g| g.category.as_ref().map(|c| c.to_lowercase()) == category)
+                    {
+                        Some(group) => group.bom.push(part.clone()),
+                        None => groups.push(BomGroup {
+                            category: part.category.clone(),
+                            part_id: None,
+                            name: None,
+                            bom: vec![part.clone()],
+                            summary: ProjectSummary::default(),
+                        }),
+                    }
+                }
+                groups.sort_by_key(|g| {
+                    (
+                        g.category.is_none(),
+                        g.category.clone().map(|c| c.to_lowercase()),
+                    )
+                });
+            }
+            BomGrouping::Parent => {
+                for part in &self.bom {
+                    let (parent_id, parent_name) = match &part.variant {
+                        Some(variant) => (variant.parent_id, &variant.parent_name),
+                        None => (part.part_id, &part.name),
+                    };
+                    match groups.iter_mut().find(|g| g.part_id == Some(parent_id)) {
+                        Some(group) => group.bom.push(part.clone()),
+                        None => groups.push(BomGroup {
+                            category: None,
+                            part_id: Some(parent_id),
+                            name: Some(parent_name.clone()),
+                            bom: vec![part.clone()],
+                            summary: ProjectSummary::default(),
+                        }),
+                    }
+                }
+                groups.sort_by_key(|g| g.name.clone().map(|n| n.to_lowercase()));
+            }
+        }
+        for group in groups.iter_mut() {
+            group.summary = ProjectSummary::of(&group.bom);
+        }
+        Self {
+            groups: Some(groups),
+            ..self
         }
     }
 }
@@ -34,6 +216,57 @@ impl ProjectPart {
             part_id,
             name,
             quantity,
+            printed: 0,
+            revision: FIRST_REVISION,
+            latest_revision: FIRST_REVISION,
+            material: None,
+            color: None,
+            category: None,
+            variant: None,
+            grams: None,
+            minutes: None,
+            cost: None,
+        }
+    }
+
+    pub fn with_printed(self, printed: u32) -> Self {
+        Self { printed, ..self }
+    }
+
+    /// Adds the pinned revision and the latest revision of the part.
+    pub fn with_revision(self, revision: PartRevisionNumber, latest: PartRevisionNumber) -> Self {
+        Self {
+            revision,
+            latest_revision: latest,
+            ..self
+        }
+    }
+
+    pub fn with_category(self, category: Option<&str>) -> Self {
+        Self {
+            category: category.map(str::to_string),
+            ..self
+        }
+    }
+
+    pub fn with_variant(self, variant: Option<BomVariant>) -> Self {
+        Self { variant, ..self }
+    }
+
+    /// Adds printing properties of the part used to calculate project totals.
+    pub fn with_estimates(
+        self,
+        material: Option<&str>,
+        color: Option<&str>,
+        grams: Option<f64>,
+        minutes: Option<u32>,
+    ) -> Self {
+        Self {
+            material: material.map(str::to_string),
+            color: color.map(str::to_string),
+            grams,
+            minutes,
+            ..self
         }
     }
 }
@@ -51,9 +284,23 @@ impl ProjectView {
         self.bom.len()
     }
 
+    /// Checks if the project is a template of other projects.
+    pub fn is_template(&self) -> bool {
+        self.template
+    }
+
     pub fn parts(&self) -> &[ProjectPart] {
         self.bom.as_slice()
     }
+
+    pub fn summary(&self) -> &ProjectSummary {
+        &self.summary
+    }
+
+    /// BOM lines grouped with [`ProjectView::grouped`].
+    pub fn groups(&self) -> Option<&[BomGroup]> {
+        self.groups.as_deref()
+    }
 }
 
 impl ProjectPart {
@@ -68,4 +315,167 @@ impl ProjectPart {
     pub fn quantity(&self) -> u32 {
         self.quantity
     }
+
+    pub fn printed(&self) -> u32 {
+        self.printed
+    }
+
+    /// Number of parts left to print.
+    pub fn remaining(&self) -> u32 {
+        self.quantity.saturating_sub(self.printed)
+    }
+
+    pub fn revision(&self) -> PartRevisionNumber {
+        self.revision
+    }
+
+    pub fn latest_revision(&self) -> PartRevisionNumber {
+        self.latest_revision
+    }
+
+    /// Checks whether a newer revision of the part is available.
+    pub fn is_outdated(&self) -> bool {
+        self.revision < self.latest_revision
+    }
+
+    pub fn material(&self) -> Option<&str> {
+        self.material.as_deref()
+    }
+
+    pub fn color(&self) -> Option<&str> {
+        self.color.as_deref()
+    }
+
+    pub fn category(&self) -> Option<&str> {
+        self.category.as_deref()
+    }
+
+    pub fn variant(&self) -> Option<&BomVariant> {
+        self.variant.as_ref()
+    }
+
+    pub fn grams(&self) -> Option<f64> {
+        self.grams
+    }
+
+    pub fn minutes(&self) -> Option<u32> {
+        self.minutes
+    }
+
+    pub fn cost(&self) -> Option<&Cost> {
+        self.cost.as_ref()
+    }
+}
+
+impl ProjectListItem {
+    pub fn new(id: ProjectId, name: String, bom_size: u32) -> Self {
+        Self {
+            id,
+            name,
+            bom_size,
+            template: false,
+        }
+    }
+
+    pub fn with_template(self, template: bool) -> Self {
+        Self { template, ..self }
+    }
+
+    pub fn id(&self) -> ProjectId {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn bom_size(&self) -> u32 {
+        self.bom_size
+    }
+
+    pub fn is_template(&self) -> bool {
+        self.template
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn part(name: &str, category: Option<&str>) -> ProjectPart {
+        ProjectPart::new(Uuid::now_v7(), name.to_string(), 2)
+            .with_category(category)
+            .with_estimates(None, None, Some(10.0), Some(30))
+    }
+
+    #[test]
+    fn group_bom_by_category() {
+        let view = ProjectView::new(
+            Uuid::now_v7(),
+            "Drawers".to_string(),
+            vec![
+                part("Handle", None),
+                part("Bin", Some("Storage › Gridfinity")),
+                part("Hook", Some("Garage")),
+                part("Lid", Some("storage › gridfinity")),
+            ],
+        );
+
+        let view = view.grouped(BomGrouping::Category);
+
+        let groups: Vec<(Option<&str>, Vec<&str>)> = view
+            .groups()
+            .unwrap()
+            .iter()
+            .map(|g| {
+                (
+                    g.category.as_deref(),
+                    g.bom.iter().map(|p| p.name()).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                (Some("Garage"), vec!["Hook"]),
+                (Some("Storage › Gridfinity"), vec!["Bin", "Lid"]),
+                (None, vec!["Handle"]),
+            ]
+        );
+        assert_eq!(view.groups().unwrap()[1].summary.print_minutes, 120);
+    }
+
+    #[test]
+    fn group_variants_under_parent() {
+        let bin = Uuid::now_v7();
+        let variant = |name: &str, color: &str| {
+            part(name, None).with_variant(Some(BomVariant {
+                parent_id: bin,
+                parent_name: "Bin".to_string(),
+                color: Some(color.to_string()),
+                size: None,
+            }))
+        };
+        let view = ProjectView::new(
+            Uuid::now_v7(),
+            "Drawers".to_string(),
+            vec![
+                variant("Bin (red)", "red"),
+                part("Handle", None),
+                variant("Bin (blue)", "blue"),
+            ],
+        );
+
+        let view = view.grouped(BomGrouping::Parent);
+
+        let groups = view.groups().unwrap();
+        let names: Vec<Option<&str>> = groups.iter().map(|g| g.name.as_deref()).collect();
+        assert_eq!(names, vec![Some("Bin"), Some("Handle")]);
+        assert_eq!(groups[0].part_id, Some(bin));
+        assert_eq!(groups[0].bom.len(), 2);
+        assert_eq!(groups[0].summary.print_minutes, 120);
+    }
 }
@@ -2,7 +2,10 @@ use async_trait::async_trait;
 
 use crate::projects::domain::project::ProjectId;
 
-use super::project::ProjectView;
+use super::demand::{DemandGrouping, PartDemand};
+use super::license::LicenseReport;
+use super::outdated::OutdatedProject;
+use super::project::{ProjectListItem, ProjectView};
 
 #[async_trait]
 pub trait ProjectViewRepository: Send + Sync {
@@ -10,4 +13,20 @@ pub trait ProjectViewRepository: Send + Sync {
     ///
     /// Returns Ok(None) when project with the given ID does not exist.
     async fn get_view_by_id(&self, id: ProjectId) -> anyhow::Result<Option<ProjectView>>;
+
+    /// Lists all projects, the most recent first.
+    async fn list_projects(&self) -> anyhow::Result<Vec<ProjectListItem>>;
+
+    /// Lists projects with BOM lines pinned to older revisions of their parts, the most recent
+    /// project first.
+    async fn list_outdated(&self) -> anyhow::Result<Vec<OutdatedProject>>;
+
+    /// Adds up BOM lines of regular projects by part or by parent part, ordered by part name
+    /// ignoring case. See [`super::demand::demand`].
+    async fn part_demand(&self, grouping: DemandGrouping) -> anyhow::Result<Vec<PartDemand>>;
+
+    /// Lists licenses of parts in the project BOM.
+    ///
+    /// Returns Ok(None) when project with the given ID does not exist.
+    async fn get_licenses_by_id(&self, id: ProjectId) -> anyhow::Result<Option<LicenseReport>>;
 }
@@ -0,0 +1,171 @@
+//! Filament and print time totals of the project.
+//!
+//! Totals are added up by the read model from estimates of BOM parts. Remaining amounts cover
+//! parts that are still to be printed.
+
+use serde::{Deserialize, Serialize};
+
+use crate::settings::domain::costs::{Cost, CostSettings};
+use crate::shared::rounding::round;
+
+use super::project::ProjectPart;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProjectSummary {
+    /// Filament needed per material and color, ordered by material and color.
+    pub filament: Vec<FilamentTotal>,
+    pub print_minutes: u64,
+    pub remaining_print_minutes: u64,
+    pub print_hours: f64,
+    pub remaining_print_hours: f64,
+    /// Number of BOM lines without weight or time estimates.
+    /// Totals are underestimated when there are such lines.
+    pub missing_estimates: u32,
+    /// Cost of printing all BOM parts. Calculated when prices are configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<Cost>,
+    /// Cost of printing parts left to print.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remaining_cost: Option<Cost>,
+}
+
+/// Filament of the single material and color. Parts without material or color are summed up
+/// under the missing value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FilamentTotal {
+    pub material: Option<String>,
+    pub color: Option<String>,
+    pub grams: f64,
+    pub remaining_grams: f64,
+}
+
+impl ProjectSummary {
+    /// Rounds totals added up by the read model and converts print times to hours.
+    pub fn new(
+        mut filament: Vec<FilamentTotal>,
+        print_minutes: u64,
+        remaining_print_minutes: u64,
+        missing_estimates: u32,
+    ) -> Self {
+        for total in filament.iter_mut() {
+            total.grams = round(total.grams);
+            total.remaining_grams = round(total.remaining_grams);
+        }
+        Self {
+            filament,
+            print_minutes,
+            remaining_print_minutes,
+            print_hours: round(print_minutes as f64 / 60.0),
+            remaining_print_hours: round(remaining_print_minutes as f64 / 60.0),
+            missing_estimates,
+            cost: None,
+            remaining_cost: None,
+        }
+    }
+
+    /// Adds up totals of the given BOM lines.
+    pub fn of(parts: &[ProjectPart]) -> Self {
+        let mut filament: Vec<FilamentTotal> = vec![];
+        let (mut print_minutes, mut remaining_print_minutes, mut missing_estimates) = (0, 0, 0);
+        for part in parts {
+            if part.grams().is_none() || part.minutes().is_none() {
+                missing_estimates += 1;
+            }
+            if let Some(grams) = part.grams() {
+                add_filament(&mut filament, part, grams);
+            }
+            if let Some(minutes) = part.minutes() {
+                print_minutes += u64::from(minutes) * u64::from(part.quantity());
+                remaining_print_minutes += u64::from(minutes) * u64::from(part.remaining());
+            }
+        }
+        filament.sort_by(|a, b| (&a.material, &a.color).cmp(&(&b.material, &b.color)));
+        Self::new(
+            filament,
+            print_minutes,
+            remaining_print_minutes,
+            missing_estimates,
+        )
+    }
+
+    /// Calculates costs of the totals.
+    pub fn with_costs(self, settings: &CostSettings) -> Self {
+        let grams = self.filament.iter().map(|t| t.grams).sum();
+        let remaining_grams = self.filament.iter().map(|t| t.remaining_grams).sum();
+        Self {
+            cost: Some(settings.cost(grams, self.print_minutes as f64)),
+            remaining_cost: Some(
+                settings.cost(remaining_grams, self.remaining_print_minutes as f64),
+            ),
+            ..self
+        }
+    }
+}
+
+fn add_filament(filament: &mut Vec<FilamentTotal>, part: &ProjectPart, grams: f64) {
+    let (material, color) = (part.material(), part.color());
+    let total = match filament
+        .iter()
+        .position(|t| t.material.as_deref() == material && t.color.as_deref() == color)
+    {
+        Some(index) => &mut filament[index],
+        None => {
+            filament.push(FilamentTotal {
+                material: material.map(str::to_string),
+                color: color.map(str::to_string),
+                grams: 0.0,
+                remaining_grams: 0.0,
+            });
+            filament.last_mut().unwrap()
+        }
+    };
+    total.grams += grams * f64::from(part.quantity());
+    total.remaining_grams += grams * f64::from(part.remaining());
+}
+
+#[cfg(test)]
+mod tests {
+
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn part(quantity: u32, printed: u32) -> ProjectPart {
+        ProjectPart::new(Uuid::now_v7(), "Part".to_string(), quantity).with_printed(printed)
+    }
+
+    #[test]
+    fn sum_filament_per_material_and_color() {
+        let parts = vec![
+            part(9, 3).with_estimates(Some("PLA"), Some("black"), Some(10.5), Some(30)),
+            part(2, 0).with_estimates(Some("PETG"), None, Some(100.0), Some(120)),
+            part(4, 4).with_estimates(Some("PLA"), Some("black"), Some(5.0), None),
+            part(1, 0),
+        ];
+
+        let summary = ProjectSummary::of(&parts);
+
+        assert_eq!(
+            summary.filament,
+            vec![
+                FilamentTotal {
+                    material: Some("PETG".to_string()),
+                    color: None,
+                    grams: 200.0,
+                    remaining_grams: 200.0,
+                },
+                FilamentTotal {
+                    material: Some("PLA".to_string()),
+                    color: Some("black".to_string()),
+                    grams: 114.5,
+                    remaining_grams: 63.0,
+                },
+            ]
+        );
+        assert_eq!(summary.print_minutes, 510);
+        assert_eq!(summary.remaining_print_minutes, 420);
+        assert_eq!(summary.print_hours, 8.5);
+        assert_eq!(summary.remaining_print_hours, 7.0);
+        assert_eq!(summary.missing_estimates, 2);
+    }
+}
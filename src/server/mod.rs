@@ -2,3 +2,4 @@
 pub mod rest;
 pub mod routes;
 pub mod startup;
+pub mod state;
@@ -1,5 +1,6 @@
 //! Common functionality for REST endpoints implementation
 
+use axum::extract::rejection::JsonRejection;
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use axum::Json;
@@ -7,9 +8,14 @@ use serde::{Deserialize, Serialize};
 
 use crate::shared::validation::error::ValidationError;
 
+/// Request header naming the user on whose behalf the request is made.
+pub const ACTOR_HEADER: &str = "x-actor";
+
 #[derive(Debug)]
 pub enum ErrorResponse {
     NotFound,
+    NotAcceptable,
+    PayloadTooLarge,
     ValidationFailed(Vec<ValidationError>),
     InternalError,
 }
@@ -18,6 +24,8 @@ impl IntoResponse for ErrorResponse {
     fn into_response(self) -> Response {
         match self {
             ErrorResponse::NotFound => StatusCode::NOT_FOUND.into_response(),
+            ErrorResponse::NotAcceptable => StatusCode::NOT_ACCEPTABLE.into_response(),
+            ErrorResponse::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE.into_response(),
             ErrorResponse::ValidationFailed(errors) => {
                 let message = ValidationMessage { errors };
                 (StatusCode::BAD_REQUEST, Json(message)).into_response()
@@ -27,6 +35,22 @@ impl IntoResponse for ErrorResponse {
     }
 }
 
+/// Reads an optional JSON payload. Requests without a body have no payload, while malformed
+/// payloads are rejected rather than treated as missing.
+pub fn optional_payload<T>(
+    payload: Result<Json<T>, JsonRejection>,
+) -> Result<Option<T>, ErrorResponse> {
+    match payload {
+        Ok(Json(payload)) => Ok(Some(payload)),
+        Err(JsonRejection::MissingJsonContentType(_)) => Ok(None),
+        Err(rejection) => Err(ErrorResponse::ValidationFailed(vec![ValidationError::new(
+            "body",
+            "request.body.invalid",
+            rejection.body_text(),
+        )])),
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ValidationMessage {
     pub errors: Vec<ValidationError>,
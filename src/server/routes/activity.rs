@@ -0,0 +1,59 @@
+//! Activity history of projects and the global activity feed.
+
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::Deserialize;
+
+use crate::activity::app::service::ActivityService;
+use crate::projects::app::service::{ProjectError, ProjectsService};
+use crate::projects::domain::project::ProjectId;
+use crate::server::rest::ErrorResponse;
+use crate::shared::pagination::PageRequest;
+
+/// Lists changes of the project, the most recent first.
+pub async fn project_history(
+    State(projects): State<Arc<dyn ProjectsService>>,
+    State(activity): State<Arc<dyn ActivityService>>,
+    Path(project_id): Path<ProjectId>,
+    Query(params): Query<PageParams>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let page = params.parse()?;
+    match projects.view_project(project_id).await {
+        Ok(_) => {}
+        Err(ProjectError::MissingProject) => return Err(ErrorResponse::NotFound),
+        Err(_) => return Err(ErrorResponse::InternalError),
+    }
+    let history = activity
+        .history(project_id, page)
+        .await
+        .map_err(|_| ErrorResponse::InternalError)?;
+    Ok(Json(history))
+}
+
+/// Lists all changes, the most recent first.
+pub async fn activity_feed(
+    State(activity): State<Arc<dyn ActivityService>>,
+    Query(params): Query<PageParams>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let page = params.parse()?;
+    let feed = activity
+        .feed(page)
+        .await
+        .map_err(|_| ErrorResponse::InternalError)?;
+    Ok(Json(feed))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PageParams {
+    page: Option<u32>,
+    per_page: Option<u32>,
+}
+
+impl PageParams {
+    fn parse(&self) -> Result<PageRequest, ErrorResponse> {
+        PageRequest::parse(self.page, self.per_page).map_err(ErrorResponse::ValidationFailed)
+    }
+}
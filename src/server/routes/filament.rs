@@ -0,0 +1,115 @@
+//! Filament spools, their consumption by projects and filament shortages of projects.
+
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use crate::filament::app::service::{FilamentError, FilamentService};
+use crate::filament::domain::spool::{RawSpool, Spool, SpoolId};
+use crate::inventory::domain::metadata::Grams;
+use crate::projects::domain::project::ProjectId;
+use crate::server::rest::ErrorResponse;
+
+pub async fn register_spool(
+    State(filament): State<Arc<dyn FilamentService>>,
+    Json(command): Json<RawSpool>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let spool = Spool::new(command).map_err(ErrorResponse::ValidationFailed)?;
+    let id = filament
+        .register_spool(spool)
+        .await
+        .map_err(error_response)?;
+    Ok(RegisterSpoolResponse { id })
+}
+
+pub async fn view_spool(
+    State(filament): State<Arc<dyn FilamentService>>,
+    Path(spool_id): Path<SpoolId>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let spool = filament
+        .view_spool(spool_id)
+        .await
+        .map_err(error_response)?;
+    Ok(Json(spool))
+}
+
+/// Lists spools ordered by material and color.
+pub async fn list_spools(
+    State(filament): State<Arc<dyn FilamentService>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let spools = filament.list_spools().await.map_err(error_response)?;
+    Ok(Json(spools))
+}
+
+/// Takes filament used by the project off the spool. Responds with the updated spool.
+pub async fn consume_filament(
+    State(filament): State<Arc<dyn FilamentService>>,
+    Path(spool_id): Path<SpoolId>,
+    Json(command): Json<ConsumeCommand>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let grams =
+        Grams::parse(command.grams).map_err(|e| ErrorResponse::ValidationFailed(vec![e]))?;
+    let spool = filament
+        .consume(spool_id, command.project, grams)
+        .await
+        .map_err(error_response)?;
+    Ok(Json(spool))
+}
+
+/// Lists filament used by the project, the oldest first.
+pub async fn project_consumption(
+    State(filament): State<Arc<dyn FilamentService>>,
+    Path(project_id): Path<ProjectId>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let consumption = filament
+        .list_consumption(project_id)
+        .await
+        .map_err(error_response)?;
+    Ok(Json(consumption))
+}
+
+/// Lists filament the project still needs but spools on the shelf lack.
+pub async fn project_shortages(
+    State(filament): State<Arc<dyn FilamentService>>,
+    Path(project_id): Path<ProjectId>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let shortages = filament
+        .shortages(project_id)
+        .await
+        .map_err(error_response)?;
+    Ok(Json(shortages))
+}
+
+fn error_response(error: FilamentError) -> ErrorResponse {
+    match error {
+        FilamentError::MissingSpool | FilamentError::MissingProject => ErrorResponse::NotFound,
+        FilamentError::ValidationFailed(errors) => ErrorResponse::ValidationFailed(errors),
+        FilamentError::GeneralError(_) => ErrorResponse::InternalError,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConsumeCommand {
+    project: ProjectId,
+    grams: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct RegisterSpoolResponse {
+    id: SpoolId,
+}
+
+impl IntoResponse for RegisterSpoolResponse {
+    fn into_response(self) -> Response {
+        (
+            StatusCode::CREATED,
+            [(header::LOCATION, format!("/v1/filament/spools/{}", self.id))],
+            Json(self),
+        )
+            .into_response()
+    }
+}
@@ -0,0 +1,166 @@
+//! Model files attached to parts. Files are uploaded as multipart forms and streamed back on
+//! download.
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::extract::multipart::MultipartError;
+use axum::extract::{Multipart, Path, Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use futures_util::TryStreamExt;
+use serde::Deserialize;
+use tokio_util::io::{ReaderStream, StreamReader};
+
+use crate::inventory::app::files::{PartFileError, PartFileService};
+use crate::inventory::domain::file::{FileName, PartFile, PartFileId};
+use crate::inventory::domain::part::PartId;
+use crate::inventory::domain::thumbnail::ThumbnailFormat;
+use crate::server::rest::ErrorResponse;
+use crate::shared::validation::error::ValidationError;
+
+/// Attaches the file sent in the `file` field of the multipart form to the part.
+/// The format is detected by the file name extension. STL meshes are measured and
+/// slicer estimates are read from G-code on upload.
+pub async fn upload_part_file(
+    State(files): State<Arc<dyn PartFileService>>,
+    Path(part_id): Path<PartId>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| multipart_error(&e))?
+    {
+        if field.name() != Some("file") {
+            continue;
+        }
+        let file_name = FileName::parse(field.file_name().unwrap_or_default())
+            .map_err(|e| ErrorResponse::ValidationFailed(vec![e]))?;
+        let mut content = StreamReader::new(field.map_err(std::io::Error::other));
+        let file = files
+            .attach_file(part_id, file_name, &mut content)
+            .await
+            .map_err(upload_error)?;
+        return Ok(UploadedFileResponse(file));
+    }
+    Err(missing_file())
+}
+
+/// Lists files of the part, the oldest upload first.
+pub async fn list_part_files(
+    State(files): State<Arc<dyn PartFileService>>,
+    Path(part_id): Path<PartId>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let files = files.list_files(part_id).await.map_err(error_response)?;
+    Ok(Json(files))
+}
+
+/// Streams file content under its original name.
+pub async fn download_part_file(
+    State(files): State<Arc<dyn PartFileService>>,
+    Path((part_id, file_id)): Path<(PartId, PartFileId)>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let (file, content) = files
+        .open_file(part_id, file_id)
+        .await
+        .map_err(error_response)?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, file.format.media_type().to_string()),
+            (header::CONTENT_LENGTH, file.size.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"{}\"",
+                    file.file_name.as_ref().replace('"', "")
+                ),
+            ),
+        ],
+        Body::from_stream(ReaderStream::new(content)),
+    ))
+}
+
+/// Renders the STL mesh attached to the part last as a PNG image, or as SVG with `?format=svg`.
+pub async fn part_thumbnail(
+    State(files): State<Arc<dyn PartFileService>>,
+    Path(part_id): Path<PartId>,
+    Query(params): Query<ThumbnailParams>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let format = match params.format {
+        Some(format) => {
+            ThumbnailFormat::parse(&format).map_err(|e| ErrorResponse::ValidationFailed(vec![e]))?
+        }
+        None => ThumbnailFormat::default(),
+    };
+    let image = files
+        .thumbnail(part_id, format)
+        .await
+        .map_err(error_response)?;
+    Ok(([(header::CONTENT_TYPE, format.media_type())], image))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ThumbnailParams {
+    format: Option<String>,
+}
+
+fn missing_file() -> ErrorResponse {
+    ErrorResponse::ValidationFailed(vec![ValidationError::new(
+        "file",
+        "file.content.missing",
+        "multipart form must contain the file field",
+    )])
+}
+
+/// Bodies over the size limit or cut off by the client fail while the content is stored.
+/// They are told apart from storage failures by the multipart error under the I/O error.
+fn upload_error(error: PartFileError) -> ErrorResponse {
+    if let PartFileError::GeneralError(e) = &error {
+        let rejection = e
+            .downcast_ref::<std::io::Error>()
+            .and_then(|e| e.get_ref())
+            .and_then(|e| e.downcast_ref::<MultipartError>());
+        if let Some(rejection) = rejection {
+            return multipart_error(rejection);
+        }
+    }
+    error_response(error)
+}
+
+fn multipart_error(error: &MultipartError) -> ErrorResponse {
+    match error.status() {
+        StatusCode::PAYLOAD_TOO_LARGE => ErrorResponse::PayloadTooLarge,
+        _ => ErrorResponse::ValidationFailed(vec![ValidationError::new(
+            "file",
+            "file.content.invalid",
+            error.body_text(),
+        )]),
+    }
+}
+
+fn error_response(error: PartFileError) -> ErrorResponse {
+    match error {
+        PartFileError::MissingPart | PartFileError::MissingFile => ErrorResponse::NotFound,
+        PartFileError::ValidationFailed(errors) => ErrorResponse::ValidationFailed(errors),
+        PartFileError::GeneralError(_) => ErrorResponse::InternalError,
+    }
+}
+
+struct UploadedFileResponse(PartFile);
+
+impl IntoResponse for UploadedFileResponse {
+    fn into_response(self) -> Response {
+        let file = self.0;
+        (
+            StatusCode::CREATED,
+            [(
+                header::LOCATION,
+                format!("/v1/inventory/parts/{}/files/{}", file.part, file.id),
+            )],
+            Json(file),
+        )
+            .into_response()
+    }
+}
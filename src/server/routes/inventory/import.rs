@@ -0,0 +1,44 @@
+//! Imports inventory parts from 3MF model packages.
+
+use std::sync::Arc;
+
+use axum::body::Bytes;
+use axum::extract::{Query, State};
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::Deserialize;
+use tracing::error;
+
+use crate::projects::app::model_import::{ModelImportError, ModelImportOptions, ModelImporter};
+use crate::projects::domain::name::Name as ProjectName;
+use crate::server::rest::ErrorResponse;
+
+/// Registers parts for objects of the 3MF package sent as the request body.
+/// Creates a project with the BOM of imported objects when the `project` name is given.
+pub async fn import_model(
+    State(importer): State<Arc<dyn ModelImporter>>,
+    Query(params): Query<ImportModelParams>,
+    content: Bytes,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let project = params
+        .project
+        .map(ProjectName::parse)
+        .transpose()
+        .map_err(|e| ErrorResponse::ValidationFailed(vec![e.nested("project")]))?;
+    let result = importer
+        .import_3mf(&content, ModelImportOptions { project })
+        .await;
+    match result {
+        Ok(report) => Ok(Json(report)),
+        Err(ModelImportError::InvalidModel(errors)) => Err(ErrorResponse::ValidationFailed(errors)),
+        Err(ModelImportError::GeneralError(e)) => {
+            error!("3MF import failed: {:?}", e);
+            Err(ErrorResponse::InternalError)
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ImportModelParams {
+    project: Option<String>,
+}
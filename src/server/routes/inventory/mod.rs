@@ -1 +1,6 @@
+pub mod files;
+pub mod import;
+pub mod packing;
 pub mod parts;
+pub mod revisions;
+pub mod variants;
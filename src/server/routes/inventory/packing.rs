@@ -0,0 +1,52 @@
+//! Fits parts on printer beds.
+
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::Deserialize;
+use tracing::error;
+
+use crate::inventory::domain::part::PartId;
+use crate::projects::app::packing::{PackingError, PackingOptions, PackingService};
+use crate::server::rest::ErrorResponse;
+
+/// Checks whether the part fits the bed given as `?bed=250x210` and how many copies fit on one
+/// plate. The optional `spacing` sets the gap between copies in millimeters.
+pub async fn pack_part(
+    State(packing): State<Arc<dyn PackingService>>,
+    Path(part_id): Path<PartId>,
+    Query(params): Query<PackingParams>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let options = params.parse()?;
+    packing
+        .pack_part(part_id, options)
+        .await
+        .map(Json)
+        .map_err(packing_error)
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct PackingParams {
+    bed: Option<String>,
+    spacing: Option<f64>,
+}
+
+impl PackingParams {
+    pub fn parse(self) -> Result<PackingOptions, ErrorResponse> {
+        PackingOptions::parse(self.bed.unwrap_or_default(), self.spacing)
+            .map_err(ErrorResponse::ValidationFailed)
+    }
+}
+
+pub fn packing_error(e: PackingError) -> ErrorResponse {
+    match e {
+        PackingError::MissingPart | PackingError::MissingProject => ErrorResponse::NotFound,
+        PackingError::ValidationFailed(errors) => ErrorResponse::ValidationFailed(errors),
+        PackingError::GeneralError(e) => {
+            error!("packing failed: {:?}", e);
+            ErrorResponse::InternalError
+        }
+    }
+}
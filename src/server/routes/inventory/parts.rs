@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::http::{header, StatusCode};
 use axum::response::{IntoResponse, Response};
 use axum::Json;
@@ -8,9 +8,19 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::inventory::app::service::{InventoryError, InventoryService};
+use crate::inventory::domain::classification::{
+    Category, Classification, PartFilter, RawClassification, Tag,
+};
+use crate::inventory::domain::geometry::Geometry;
+use crate::inventory::domain::metadata::{PartMetadata, RawPartMetadata};
 use crate::inventory::domain::name::Name;
 use crate::inventory::domain::part::{Part, PartId};
+use crate::inventory::domain::revision::PartRevisionNumber;
+use crate::inventory::domain::source::{PartSource, RawPartSource};
+use crate::inventory::domain::variant::Variant;
 use crate::server::rest::ErrorResponse;
+use crate::settings::app::service::SettingsService;
+use crate::settings::domain::costs::{Cost, CostSettings};
 use crate::shared::validation::validator::CollectingValidator;
 
 pub async fn register_part(
@@ -27,16 +37,150 @@ pub async fn register_part(
 
 pub async fn view_part(
     State(inventory): State<Arc<dyn InventoryService>>,
+    State(settings): State<Arc<dyn SettingsService>>,
     Path(part_id): Path<Uuid>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
+    let costs = cost_settings(settings.as_ref()).await?;
     let result = inventory.view_part(part_id).await;
     match result {
-        Ok(part) => Ok(PartView::from(part)),
+        Ok(part) => Ok(PartView::new(part, costs.as_ref())),
         Err(InventoryError::MissingPart) => Err(ErrorResponse::NotFound),
+        Err(InventoryError::ValidationFailed(errors)) => {
+            Err(ErrorResponse::ValidationFailed(errors))
+        }
+        Err(InventoryError::GeneralError(_)) => Err(ErrorResponse::InternalError),
+    }
+}
+
+pub async fn rename_part(
+    State(inventory): State<Arc<dyn InventoryService>>,
+    Path(part_id): Path<Uuid>,
+    Json(command): Json<RegisterPartCommand>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let name = parse_register_part_command(command)?;
+    let result = inventory.rename_part(part_id, name).await;
+    match result {
+        Ok(_) => Ok(StatusCode::OK),
+        Err(InventoryError::MissingPart) => Err(ErrorResponse::NotFound),
+        Err(InventoryError::ValidationFailed(errors)) => {
+            Err(ErrorResponse::ValidationFailed(errors))
+        }
+        Err(InventoryError::GeneralError(_)) => Err(ErrorResponse::InternalError),
+    }
+}
+
+/// Replaces printing properties of the part. Missing properties are cleared.
+pub async fn describe_part(
+    State(inventory): State<Arc<dyn InventoryService>>,
+    Path(part_id): Path<Uuid>,
+    Json(command): Json<RawPartMetadata>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let metadata = PartMetadata::parse(command).map_err(ErrorResponse::ValidationFailed)?;
+    let result = inventory.describe_part(part_id, metadata).await;
+    match result {
+        Ok(_) => Ok(StatusCode::OK),
+        Err(InventoryError::MissingPart) => Err(ErrorResponse::NotFound),
+        Err(InventoryError::ValidationFailed(errors)) => {
+            Err(ErrorResponse::ValidationFailed(errors))
+        }
+        Err(InventoryError::GeneralError(_)) => Err(ErrorResponse::InternalError),
+    }
+}
+
+/// Replaces tags and the category of the part. Missing values are cleared.
+pub async fn classify_part(
+    State(inventory): State<Arc<dyn InventoryService>>,
+    Path(part_id): Path<Uuid>,
+    Json(command): Json<RawClassification>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let classification = Classification::parse(command).map_err(ErrorResponse::ValidationFailed)?;
+    let result = inventory.classify_part(part_id, classification).await;
+    match result {
+        Ok(_) => Ok(StatusCode::OK),
+        Err(InventoryError::MissingPart) => Err(ErrorResponse::NotFound),
+        Err(InventoryError::ValidationFailed(errors)) => {
+            Err(ErrorResponse::ValidationFailed(errors))
+        }
         Err(InventoryError::GeneralError(_)) => Err(ErrorResponse::InternalError),
     }
 }
 
+/// Replaces the source URL, the designer, the license and the commercial use flag of the part.
+/// Missing values are cleared.
+pub async fn attribute_part(
+    State(inventory): State<Arc<dyn InventoryService>>,
+    Path(part_id): Path<Uuid>,
+    Json(command): Json<RawPartSource>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let source = PartSource::parse(command).map_err(ErrorResponse::ValidationFailed)?;
+    let result = inventory.attribute_part(part_id, source).await;
+    match result {
+        Ok(_) => Ok(StatusCode::OK),
+        Err(InventoryError::MissingPart) => Err(ErrorResponse::NotFound),
+        Err(InventoryError::ValidationFailed(errors)) => {
+            Err(ErrorResponse::ValidationFailed(errors))
+        }
+        Err(InventoryError::GeneralError(_)) => Err(ErrorResponse::InternalError),
+    }
+}
+
+/// Lists inventory parts.
+/// The `name` query parameter narrows the list down to the part with the given name.
+/// The `tag` and `category` parameters keep parts with the tag and parts of the category or
+/// of categories below it. The `variant_of` parameter keeps variants of the part.
+pub async fn list_parts(
+    State(inventory): State<Arc<dyn InventoryService>>,
+    State(settings): State<Arc<dyn SettingsService>>,
+    Query(query): Query<PartsQuery>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let costs = cost_settings(settings.as_ref()).await?;
+    let (name, filter) = parse_parts_query(query)?;
+    let result = match name {
+        Some(name) => inventory.find_part_by_name(&name).await.map(|part| {
+            part.into_iter()
+                .filter(|part| filter.matches(part))
+                .collect()
+        }),
+        None => inventory.list_parts(&filter).await,
+    };
+    match result {
+        Ok(parts) => Ok(Json(
+            parts
+                .into_iter()
+                .map(|part| PartView::new(part, costs.as_ref()))
+                .collect::<Vec<_>>(),
+        )),
+        Err(_) => Err(ErrorResponse::InternalError),
+    }
+}
+
+async fn cost_settings(
+    settings: &dyn SettingsService,
+) -> Result<Option<CostSettings>, ErrorResponse> {
+    settings
+        .cost_settings()
+        .await
+        .map_err(|_| ErrorResponse::InternalError)
+}
+
+fn parse_parts_query(query: PartsQuery) -> Result<(Option<Name>, PartFilter), ErrorResponse> {
+    let mut validator = CollectingValidator::default();
+    let name = query.name.map(|n| validator.parse_string::<Name>(n));
+    let tag = query.tag.map(|t| validator.parse_string::<Tag>(t));
+    let category = query
+        .category
+        .map(|c| validator.parse_string::<Category>(c));
+    if validator.has_errors() {
+        return Err(ErrorResponse::ValidationFailed(validator.into_errors()));
+    }
+    let filter = PartFilter {
+        tag: tag.transpose().unwrap(),
+        category: category.transpose().unwrap(),
+        variant_of: query.variant_of,
+    };
+    Ok((name.transpose().unwrap(), filter))
+}
+
 fn parse_register_part_command(payload: RegisterPartCommand) -> Result<Name, ErrorResponse> {
     let mut validator = CollectingValidator::default();
     let name = validator.parse_string::<Name>(payload.name);
@@ -53,6 +197,14 @@ pub struct RegisterPartCommand {
     name: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct PartsQuery {
+    name: Option<String>,
+    tag: Option<String>,
+    category: Option<String>,
+    variant_of: Option<PartId>,
+}
+
 #[derive(Debug, Serialize)]
 struct RegisterPartResponse {
     id: PartId,
@@ -62,13 +214,47 @@ struct RegisterPartResponse {
 struct PartView {
     id: PartId,
     name: String,
+    /// The latest revision of the part.
+    revision: PartRevisionNumber,
+    #[serde(flatten)]
+    metadata: PartMetadata,
+    #[serde(flatten)]
+    classification: Classification,
+    #[serde(flatten)]
+    source: PartSource,
+    /// Measured from the last attached STL mesh.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    geometry: Option<Geometry>,
+    /// Parent part and attributes of a variant.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    variant: Option<Variant>,
+    /// Cost of printing the part. Calculated when prices are configured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cost: Option<Cost>,
 }
 
-impl From<Part> for PartView {
-    fn from(value: Part) -> Self {
+impl PartView {
+    fn new(part: Part, costs: Option<&CostSettings>) -> Self {
+        let metadata = part.metadata().clone();
+        let cost = costs.map(|costs| {
+            costs.cost(
+                metadata.grams.map(|g| g.value()).unwrap_or_default(),
+                metadata
+                    .minutes
+                    .map(|m| f64::from(m.value()))
+                    .unwrap_or_default(),
+            )
+        });
         Self {
-            id: value.id(),
-            name: value.name().to_string(),
+            id: part.id(),
+            name: part.name().to_string(),
+            revision: part.revision(),
+            metadata,
+            classification: part.classification().clone(),
+            source: part.source().clone(),
+            geometry: part.geometry().copied(),
+            variant: part.variant().cloned(),
+            cost,
         }
     }
 }
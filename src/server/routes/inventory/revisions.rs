@@ -0,0 +1,50 @@
+//! Starts and lists revisions of parts.
+
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::Serialize;
+
+use crate::inventory::app::service::{InventoryError, InventoryService};
+use crate::inventory::domain::part::PartId;
+use crate::inventory::domain::revision::PartRevisionNumber;
+use crate::server::rest::ErrorResponse;
+
+/// Starts a new revision of the part and responds with its number.
+pub async fn revise_part(
+    State(inventory): State<Arc<dyn InventoryService>>,
+    Path(part_id): Path<PartId>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let revision = inventory
+        .revise_part(part_id)
+        .await
+        .map_err(error_response)?;
+    Ok(Json(RevisedPart { revision }))
+}
+
+/// Lists revisions of the part with their printing properties, the latest first.
+pub async fn list_part_revisions(
+    State(inventory): State<Arc<dyn InventoryService>>,
+    Path(part_id): Path<PartId>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let revisions = inventory
+        .list_revisions(part_id)
+        .await
+        .map_err(error_response)?;
+    Ok(Json(revisions))
+}
+
+fn error_response(error: InventoryError) -> ErrorResponse {
+    match error {
+        InventoryError::MissingPart => ErrorResponse::NotFound,
+        InventoryError::ValidationFailed(errors) => ErrorResponse::ValidationFailed(errors),
+        InventoryError::GeneralError(_) => ErrorResponse::InternalError,
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct RevisedPart {
+    revision: PartRevisionNumber,
+}
@@ -0,0 +1,109 @@
+//! Registers variants of parts and links existing parts to their parents.
+
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use crate::inventory::app::service::{InventoryError, InventoryService};
+use crate::inventory::domain::name::Name;
+use crate::inventory::domain::part::PartId;
+use crate::inventory::domain::variant::{RawVariantAttributes, Variant, VariantAttributes};
+use crate::server::rest::ErrorResponse;
+use crate::shared::validation::validator::CollectingValidator;
+
+/// Registers a variant of the part. The variant takes printing properties, tags, the category
+/// and the source of the part. Variants are listed with `GET /v1/inventory/parts?variant_of=`.
+pub async fn register_variant(
+    State(inventory): State<Arc<dyn InventoryService>>,
+    Path(part_id): Path<PartId>,
+    Json(command): Json<RegisterVariantCommand>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let mut validator = CollectingValidator::default();
+    let name = command.name.map(|n| validator.parse_string::<Name>(n));
+    let attributes = VariantAttributes::parse(command.attributes);
+    if let Err(errors) = &attributes {
+        errors.iter().cloned().for_each(|e| validator.report(e));
+    }
+    if validator.has_errors() {
+        return Err(ErrorResponse::ValidationFailed(validator.into_errors()));
+    }
+    let id = inventory
+        .register_variant(part_id, name.transpose().unwrap(), attributes.unwrap())
+        .await
+        .map_err(error_response)?;
+    Ok(RegisteredVariant { id })
+}
+
+/// Makes the part a variant of the parent part given in the payload.
+pub async fn define_variant(
+    State(inventory): State<Arc<dyn InventoryService>>,
+    Path(part_id): Path<PartId>,
+    Json(command): Json<DefineVariantCommand>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let attributes =
+        VariantAttributes::parse(command.attributes).map_err(ErrorResponse::ValidationFailed)?;
+    let variant = Variant {
+        parent: command.parent,
+        attributes,
+    };
+    inventory
+        .define_variant(part_id, Some(variant))
+        .await
+        .map_err(error_response)?;
+    Ok(StatusCode::OK)
+}
+
+/// Turns the variant back into a standalone part.
+pub async fn remove_variant(
+    State(inventory): State<Arc<dyn InventoryService>>,
+    Path(part_id): Path<PartId>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    inventory
+        .define_variant(part_id, None)
+        .await
+        .map_err(error_response)?;
+    Ok(StatusCode::OK)
+}
+
+fn error_response(error: InventoryError) -> ErrorResponse {
+    match error {
+        InventoryError::MissingPart => ErrorResponse::NotFound,
+        InventoryError::ValidationFailed(errors) => ErrorResponse::ValidationFailed(errors),
+        InventoryError::GeneralError(_) => ErrorResponse::InternalError,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RegisterVariantCommand {
+    /// Defaults to the parent name with variant attributes.
+    name: Option<String>,
+    #[serde(flatten)]
+    attributes: RawVariantAttributes,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DefineVariantCommand {
+    parent: PartId,
+    #[serde(flatten)]
+    attributes: RawVariantAttributes,
+}
+
+#[derive(Debug, Serialize)]
+struct RegisteredVariant {
+    id: PartId,
+}
+
+impl IntoResponse for RegisteredVariant {
+    fn into_response(self) -> Response {
+        (
+            StatusCode::CREATED,
+            [(header::LOCATION, format!("/v1/inventory/parts/{}", self.id))],
+            Json(self),
+        )
+            .into_response()
+    }
+}
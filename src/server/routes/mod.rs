@@ -1,5 +1,8 @@
 //! Route configuration and handlers.
+pub mod activity;
+pub mod filament;
 pub mod health;
 pub mod inventory;
 pub mod project;
 pub mod router;
+pub mod settings;
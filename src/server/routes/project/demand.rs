@@ -0,0 +1,36 @@
+//! Reports parts needed by all projects.
+
+use std::sync::Arc;
+
+use axum::extract::{Query, State};
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::Deserialize;
+
+use crate::projects::app::service::ProjectsService;
+use crate::projects::view::demand::DemandGrouping;
+use crate::server::rest::ErrorResponse;
+
+/// Lists copies of parts needed by regular projects, per variant by default.
+/// Variants are added up under their parent part with `?group_by=parent`.
+pub async fn part_demand(
+    State(project_service): State<Arc<dyn ProjectsService>>,
+    Query(params): Query<DemandParams>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let grouping = params
+        .group_by
+        .map(|g| DemandGrouping::parse(&g))
+        .transpose()
+        .map_err(|e| ErrorResponse::ValidationFailed(vec![e]))?
+        .unwrap_or_default();
+    let demand = project_service
+        .part_demand(grouping)
+        .await
+        .map_err(|_| ErrorResponse::InternalError)?;
+    Ok(Json(demand))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DemandParams {
+    group_by: Option<String>,
+}
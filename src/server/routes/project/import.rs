@@ -0,0 +1,42 @@
+//! Imports project BOM from CSV files.
+
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::Deserialize;
+use tracing::error;
+
+use crate::projects::app::import::{BomImportError, BomImportOptions, BomImporter};
+use crate::projects::domain::project::ProjectId;
+use crate::server::rest::ErrorResponse;
+
+pub async fn import_project_bom(
+    State(importer): State<Arc<dyn BomImporter>>,
+    Path(project_id): Path<ProjectId>,
+    Query(params): Query<ImportParams>,
+    csv: String,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let options = BomImportOptions {
+        dry_run: params.dry_run,
+        register_missing: params.register_missing,
+    };
+    let result = importer.import_csv(project_id, &csv, options).await;
+    match result {
+        Ok(report) => Ok(Json(report)),
+        Err(BomImportError::MissingProject) => Err(ErrorResponse::NotFound),
+        Err(BomImportError::InvalidBom(errors)) => Err(ErrorResponse::ValidationFailed(errors)),
+        Err(BomImportError::GeneralError(e)) => {
+            error!("BOM import failed: {:?}", e);
+            Err(ErrorResponse::InternalError)
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ImportParams {
+    dry_run: bool,
+    register_missing: bool,
+}
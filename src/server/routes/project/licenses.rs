@@ -0,0 +1,25 @@
+//! Reports licenses of parts in the project BOM.
+
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::response::IntoResponse;
+use axum::Json;
+
+use crate::projects::app::service::{ProjectError, ProjectsService};
+use crate::projects::domain::project::ProjectId;
+use crate::server::rest::ErrorResponse;
+
+/// Lists the source, designer and license of every part in the project BOM.
+/// Parts forbidding commercial use or with an unknown license are counted, so the report tells
+/// whether prints of the project may be sold.
+pub async fn project_licenses(
+    State(project_service): State<Arc<dyn ProjectsService>>,
+    Path(project_id): Path<ProjectId>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    match project_service.license_report(project_id).await {
+        Ok(report) => Ok(Json(report)),
+        Err(ProjectError::MissingProject) => Err(ErrorResponse::NotFound),
+        Err(_) => Err(ErrorResponse::InternalError),
+    }
+}
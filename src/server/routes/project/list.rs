@@ -0,0 +1,18 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+
+use crate::projects::app::service::ProjectsService;
+use crate::server::rest::ErrorResponse;
+
+pub async fn list_projects(
+    State(project_service): State<Arc<dyn ProjectsService>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let result = project_service.list_projects().await;
+    match result {
+        Ok(projects) => Ok(Json(projects)),
+        Err(_) => Err(ErrorResponse::InternalError),
+    }
+}
@@ -1,3 +1,12 @@
+pub mod demand;
+pub mod import;
+pub mod licenses;
+pub mod list;
 pub mod parts;
+pub mod plates;
+pub mod progress;
 pub mod register;
+pub mod revisions;
+pub mod template;
+pub mod upgrade;
 pub mod view;
@@ -9,7 +9,7 @@ use axum::Json;
 use serde::Deserialize;
 
 use crate::inventory::domain::part::PartId;
-use crate::projects::app::service::ProjectsService;
+use crate::projects::app::service::{ProjectError, ProjectsService};
 use crate::projects::domain::project::{ProjectId, ProjectPart};
 use crate::server::rest::ErrorResponse;
 
@@ -26,6 +26,8 @@ pub async fn define_project_bom(
     let result = project_service.set_project_bom(project_id, bom).await;
     match result {
         Ok(_) => Ok(StatusCode::OK),
+        Err(ProjectError::MissingProject) => Err(ErrorResponse::NotFound),
+        Err(ProjectError::ValidationFailed(errors)) => Err(ErrorResponse::ValidationFailed(errors)),
         Err(_) => Err(ErrorResponse::InternalError),
     }
 }
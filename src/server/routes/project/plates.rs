@@ -0,0 +1,26 @@
+//! Proposes plates for parts left to print in a project.
+
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::response::IntoResponse;
+use axum::Json;
+
+use crate::projects::app::packing::PackingService;
+use crate::projects::domain::project::ProjectId;
+use crate::server::rest::ErrorResponse;
+use crate::server::routes::inventory::packing::{packing_error, PackingParams};
+
+/// Packs copies left to print on mixed plates of the bed given as `?bed=250x210`.
+pub async fn plan_project_plates(
+    State(packing): State<Arc<dyn PackingService>>,
+    Path(project_id): Path<ProjectId>,
+    Query(params): Query<PackingParams>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let options = params.parse()?;
+    packing
+        .plan_plates(project_id, options)
+        .await
+        .map(Json)
+        .map_err(packing_error)
+}
@@ -0,0 +1,36 @@
+//! Reports printing progress of project parts.
+
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::Deserialize;
+
+use crate::inventory::domain::part::PartId;
+use crate::projects::app::service::{ProjectError, ProjectsService};
+use crate::projects::domain::project::ProjectId;
+use crate::server::rest::ErrorResponse;
+
+pub async fn report_progress(
+    State(project_service): State<Arc<dyn ProjectsService>>,
+    Path(project_id): Path<ProjectId>,
+    Json(payload): Json<ProgressPayload>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let result = project_service
+        .report_progress(project_id, payload.part, payload.printed)
+        .await;
+    match result {
+        Ok(_) => Ok(StatusCode::OK),
+        Err(ProjectError::MissingProject) => Err(ErrorResponse::NotFound),
+        Err(ProjectError::ValidationFailed(errors)) => Err(ErrorResponse::ValidationFailed(errors)),
+        Err(_) => Err(ErrorResponse::InternalError),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProgressPayload {
+    part: PartId,
+    printed: u32,
+}
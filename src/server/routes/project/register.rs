@@ -33,6 +33,12 @@ pub async fn register_project(
     }
 }
 
+impl ProjectCreatedResponse {
+    pub fn new(id: ProjectId) -> Self {
+        Self { id }
+    }
+}
+
 impl IntoResponse for ProjectCreatedResponse {
     fn into_response(self) -> axum::response::Response {
         (
@@ -0,0 +1,66 @@
+//! Lists, compares and restores revisions of the project BOM.
+
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::Deserialize;
+
+use crate::projects::app::revisions::{BomRevisionError, BomRevisionService};
+use crate::projects::domain::project::ProjectId;
+use crate::projects::domain::revision::RevisionNumber;
+use crate::server::rest::ErrorResponse;
+
+/// Lists BOM revisions of the project, the most recent first.
+pub async fn list_bom_revisions(
+    State(revisions): State<Arc<dyn BomRevisionService>>,
+    Path(project_id): Path<ProjectId>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let revisions = revisions
+        .list_revisions(project_id)
+        .await
+        .map_err(error_response)?;
+    Ok(Json(revisions))
+}
+
+/// Compares BOMs of revisions given by `from` and `to` query parameters.
+pub async fn diff_bom_revisions(
+    State(revisions): State<Arc<dyn BomRevisionService>>,
+    Path(project_id): Path<ProjectId>,
+    Query(params): Query<DiffParams>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let diff = revisions
+        .diff_revisions(project_id, params.from, params.to)
+        .await
+        .map_err(error_response)?;
+    Ok(Json(diff))
+}
+
+/// Replaces the project BOM with the BOM of the revision.
+pub async fn restore_bom_revision(
+    State(revisions): State<Arc<dyn BomRevisionService>>,
+    Path((project_id, revision)): Path<(ProjectId, RevisionNumber)>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    revisions
+        .restore_revision(project_id, revision)
+        .await
+        .map_err(error_response)?;
+    Ok(StatusCode::OK)
+}
+
+fn error_response(error: BomRevisionError) -> ErrorResponse {
+    match error {
+        BomRevisionError::MissingProject | BomRevisionError::MissingRevision => {
+            ErrorResponse::NotFound
+        }
+        BomRevisionError::GeneralError(_) => ErrorResponse::InternalError,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DiffParams {
+    from: RevisionNumber,
+    to: RevisionNumber,
+}
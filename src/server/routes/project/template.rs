@@ -0,0 +1,95 @@
+//! Duplicates projects and creates projects from templates.
+
+use std::sync::Arc;
+
+use axum::extract::rejection::JsonRejection;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::Deserialize;
+
+use crate::projects::app::service::{ProjectError, ProjectsService};
+use crate::projects::domain::multiplier::Multiplier;
+use crate::projects::domain::name::Name;
+use crate::projects::domain::project::ProjectId;
+use crate::server::rest::{optional_payload, ErrorResponse};
+use crate::server::routes::project::register::ProjectCreatedResponse;
+use crate::shared::validation::validator::CollectingValidator;
+
+/// Copies the project with its BOM. The request payload is optional, but malformed payloads
+/// are rejected.
+pub async fn duplicate_project(
+    State(project_service): State<Arc<dyn ProjectsService>>,
+    Path(project_id): Path<ProjectId>,
+    payload: Result<Json<DuplicateProjectCommand>, JsonRejection>,
+) -> Result<ProjectCreatedResponse, ErrorResponse> {
+    let name = optional_payload(payload)?.and_then(|command| command.name);
+    let name = name
+        .map(Name::parse)
+        .transpose()
+        .map_err(|e| ErrorResponse::ValidationFailed(vec![e]))?;
+    project_service
+        .duplicate_project(project_id, name)
+        .await
+        .map(ProjectCreatedResponse::new)
+        .map_err(error_response)
+}
+
+/// Marks the project as a template or turns it back into a regular project.
+pub async fn mark_template(
+    State(project_service): State<Arc<dyn ProjectsService>>,
+    Path(project_id): Path<ProjectId>,
+    Json(command): Json<MarkTemplateCommand>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    project_service
+        .mark_template(project_id, command.template)
+        .await
+        .map_err(error_response)?;
+    Ok(StatusCode::OK)
+}
+
+/// Creates a new project from the template.
+pub async fn instantiate_template(
+    State(project_service): State<Arc<dyn ProjectsService>>,
+    Path(project_id): Path<ProjectId>,
+    Json(command): Json<InstantiateTemplateCommand>,
+) -> Result<ProjectCreatedResponse, ErrorResponse> {
+    let mut validator = CollectingValidator::default();
+    let multiplier = validator.parse::<Multiplier, _>(command.multiplier.unwrap_or(1));
+    let name = command
+        .name
+        .map(|name| validator.parse_string::<Name>(name));
+    if validator.has_errors() {
+        return Err(ErrorResponse::ValidationFailed(validator.into_errors()));
+    }
+    project_service
+        .instantiate_template(project_id, multiplier.unwrap(), name.map(Result::unwrap))
+        .await
+        .map(ProjectCreatedResponse::new)
+        .map_err(error_response)
+}
+
+fn error_response(error: ProjectError) -> ErrorResponse {
+    match error {
+        ProjectError::MissingProject => ErrorResponse::NotFound,
+        ProjectError::ValidationFailed(errors) => ErrorResponse::ValidationFailed(errors),
+        ProjectError::GeneralError(_) => ErrorResponse::InternalError,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DuplicateProjectCommand {
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MarkTemplateCommand {
+    template: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InstantiateTemplateCommand {
+    multiplier: Option<u32>,
+    name: Option<String>,
+}
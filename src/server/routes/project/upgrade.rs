@@ -0,0 +1,62 @@
+//! Reports projects using outdated part revisions and upgrades their BOMs.
+
+use std::sync::Arc;
+
+use axum::extract::rejection::JsonRejection;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::Deserialize;
+
+use crate::inventory::domain::part::PartId;
+use crate::projects::app::service::{ProjectError, ProjectsService};
+use crate::projects::domain::project::ProjectId;
+use crate::server::rest::{optional_payload, ErrorResponse};
+
+/// Lists projects with BOM lines pinned to older part revisions.
+/// The `part` query parameter keeps only projects using an older revision of the part.
+pub async fn list_outdated_projects(
+    State(project_service): State<Arc<dyn ProjectsService>>,
+    Query(params): Query<OutdatedParams>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let projects = project_service
+        .list_outdated(params.part)
+        .await
+        .map_err(error_response)?;
+    Ok(Json(projects))
+}
+
+/// Pins the latest part revisions in the project BOM. The request payload is optional and
+/// narrows the upgrade down to a single part. Malformed payloads are rejected, so they never
+/// upgrade the whole BOM by accident.
+pub async fn upgrade_project_parts(
+    State(project_service): State<Arc<dyn ProjectsService>>,
+    Path(project_id): Path<ProjectId>,
+    payload: Result<Json<UpgradePartsCommand>, JsonRejection>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let part = optional_payload(payload)?.and_then(|command| command.part);
+    project_service
+        .upgrade_parts(project_id, part)
+        .await
+        .map_err(error_response)?;
+    Ok(StatusCode::OK)
+}
+
+fn error_response(error: ProjectError) -> ErrorResponse {
+    match error {
+        ProjectError::MissingProject => ErrorResponse::NotFound,
+        ProjectError::ValidationFailed(errors) => ErrorResponse::ValidationFailed(errors),
+        ProjectError::GeneralError(_) => ErrorResponse::InternalError,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OutdatedParams {
+    part: Option<PartId>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpgradePartsCommand {
+    part: Option<PartId>,
+}
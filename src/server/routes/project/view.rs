@@ -1,22 +1,149 @@
 use std::sync::Arc;
 
-use axum::extract::{Path, State};
-use axum::response::IntoResponse;
+use axum::extract::{Path, Query, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
 use axum::Json;
+use serde::Deserialize;
+use tracing::error;
 use uuid::Uuid;
 
 use crate::projects::app::service::ProjectError;
 use crate::projects::app::service::ProjectsService;
+use crate::projects::view::export::{render, ExportFormat};
+use crate::projects::view::project::{BomGrouping, ProjectView};
 use crate::server::rest::ErrorResponse;
+use crate::shared::validation::error::ValidationError;
 
+/// Responds with the project view.
+/// The representation is negotiated with the `Accept` header. JSON is the default.
+/// JSON views list BOM lines grouped by the category of their parts with `?group_by=category`,
+/// or variants grouped under their parent part with `?group_by=parent`.
+/// Other representations can't be grouped.
 pub async fn view_project(
     State(project_service): State<Arc<dyn ProjectsService>>,
     Path(project_id): Path<Uuid>,
-) -> Result<impl IntoResponse, ErrorResponse> {
-    let project = project_service.view_project(project_id).await;
-    match project {
-        Ok(p) => Ok(Json(p)),
+    Query(params): Query<ViewParams>,
+    headers: HeaderMap,
+) -> Result<Response, ErrorResponse> {
+    let format = match headers.get(header::ACCEPT) {
+        Some(accept) => accept
+            .to_str()
+            .ok()
+            .and_then(ExportFormat::from_accept)
+            .ok_or(ErrorResponse::NotAcceptable)?,
+        None => ExportFormat::Json,
+    };
+    let grouping = parse_grouping(params.group_by, format)?;
+    let project = find_project(project_service.as_ref(), project_id).await?;
+    match format {
+        ExportFormat::Json => match grouping {
+            Some(grouping) => Ok(Json(project.grouped(grouping)).into_response()),
+            None => Ok(Json(project).into_response()),
+        },
+        format => export(&project, format, None),
+    }
+}
+
+/// Responds with filament and print time totals of the project.
+pub async fn project_summary(
+    State(project_service): State<Arc<dyn ProjectsService>>,
+    Path(project_id): Path<Uuid>,
+) -> Result<Response, ErrorResponse> {
+    let project = find_project(project_service.as_ref(), project_id).await?;
+    Ok(Json(project.summary()).into_response())
+}
+
+/// Downloads the project view as a file in the format given by the `format` query parameter.
+/// Unknown formats are invalid requests, unlike unsupported `Accept` headers of views.
+/// JSON files can be grouped with `group_by` like project views.
+pub async fn export_project(
+    State(project_service): State<Arc<dyn ProjectsService>>,
+    Path(project_id): Path<Uuid>,
+    Query(params): Query<ExportParams>,
+) -> Result<Response, ErrorResponse> {
+    let format = params.format.parse::<ExportFormat>().map_err(|e| {
+        ErrorResponse::ValidationFailed(vec![ValidationError::new(
+            "format",
+            "project.export.unsupported-format",
+            e,
+        )])
+    })?;
+    let grouping = parse_grouping(params.group_by, format)?;
+    let mut project = find_project(project_service.as_ref(), project_id).await?;
+    if let Some(grouping) = grouping {
+        project = project.grouped(grouping);
+    }
+    let file_name = format!("project-{}.{}", project.id(), format.file_extension());
+    export(&project, format, Some(file_name))
+}
+
+/// Parses the grouping of BOM lines, which only JSON representations can show.
+fn parse_grouping(
+    group_by: Option<String>,
+    format: ExportFormat,
+) -> Result<Option<BomGrouping>, ErrorResponse> {
+    let Some(group_by) = group_by else {
+        return Ok(None);
+    };
+    if format != ExportFormat::Json {
+        return Err(ErrorResponse::ValidationFailed(vec![ValidationError::new(
+            "group_by",
+            "project.group-by.unsupported-format",
+            "only JSON views of projects can be grouped",
+        )]));
+    }
+    BomGrouping::parse(&group_by)
+        .map(Some)
+        .map_err(|e| ErrorResponse::ValidationFailed(vec![e]))
+}
+
+async fn find_project(
+    project_service: &dyn ProjectsService,
+    project_id: Uuid,
+) -> Result<ProjectView, ErrorResponse> {
+    match project_service.view_project(project_id).await {
+        Ok(p) => Ok(p),
         Err(ProjectError::MissingProject) => Err(ErrorResponse::NotFound),
         Err(_) => Err(ErrorResponse::InternalError),
     }
 }
+
+fn export(
+    project: &ProjectView,
+    format: ExportFormat,
+    file_name: Option<String>,
+) -> Result<Response, ErrorResponse> {
+    let body = render(project, format).map_err(|e| {
+        error!("project export failed: {:?}", e);
+        ErrorResponse::InternalError
+    })?;
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        header::HeaderValue::from_static(format.content_type()),
+    );
+    if let Some(file_name) = file_name {
+        let disposition = format!("attachment; filename=\"{}\"", file_name);
+        if let Ok(value) = header::HeaderValue::from_str(&disposition) {
+            headers.insert(header::CONTENT_DISPOSITION, value);
+        }
+    }
+    Ok((StatusCode::OK, headers, body).into_response())
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ViewParams {
+    group_by: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportParams {
+    #[serde(default = "default_export_format")]
+    format: String,
+    group_by: Option<String>,
+}
+
+fn default_export_format() -> String {
+    "json".to_string()
+}
@@ -1,33 +1,171 @@
 //! Application router configuration.
 
-use std::sync::Arc;
-
-use crate::inventory::app::service::InventoryService;
-use crate::projects::app::service::ProjectsService;
+use crate::server::routes::activity::{activity_feed, project_history};
+use crate::server::routes::filament::{
+    consume_filament, list_spools, project_consumption, project_shortages, register_spool,
+    view_spool,
+};
 use crate::server::routes::health::health;
+use crate::server::routes::project::demand::part_demand;
+use crate::server::routes::project::import::import_project_bom;
+use crate::server::routes::project::licenses::project_licenses;
+use crate::server::routes::project::list::list_projects;
 use crate::server::routes::project::parts::define_project_bom;
+use crate::server::routes::project::plates::plan_project_plates;
+use crate::server::routes::project::progress::report_progress;
 use crate::server::routes::project::register::register_project;
-use crate::server::routes::project::view::view_project;
+use crate::server::routes::project::revisions::{
+    diff_bom_revisions, list_bom_revisions, restore_bom_revision,
+};
+use crate::server::routes::project::template::{
+    duplicate_project, instantiate_template, mark_template,
+};
+use crate::server::routes::project::upgrade::{list_outdated_projects, upgrade_project_parts};
+use crate::server::routes::project::view::{export_project, project_summary, view_project};
+use crate::server::routes::settings::{configure_costs, cost_settings};
+use crate::server::state::AppState;
+use axum::extract::{DefaultBodyLimit, Request};
+use axum::middleware::{self, Next};
+use axum::response::Response;
 use axum::routing::{get, post, put};
 use axum::Router;
 use tower::ServiceBuilder;
 use tower_http::trace::TraceLayer;
 
-use super::inventory::parts::{register_part, view_part};
+use crate::app::actor::{act_as, Actor};
+use crate::server::rest::ACTOR_HEADER;
+
+use super::inventory::files::{
+    download_part_file, list_part_files, part_thumbnail, upload_part_file,
+};
+use super::inventory::import::import_model;
+use super::inventory::packing::pack_part;
+use super::inventory::parts::{
+    attribute_part, classify_part, describe_part, list_parts, register_part, rename_part, view_part,
+};
+use super::inventory::revisions::{list_part_revisions, revise_part};
+use super::inventory::variants::{define_variant, register_variant, remove_variant};
+
+/// Largest model file accepted by uploads.
+const MAX_FILE_SIZE: usize = 512 * 1024 * 1024;
 
 /// Provide a default router for HTTP requests.
-pub fn router(
-    project_service: Arc<dyn ProjectsService>,
-    inventory: Arc<dyn InventoryService>,
-) -> Router {
+pub fn router(state: AppState) -> Router {
     Router::new()
         .route("/health", get(health))
-        .route("/v1/projects", post(register_project))
+        .route("/v1/activity", get(activity_feed))
+        .route("/v1/projects", post(register_project).get(list_projects))
+        .route("/v1/projects/demand", get(part_demand))
+        .route("/v1/projects/outdated", get(list_outdated_projects))
         .route("/v1/projects/:project_id", get(view_project))
+        .route(
+            "/v1/projects/:project_id/consumption",
+            get(project_consumption),
+        )
+        .route(
+            "/v1/projects/:project_id/duplicate",
+            post(duplicate_project),
+        )
+        .route("/v1/projects/:project_id/export", get(export_project))
+        .route("/v1/projects/:project_id/history", get(project_history))
+        .route("/v1/projects/:project_id/licenses", get(project_licenses))
         .route("/v1/projects/:project_id/parts", put(define_project_bom))
-        .with_state(Arc::clone(&project_service))
-        .route("/v1/inventory/parts", post(register_part))
-        .route("/v1/inventory/parts/:part_id", get(view_part))
-        .with_state(Arc::clone(&inventory))
-        .layer(ServiceBuilder::new().layer(TraceLayer::new_for_http()))
+        .route(
+            "/v1/projects/:project_id/parts/import",
+            post(import_project_bom),
+        )
+        .route("/v1/projects/:project_id/plates", post(plan_project_plates))
+        .route("/v1/projects/:project_id/progress", post(report_progress))
+        .route(
+            "/v1/projects/:project_id/revisions",
+            get(list_bom_revisions),
+        )
+        .route(
+            "/v1/projects/:project_id/revisions/diff",
+            get(diff_bom_revisions),
+        )
+        .route(
+            "/v1/projects/:project_id/revisions/:revision/restore",
+            post(restore_bom_revision),
+        )
+        .route("/v1/projects/:project_id/shortages", get(project_shortages))
+        .route("/v1/projects/:project_id/summary", get(project_summary))
+        .route("/v1/projects/:project_id/template", put(mark_template))
+        .route(
+            "/v1/projects/:project_id/upgrade",
+            post(upgrade_project_parts),
+        )
+        .route(
+            "/v1/projects/:project_id/instantiate",
+            post(instantiate_template),
+        )
+        .route("/v1/inventory/parts", post(register_part).get(list_parts))
+        .route(
+            "/v1/inventory/parts/import",
+            post(import_model).layer(DefaultBodyLimit::max(MAX_FILE_SIZE)),
+        )
+        .route(
+            "/v1/inventory/parts/:part_id",
+            get(view_part).put(rename_part),
+        )
+        .route(
+            "/v1/inventory/parts/:part_id/files",
+            post(upload_part_file)
+                .get(list_part_files)
+                .layer(DefaultBodyLimit::max(MAX_FILE_SIZE)),
+        )
+        .route(
+            "/v1/inventory/parts/:part_id/files/:file_id",
+            get(download_part_file),
+        )
+        .route(
+            "/v1/inventory/parts/:part_id/classification",
+            put(classify_part),
+        )
+        .route("/v1/inventory/parts/:part_id/metadata", put(describe_part))
+        .route("/v1/inventory/parts/:part_id/packing", post(pack_part))
+        .route("/v1/inventory/parts/:part_id/source", put(attribute_part))
+        .route(
+            "/v1/inventory/parts/:part_id/revisions",
+            post(revise_part).get(list_part_revisions),
+        )
+        .route(
+            "/v1/inventory/parts/:part_id/thumbnail",
+            get(part_thumbnail),
+        )
+        .route(
+            "/v1/inventory/parts/:part_id/variant",
+            put(define_variant).delete(remove_variant),
+        )
+        .route(
+            "/v1/inventory/parts/:part_id/variants",
+            post(register_variant),
+        )
+        .route(
+            "/v1/settings/costs",
+            get(cost_settings).put(configure_costs),
+        )
+        .route("/v1/filament/spools", post(register_spool).get(list_spools))
+        .route("/v1/filament/spools/:spool_id", get(view_spool))
+        .route(
+            "/v1/filament/spools/:spool_id/consumption",
+            post(consume_filament),
+        )
+        .with_state(state)
+        .layer(
+            ServiceBuilder::new()
+                .layer(TraceLayer::new_for_http())
+                .layer(middleware::from_fn(identify_actor)),
+        )
+}
+
+/// Runs the request on behalf of the actor named in the `X-Actor` header.
+async fn identify_actor(request: Request, next: Next) -> Response {
+    let actor = request
+        .headers()
+        .get(ACTOR_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(Actor::named)
+        .unwrap_or_else(Actor::anonymous);
+    act_as(actor, next.run(request)).await
 }
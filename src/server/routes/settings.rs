@@ -0,0 +1,35 @@
+//! Settings shared by all users.
+
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+
+use crate::server::rest::ErrorResponse;
+use crate::settings::app::service::SettingsService;
+use crate::settings::domain::costs::{CostSettings, RawCostSettings};
+
+/// Responds with prices used to calculate costs, or with 404 until prices are configured.
+pub async fn cost_settings(
+    State(settings): State<Arc<dyn SettingsService>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let costs = settings
+        .cost_settings()
+        .await
+        .map_err(|_| ErrorResponse::InternalError)?;
+    costs.map(Json).ok_or(ErrorResponse::NotFound)
+}
+
+/// Replaces prices used to calculate costs. Responds with the stored prices.
+pub async fn configure_costs(
+    State(settings): State<Arc<dyn SettingsService>>,
+    Json(command): Json<RawCostSettings>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let costs = CostSettings::parse(command).map_err(ErrorResponse::ValidationFailed)?;
+    settings
+        .configure_costs(costs)
+        .await
+        .map_err(|_| ErrorResponse::InternalError)?;
+    Ok(Json(costs))
+}
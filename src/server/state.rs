@@ -0,0 +1,109 @@
+//! Shared state of request handlers.
+//! Handlers extract only the services they use, e.g., `State<Arc<dyn ProjectsService>>`.
+
+use std::sync::Arc;
+
+use axum::extract::FromRef;
+
+use crate::activity::app::service::ActivityService;
+use crate::filament::app::service::FilamentService;
+use crate::infra::services::Services;
+use crate::inventory::app::files::PartFileService;
+use crate::inventory::app::service::InventoryService;
+use crate::projects::app::import::BomImporter;
+use crate::projects::app::model_import::ModelImporter;
+use crate::projects::app::packing::PackingService;
+use crate::projects::app::revisions::BomRevisionService;
+use crate::projects::app::service::ProjectsService;
+use crate::settings::app::service::SettingsService;
+
+#[derive(Clone)]
+pub struct AppState {
+    pub projects: Arc<dyn ProjectsService>,
+    pub inventory: Arc<dyn InventoryService>,
+    pub files: Arc<dyn PartFileService>,
+    pub bom_importer: Arc<dyn BomImporter>,
+    pub model_importer: Arc<dyn ModelImporter>,
+    pub revisions: Arc<dyn BomRevisionService>,
+    pub packing: Arc<dyn PackingService>,
+    pub activity: Arc<dyn ActivityService>,
+    pub filament: Arc<dyn FilamentService>,
+    pub settings: Arc<dyn SettingsService>,
+}
+
+impl From<&Services> for AppState {
+    fn from(value: &Services) -> Self {
+        Self {
+            projects: Arc::clone(&value.projects),
+            inventory: Arc::clone(&value.inventory),
+            files: Arc::clone(&value.files),
+            bom_importer: Arc::clone(&value.bom_importer),
+            model_importer: Arc::clone(&value.model_importer),
+            revisions: Arc::clone(&value.revisions),
+            packing: Arc::clone(&value.packing),
+            activity: Arc::clone(&value.activity),
+            filament: Arc::clone(&value.filament),
+            settings: Arc::clone(&value.settings),
+        }
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn ProjectsService> {
+    fn from_ref(input: &AppState) -> Self {
+        Arc::clone(&input.projects)
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn InventoryService> {
+    fn from_ref(input: &AppState) -> Self {
+        Arc::clone(&input.inventory)
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn PartFileService> {
+    fn from_ref(input: &AppState) -> Self {
+        Arc::clone(&input.files)
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn BomImporter> {
+    fn from_ref(input: &AppState) -> Self {
+        Arc::clone(&input.bom_importer)
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn ModelImporter> {
+    fn from_ref(input: &AppState) -> Self {
+        Arc::clone(&input.model_importer)
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn BomRevisionService> {
+    fn from_ref(input: &AppState) -> Self {
+        Arc::clone(&input.revisions)
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn PackingService> {
+    fn from_ref(input: &AppState) -> Self {
+        Arc::clone(&input.packing)
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn ActivityService> {
+    fn from_ref(input: &AppState) -> Self {
+        Arc::clone(&input.activity)
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn FilamentService> {
+    fn from_ref(input: &AppState) -> Self {
+        Arc::clone(&input.filament)
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn SettingsService> {
+    fn from_ref(input: &AppState) -> Self {
+        Arc::clone(&input.settings)
+    }
+}
@@ -0,0 +1,41 @@
+//! Reads and changes settings.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::info;
+
+use crate::settings::domain::costs::CostSettings;
+use crate::settings::domain::repository::SettingsRepository;
+
+#[async_trait]
+pub trait SettingsService: Send + Sync {
+    /// Prices used to calculate costs. Returns Ok(None) until prices are configured.
+    async fn cost_settings(&self) -> anyhow::Result<Option<CostSettings>>;
+
+    /// Replaces prices used to calculate costs.
+    async fn configure_costs(&self, settings: CostSettings) -> anyhow::Result<()>;
+}
+
+pub struct DefaultSettingsService {
+    settings_repo: Arc<dyn SettingsRepository>,
+}
+
+impl DefaultSettingsService {
+    pub fn new(settings_repo: Arc<dyn SettingsRepository>) -> Self {
+        Self { settings_repo }
+    }
+}
+
+#[async_trait]
+impl SettingsService for DefaultSettingsService {
+    async fn cost_settings(&self) -> anyhow::Result<Option<CostSettings>> {
+        self.settings_repo.cost_settings().await
+    }
+
+    async fn configure_costs(&self, settings: CostSettings) -> anyhow::Result<()> {
+        self.settings_repo.save_cost_settings(settings).await?;
+        info!("cost settings changed");
+        Ok(())
+    }
+}
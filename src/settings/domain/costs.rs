@@ -0,0 +1,188 @@
+//! Prices used to calculate costs of printing parts.
+//!
+//! Printing a part costs the filament it's made of, the electricity the printer draws while
+//! printing and, optionally, wear of the machine per printing hour.
+
+use serde::{Deserialize, Serialize};
+
+use crate::shared::rounding::round;
+use crate::shared::validation::error::ValidationError;
+use crate::shared::validation::validator::CollectingValidator;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct CostSettings {
+    filament_price_per_kg: f64,
+    printer_power_watts: f64,
+    electricity_price_per_kwh: f64,
+    machine_wear_per_hour: f64,
+}
+
+/// Unvalidated cost settings, e.g., from request payloads or storage.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct RawCostSettings {
+    pub filament_price_per_kg: f64,
+    pub printer_power_watts: f64,
+    pub electricity_price_per_kwh: f64,
+    /// Machine wear is not charged when missing.
+    pub machine_wear_per_hour: Option<f64>,
+}
+
+/// Cost split into its components. Amounts are rounded to cents.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Cost {
+    pub filament: f64,
+    pub electricity: f64,
+    pub machine_wear: f64,
+    pub total: f64,
+}
+
+impl CostSettings {
+    /// Validates all prices, reporting every invalid one.
+    pub fn parse(raw: RawCostSettings) -> Result<Self, Vec<ValidationError>> {
+        let mut validator = CollectingValidator::default();
+        let filament_price_per_kg = price(
+            &mut validator,
+            "filament_price_per_kg",
+            "costs.filament-price.out-of-range",
+            raw.filament_price_per_kg,
+            10_000.0,
+        );
+        let printer_power_watts = price(
+            &mut validator,
+            "printer_power_watts",
+            "costs.printer-power.out-of-range",
+            raw.printer_power_watts,
+            10_000.0,
+        );
+        let electricity_price_per_kwh = price(
+            &mut validator,
+            "electricity_price_per_kwh",
+            "costs.electricity-price.out-of-range",
+            raw.electricity_price_per_kwh,
+            100.0,
+        );
+        let machine_wear_per_hour = price(
+            &mut validator,
+            "machine_wear_per_hour",
+            "costs.machine-wear.out-of-range",
+            raw.machine_wear_per_hour.unwrap_or_default(),
+            10_000.0,
+        );
+        if validator.has_errors() {
+            return Err(validator.into_errors());
+        }
+        Ok(Self {
+            filament_price_per_kg,
+            printer_power_watts,
+            electricity_price_per_kwh,
+            machine_wear_per_hour,
+        })
+    }
+
+    /// Calculates the cost of printing `grams` of filament for `minutes`.
+    pub fn cost(&self, grams: f64, minutes: f64) -> Cost {
+        let hours = minutes / 60.0;
+        let filament = grams / 1000.0 * self.filament_price_per_kg;
+        let electricity =
+            hours * self.printer_power_watts / 1000.0 * self.electricity_price_per_kwh;
+        let machine_wear = hours * self.machine_wear_per_hour;
+        Cost {
+            filament: round(filament),
+            electricity: round(electricity),
+            machine_wear: round(machine_wear),
+            total: round(filament + electricity + machine_wear),
+        }
+    }
+}
+
+impl From<&CostSettings> for RawCostSettings {
+    fn from(value: &CostSettings) -> Self {
+        Self {
+            filament_price_per_kg: value.filament_price_per_kg,
+            printer_power_watts: value.printer_power_watts,
+            electricity_price_per_kwh: value.electricity_price_per_kwh,
+            machine_wear_per_hour: Some(value.machine_wear_per_hour),
+        }
+    }
+}
+
+/// Checks that the price is between 0 and `max`. Invalid prices are reported as zero.
+fn price(
+    validator: &mut CollectingValidator,
+    attribute: &str,
+    code: &str,
+    value: f64,
+    max: f64,
+) -> f64 {
+    if value.is_finite() && (0.0..=max).contains(&value) {
+        return value;
+    }
+    validator.report(ValidationError::new(
+        attribute,
+        code,
+        format!("{} must be between 0 and {}", attribute, max),
+    ));
+    0.0
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn settings() -> CostSettings {
+        CostSettings::parse(RawCostSettings {
+            filament_price_per_kg: 25.0,
+            printer_power_watts: 120.0,
+            electricity_price_per_kwh: 0.3,
+            machine_wear_per_hour: Some(0.5),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn calculate_cost_of_filament_and_time() {
+        let cost = settings().cost(42.5, 90.0);
+
+        assert_eq!(
+            cost,
+            Cost {
+                filament: 1.06,
+                electricity: 0.05,
+                machine_wear: 0.75,
+                total: 1.87,
+            }
+        );
+    }
+
+    #[test]
+    fn skip_machine_wear_when_missing() {
+        let settings = CostSettings::parse(RawCostSettings {
+            machine_wear_per_hour: None,
+            ..RawCostSettings::from(&settings())
+        })
+        .unwrap();
+
+        assert_eq!(settings.cost(0.0, 600.0).machine_wear, 0.0);
+    }
+
+    #[test]
+    fn report_all_invalid_prices() {
+        let errors = CostSettings::parse(RawCostSettings {
+            filament_price_per_kg: -1.0,
+            printer_power_watts: 120.0,
+            electricity_price_per_kwh: f64::NAN,
+            machine_wear_per_hour: None,
+        })
+        .unwrap_err();
+
+        let codes: Vec<&str> = errors.iter().map(|e| e.code()).collect();
+        assert_eq!(
+            codes,
+            vec![
+                "costs.filament-price.out-of-range",
+                "costs.electricity-price.out-of-range"
+            ]
+        );
+    }
+}
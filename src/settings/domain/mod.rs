@@ -0,0 +1,2 @@
+pub mod costs;
+pub mod repository;
@@ -0,0 +1,17 @@
+//! The repository trait to interact with settings.
+
+use async_trait::async_trait;
+
+use super::costs::CostSettings;
+
+/// Storage of settings. Every setting has a single value.
+/// This trait must be implemented in the infrastructure layer.
+#[async_trait]
+pub trait SettingsRepository: Send + Sync {
+    /// Loads prices used to calculate costs.
+    /// The implementation must return Ok(None) if prices were never configured.
+    async fn cost_settings(&self) -> anyhow::Result<Option<CostSettings>>;
+
+    /// Replaces prices used to calculate costs.
+    async fn save_cost_settings(&self, settings: CostSettings) -> anyhow::Result<()>;
+}
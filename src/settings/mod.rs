@@ -0,0 +1,4 @@
+//! Settings keep configuration shared by everyone using printtables, e.g., prices used to
+//! calculate printing costs.
+pub mod app;
+pub mod domain;
@@ -1,2 +1,4 @@
 //! The shared module is a storage for non domain-specific shared functionality.
+pub mod pagination;
+pub mod rounding;
 pub mod validation;
@@ -0,0 +1,121 @@
+//! Pages of long listings.
+
+use serde::{Deserialize, Serialize};
+
+use super::validation::error::ValidationError;
+
+const DEFAULT_PER_PAGE: u32 = 50;
+const MAX_PER_PAGE: u32 = 200;
+
+/// Requested page of a listing. Pages are numbered from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageRequest {
+    page: u32,
+    per_page: u32,
+}
+
+impl PageRequest {
+    /// Validates page parameters. Missing parameters request the first page of default size.
+    pub fn parse(page: Option<u32>, per_page: Option<u32>) -> Result<Self, Vec<ValidationError>> {
+        let page = page.unwrap_or(1);
+        let per_page = per_page.unwrap_or(DEFAULT_PER_PAGE);
+        let mut errors = vec![];
+        if page == 0 {
+            errors.push(ValidationError::new(
+                "page",
+                "page.page.too-small",
+                "pages are numbered from 1",
+            ));
+        }
+        if per_page == 0 || per_page > MAX_PER_PAGE {
+            errors.push(ValidationError::new(
+                "per_page",
+                "page.per_page.out-of-range",
+                format!("page size must be between 1 and {}", MAX_PER_PAGE),
+            ));
+        }
+        if errors.is_empty() {
+            Ok(Self { page, per_page })
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub fn page(&self) -> u32 {
+        self.page
+    }
+
+    pub fn per_page(&self) -> u32 {
+        self.per_page
+    }
+
+    /// Number of items preceding the page.
+    /// Counted in u64, as far pages of large pages would overflow u32.
+    pub fn offset(&self) -> u64 {
+        u64::from(self.page - 1) * u64::from(self.per_page)
+    }
+}
+
+impl Default for PageRequest {
+    fn default() -> Self {
+        Self {
+            page: 1,
+            per_page: DEFAULT_PER_PAGE,
+        }
+    }
+}
+
+/// Page of a listing with the total number of items in the listing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub page: u32,
+    pub per_page: u32,
+    pub total: u64,
+}
+
+impl<T> Page<T> {
+    pub fn new(items: Vec<T>, request: PageRequest, total: u64) -> Self {
+        Self {
+            items,
+            page: request.page,
+            per_page: request.per_page,
+            total,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn default_to_first_page() {
+        let request = PageRequest::parse(None, None).unwrap();
+        assert_eq!(request, PageRequest::default());
+        assert_eq!(request.offset(), 0);
+    }
+
+    #[test]
+    fn calculate_offset() {
+        let request = PageRequest::parse(Some(3), Some(20)).unwrap();
+        assert_eq!(request.offset(), 40);
+    }
+
+    #[test]
+    fn calculate_offset_of_last_page() {
+        let request = PageRequest::parse(Some(u32::MAX), Some(MAX_PER_PAGE)).unwrap();
+        assert_eq!(request.offset(), 858_993_458_800);
+    }
+
+    #[test]
+    fn reject_invalid_page() {
+        let errors = PageRequest::parse(Some(0), Some(500)).unwrap_err();
+        let codes: Vec<&str> = errors.iter().map(|e| e.code()).collect();
+        assert_eq!(
+            codes,
+            vec!["page.page.too-small", "page.per_page.out-of-range"]
+        );
+    }
+}
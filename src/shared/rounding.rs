@@ -0,0 +1,6 @@
+//! Rounding of measured and calculated amounts, e.g., grams, hours and prices.
+
+/// Rounds to two decimal places.
+pub fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
@@ -33,6 +33,15 @@ impl ValidationError {
         }
     }
 
+    /// Places the error under the path of an enclosing item.
+    /// Nesting `name` error under `rows[3]` path produces `rows[3].name` attribute.
+    pub fn nested(self, path: &str) -> Self {
+        Self {
+            attribute: format!("{}.{}", path, self.attribute),
+            ..self
+        }
+    }
+
     pub fn attribute(&self) -> &str {
         &self.attribute
     }
@@ -51,3 +60,12 @@ impl Display for ValidationError {
         write!(f, "invalid {} value: {}", self.attribute, self.message)
     }
 }
+
+/// Describes multiple validation errors in a single line.
+pub fn describe(errors: &[ValidationError]) -> String {
+    errors
+        .iter()
+        .map(|e| e.to_string())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
@@ -40,6 +40,29 @@ impl CollectingValidator {
         self.parse(payload)
     }
 
+    /// Parses a string payload of a nested item, like a row of an imported file.
+    /// Attributes of reported errors are prefixed with the item path, e.g., `rows[3].name`.
+    pub fn parse_string_at<V>(
+        &mut self,
+        path: &str,
+        payload: impl Into<String>,
+    ) -> Result<V, ValidationError>
+    where
+        V: TryFrom<String, Error = ValidationError>,
+    {
+        let result = V::try_from(payload.into()).map_err(|e| e.nested(path));
+        if let Err(invalid_payload) = &result {
+            self.errors.push(invalid_payload.clone());
+        }
+        result
+    }
+
+    /// Reports an error detected outside of value parsing.
+    /// For example, a reference to an entity that does not exist.
+    pub fn report(&mut self, error: ValidationError) {
+        self.errors.push(error);
+    }
+
     pub fn has_errors(&self) -> bool {
         !self.errors.is_empty()
     }
@@ -50,6 +73,7 @@ impl CollectingValidator {
 }
 
 #[cfg(test)]
+#[allow(clippy::redundant_static_lifetimes)]
 mod tests {
 
     use super::*;
@@ -121,6 +145,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_nested_items() {
+        let mut validator = CollectingValidator::default();
+        let val_1 = validator.parse_string_at::<TestValue1>("rows[1]", VALID_VALUE);
+        let val_2 = validator.parse_string_at::<TestValue2>("rows[2]", INVALID_VALUE);
+
+        assert!(val_1.is_ok());
+        assert!(val_2.is_err());
+
+        let errors = validator.into_errors();
+        has_errors(&errors, "rows[2].value_2");
+        assert_eq!(errors.len(), 1, "only nested value 2 is invalid");
+    }
+
+    #[test]
+    fn report_custom_errors() {
+        let mut validator = CollectingValidator::default();
+        validator.report(ValidationError::new(VALUE_1, "value_1.missing", "missing"));
+
+        assert!(validator.has_errors());
+        has_errors(&validator.into_errors(), VALUE_1);
+    }
+
     fn has_errors(errors: &[ValidationError], value: &str) {
         let error_count = errors.iter().filter(|e| e.attribute() == value).count();
         assert!(
@@ -0,0 +1,396 @@
+//! State of the terminal application and its reaction to user input.
+//!
+//! The state does not talk to services directly. Key handlers return commands, and the
+//! event loop executes them and feeds results back into the state.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::inventory::domain::part::{Part, PartId};
+use crate::projects::domain::project::ProjectId;
+use crate::projects::domain::quantity::Quantity;
+use crate::projects::view::project::{ProjectListItem, ProjectView};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Screen {
+    Projects,
+    Project,
+    Parts,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mode {
+    Normal,
+    /// User types a value in the prompt.
+    Input(Prompt),
+    /// User picks an inventory part to add to the project BOM.
+    PickPart,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Prompt {
+    pub purpose: PromptPurpose,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptPurpose {
+    ProjectName,
+    PartName,
+    Quantity(PartId),
+}
+
+impl PromptPurpose {
+    pub fn title(&self) -> &'static str {
+        match self {
+            PromptPurpose::ProjectName => "New project name",
+            PromptPurpose::PartName => "New part name",
+            PromptPurpose::Quantity(_) => "Quantity",
+        }
+    }
+}
+
+/// Operations requiring application services.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    LoadProjects,
+    LoadParts,
+    OpenProject(ProjectId),
+    RegisterProject(String),
+    RegisterPart(String),
+    /// Replaces the BOM of the project with the given part quantities.
+    SetBom(ProjectId, Vec<(PartId, u32)>),
+    /// Reports the number of printed parts.
+    ReportProgress(ProjectId, PartId, u32),
+}
+
+#[derive(Debug)]
+pub struct App {
+    pub screen: Screen,
+    pub mode: Mode,
+    pub projects: Vec<ProjectListItem>,
+    pub project: Option<ProjectView>,
+    pub parts: Vec<Part>,
+    pub selected_project: usize,
+    pub selected_line: usize,
+    pub selected_part: usize,
+    pub status: Option<String>,
+    pub running: bool,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self {
+            screen: Screen::Projects,
+            mode: Mode::Normal,
+            projects: vec![],
+            project: None,
+            parts: vec![],
+            selected_project: 0,
+            selected_line: 0,
+            selected_part: 0,
+            status: None,
+            running: true,
+        }
+    }
+}
+
+impl App {
+    /// Updates the state according to the pressed key.
+    /// Returns the command to execute, if the key requires any.
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<Command> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.running = false;
+            return None;
+        }
+        self.status = None;
+        match self.mode.clone() {
+            Mode::Input(prompt) => self.handle_input(prompt, key.code),
+            Mode::PickPart => self.handle_pick_part(key.code),
+            Mode::Normal => match self.screen {
+                Screen::Projects => self.handle_projects(key.code),
+                Screen::Project => self.handle_project(key.code),
+                Screen::Parts => self.handle_parts(key.code),
+            },
+        }
+    }
+
+    pub fn projects_loaded(&mut self, projects: Vec<ProjectListItem>) {
+        self.projects = projects;
+        self.selected_project = clamp(self.selected_project, self.projects.len());
+    }
+
+    pub fn project_loaded(&mut self, project: ProjectView) {
+        self.selected_line = clamp(self.selected_line, project.parts().len());
+        self.project = Some(project);
+        self.screen = Screen::Project;
+    }
+
+    pub fn parts_loaded(&mut self, parts: Vec<Part>) {
+        self.parts = parts;
+        self.selected_part = clamp(self.selected_part, self.parts.len());
+    }
+
+    pub fn report(&mut self, message: impl Into<String>) {
+        self.status = Some(message.into());
+    }
+
+    fn handle_projects(&mut self, code: KeyCode) -> Option<Command> {
+        match code {
+            KeyCode::Char('q') => self.running = false,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected_project = self.selected_project.saturating_sub(1)
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected_project = next(self.selected_project, self.projects.len())
+            }
+            KeyCode::Enter => {
+                let project = self.projects.get(self.selected_project)?;
+                self.selected_line = 0;
+                return Some(Command::OpenProject(project.id()));
+            }
+            KeyCode::Char('n') => self.prompt(PromptPurpose::ProjectName, ""),
+            KeyCode::Char('r') => return Some(Command::LoadProjects),
+            KeyCode::Tab => {
+                self.screen = Screen::Parts;
+                return Some(Command::LoadParts);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn handle_parts(&mut self, code: KeyCode) -> Option<Command> {
+        match code {
+            KeyCode::Char('q') => self.running = false,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected_part = self.selected_part.saturating_sub(1)
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected_part = next(self.selected_part, self.parts.len())
+            }
+            KeyCode::Char('n') => self.prompt(PromptPurpose::PartName, ""),
+            KeyCode::Char('r') => return Some(Command::LoadParts),
+            KeyCode::Tab => {
+                self.screen = Screen::Projects;
+                return Some(Command::LoadProjects);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn handle_project(&mut self, code: KeyCode) -> Option<Command> {
+        let lines = self.project.as_ref().map_or(0, |p| p.parts().len());
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.screen = Screen::Projects;
+                return Some(Command::LoadProjects);
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected_line = self.selected_line.saturating_sub(1)
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected_line = next(self.selected_line, lines)
+            }
+            KeyCode::Char('a') => {
+                self.mode = Mode::PickPart;
+                return Some(Command::LoadParts);
+            }
+            KeyCode::Char('+') => return self.change_selected_line(|q| q.saturating_add(1)),
+            KeyCode::Char('-') => return self.change_selected_line(|q| q.saturating_sub(1)),
+            KeyCode::Char('d') | KeyCode::Delete => return self.change_selected_line(|_| 0),
+            KeyCode::Char('p') => {
+                let project = self.project.as_ref()?;
+                // Parts printed in full take no more progress.
+                let line = project
+                    .parts()
+                    .get(self.selected_line)
+                    .filter(|line| line.remaining() > 0)?;
+                return Some(Command::ReportProgress(project.id(), line.id(), 1));
+            }
+            KeyCode::Enter | KeyCode::Char('e') => {
+                let line = self.project.as_ref()?.parts().get(self.selected_line)?;
+                let (part, quantity) = (line.id(), line.quantity().to_string());
+                self.prompt(PromptPurpose::Quantity(part), &quantity);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn handle_pick_part(&mut self, code: KeyCode) -> Option<Command> {
+        match code {
+            KeyCode::Esc => self.mode = Mode::Normal,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected_part = self.selected_part.saturating_sub(1)
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected_part = next(self.selected_part, self.parts.len())
+            }
+            KeyCode::Enter => {
+                let part = self.parts.get(self.selected_part)?.id();
+                self.prompt(PromptPurpose::Quantity(part), "1");
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn handle_input(&mut self, mut prompt: Prompt, code: KeyCode) -> Option<Command> {
+        match code {
+            KeyCode::Esc => self.mode = Mode::Normal,
+            KeyCode::Backspace => {
+                prompt.value.pop();
+                self.mode = Mode::Input(prompt);
+            }
+            KeyCode::Char(c) => {
+                prompt.value.push(c);
+                self.mode = Mode::Input(prompt);
+            }
+            KeyCode::Enter => {
+                self.mode = Mode::Normal;
+                return self.submit(prompt);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn submit(&mut self, prompt: Prompt) -> Option<Command> {
+        match prompt.purpose {
+            PromptPurpose::ProjectName => Some(Command::RegisterProject(prompt.value)),
+            PromptPurpose::PartName => Some(Command::RegisterPart(prompt.value)),
+            PromptPurpose::Quantity(part) => match Quantity::try_from(prompt.value) {
+                Ok(quantity) => self.set_quantity(part, quantity.value()),
+                Err(e) => {
+                    self.report(e.message());
+                    None
+                }
+            },
+        }
+    }
+
+    fn change_selected_line(&mut self, change: impl Fn(u32) -> u32) -> Option<Command> {
+        let line = self.project.as_ref()?.parts().get(self.selected_line)?;
+        let (part, quantity) = (line.id(), change(line.quantity()));
+        self.set_quantity(part, quantity)
+    }
+
+    /// Builds the BOM with the updated part quantity. Zero quantity removes the part.
+    fn set_quantity(&self, part: PartId, quantity: u32) -> Option<Command> {
+        let project = self.project.as_ref()?;
+        let mut bom: Vec<(PartId, u32)> = project
+            .parts()
+            .iter()
+            .map(|p| (p.id(), p.quantity()))
+            .collect();
+        match bom.iter_mut().find(|(id, _)| *id == part) {
+            Some(line) => line.1 = quantity,
+            None => bom.push((part, quantity)),
+        }
+        bom.retain(|(_, quantity)| *quantity > 0);
+        Some(Command::SetBom(project.id(), bom))
+    }
+
+    fn prompt(&mut self, purpose: PromptPurpose, value: &str) {
+        self.mode = Mode::Input(Prompt {
+            purpose,
+            value: value.to_string(),
+        });
+    }
+}
+
+fn next(selected: usize, len: usize) -> usize {
+    clamp(selected + 1, len)
+}
+
+fn clamp(selected: usize, len: usize) -> usize {
+    selected.min(len.saturating_sub(1))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::projects::view::project::ProjectPart;
+
+    fn press(app: &mut App, code: KeyCode) -> Option<Command> {
+        app.handle_key(KeyEvent::from(code))
+    }
+
+    fn app_with_project() -> (App, ProjectId, PartId) {
+        let (project_id, part_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let mut app = App::default();
+        app.project_loaded(ProjectView::new(
+            project_id,
+            "Organizers".to_string(),
+            vec![ProjectPart::new(part_id, "Bin".to_string(), 5)],
+        ));
+        (app, project_id, part_id)
+    }
+
+    #[test]
+    fn register_project_from_prompt() {
+        let mut app = App::default();
+        press(&mut app, KeyCode::Char('n'));
+        for c in "Bins".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        let command = press(&mut app, KeyCode::Enter);
+
+        assert_eq!(command, Some(Command::RegisterProject("Bins".to_string())));
+        assert_eq!(app.mode, Mode::Normal);
+    }
+
+    #[test]
+    fn increase_part_quantity() {
+        let (mut app, project_id, part_id) = app_with_project();
+        let command = press(&mut app, KeyCode::Char('+'));
+
+        assert_eq!(
+            command,
+            Some(Command::SetBom(project_id, vec![(part_id, 6)]))
+        );
+    }
+
+    #[test]
+    fn remove_part_from_bom() {
+        let (mut app, project_id, _) = app_with_project();
+        let command = press(&mut app, KeyCode::Char('d'));
+
+        assert_eq!(command, Some(Command::SetBom(project_id, vec![])));
+    }
+
+    #[test]
+    fn report_printed_part() {
+        let (mut app, project_id, part_id) = app_with_project();
+        let command = press(&mut app, KeyCode::Char('p'));
+
+        assert_eq!(
+            command,
+            Some(Command::ReportProgress(project_id, part_id, 1))
+        );
+    }
+
+    #[test]
+    fn reject_invalid_quantity() {
+        let (mut app, _, _) = app_with_project();
+        press(&mut app, KeyCode::Char('e'));
+        press(&mut app, KeyCode::Backspace);
+        let command = press(&mut app, KeyCode::Char('x'));
+        assert_eq!(command, None);
+        let command = press(&mut app, KeyCode::Enter);
+
+        assert_eq!(command, None);
+        assert!(app.status.is_some(), "validation error must be reported");
+    }
+
+    #[test]
+    fn quit_from_project_list() {
+        let mut app = App::default();
+        press(&mut app, KeyCode::Char('q'));
+        assert!(!app.running);
+    }
+}
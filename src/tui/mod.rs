@@ -0,0 +1,107 @@
+//! Terminal user interface of printtables.
+//!
+//! The TUI lists projects, shows and edits project BOM, and registers inventory parts.
+//! It uses the same application services as the server.
+pub mod app;
+pub mod ui;
+
+use std::time::Duration;
+
+use anyhow::anyhow;
+use crossterm::event::{self, Event, KeyEventKind};
+use ratatui::DefaultTerminal;
+
+use crate::infra::services::Services;
+use crate::inventory::domain::classification::PartFilter;
+use crate::inventory::domain::name::Name as PartName;
+use crate::projects::domain::name::Name as ProjectName;
+use crate::projects::domain::project::ProjectPart;
+use app::{App, Command};
+
+const TICK: Duration = Duration::from_millis(250);
+
+/// Runs the terminal application until user quits.
+pub async fn run(services: Services) -> anyhow::Result<()> {
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &services).await;
+    ratatui::restore();
+    result
+}
+
+async fn event_loop(terminal: &mut DefaultTerminal, services: &Services) -> anyhow::Result<()> {
+    let mut app = App::default();
+    execute(&mut app, services, Command::LoadProjects).await;
+
+    while app.running {
+        terminal.draw(|frame| ui::draw(frame, &app))?;
+        if !event::poll(TICK)? {
+            continue;
+        }
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            if let Some(command) = app.handle_key(key) {
+                execute(&mut app, services, command).await;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Executes the command and reports failures in the status line.
+async fn execute(app: &mut App, services: &Services, command: Command) {
+    if let Err(e) = try_execute(app, services, command).await {
+        app.report(e.to_string());
+    }
+}
+
+async fn try_execute(app: &mut App, services: &Services, command: Command) -> anyhow::Result<()> {
+    match command {
+        Command::LoadProjects => {
+            let projects = services.projects.list_projects().await?;
+            app.projects_loaded(projects);
+        }
+        Command::LoadParts => {
+            let parts = services
+                .inventory
+                .list_parts(&PartFilter::default())
+                .await?;
+            app.parts_loaded(parts);
+        }
+        Command::OpenProject(id) => {
+            let project = services.projects.view_project(id).await?;
+            app.project_loaded(project);
+        }
+        Command::RegisterProject(name) => {
+            let name = ProjectName::parse(name).map_err(|e| anyhow!(e.message().to_string()))?;
+            services.projects.register_project(name).await?;
+            let projects = services.projects.list_projects().await?;
+            app.projects_loaded(projects);
+        }
+        Command::RegisterPart(name) => {
+            let name = PartName::parse(name).map_err(|e| anyhow!(e.message().to_string()))?;
+            services.inventory.register_part(name).await?;
+            let parts = services
+                .inventory
+                .list_parts(&PartFilter::default())
+                .await?;
+            app.parts_loaded(parts);
+        }
+        Command::SetBom(id, bom) => {
+            let parts = bom
+                .into_iter()
+                .map(|(part, quantity)| ProjectPart::new(part, quantity))
+                .collect();
+            services.projects.set_project_bom(id, parts).await?;
+            let project = services.projects.view_project(id).await?;
+            app.project_loaded(project);
+        }
+        Command::ReportProgress(id, part, printed) => {
+            services.projects.report_progress(id, part, printed).await?;
+            let project = services.projects.view_project(id).await?;
+            app.project_loaded(project);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,133 @@
+//! Draws the application state.
+
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::{Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Clear, List, ListState, Paragraph, Row, Table, TableState, Tabs};
+use ratatui::Frame;
+
+use super::app::{App, Mode, Screen};
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [header, body, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    draw_tabs(frame, app, header);
+    match app.screen {
+        Screen::Projects => draw_projects(frame, app, body),
+        Screen::Project => draw_project(frame, app, body),
+        Screen::Parts => draw_parts(frame, app, body, " Inventory "),
+    }
+    draw_footer(frame, app, footer);
+
+    match &app.mode {
+        Mode::Normal => {}
+        Mode::PickPart => {
+            let area = popup(body, Constraint::Percentage(60), Constraint::Percentage(60));
+            frame.render_widget(Clear, area);
+            draw_parts(
+                frame,
+                app,
+                area,
+                " Add part (Enter to pick, Esc to cancel) ",
+            );
+        }
+        Mode::Input(prompt) => {
+            let area = popup(body, Constraint::Percentage(50), Constraint::Length(3));
+            let input = Paragraph::new(format!("{}_", prompt.value))
+                .block(Block::bordered().title(format!(" {} ", prompt.purpose.title())));
+            frame.render_widget(Clear, area);
+            frame.render_widget(input, area);
+        }
+    }
+}
+
+fn draw_tabs(frame: &mut Frame, app: &App, area: Rect) {
+    let selected = match app.screen {
+        Screen::Projects | Screen::Project => 0,
+        Screen::Parts => 1,
+    };
+    let tabs = Tabs::new(["Projects", "Parts"])
+        .select(selected)
+        .highlight_style(Style::new().bold().reversed());
+    frame.render_widget(tabs, area);
+}
+
+fn draw_projects(frame: &mut Frame, app: &App, area: Rect) {
+    let items = app
+        .projects
+        .iter()
+        .map(|p| format!("{} ({} parts)", p.name(), p.bom_size()));
+    let list = List::new(items)
+        .block(Block::bordered().title(" Projects "))
+        .highlight_style(Style::new().reversed());
+    let mut state = ListState::default().with_selected(Some(app.selected_project));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn draw_project(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(project) = &app.project else {
+        return;
+    };
+    let rows = project.parts().iter().map(|p| {
+        Row::new([
+            p.name().to_string(),
+            p.quantity().to_string(),
+            p.printed().to_string(),
+        ])
+    });
+    let widths = [
+        Constraint::Fill(1),
+        Constraint::Length(10),
+        Constraint::Length(10),
+    ];
+    let table = Table::new(rows, widths)
+        .header(Row::new(["Part", "Quantity", "Printed"]).bold())
+        .block(Block::bordered().title(format!(" {} ", project.name())))
+        .row_highlight_style(Style::new().reversed());
+    let mut state = TableState::default().with_selected(Some(app.selected_line));
+    frame.render_stateful_widget(table, area, &mut state);
+}
+
+fn draw_parts(frame: &mut Frame, app: &App, area: Rect, title: &str) {
+    let items = app.parts.iter().map(|p| p.name().to_string());
+    let list = List::new(items)
+        .block(Block::bordered().title(title.to_string()))
+        .highlight_style(Style::new().reversed());
+    let mut state = ListState::default().with_selected(Some(app.selected_part));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
+    let line = match &app.status {
+        Some(status) => Line::from(status.as_str()).red(),
+        None => Line::from(help(app)).dim(),
+    };
+    frame.render_widget(line, area);
+}
+
+fn help(app: &App) -> &'static str {
+    match (&app.mode, app.screen) {
+        (Mode::Input(_), _) => "Enter: confirm  Esc: cancel",
+        (Mode::PickPart, _) => "↑↓: select  Enter: pick  Esc: cancel",
+        (Mode::Normal, Screen::Projects) => {
+            "↑↓: select  Enter: open  n: new project  r: reload  Tab: parts  q: quit"
+        }
+        (Mode::Normal, Screen::Project) => {
+            "↑↓: select  a: add  e: quantity  +/-: adjust  d: remove  p: printed  Esc: back"
+        }
+        (Mode::Normal, Screen::Parts) => {
+            "↑↓: select  n: register part  r: reload  Tab: projects  q: quit"
+        }
+    }
+}
+
+fn popup(area: Rect, width: Constraint, height: Constraint) -> Rect {
+    let [area] = Layout::vertical([height]).flex(Flex::Center).areas(area);
+    let [area] = Layout::horizontal([width]).flex(Flex::Center).areas(area);
+    area
+}
@@ -0,0 +1,109 @@
+use crate::server::{
+    project::CreateProjectPayload, rest::CreatedResponse, start_test_server, TestServer,
+};
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn record_project_history() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let project_uri = given_project(&test_server, "alice").await?;
+    let response = test_server
+        .import_bom(&project_uri, "Drawer,9\n", "register_missing=true")
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let history = get_json(&test_server, &format!("{}/history", project_uri)).await?;
+
+    assert_eq!(history["total"], 2);
+    let entries = history["items"].as_array().unwrap();
+    assert_eq!(entries[0]["event"], "bom-defined");
+    assert_eq!(entries[0]["actor"], "anonymous");
+    assert_eq!(
+        entries[0]["changes"]["bom"][0]["quantity"],
+        json!({ "from": 0, "to": 9 })
+    );
+    assert_eq!(entries[1]["event"], "project-registered");
+    assert_eq!(entries[1]["actor"], "alice");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn paginate_activity_feed() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let project_uri = given_project(&test_server, "bob").await?;
+    let response = test_server
+        .import_bom(&project_uri, "Drawer,9\nBin,2\n", "register_missing=true")
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let feed = get_json(&test_server, "/v1/activity?page=2&per_page=2").await?;
+
+    assert_eq!(feed["total"], 4);
+    assert_eq!(feed["page"], 2);
+    let events: Vec<&str> = feed["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["event"].as_str().unwrap())
+        .collect();
+    assert_eq!(events, vec!["part-registered", "project-registered"]);
+    let feed = get_json(&test_server, "/v1/activity?page=4294967295&per_page=200").await?;
+    assert_eq!(feed["items"], json!([]));
+    assert_eq!(feed["total"], 4);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_invalid_page() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+
+    let response = test_server
+        .api_client
+        .get(test_server.uri("/v1/activity?page=0"))
+        .send()
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn history_of_missing_project() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+
+    let response = test_server
+        .api_client
+        .get(test_server.uri(&format!("/v1/projects/{}/history", Uuid::now_v7())))
+        .send()
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+async fn given_project(test_server: &TestServer, actor: &str) -> anyhow::Result<String> {
+    let response = test_server
+        .api_client
+        .post(test_server.uri("/v1/projects"))
+        .header("X-Actor", actor)
+        .json(&CreateProjectPayload::default())
+        .send()
+        .await?;
+    Ok(CreatedResponse::<Value>::from(response).await.location)
+}
+
+async fn get_json(test_server: &TestServer, uri: &str) -> anyhow::Result<Value> {
+    let response = test_server
+        .api_client
+        .get(test_server.uri(uri))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    Ok(response.json().await?)
+}
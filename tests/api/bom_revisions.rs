@@ -0,0 +1,111 @@
+use crate::server::start_test_server;
+use printtables::infra::services::Services;
+use printtables::inventory::domain::name::Name as PartName;
+use printtables::projects::app::revisions::BomRevisionError;
+use printtables::projects::domain::name::Name as ProjectName;
+use printtables::projects::domain::project::ProjectPart;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn restore_wiped_bom() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let services = Services::remote(&test_server.base_url())?;
+    let bin = services
+        .inventory
+        .register_part(PartName::parse("Bin")?)
+        .await?;
+    let project = services
+        .projects
+        .register_project(ProjectName::parse("Organizers")?)
+        .await?;
+    services
+        .projects
+        .set_project_bom(project, vec![ProjectPart::new(bin, 5)])
+        .await?;
+    services.projects.report_progress(project, bin, 2).await?;
+    services.projects.set_project_bom(project, vec![]).await?;
+
+    let response = test_server
+        .api_client
+        .post(test_server.uri(&format!("/v1/projects/{}/revisions/1/restore", project)))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let view = services.projects.view_project(project).await?;
+    assert_eq!(view.parts()[0].quantity(), 5);
+    let revisions: Value = test_server
+        .api_client
+        .get(test_server.uri(&format!("/v1/projects/{}/revisions", project)))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let revisions = revisions.as_array().unwrap();
+    assert_eq!(revisions.len(), 3);
+    assert_eq!(revisions[0]["revision"], 3);
+    assert_eq!(
+        revisions[0]["parts"],
+        json!([{ "part_id": bin, "quantity": 5 }])
+    );
+    assert_eq!(revisions[1]["parts"], json!([]));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn diff_revisions() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let services = Services::remote(&test_server.base_url())?;
+    let bin = services
+        .inventory
+        .register_part(PartName::parse("Bin")?)
+        .await?;
+    let drawer = services
+        .inventory
+        .register_part(PartName::parse("Drawer")?)
+        .await?;
+    let project = services
+        .projects
+        .register_project(ProjectName::parse("Organizers")?)
+        .await?;
+    services
+        .projects
+        .set_project_bom(project, vec![ProjectPart::new(bin, 5)])
+        .await?;
+    services
+        .projects
+        .set_project_bom(
+            project,
+            vec![ProjectPart::new(bin, 6), ProjectPart::new(drawer, 9)],
+        )
+        .await?;
+
+    let diff = services.revisions.diff_revisions(project, 1, 2).await?;
+
+    assert_eq!(diff.added.len(), 1);
+    assert_eq!(diff.added[0].part_id, drawer);
+    assert!(diff.removed.is_empty());
+    assert_eq!((diff.changed[0].from, diff.changed[0].to), (5, 6));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn report_missing_revisions() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let services = Services::remote(&test_server.base_url())?;
+    let project = services
+        .projects
+        .register_project(ProjectName::parse("Organizers")?)
+        .await?;
+
+    let diff = services.revisions.diff_revisions(project, 1, 2).await;
+    assert!(matches!(diff, Err(BomRevisionError::MissingRevision)));
+    let listed = services.revisions.list_revisions(Uuid::now_v7()).await;
+    assert!(matches!(listed, Err(BomRevisionError::MissingProject)));
+
+    Ok(())
+}
@@ -0,0 +1,213 @@
+use crate::server::start_test_server;
+use printtables::cli::{self, error::CliError, Cli, Command};
+use printtables::infra::services::Services;
+use serde_json::Value;
+use std::process::ExitCode;
+
+async fn run(services: &Services, args: &[&str]) -> Result<String, CliError> {
+    let args = std::iter::once("printtables").chain(args.iter().copied());
+    let cli = <Cli as clap::Parser>::try_parse_from(args).expect("invalid test arguments");
+    let Command::Services(command) = cli.command else {
+        panic!("test commands must use services");
+    };
+    let mut out = vec![];
+    cli::execute(command, cli.output, services, &mut out).await?;
+    Ok(String::from_utf8(out).unwrap())
+}
+
+#[tokio::test]
+async fn manage_project_from_command_line() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let services = Services::remote(&test_server.base_url())?;
+
+    run(&services, &["part", "add", "Drawer"]).await?;
+    let created = run(
+        &services,
+        &["project", "create", "Organizers", "-o", "json"],
+    )
+    .await?;
+    let project_id = serde_json::from_str::<Value>(&created)?["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    run(
+        &services,
+        &["project", "bom", "set", &project_id, "Drawer=9"],
+    )
+    .await?;
+    run(
+        &services,
+        &["progress", "report", &project_id, "Drawer", "3"],
+    )
+    .await?;
+
+    let project = run(&services, &["project", "show", &project_id, "-o", "json"]).await?;
+    let project: Value = serde_json::from_str(&project)?;
+    assert_eq!(project["bom"][0]["quantity"], 9);
+    assert_eq!(project["bom"][0]["printed"], 3);
+
+    let part = run(
+        &services,
+        &[
+            "part",
+            "describe",
+            "Drawer",
+            "--material",
+            "PLA",
+            "--grams",
+            "40",
+            "-o",
+            "json",
+        ],
+    )
+    .await?;
+    run(
+        &services,
+        &["part", "describe", "Drawer", "--minutes", "90"],
+    )
+    .await?;
+    let part: Value = serde_json::from_str(&part)?;
+    assert_eq!(part[0]["material"], "PLA");
+    let parts = run(&services, &["part", "list", "-o", "json"]).await?;
+    let parts: Value = serde_json::from_str(&parts)?;
+    assert_eq!(parts[0]["grams"], 40.0);
+    assert_eq!(parts[0]["minutes"], 90);
+
+    let table = run(&services, &["project", "list"]).await?;
+    assert!(table.starts_with("ID"), "unexpected table {}", table);
+    assert!(table.contains("Organizers"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exit_with_distinct_codes() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let services = Services::remote(&test_server.base_url())?;
+    let created = run(&services, &["project", "create", "Bins", "-o", "json"]).await?;
+    let project_id = serde_json::from_str::<Value>(&created)?["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let missing_part = run(&services, &["project", "bom", "set", &project_id, "Lid=1"]).await;
+    let Err(error) = missing_part else {
+        panic!("missing part must be reported");
+    };
+    assert_eq!(
+        error.exit_code(),
+        ExitCode::from(cli::error::EXIT_NOT_FOUND)
+    );
+
+    let invalid = run(&services, &["project", "create", ""]).await;
+    let Err(error) = invalid else {
+        panic!("empty name must be rejected");
+    };
+    assert_eq!(error.exit_code(), ExitCode::from(cli::error::EXIT_INVALID));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn track_spools_from_command_line() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let services = Services::remote(&test_server.base_url())?;
+    let created = run(&services, &["project", "create", "Bins", "-o", "json"]).await?;
+    let project_id = serde_json::from_str::<Value>(&created)?["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    run(&services, &["part", "add", "Bin"]).await?;
+    run(
+        &services,
+        &[
+            "part",
+            "describe",
+            "Bin",
+            "--material",
+            "PETG",
+            "--color",
+            "red",
+            "--grams",
+            "300",
+        ],
+    )
+    .await?;
+    run(&services, &["project", "bom", "set", &project_id, "Bin=4"]).await?;
+
+    let spool_id = run(
+        &services,
+        &[
+            "spool",
+            "add",
+            "--material",
+            "petg",
+            "--color",
+            "red",
+            "--grams",
+            "1000",
+        ],
+    )
+    .await?;
+    let spool = run(
+        &services,
+        &[
+            "spool",
+            "consume",
+            spool_id.trim(),
+            &project_id,
+            "50",
+            "-o",
+            "json",
+        ],
+    )
+    .await?;
+    let spool: Value = serde_json::from_str(&spool)?;
+    assert_eq!(spool[0]["remaining_grams"], 950.0);
+
+    let shortages = run(&services, &["spool", "shortages", &project_id]).await?;
+    assert!(
+        shortages.starts_with("MATERIAL"),
+        "unexpected table {}",
+        shortages
+    );
+    let row: Vec<&str> = shortages
+        .lines()
+        .nth(1)
+        .unwrap()
+        .split_whitespace()
+        .collect();
+    assert_eq!(row, vec!["PETG", "red", "1200", "950", "250"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn clear_commercial_use_flag_from_command_line() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let services = Services::remote(&test_server.base_url())?;
+    run(&services, &["part", "add", "Bin"]).await?;
+    let source = ["part", "source", "Bin", "-o", "json"];
+    let part = run(
+        &services,
+        &[
+            &source[..],
+            &["--license", "CC-BY-NC-4.0", "--commercial", "true"],
+        ]
+        .concat(),
+    )
+    .await?;
+    assert_eq!(
+        serde_json::from_str::<Value>(&part)?[0]["commercial_use"],
+        true
+    );
+
+    let part = run(&services, &[&source[..], &["--clear-commercial"]].concat()).await?;
+
+    let part: Value = serde_json::from_str(&part)?;
+    assert_eq!(part[0]["license"], "CC-BY-NC-4.0");
+    assert_eq!(part[0]["commercial_use"], Value::Null);
+
+    Ok(())
+}
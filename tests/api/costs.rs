@@ -0,0 +1,118 @@
+use crate::server::{start_test_server, TestServer};
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn configure_cost_settings() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+
+    let response = test_server
+        .api_client
+        .get(test_server.uri("/v1/settings/costs"))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    given_prices(&test_server).await?;
+
+    let costs: Value = test_server
+        .api_client
+        .get(test_server.uri("/v1/settings/costs"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(costs["filament_price_per_kg"], 25.0);
+    assert_eq!(costs["machine_wear_per_hour"], 0.5);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_invalid_cost_settings() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+
+    let response = test_server
+        .api_client
+        .put(test_server.uri("/v1/settings/costs"))
+        .json(&json!({
+            "filament_price_per_kg": -25.0,
+            "printer_power_watts": 120.0,
+            "electricity_price_per_kwh": 0.3
+        }))
+        .send()
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body: Value = response.json().await?;
+    assert_eq!(
+        body["errors"][0]["code"],
+        "costs.filament-price.out-of-range"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn calculate_part_and_project_costs() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let (project_uri, part_id) = test_server.given_project_with_bom("Drawer,9\n").await?;
+    let project: Value = test_server
+        .view_project_by_uri(&project_uri)
+        .await?
+        .json()
+        .await?;
+    assert_eq!(project["summary"].get("cost"), None);
+    let part_uri = format!("/v1/inventory/parts/{}", part_id);
+    test_server
+        .api_client
+        .put(test_server.uri(&format!("{}/metadata", part_uri)))
+        .json(&json!({ "grams": 42.5, "minutes": 90 }))
+        .send()
+        .await?;
+    test_server
+        .report_progress(&project_uri, &json!({"part": part_id, "printed": 3}))
+        .await?;
+
+    given_prices(&test_server).await?;
+
+    let part: Value = test_server
+        .view_part_by_uri(&part_uri)
+        .await?
+        .json()
+        .await?;
+    assert_eq!(
+        part["cost"],
+        json!({ "filament": 1.06, "electricity": 0.05, "machine_wear": 0.75, "total": 1.87 })
+    );
+    let project: Value = test_server
+        .view_project_by_uri(&project_uri)
+        .await?
+        .json()
+        .await?;
+    assert_eq!(project["bom"][0]["cost"]["total"], 1.87);
+    assert_eq!(project["summary"]["cost"]["total"], 16.8);
+    assert_eq!(project["summary"]["remaining_cost"]["total"], 11.2);
+
+    Ok(())
+}
+
+async fn given_prices(test_server: &TestServer) -> anyhow::Result<()> {
+    let response = test_server
+        .api_client
+        .put(test_server.uri("/v1/settings/costs"))
+        .json(&json!({
+            "filament_price_per_kg": 25.0,
+            "printer_power_watts": 120.0,
+            "electricity_price_per_kwh": 0.3,
+            "machine_wear_per_hour": 0.5
+        }))
+        .send()
+        .await?;
+    assert_eq!(
+        response.status(),
+        StatusCode::OK,
+        "failed to configure prices"
+    );
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use crate::server::{
+    project::CreateProjectPayload, rest::CreatedResponse, start_test_server, TestServer,
+};
+use printtables::server::rest::ValidationMessage;
+use reqwest::{header::CONTENT_TYPE, StatusCode};
+
+#[tokio::test]
+async fn negotiate_csv_representation() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let project_uri = given_project_with_bom(&test_server, "Drawer,9\nBin,5\n").await?;
+
+    let response = test_server
+        .view_project_as(&project_uri, "text/csv")
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers().get(CONTENT_TYPE).unwrap(),
+        "text/csv; charset=utf-8"
+    );
+    let body = response.text().await?;
+    assert_eq!(
+        body,
+        "part name,quantity,printed,remaining\nDrawer,9,0,9\nBin,5,0,5\n"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn export_project_tree() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let project_uri = given_project_with_bom(&test_server, "Drawer,9\n").await?;
+
+    let response = test_server
+        .view_project_as(&format!("{}/export?format=txt", project_uri), "*/*")
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.text().await?;
+    assert!(
+        body.ends_with("\n+-- Drawer: 9\n"),
+        "unexpected tree {}",
+        body
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_unsupported_representation() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let project_uri = given_project_with_bom(&test_server, "Drawer,9\n").await?;
+
+    let response = test_server
+        .view_project_as(&project_uri, "image/png")
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_unknown_export_format() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let project_uri = given_project_with_bom(&test_server, "Drawer,9\n").await?;
+
+    let response = test_server
+        .view_project_as(&format!("{}/export?format=pdf", project_uri), "*/*")
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let message: ValidationMessage = response.json().await?;
+    assert_eq!(
+        message.errors[0].code(),
+        "project.export.unsupported-format"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_grouping_of_csv_representation() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let project_uri = given_project_with_bom(&test_server, "Drawer,9\n").await?;
+
+    let response = test_server
+        .view_project_as(&format!("{}?group_by=category", project_uri), "text/csv")
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let message: ValidationMessage = response.json().await?;
+    assert_eq!(
+        message.errors[0].code(),
+        "project.group-by.unsupported-format"
+    );
+    let response = test_server
+        .view_project_as(
+            &format!("{}/export?format=md&group_by=category", project_uri),
+            "*/*",
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+async fn given_project_with_bom(test_server: &TestServer, csv: &str) -> anyhow::Result<String> {
+    let response = test_server
+        .create_project(&CreateProjectPayload::default())
+        .await?;
+    let project_uri = CreatedResponse::<serde_json::Value>::from(response)
+        .await
+        .location;
+    let response = test_server
+        .import_bom(&project_uri, csv, "register_missing=true")
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK, "failed to import BOM");
+    Ok(project_uri)
+}
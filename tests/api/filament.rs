@@ -0,0 +1,193 @@
+use crate::server::{rest::CreatedResponse, start_test_server, TestServer};
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn register_and_list_spools() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let spool_uri = given_spool(&test_server, "PLA", "white", 1000.0).await?;
+    given_spool(&test_server, "ABS", "black", 750.0).await?;
+
+    let spool: Value = test_server
+        .api_client
+        .get(test_server.uri(&spool_uri))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(spool["material"], "PLA");
+    assert_eq!(spool["brand"], "Prusament");
+    assert_eq!(spool["remaining_grams"], 1000.0);
+    assert_eq!(spool["cost"], 24.99);
+    let spools: Value = test_server
+        .api_client
+        .get(test_server.uri("/v1/filament/spools"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let materials: Vec<&str> = spools
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["material"].as_str().unwrap())
+        .collect();
+    assert_eq!(materials, vec!["ABS", "PLA"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_invalid_spool() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+
+    let response = test_server
+        .api_client
+        .post(test_server.uri("/v1/filament/spools"))
+        .json(&json!({
+            "material": "PLA",
+            "color": "",
+            "initial_grams": 1000.0,
+            "remaining_grams": 1001.0
+        }))
+        .send()
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body: Value = response.json().await?;
+    assert_eq!(body["errors"][0]["code"], "spool.color.too-short");
+    assert_eq!(body["errors"][1]["code"], "spool.remaining.out-of-range");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn consume_filament_for_project() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let spool_uri = given_spool(&test_server, "PLA", "black", 1000.0).await?;
+    let (project_uri, _) = given_project_needing(&test_server, "black", 100.0).await?;
+    let project_id = project_uri.rsplit('/').next().unwrap();
+
+    let response = test_server
+        .api_client
+        .post(test_server.uri(&format!("{}/consumption", spool_uri)))
+        .json(&json!({ "project": project_id, "grams": 250.5 }))
+        .send()
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let spool: Value = response.json().await?;
+    assert_eq!(spool["remaining_grams"], 749.5);
+    let consumption: Value = test_server
+        .api_client
+        .get(test_server.uri(&format!("{}/consumption", project_uri)))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(consumption[0]["grams"], 250.5);
+    assert_eq!(consumption[0]["spool"], spool["id"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_consumption_exceeding_spool() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let spool_uri = given_spool(&test_server, "PLA", "black", 100.0).await?;
+    let (project_uri, _) = given_project_needing(&test_server, "black", 1.0).await?;
+    let project_id = project_uri.rsplit('/').next().unwrap();
+
+    let response = test_server
+        .api_client
+        .post(test_server.uri(&format!("{}/consumption", spool_uri)))
+        .json(&json!({ "project": project_id, "grams": 100.5 }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body: Value = response.json().await?;
+    assert_eq!(body["errors"][0]["code"], "spool.grams.exceeds-remaining");
+
+    let response = test_server
+        .api_client
+        .post(test_server.uri(&format!("{}/consumption", spool_uri)))
+        .json(&json!({ "project": Uuid::now_v7(), "grams": 1.0 }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn warn_about_filament_shortage() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    given_spool(&test_server, "PLA", "Black", 1000.0).await?;
+    given_spool(&test_server, "PLA", "white", 1000.0).await?;
+    let (project_uri, _) = given_project_needing(&test_server, "black", 150.0).await?;
+
+    let shortages: Value = test_server
+        .api_client
+        .get(test_server.uri(&format!("{}/shortages", project_uri)))
+        .send()
+        .await?
+        .json()
+        .await?;
+
+    assert_eq!(
+        shortages,
+        json!([{
+            "material": "PLA",
+            "color": "black",
+            "required_grams": 1350.0,
+            "available_grams": 1000.0,
+            "missing_grams": 350.0
+        }])
+    );
+
+    Ok(())
+}
+
+async fn given_spool(
+    test_server: &TestServer,
+    material: &str,
+    color: &str,
+    grams: f64,
+) -> anyhow::Result<String> {
+    let response = test_server
+        .api_client
+        .post(test_server.uri("/v1/filament/spools"))
+        .json(&json!({
+            "material": material,
+            "color": color,
+            "brand": "Prusament",
+            "initial_grams": grams,
+            "cost": 24.99
+        }))
+        .send()
+        .await?;
+    assert_eq!(
+        response.status(),
+        StatusCode::CREATED,
+        "failed to add spool"
+    );
+    Ok(CreatedResponse::<Value>::from(response).await.location)
+}
+
+/// Creates a project with nine drawers printed from PLA of the given color.
+async fn given_project_needing(
+    test_server: &TestServer,
+    color: &str,
+    grams: f64,
+) -> anyhow::Result<(String, String)> {
+    let (project_uri, part_id) = test_server.given_project_with_bom("Drawer,9\n").await?;
+    let response = test_server
+        .api_client
+        .put(test_server.uri(&format!("/v1/inventory/parts/{}/metadata", part_id)))
+        .json(&json!({ "material": "PLA", "color": color, "grams": grams }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK, "failed to describe part");
+    Ok((project_uri, part_id))
+}
@@ -0,0 +1,106 @@
+use crate::server::{
+    inventory::{CreatePartResponsePayload, RegisterPartPayload},
+    project::CreateProjectPayload,
+    rest::CreatedResponse,
+    start_test_server, TestServer,
+};
+use printtables::{projects::view::project::ProjectView, server::rest::ValidationMessage};
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct ImportReportPayload {
+    dry_run: bool,
+    rows: Vec<ImportedRowPayload>,
+    changes: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ImportedRowPayload {
+    status: String,
+}
+
+#[tokio::test]
+async fn dry_run_does_not_change_project() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let project_uri = given_project(&test_server).await?;
+    let part = given_part(&test_server).await?;
+
+    let csv = format!("part name,quantity\n{},3\nBrand new part,2\n", part.name());
+    let response = test_server
+        .import_bom(&project_uri, &csv, "dry_run=true&register_missing=true")
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let report: ImportReportPayload = response.json().await?;
+    assert!(report.dry_run);
+    let statuses: Vec<&str> = report.rows.iter().map(|r| r.status.as_str()).collect();
+    assert_eq!(statuses, vec!["matched", "will-register"]);
+    assert_eq!(report.changes.len(), 2, "both lines are added to the BOM");
+
+    let view = view_project(&test_server, &project_uri).await?;
+    assert!(view.parts().is_empty(), "dry run must not modify the BOM");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn import_registers_missing_parts() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let project_uri = given_project(&test_server).await?;
+    let part = given_part(&test_server).await?;
+
+    let csv = format!("{},3\nBrand new part,2\n{},1\n", part.name(), part.name());
+    let response = test_server
+        .import_bom(&project_uri, &csv, "register_missing=true")
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let view = view_project(&test_server, &project_uri).await?;
+    assert_eq!(view.parts().len(), 2);
+    let existing = view
+        .parts()
+        .iter()
+        .find(|p| p.name() == part.name())
+        .expect("existing part must be in the BOM");
+    assert_eq!(existing.quantity(), 4, "duplicate rows must be summed up");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn import_reports_invalid_rows() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let project_uri = given_project(&test_server).await?;
+
+    let csv = "Unknown part,1\nBin,zero\n";
+    let response = test_server.import_bom(&project_uri, csv, "").await?;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let message: ValidationMessage = response.json().await?;
+    let codes: Vec<&str> = message.errors.iter().map(|e| e.code()).collect();
+    assert!(codes.contains(&"bom.part.missing"));
+    assert!(codes.contains(&"bom.quantity.not-a-number"));
+
+    Ok(())
+}
+
+async fn given_project(test_server: &TestServer) -> anyhow::Result<String> {
+    let response = test_server
+        .create_project(&CreateProjectPayload::default())
+        .await?;
+    let created = CreatedResponse::<serde_json::Value>::from(response).await;
+    Ok(created.location)
+}
+
+async fn given_part(test_server: &TestServer) -> anyhow::Result<RegisterPartPayload> {
+    let payload = RegisterPartPayload::default();
+    let response = test_server.register_part(&payload).await?;
+    CreatedResponse::<CreatePartResponsePayload>::from(response).await;
+    Ok(payload)
+}
+
+async fn view_project(test_server: &TestServer, project_uri: &str) -> anyhow::Result<ProjectView> {
+    let response = test_server.view_project_by_uri(project_uri).await?;
+    Ok(response.json().await?)
+}
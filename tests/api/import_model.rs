@@ -0,0 +1,98 @@
+use std::io::{Cursor, Write};
+
+use crate::server::{start_test_server, TestServer};
+use printtables::projects::app::model_import::ModelImportReport;
+use printtables::projects::view::project::ProjectView;
+use printtables::server::rest::ValidationMessage;
+use reqwest::{Response, StatusCode};
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+/// Model with two bins and a lid on the plate, and an object without a name.
+const MODEL: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+  <resources>
+    <object id="1" name="Bin" type="model"><mesh/></object>
+    <object id="2" name="Lid" type="model"><mesh/></object>
+    <object id="3" type="model"><mesh/></object>
+  </resources>
+  <build>
+    <item objectid="1"/>
+    <item objectid="2"/>
+    <item objectid="1"/>
+    <item objectid="3"/>
+  </build>
+</model>
+"#;
+
+fn package(model: &str) -> anyhow::Result<Vec<u8>> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    writer.start_file("3D/3dmodel.model", SimpleFileOptions::default())?;
+    writer.write_all(model.as_bytes())?;
+    Ok(writer.finish()?.into_inner())
+}
+
+impl TestServer {
+    async fn import_model(&self, content: Vec<u8>, query: &str) -> anyhow::Result<Response> {
+        Ok(self
+            .api_client
+            .post(self.uri(&format!("/v1/inventory/parts/import?{}", query)))
+            .header("Content-Type", "model/3mf")
+            .body(content)
+            .send()
+            .await?)
+    }
+}
+
+#[tokio::test]
+async fn import_objects_into_project() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+
+    let response = test_server
+        .import_model(package(MODEL)?, "project=Drawers")
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let report: ModelImportReport = response.json().await?;
+    let names: Vec<(&str, u32)> = report
+        .objects
+        .iter()
+        .map(|o| (o.name.as_str(), o.count))
+        .collect();
+    assert_eq!(names, vec![("Bin", 2), ("Lid", 1), ("", 1)]);
+    assert!(report.objects[2].part_id.is_none());
+    assert_eq!(
+        report.objects[2].errors[0].code(),
+        "part.name.too-short",
+        "unnamed objects are reported without failing the import"
+    );
+
+    let project_uri = format!("/v1/projects/{}", report.project_id.unwrap());
+    let project: ProjectView = test_server
+        .view_project_by_uri(&project_uri)
+        .await?
+        .json()
+        .await?;
+    assert_eq!(project.name(), "Drawers");
+    let bom: Vec<(&str, u32)> = project
+        .parts()
+        .iter()
+        .map(|p| (p.name(), p.quantity()))
+        .collect();
+    assert_eq!(bom, vec![("Bin", 2), ("Lid", 1)]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_invalid_model() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+
+    let response = test_server.import_model(b"solid cube".to_vec(), "").await?;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let message: ValidationMessage = response.json().await?;
+    assert_eq!(message.errors[0].code(), "3mf.content.invalid");
+
+    Ok(())
+}
@@ -1,5 +1,24 @@
+mod activity;
+mod bom_revisions;
+mod cli;
+mod costs;
+mod export_project;
+mod filament;
+mod import_bom;
+mod import_model;
 mod manage_parts;
+mod packing;
+mod part_files;
+mod part_licenses;
+mod part_metadata;
+mod part_revisions;
+mod part_tags;
+mod part_variants;
+mod project_summary;
+mod project_templates;
 mod register_part;
 mod register_project;
+mod remote_client;
+mod report_progress;
 mod server;
 mod server_health;
@@ -2,6 +2,7 @@ use crate::server::project::CreateProjectPayload;
 use crate::server::{start_test_server, TestServer};
 use fake::{faker::name::en::Name, Fake};
 use printtables::projects::view::project::ProjectView;
+use printtables::server::rest::ValidationMessage;
 use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 
@@ -61,6 +62,71 @@ async fn register_project_and_set_parts() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn keep_bom_when_update_fails() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let project_uri = given_new_project(&test_server).await?;
+    let part_id = create_part(&test_server, &RegisterPartRequest::random()).await?;
+    let response = put_parts(&test_server, &project_uri, &[(&part_id, 3)]).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let unknown_part = uuid::Uuid::now_v7().to_string();
+    let response = put_parts(
+        &test_server,
+        &project_uri,
+        &[(&part_id, 5), (&unknown_part, 1)],
+    )
+    .await?;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let message: ValidationMessage = response.json().await?;
+    assert_eq!(message.errors[0].code(), "bom.part.missing");
+    let project_view = view_project(&test_server, &project_uri).await?;
+    assert_eq!(
+        project_view.parts().len(),
+        1,
+        "failed update must not change the BOM"
+    );
+    assert_eq!(project_view.parts()[0].quantity(), 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_duplicate_parts() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let project_uri = given_new_project(&test_server).await?;
+    let part_id = create_part(&test_server, &RegisterPartRequest::random()).await?;
+
+    let response = put_parts(&test_server, &project_uri, &[(&part_id, 5), (&part_id, 3)]).await?;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let message: ValidationMessage = response.json().await?;
+    assert_eq!(message.errors[0].code(), "bom.part.duplicate");
+    assert_eq!(message.errors[0].attribute(), "parts[1].part");
+    let project_view = view_project(&test_server, &project_uri).await?;
+    assert!(project_view.parts().is_empty());
+
+    Ok(())
+}
+
+async fn put_parts(
+    test_server: &TestServer,
+    project_uri: &str,
+    parts: &[(&str, u32)],
+) -> anyhow::Result<reqwest::Response> {
+    let parts: Vec<_> = parts
+        .iter()
+        .map(|(part, quantity)| serde_json::json!({ "part": part, "quantity": quantity }))
+        .collect();
+    let response = reqwest::Client::new()
+        .put(test_server.uri(&format!("{}/parts", project_uri)))
+        .json(&serde_json::json!({ "parts": parts }))
+        .send()
+        .await?;
+    Ok(response)
+}
+
 async fn ensure_project_have_no_parts(
     test_server: &TestServer,
     project_uri: &str,
@@ -0,0 +1,129 @@
+use crate::server::{inventory::RegisterPartPayload, TestServer};
+use crate::server::{project::CreateProjectPayload, rest::CreatedResponse, start_test_server};
+use printtables::server::rest::ValidationMessage;
+use reqwest::{Response, StatusCode};
+use serde_json::{json, Value};
+
+impl TestServer {
+    async fn describe_size(&self, part_id: &str, size: Value) -> anyhow::Result<()> {
+        let response = self
+            .api_client
+            .put(self.uri(&format!("/v1/inventory/parts/{}/metadata", part_id)))
+            .json(&json!({ "dimensions": size }))
+            .send()
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        Ok(())
+    }
+
+    async fn pack(&self, path: &str, query: &str) -> anyhow::Result<Response> {
+        Ok(self
+            .api_client
+            .post(self.uri(&format!("{}?{}", path, query)))
+            .send()
+            .await?)
+    }
+}
+
+#[tokio::test]
+async fn count_copies_on_bed() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let response = test_server
+        .register_part(&RegisterPartPayload::default())
+        .await?;
+    let part_uri = CreatedResponse::<Value>::from(response).await.location;
+    let part_id = part_uri.rsplit('/').next().unwrap();
+    test_server
+        .describe_size(part_id, json!({ "x": 60.0, "y": 40.0, "z": 20.0 }))
+        .await?;
+
+    let response = test_server
+        .pack(&format!("{}/packing", part_uri), "bed=250x210&spacing=5")
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let packing: Value = response.json().await?;
+    assert_eq!(packing["fits"], true);
+    assert_eq!(packing["copies_per_plate"], 15);
+    assert_eq!(
+        packing["grid"],
+        json!({ "columns": 5, "rows": 3, "rotated": true })
+    );
+
+    let response = test_server
+        .pack(&format!("{}/packing", part_uri), "bed=50x50")
+        .await?;
+    let packing: Value = response.json().await?;
+    assert_eq!(packing["fits"], false);
+    assert_eq!(packing["copies_per_plate"], 0);
+
+    let response = test_server
+        .pack(&format!("{}/packing", part_uri), "spacing=5")
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let message: ValidationMessage = response.json().await?;
+    assert_eq!(message.errors[0].code(), "packing.bed.invalid-format");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn plan_project_plates() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let response = test_server
+        .create_project(&CreateProjectPayload::default())
+        .await?;
+    let project_uri = CreatedResponse::<Value>::from(response).await.location;
+    test_server
+        .import_bom(
+            &project_uri,
+            "Bin,3\nLid,6\nHandle,2\n",
+            "register_missing=true",
+        )
+        .await?;
+    let project: Value = test_server
+        .view_project_by_uri(&project_uri)
+        .await?
+        .json()
+        .await?;
+    let part = |name: &str| {
+        project["bom"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|line| line["name"] == name)
+            .unwrap()["part_id"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    };
+    test_server
+        .describe_size(&part("Bin"), json!({ "x": 120.0, "y": 100.0, "z": 50.0 }))
+        .await?;
+    test_server
+        .describe_size(&part("Lid"), json!({ "x": 120.0, "y": 20.0, "z": 3.0 }))
+        .await?;
+
+    let response = test_server
+        .pack(&format!("{}/plates", project_uri), "bed=250x210&spacing=0")
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let plan: Value = response.json().await?;
+    let plates = plan["plates"].as_array().unwrap();
+    assert_eq!(plates.len(), 2);
+    assert_eq!(
+        plates[0]["parts"],
+        json!([
+            { "part_id": part("Bin"), "name": "Bin", "copies": 3 },
+            { "part_id": part("Lid"), "name": "Lid", "copies": 1 }
+        ])
+    );
+    assert_eq!(plates[1]["placements"].as_array().unwrap().len(), 5);
+    assert_eq!(
+        plan["unplaced"],
+        json!([{ "part_id": part("Handle"), "name": "Handle", "copies": 2, "reason": "missing-dimensions" }])
+    );
+
+    Ok(())
+}
@@ -0,0 +1,295 @@
+use crate::server::inventory::RegisterPartPayload;
+use crate::server::rest::CreatedResponse;
+use crate::server::{start_test_server, TestServer};
+use printtables::server::rest::ValidationMessage;
+use reqwest::multipart::{Form, Part};
+use reqwest::{header, Response, StatusCode};
+use serde_json::Value;
+
+/// Tetrahedron with 10 mm legs along the axes.
+const MESH: &[u8] = b"solid corner
+  facet normal 0 0 -1
+    outer loop
+      vertex 0 0 0
+      vertex 0 10 0
+      vertex 10 0 0
+    endloop
+  endfacet
+  facet normal 0 -1 0
+    outer loop
+      vertex 0 0 0
+      vertex 10 0 0
+      vertex 0 0 10
+    endloop
+  endfacet
+  facet normal -1 0 0
+    outer loop
+      vertex 0 0 0
+      vertex 0 0 10
+      vertex 0 10 0
+    endloop
+  endfacet
+  facet normal 1 1 1
+    outer loop
+      vertex 10 0 0
+      vertex 0 10 0
+      vertex 0 0 10
+    endloop
+  endfacet
+endsolid corner
+";
+
+impl TestServer {
+    async fn upload_file(
+        &self,
+        part_uri: &str,
+        file_name: &str,
+        content: &'static [u8],
+    ) -> anyhow::Result<Response> {
+        let form = Form::new().part(
+            "file",
+            Part::bytes(content).file_name(file_name.to_string()),
+        );
+        Ok(self
+            .api_client
+            .post(self.uri(&format!("{}/files", part_uri)))
+            .multipart(form)
+            .send()
+            .await?)
+    }
+}
+
+async fn register_part(test_server: &TestServer) -> anyhow::Result<String> {
+    let response = test_server
+        .register_part(&RegisterPartPayload::default())
+        .await?;
+    Ok(CreatedResponse::<Value>::from(response).await.location)
+}
+
+#[tokio::test]
+async fn upload_and_download_part_file() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let part_uri = register_part(&test_server).await?;
+
+    let response = test_server
+        .upload_file(&part_uri, "models/cube.stl", MESH)
+        .await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let created = CreatedResponse::<Value>::from(response).await;
+    assert_eq!(created.payload["file_name"], "cube.stl");
+    assert_eq!(created.payload["format"], "stl");
+    assert_eq!(created.payload["size"], MESH.len());
+
+    let response = test_server
+        .api_client
+        .get(test_server.uri(&created.location))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "model/stl");
+    assert_eq!(
+        response.headers()[header::CONTENT_DISPOSITION],
+        "attachment; filename=\"cube.stl\""
+    );
+    assert_eq!(response.bytes().await?.as_ref(), MESH);
+
+    let files: Vec<Value> = test_server
+        .api_client
+        .get(test_server.uri(&format!("{}/files", part_uri)))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(files, vec![created.payload]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn deduplicate_identical_files() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let first_part = register_part(&test_server).await?;
+    let second_part = register_part(&test_server).await?;
+
+    let first: Value = test_server
+        .upload_file(&first_part, "cube.stl", MESH)
+        .await?
+        .json()
+        .await?;
+    let second: Value = test_server
+        .upload_file(&second_part, "box.3mf", MESH)
+        .await?
+        .json()
+        .await?;
+
+    assert_ne!(first["id"], second["id"]);
+    assert_eq!(first["sha256"], second["sha256"]);
+    assert_eq!(second["format"], "3mf");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_unsupported_file() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let part_uri = register_part(&test_server).await?;
+
+    let response = test_server.upload_file(&part_uri, "cube.obj", MESH).await?;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let message: ValidationMessage = response.json().await?;
+    assert_eq!(message.errors[0].code(), "file.format.unsupported");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_truncated_upload() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let part_uri = register_part(&test_server).await?;
+
+    let response = test_server
+        .api_client
+        .post(test_server.uri(&format!("{}/files", part_uri)))
+        .header(header::CONTENT_TYPE, "multipart/form-data; boundary=cut")
+        .body("--cut\r\nContent-Disposition: form-data; name=\"file\"; filename=\"corner.stl\"\r\n\r\nsolid")
+        .send()
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let message: ValidationMessage = response.json().await?;
+    assert_eq!(message.errors[0].code(), "file.content.invalid");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn report_missing_part_and_file() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let missing_part = format!("/v1/inventory/parts/{}", uuid::Uuid::now_v7());
+
+    let response = test_server
+        .upload_file(&missing_part, "cube.stl", MESH)
+        .await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let part_uri = register_part(&test_server).await?;
+    let response = test_server
+        .api_client
+        .get(test_server.uri(&format!("{}/files/{}", part_uri, uuid::Uuid::now_v7())))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn measure_uploaded_stl() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let part_uri = register_part(&test_server).await?;
+
+    let response = test_server
+        .upload_file(&part_uri, "corner.stl", MESH)
+        .await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let part: Value = test_server
+        .view_part_by_uri(&part_uri)
+        .await?
+        .json()
+        .await?;
+    assert_eq!(part["geometry"]["triangles"], 4);
+    assert_eq!(part["geometry"]["volume"], 166.67);
+    assert_eq!(part["dimensions"]["x"], 10.0);
+    assert_eq!(part["grams"], 1.0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_invalid_stl() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let part_uri = register_part(&test_server).await?;
+
+    let response = test_server
+        .upload_file(&part_uri, "corner.stl", b"not a mesh")
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let message: ValidationMessage = response.json().await?;
+    assert_eq!(message.errors[0].code(), "stl.content.invalid");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn take_estimates_from_uploaded_gcode() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let part_uri = register_part(&test_server).await?;
+
+    let response = test_server
+        .upload_file(
+            &part_uri,
+            "corner.gcode",
+            b";FLAVOR:Marlin\n;TIME:5400\n;Filament used: 4.21m\nG28\n",
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let part: Value = test_server
+        .view_part_by_uri(&part_uri)
+        .await?
+        .json()
+        .await?;
+    assert_eq!(part["minutes"], 90);
+    assert_eq!(part["grams"], 12.56);
+
+    let response = test_server
+        .upload_file(&part_uri, "empty.gcode", b"G28\n")
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let message: ValidationMessage = response.json().await?;
+    assert_eq!(message.errors[0].code(), "gcode.estimates.missing");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn render_part_thumbnail() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let part_uri = register_part(&test_server).await?;
+    let thumbnail_uri = test_server.uri(&format!("{}/thumbnail", part_uri));
+
+    let response = test_server.api_client.get(&thumbnail_uri).send().await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    test_server
+        .upload_file(&part_uri, "corner.stl", MESH)
+        .await?;
+    let response = test_server.api_client.get(&thumbnail_uri).send().await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "image/png");
+    assert!(response.bytes().await?.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+    let response = test_server
+        .api_client
+        .get(&thumbnail_uri)
+        .query(&[("format", "svg")])
+        .send()
+        .await?;
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "image/svg+xml");
+    assert_eq!(response.text().await?.matches("<polygon ").count(), 4);
+
+    let response = test_server
+        .api_client
+        .get(&thumbnail_uri)
+        .query(&[("format", "gif")])
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let message: ValidationMessage = response.json().await?;
+    assert_eq!(message.errors[0].code(), "thumbnail.format.unsupported");
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use crate::server::project::CreateProjectPayload;
+use crate::server::rest::CreatedResponse;
+use crate::server::start_test_server;
+use printtables::server::rest::ValidationMessage;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn report_licenses_of_project_parts() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let lid_uri = test_server.given_part("Lid").await?;
+    let bin_uri = test_server.given_part("Bin").await?;
+    let hinge_uri = test_server.given_part("Hinge").await?;
+    let response = test_server
+        .attribute_part(
+            &bin_uri,
+            &json!({
+            "source_url": "https://www.printables.com/model/1234",
+            "designer": "Jane Maker",
+                "license": "cc by-nc-sa 4.0",
+            }),
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    test_server
+        .attribute_part(
+            &lid_uri,
+            &json!({ "license": "CC-BY-NC-4.0", "commercial_use": true }),
+        )
+        .await?;
+
+    let part: Value = test_server.view_part_by_uri(&bin_uri).await?.json().await?;
+    assert_eq!(part["license"], "CC-BY-NC-SA-4.0");
+    assert_eq!(part["designer"], "Jane Maker");
+    assert_eq!(part["source_url"], "https://www.printables.com/model/1234");
+
+    let response = test_server
+        .create_project(&CreateProjectPayload::default())
+        .await?;
+    let project_uri = CreatedResponse::<Value>::from(response).await.location;
+    let part_id = |uri: &str| uri.rsplit('/').next().unwrap().to_string();
+    test_server
+        .api_client
+        .put(test_server.uri(&format!("{}/parts", project_uri)))
+        .json(&json!({ "parts": [
+            { "part": part_id(&lid_uri), "quantity": 1 },
+            { "part": part_id(&bin_uri), "quantity": 4 },
+            { "part": part_id(&hinge_uri), "quantity": 2 },
+        ] }))
+        .send()
+        .await?;
+
+    let report: Value = test_server
+        .api_client
+        .get(test_server.uri(&format!("{}/licenses", project_uri)))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let parts: Vec<(&str, &Value)> = report["parts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| (p["name"].as_str().unwrap(), &p["commercial_use"]))
+        .collect();
+    assert_eq!(
+        parts,
+        vec![
+            ("Bin", &json!(false)),
+            ("Hinge", &Value::Null),
+            ("Lid", &json!(true))
+        ]
+    );
+    assert_eq!(report["commercial_use"], false);
+    assert_eq!(report["non_commercial"], 1);
+    assert_eq!(report["unknown"], 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_unknown_license() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let part_uri = test_server.given_part("Bin").await?;
+
+    let response = test_server
+        .attribute_part(
+            &part_uri,
+            &json!({ "source_url": "printables.com", "license": "Standard Digital File License" }),
+        )
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let message: ValidationMessage = response.json().await?;
+    let codes: Vec<&str> = message.errors.iter().map(|e| e.code()).collect();
+    assert_eq!(
+        codes,
+        vec!["part.source-url.invalid", "part.license.unknown"]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn report_licenses_of_missing_project() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+
+    let response = test_server
+        .api_client
+        .get(test_server.uri(&format!("/v1/projects/{}/licenses", Uuid::now_v7())))
+        .send()
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
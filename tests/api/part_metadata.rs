@@ -0,0 +1,73 @@
+use crate::server::inventory::RegisterPartPayload;
+use crate::server::rest::CreatedResponse;
+use crate::server::start_test_server;
+use printtables::server::rest::ValidationMessage;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn describe_part() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let response = test_server
+        .register_part(&RegisterPartPayload::default())
+        .await?;
+    let part_uri = CreatedResponse::<Value>::from(response).await.location;
+
+    let response = test_server
+        .api_client
+        .put(test_server.uri(&format!("{}/metadata", part_uri)))
+        .json(&json!({
+            "material": "petg",
+            "color": "black",
+            "grams": 42.5,
+            "minutes": 95,
+            "dimensions": { "x": 120.0, "y": 80.0, "z": 35.5 },
+            "notes": "no supports"
+        }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let part: Value = test_server
+        .view_part_by_uri(&part_uri)
+        .await?
+        .json()
+        .await?;
+    assert_eq!(part["material"], "PETG");
+    assert_eq!(part["color"], "black");
+    assert_eq!(part["grams"], 42.5);
+    assert_eq!(part["minutes"], 95);
+    assert_eq!(
+        part["dimensions"],
+        json!({ "x": 120.0, "y": 80.0, "z": 35.5 })
+    );
+    assert_eq!(part["notes"], "no supports");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_invalid_metadata() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let response = test_server
+        .register_part(&RegisterPartPayload::default())
+        .await?;
+    let part_uri = CreatedResponse::<Value>::from(response).await.location;
+
+    let response = test_server
+        .api_client
+        .put(test_server.uri(&format!("{}/metadata", part_uri)))
+        .json(&json!({ "material": "wood", "grams": -1.0 }))
+        .send()
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let message: ValidationMessage = response.json().await?;
+    let codes: Vec<&str> = message.errors.iter().map(|e| e.code()).collect();
+    assert_eq!(
+        codes,
+        vec!["part.material.unknown", "part.grams.out-of-range"]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,202 @@
+use crate::server::project::CreateProjectPayload;
+use crate::server::rest::CreatedResponse;
+use crate::server::start_test_server;
+use printtables::server::rest::ValidationMessage;
+use reqwest::multipart::{Form, Part};
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn upgrade_projects_to_latest_revision() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let (project_uri, hinge) = test_server.given_project_with_bom("Hinge,4\n").await?;
+    let hinge_uri = format!("/v1/inventory/parts/{}", hinge);
+    let project: Value = test_server
+        .view_project_by_uri(&project_uri)
+        .await?
+        .json()
+        .await?;
+    assert_eq!(project["bom"][0]["revision"], 1);
+
+    let response = test_server
+        .api_client
+        .post(test_server.uri(&format!("{}/revisions", hinge_uri)))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.json::<Value>().await?, json!({ "revision": 2 }));
+    let form = Form::new().part(
+        "file",
+        Part::bytes(&b"ISO-10303-21;"[..]).file_name("hinge-v2.step"),
+    );
+    let file: Value = test_server
+        .api_client
+        .post(test_server.uri(&format!("{}/files", hinge_uri)))
+        .multipart(form)
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(file["revision"], 2);
+
+    let project: Value = test_server
+        .view_project_by_uri(&project_uri)
+        .await?
+        .json()
+        .await?;
+    assert_eq!(project["bom"][0]["revision"], 1);
+    assert_eq!(project["bom"][0]["latest_revision"], 2);
+    let outdated: Vec<Value> = test_server
+        .api_client
+        .get(test_server.uri("/v1/projects/outdated"))
+        .query(&[("part", &hinge)])
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(outdated.len(), 1);
+    assert_eq!(outdated[0]["name"], project["name"]);
+    assert_eq!(
+        outdated[0]["parts"],
+        json!([{ "part_id": hinge, "name": "Hinge", "revision": 1, "latest_revision": 2 }])
+    );
+
+    let response = test_server
+        .api_client
+        .post(test_server.uri(&format!("{}/upgrade", project_uri)))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let project: Value = test_server
+        .view_project_by_uri(&project_uri)
+        .await?
+        .json()
+        .await?;
+    assert_eq!(project["bom"][0]["revision"], 2);
+    let outdated: Vec<Value> = test_server
+        .api_client
+        .get(test_server.uri("/v1/projects/outdated"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert!(outdated.is_empty());
+    let revisions: Vec<Value> = test_server
+        .api_client
+        .get(test_server.uri(&format!("{}/revisions", hinge_uri)))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let numbers: Vec<&Value> = revisions.iter().map(|r| &r["revision"]).collect();
+    assert_eq!(numbers, vec![&json!(2), &json!(1)]);
+    assert_eq!(revisions[0]["replaced_at"], Value::Null);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn keep_estimates_of_pinned_revision() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let (project_uri, hinge) = test_server.given_project_with_bom("Hinge,4\n").await?;
+    let hinge_uri = format!("/v1/inventory/parts/{}", hinge);
+    let describe = |grams: f64| {
+        test_server
+            .api_client
+            .put(test_server.uri(&format!("{}/metadata", hinge_uri)))
+            .json(&json!({ "material": "PETG", "grams": grams, "minutes": 30 }))
+            .send()
+    };
+    describe(12.0).await?;
+    test_server
+        .api_client
+        .post(test_server.uri(&format!("{}/revisions", hinge_uri)))
+        .send()
+        .await?;
+    describe(18.0).await?;
+
+    let project: Value = test_server
+        .view_project_by_uri(&project_uri)
+        .await?
+        .json()
+        .await?;
+    assert_eq!(project["bom"][0]["revision"], 1);
+    assert_eq!(project["bom"][0]["grams"], 12.0);
+    assert_eq!(project["bom"][0]["material"], "PETG");
+    test_server
+        .api_client
+        .post(test_server.uri(&format!("{}/upgrade", project_uri)))
+        .send()
+        .await?;
+    let project: Value = test_server
+        .view_project_by_uri(&project_uri)
+        .await?
+        .json()
+        .await?;
+    assert_eq!(project["bom"][0]["grams"], 18.0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_malformed_upgrade_payload() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let response = test_server
+        .create_project(&CreateProjectPayload::default())
+        .await?;
+    let project_uri = CreatedResponse::<Value>::from(response).await.location;
+
+    let response = test_server
+        .api_client
+        .post(test_server.uri(&format!("{}/upgrade", project_uri)))
+        .json(&json!({ "part": "hinge" }))
+        .send()
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let message: ValidationMessage = response.json().await?;
+    assert_eq!(message.errors[0].code(), "request.body.invalid");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_upgrade_of_part_outside_bom() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let response = test_server
+        .create_project(&CreateProjectPayload::default())
+        .await?;
+    let project_uri = CreatedResponse::<Value>::from(response).await.location;
+
+    let response = test_server
+        .api_client
+        .post(test_server.uri(&format!("{}/upgrade", project_uri)))
+        .json(&json!({ "part": uuid::Uuid::now_v7() }))
+        .send()
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let message: ValidationMessage = response.json().await?;
+    assert_eq!(message.errors[0].code(), "upgrade.part.not-in-bom");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn revise_missing_part() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+
+    let response = test_server
+        .api_client
+        .post(test_server.uri(&format!(
+            "/v1/inventory/parts/{}/revisions",
+            uuid::Uuid::now_v7()
+        )))
+        .send()
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
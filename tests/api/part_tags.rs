@@ -0,0 +1,129 @@
+use crate::server::inventory::RegisterPartPayload;
+use crate::server::project::CreateProjectPayload;
+use crate::server::rest::CreatedResponse;
+use crate::server::start_test_server;
+use printtables::server::rest::ValidationMessage;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn classify_and_filter_parts() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let response = test_server
+        .register_part(&RegisterPartPayload::new("Bin 2x1"))
+        .await?;
+    let bin_uri = CreatedResponse::<Value>::from(response).await.location;
+    let response = test_server
+        .register_part(&RegisterPartPayload::new("Shelf bracket"))
+        .await?;
+    let bracket_uri = CreatedResponse::<Value>::from(response).await.location;
+
+    let response = test_server
+        .api_client
+        .put(test_server.uri(&format!("{}/classification", bin_uri)))
+        .json(&json!({
+            "category": "Storage/Gridfinity/Bins",
+            "tags": ["Gridfinity", "desk"]
+        }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    test_server
+        .api_client
+        .put(test_server.uri(&format!("{}/classification", bracket_uri)))
+        .json(&json!({ "category": "Storage", "tags": ["wall-mount"] }))
+        .send()
+        .await?;
+
+    let part: Value = test_server.view_part_by_uri(&bin_uri).await?.json().await?;
+    assert_eq!(part["category"], "Storage › Gridfinity › Bins");
+    assert_eq!(part["tags"], json!(["desk", "gridfinity"]));
+
+    let parts: Vec<Value> = test_server
+        .api_client
+        .get(test_server.uri("/v1/inventory/parts?category=storage"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(parts.len(), 2);
+    let parts: Vec<Value> = test_server
+        .api_client
+        .get(test_server.uri("/v1/inventory/parts?category=Storage › Gridfinity&tag=gridfinity"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let names: Vec<&str> = parts.iter().map(|p| p["name"].as_str().unwrap()).collect();
+    assert_eq!(names, vec!["Bin 2x1"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_invalid_tags() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let response = test_server
+        .register_part(&RegisterPartPayload::default())
+        .await?;
+    let part_uri = CreatedResponse::<Value>::from(response).await.location;
+
+    let response = test_server
+        .api_client
+        .put(test_server.uri(&format!("{}/classification", part_uri)))
+        .json(&json!({ "tags": ["bins, lids"] }))
+        .send()
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let message: ValidationMessage = response.json().await?;
+    assert_eq!(message.errors[0].code(), "part.tag.invalid-character");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn group_bom_by_category() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let response = test_server
+        .create_project(&CreateProjectPayload::default())
+        .await?;
+    let project_uri = CreatedResponse::<Value>::from(response).await.location;
+    test_server
+        .import_bom(&project_uri, "Lid,1\nBin,3\n", "register_missing=true")
+        .await?;
+    let project: Value = test_server
+        .view_project_by_uri(&project_uri)
+        .await?
+        .json()
+        .await?;
+    let bin = project["bom"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|line| line["name"] == "Bin")
+        .unwrap()["part_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    test_server
+        .api_client
+        .put(test_server.uri(&format!("/v1/inventory/parts/{}/classification", bin)))
+        .json(&json!({ "category": "Storage" }))
+        .send()
+        .await?;
+
+    let project: Value = test_server
+        .view_project_by_uri(&format!("{}?group_by=category", project_uri))
+        .await?
+        .json()
+        .await?;
+
+    let groups = project["groups"].as_array().unwrap();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0]["category"], "Storage");
+    assert_eq!(groups[0]["bom"][0]["quantity"], 3);
+    assert_eq!(groups[1]["category"], Value::Null);
+
+    Ok(())
+}
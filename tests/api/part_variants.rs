@@ -0,0 +1,214 @@
+use crate::server::inventory::RegisterPartPayload;
+use crate::server::project::CreateProjectPayload;
+use crate::server::rest::CreatedResponse;
+use crate::server::start_test_server;
+use crate::server::TestServer;
+use printtables::server::rest::ValidationMessage;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn report_demand_per_variant_and_parent() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let response = test_server
+        .register_part(&RegisterPartPayload::new("Bin"))
+        .await?;
+    let bin_uri = CreatedResponse::<Value>::from(response).await.location;
+    let red_bin = register_variant(&test_server, &bin_uri, json!({ "color": "red" })).await?;
+    let blue_bin = register_variant(
+        &test_server,
+        &bin_uri,
+        json!({ "name": "Blue bin", "color": "blue", "size": "3 units" }),
+    )
+    .await?;
+
+    let part: Value = test_server.view_part_by_uri(&red_bin).await?.json().await?;
+    assert_eq!(part["name"], "Bin (red)");
+    assert_eq!(part["color"], "red");
+    let bin_id = bin_uri.rsplit('/').next().unwrap();
+    assert_eq!(
+        part["variant"],
+        json!({ "parent": bin_id, "color": "red", "size": null })
+    );
+    let variants: Vec<Value> = test_server
+        .api_client
+        .get(test_server.uri("/v1/inventory/parts"))
+        .query(&[("variant_of", bin_id)])
+        .send()
+        .await?
+        .json()
+        .await?;
+    let names: Vec<&str> = variants
+        .iter()
+        .map(|p| p["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["Bin (red)", "Blue bin"]);
+
+    let response = test_server
+        .create_project(&CreateProjectPayload::default())
+        .await?;
+    let project_uri = CreatedResponse::<Value>::from(response).await.location;
+    let part_id = |uri: &str| uri.rsplit('/').next().unwrap().to_string();
+    test_server
+        .api_client
+        .put(test_server.uri(&format!("{}/parts", project_uri)))
+        .json(&json!({ "parts": [
+            { "part": part_id(&red_bin), "quantity": 3 },
+            { "part": part_id(&blue_bin), "quantity": 2 },
+        ] }))
+        .send()
+        .await?;
+
+    test_server
+        .report_progress(
+            &project_uri,
+            &json!({ "part": part_id(&red_bin), "printed": 1 }),
+        )
+        .await?;
+    // Templates only describe projects to create, so they don't add to the demand.
+    let response = test_server
+        .api_client
+        .post(test_server.uri(&format!("{}/duplicate", project_uri)))
+        .send()
+        .await?;
+    let template_uri = CreatedResponse::<Value>::from(response).await.location;
+    test_server
+        .api_client
+        .put(test_server.uri(&format!("{}/template", template_uri)))
+        .json(&json!({ "template": true }))
+        .send()
+        .await?;
+
+    let project: Value = test_server
+        .view_project_by_uri(&format!("{}?group_by=parent", project_uri))
+        .await?
+        .json()
+        .await?;
+    assert_eq!(project["bom"][0]["variant"]["parent_name"], "Bin");
+    let groups = project["groups"].as_array().unwrap();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0]["name"], "Bin");
+    assert_eq!(groups[0]["bom"].as_array().unwrap().len(), 2);
+
+    let demand: Vec<Value> = test_server
+        .api_client
+        .get(test_server.uri("/v1/projects/demand"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let rows: Vec<(&str, u64)> = demand
+        .iter()
+        .map(|d| {
+            (
+                d["name"].as_str().unwrap(),
+                d["remaining"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(rows, vec![("Bin (red)", 2), ("Blue bin", 2)]);
+    let demand: Vec<Value> = test_server
+        .api_client
+        .get(test_server.uri("/v1/projects/demand?group_by=parent"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(demand.len(), 1);
+    assert_eq!(demand[0]["part_id"], bin_id);
+    assert_eq!(demand[0]["printed"], 1);
+    assert_eq!(demand[0]["remaining"], 4);
+    assert_eq!(demand[0]["projects"], 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn link_and_unlink_existing_parts() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let response = test_server
+        .register_part(&RegisterPartPayload::new("Bin"))
+        .await?;
+    let bin_uri = CreatedResponse::<Value>::from(response).await.location;
+    let response = test_server
+        .register_part(&RegisterPartPayload::new("Green bin"))
+        .await?;
+    let green_uri = CreatedResponse::<Value>::from(response).await.location;
+    let bin_id = bin_uri.rsplit('/').next().unwrap();
+
+    let response = test_server
+        .api_client
+        .put(test_server.uri(&format!("{}/variant", green_uri)))
+        .json(&json!({ "parent": bin_id, "color": "green" }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let part: Value = test_server
+        .view_part_by_uri(&green_uri)
+        .await?
+        .json()
+        .await?;
+    assert_eq!(part["variant"]["parent"], bin_id);
+
+    let response = test_server
+        .api_client
+        .put(test_server.uri(&format!("{}/variant", bin_uri)))
+        .json(&json!({ "parent": part["id"], "size": "2 units" }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let message: ValidationMessage = response.json().await?;
+    assert_eq!(message.errors[0].code(), "variant.parent.is-variant");
+
+    let response = test_server
+        .api_client
+        .delete(test_server.uri(&format!("{}/variant", green_uri)))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let part: Value = test_server
+        .view_part_by_uri(&green_uri)
+        .await?
+        .json()
+        .await?;
+    assert_eq!(part["variant"], Value::Null);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_variant_without_attributes() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let response = test_server
+        .register_part(&RegisterPartPayload::new("Bin"))
+        .await?;
+    let bin_uri = CreatedResponse::<Value>::from(response).await.location;
+
+    let response = test_server
+        .api_client
+        .post(test_server.uri(&format!("{}/variants", bin_uri)))
+        .json(&json!({ "name": "Bin, again" }))
+        .send()
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let message: ValidationMessage = response.json().await?;
+    assert_eq!(message.errors[0].code(), "variant.attributes.missing");
+
+    Ok(())
+}
+
+async fn register_variant(
+    test_server: &TestServer,
+    part_uri: &str,
+    payload: Value,
+) -> anyhow::Result<String> {
+    let response = test_server
+        .api_client
+        .post(test_server.uri(&format!("{}/variants", part_uri)))
+        .json(&payload)
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    Ok(CreatedResponse::<Value>::from(response).await.location)
+}
@@ -0,0 +1,61 @@
+use crate::server::start_test_server;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn summarize_filament_and_print_time() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let (project_uri, drawer) = test_server
+        .given_project_with_bom("Drawer,9\nBin,2\n")
+        .await?;
+    let response = test_server
+        .api_client
+        .put(test_server.uri(&format!("/v1/inventory/parts/{}/metadata", drawer)))
+        .json(&json!({ "material": "PLA", "color": "black", "grams": 10.5, "minutes": 30 }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    test_server
+        .report_progress(&project_uri, &json!({"part": drawer, "printed": 3}))
+        .await?;
+
+    let response = test_server
+        .api_client
+        .get(test_server.uri(&format!("{}/summary", project_uri)))
+        .send()
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let summary: Value = response.json().await?;
+    assert_eq!(
+        summary["filament"],
+        json!([{ "material": "PLA", "color": "black", "grams": 94.5, "remaining_grams": 63.0 }])
+    );
+    assert_eq!(summary["print_minutes"], 270);
+    assert_eq!(summary["remaining_print_hours"], 3.0);
+    assert_eq!(summary["missing_estimates"], 1);
+    let project: Value = test_server
+        .view_project_by_uri(&project_uri)
+        .await?
+        .json()
+        .await?;
+    assert_eq!(project["summary"], summary);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn summary_of_missing_project() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+
+    let response = test_server
+        .api_client
+        .get(test_server.uri(&format!("/v1/projects/{}/summary", Uuid::now_v7())))
+        .send()
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
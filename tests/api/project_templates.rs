@@ -0,0 +1,145 @@
+use crate::server::start_test_server;
+use printtables::infra::services::Services;
+use printtables::inventory::domain::name::Name as PartName;
+use printtables::projects::app::service::ProjectError;
+use printtables::projects::domain::multiplier::Multiplier;
+use printtables::projects::domain::name::Name as ProjectName;
+use printtables::projects::domain::project::ProjectPart;
+use printtables::projects::view::project::ProjectView;
+use printtables::server::rest::ValidationMessage;
+use reqwest::StatusCode;
+use serde_json::json;
+
+#[tokio::test]
+async fn duplicate_project() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let services = Services::remote(&test_server.base_url())?;
+    let bin = services
+        .inventory
+        .register_part(PartName::parse("Bin")?)
+        .await?;
+    let project = services
+        .projects
+        .register_project(ProjectName::parse("Organizer")?)
+        .await?;
+    services
+        .projects
+        .set_project_bom(project, vec![ProjectPart::new(bin, 5)])
+        .await?;
+    services.projects.report_progress(project, bin, 5).await?;
+
+    let response = test_server
+        .api_client
+        .post(test_server.uri(&format!("/v1/projects/{}/duplicate", project)))
+        .send()
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let location = response.headers()["location"].to_str()?.to_string();
+    let copy: ProjectView = test_server
+        .view_project_by_uri(&location)
+        .await?
+        .json()
+        .await?;
+    assert_ne!(copy.id(), project);
+    assert_eq!(copy.name(), "Organizer (copy)");
+    assert_eq!(copy.parts()[0].quantity(), 5);
+    assert_eq!(copy.parts()[0].printed(), 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn instantiate_template() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let services = Services::remote(&test_server.base_url())?;
+    let drawer = services
+        .inventory
+        .register_part(PartName::parse("Drawer")?)
+        .await?;
+    let template = services
+        .projects
+        .register_project(ProjectName::parse("Organizer")?)
+        .await?;
+    services
+        .projects
+        .set_project_bom(template, vec![ProjectPart::new(drawer, 3)])
+        .await?;
+    let response = test_server
+        .api_client
+        .put(test_server.uri(&format!("/v1/projects/{}/template", template)))
+        .json(&json!({ "template": true }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let project = services
+        .projects
+        .instantiate_template(template, Multiplier::parse(12)?, None)
+        .await?;
+
+    let view = services.projects.view_project(project).await?;
+    assert_eq!(view.name(), "Organizer ×12");
+    assert_eq!(view.parts()[0].quantity(), 36);
+    assert!(!view.is_template());
+    assert!(services
+        .projects
+        .view_project(template)
+        .await?
+        .is_template());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_invalid_instantiation() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let services = Services::remote(&test_server.base_url())?;
+    let project = services
+        .projects
+        .register_project(ProjectName::parse("Organizer")?)
+        .await?;
+
+    let response = test_server
+        .api_client
+        .post(test_server.uri(&format!("/v1/projects/{}/instantiate", project)))
+        .json(&json!({ "multiplier": 0, "name": "" }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let message: ValidationMessage = response.json().await?;
+    assert_eq!(message.errors.len(), 2);
+
+    let result = services
+        .projects
+        .instantiate_template(project, Multiplier::default(), None)
+        .await;
+    assert!(
+        matches!(result, Err(ProjectError::ValidationFailed(errors)) if errors[0].code() == "project.template.required")
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_malformed_duplicate_payload() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let services = Services::remote(&test_server.base_url())?;
+    let project = services
+        .projects
+        .register_project(ProjectName::parse("Organizer")?)
+        .await?;
+
+    let response = test_server
+        .api_client
+        .post(test_server.uri(&format!("/v1/projects/{}/duplicate", project)))
+        .json(&json!({ "name": 42 }))
+        .send()
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let message: ValidationMessage = response.json().await?;
+    assert_eq!(message.errors[0].code(), "request.body.invalid");
+
+    Ok(())
+}
@@ -39,6 +39,32 @@ async fn register_and_view_part() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn rename_part() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let response = test_server
+        .register_part(&RegisterPartPayload::default())
+        .await?;
+    let part_uri = CreateResponse::from(response).await.location;
+
+    let response = test_server
+        .rename_part(&part_uri, &RegisterPartPayload::new("Drawer, small"))
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = test_server.view_part_by_uri(&part_uri).await?;
+    let view_part_response = ViewPartResponse::from(response).await;
+    assert_eq!(view_part_response.payload().name, "Drawer, small");
+
+    let missing_uri = format!("/v1/inventory/parts/{}", Uuid::now_v7());
+    let response = test_server
+        .rename_part(&missing_uri, &RegisterPartPayload::new("Lid"))
+        .await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn viewing_missing_part_responds_404() -> anyhow::Result<()> {
     let test_server = start_test_server().await?;
@@ -0,0 +1,108 @@
+use crate::server::start_test_server;
+use printtables::infra::services::Services;
+use printtables::inventory::app::files::PartFileError;
+use printtables::inventory::domain::file::FileName;
+use printtables::inventory::domain::name::Name as PartName;
+use printtables::projects::app::import::{BomImportError, BomImportOptions};
+use printtables::projects::app::model_import::{ModelImportError, ModelImportOptions};
+use printtables::projects::app::service::ProjectError;
+use printtables::projects::domain::name::Name as ProjectName;
+use printtables::projects::domain::project::ProjectPart;
+use tokio::io::AsyncReadExt;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn manage_project_through_remote_services() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let services = Services::remote(&test_server.base_url())?;
+
+    let part_name = PartName::parse("Drawer enclosure")?;
+    let part_id = services.inventory.register_part(part_name.clone()).await?;
+    let found = services.inventory.find_part_by_name(&part_name).await?;
+    assert_eq!(found.map(|p| p.id()), Some(part_id));
+    assert_eq!(
+        services.inventory.view_part(part_id).await?.name().as_ref(),
+        "Drawer enclosure"
+    );
+
+    let project_id = services
+        .projects
+        .register_project(ProjectName::parse("Organizers")?)
+        .await?;
+    services
+        .projects
+        .set_project_bom(project_id, vec![ProjectPart::new(part_id, 9)])
+        .await?;
+
+    let project = services.projects.view_project(project_id).await?;
+    assert_eq!(project.name(), "Organizers");
+    assert_eq!(project.parts()[0].quantity(), 9);
+
+    let projects = services.projects.list_projects().await?;
+    assert!(projects
+        .iter()
+        .any(|p| p.id() == project_id && p.bom_size() == 1));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn report_remote_errors() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let services = Services::remote(&test_server.base_url())?;
+
+    let missing = services.projects.view_project(Uuid::now_v7()).await;
+    assert!(matches!(missing, Err(ProjectError::MissingProject)));
+
+    let project_id = services
+        .projects
+        .register_project(ProjectName::parse("Bins")?)
+        .await?;
+    let import = services
+        .bom_importer
+        .import_csv(project_id, "Bin,none", BomImportOptions::default())
+        .await;
+    assert!(matches!(import, Err(BomImportError::InvalidBom(errors)) if !errors.is_empty()));
+
+    let import = services
+        .model_importer
+        .import_3mf(b"solid cube", ModelImportOptions::default())
+        .await;
+    assert!(matches!(import, Err(ModelImportError::InvalidModel(errors)) if !errors.is_empty()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn attach_files_through_remote_services() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let services = Services::remote(&test_server.base_url())?;
+    let part_id = services
+        .inventory
+        .register_part(PartName::parse("Hinge")?)
+        .await?;
+
+    let file = services
+        .files
+        .attach_file(
+            part_id,
+            FileName::parse("hinge.step")?,
+            &mut "ISO-10303-21;".as_bytes(),
+        )
+        .await?;
+    assert_eq!(
+        services.files.list_files(part_id).await?,
+        vec![file.clone()]
+    );
+
+    let (opened, mut content) = services.files.open_file(part_id, file.id).await?;
+    let mut downloaded = String::new();
+    content.read_to_string(&mut downloaded).await?;
+    assert_eq!(opened, file);
+    assert_eq!(downloaded, "ISO-10303-21;");
+
+    let missing = services.files.open_file(part_id, Uuid::now_v7()).await;
+    assert!(matches!(missing, Err(PartFileError::MissingFile)));
+
+    Ok(())
+}
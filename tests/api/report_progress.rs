@@ -0,0 +1,76 @@
+use crate::server::start_test_server;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn report_printed_parts() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let (project_uri, part_id) = test_server.given_project_with_bom("Drawer,9\n").await?;
+
+    for _ in 0..2 {
+        let response = test_server
+            .report_progress(&project_uri, &json!({"part": part_id, "printed": 2}))
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let project: Value = test_server
+        .view_project_by_uri(&project_uri)
+        .await?
+        .json()
+        .await?;
+    assert_eq!(project["bom"][0]["printed"], 4);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_progress_of_part_outside_bom() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let (project_uri, _) = test_server.given_project_with_bom("Drawer,9\n").await?;
+
+    let response = test_server
+        .report_progress(&project_uri, &json!({"part": Uuid::now_v7(), "printed": 1}))
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body: Value = response.json().await?;
+    assert_eq!(body["errors"][0]["code"], "progress.part.not-in-bom");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_progress_beyond_quantity() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+    let (project_uri, part_id) = test_server.given_project_with_bom("Drawer,9\n").await?;
+
+    for printed in [0, 10] {
+        let response = test_server
+            .report_progress(&project_uri, &json!({"part": part_id, "printed": printed}))
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: Value = response.json().await?;
+        assert_eq!(body["errors"][0]["code"], "progress.printed.out-of-range");
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn report_progress_of_missing_project() -> anyhow::Result<()> {
+    let test_server = start_test_server().await?;
+
+    let response = test_server
+        .report_progress(
+            &format!("/v1/projects/{}", Uuid::now_v7()),
+            &json!({"part": Uuid::now_v7(), "printed": 1}),
+        )
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
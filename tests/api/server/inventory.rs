@@ -4,7 +4,9 @@ use anyhow::anyhow;
 use fake::{faker::name::en::Name, Fake};
 use reqwest::Response;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
+use super::rest::CreatedResponse;
 use super::TestServer;
 
 #[derive(Debug, Serialize)]
@@ -36,6 +38,20 @@ impl TestServer {
             .map_err(|e| anyhow!(e))
     }
 
+    pub async fn rename_part(
+        &self,
+        part_uri: &str,
+        payload: &RegisterPartPayload,
+    ) -> anyhow::Result<Response> {
+        let uri = self.uri(part_uri);
+        self.api_client
+            .put(uri)
+            .json(payload)
+            .send()
+            .await
+            .map_err(|e| anyhow!(e))
+    }
+
     pub async fn view_part_by_uri(&self, part_uri: &str) -> anyhow::Result<Response> {
         let uri = self.uri(part_uri);
         self.api_client
@@ -44,6 +60,26 @@ impl TestServer {
             .await
             .map_err(|e| anyhow!(e))
     }
+
+    /// Registers a part with the given name and returns its URI.
+    pub async fn given_part(&self, name: &str) -> anyhow::Result<String> {
+        let response = self.register_part(&RegisterPartPayload::new(name)).await?;
+        Ok(CreatedResponse::<Value>::from(response).await.location)
+    }
+
+    pub async fn attribute_part(
+        &self,
+        part_uri: &str,
+        payload: &Value,
+    ) -> anyhow::Result<Response> {
+        let uri = self.uri(&format!("{}/source", part_uri));
+        self.api_client
+            .put(uri)
+            .json(payload)
+            .send()
+            .await
+            .map_err(|e| anyhow!(e))
+    }
 }
 
 impl RegisterPartPayload {
@@ -1,8 +1,10 @@
 use anyhow::anyhow;
 use fake::{faker::name::en::Name, Fake};
-use reqwest::Response;
+use reqwest::{Response, StatusCode};
 use serde::Serialize;
+use serde_json::Value;
 
+use crate::server::rest::CreatedResponse;
 use crate::server::TestServer;
 
 impl TestServer {
@@ -16,6 +18,32 @@ impl TestServer {
             .map_err(|e| anyhow!(e))
     }
 
+    pub async fn import_bom(
+        &self,
+        project_uri: &str,
+        csv: &str,
+        query: &str,
+    ) -> anyhow::Result<Response> {
+        let url = self.uri(&format!("{}/parts/import?{}", project_uri, query));
+        self.api_client
+            .post(url)
+            .header("Content-Type", "text/csv")
+            .body(csv.to_string())
+            .send()
+            .await
+            .map_err(|e| anyhow!(e))
+    }
+
+    pub async fn view_project_as(&self, uri: &str, accept: &str) -> anyhow::Result<Response> {
+        let url = self.uri(uri);
+        self.api_client
+            .get(url)
+            .header("Accept", accept)
+            .send()
+            .await
+            .map_err(|e| anyhow!(e))
+    }
+
     pub async fn view_project_by_uri(&self, uri: &str) -> anyhow::Result<Response> {
         let url = self.uri(uri);
         self.api_client
@@ -24,6 +52,22 @@ impl TestServer {
             .await
             .map_err(|e| anyhow!(e))
     }
+
+    /// Creates a project with the BOM imported from CSV, registering missing parts.
+    /// Returns the project URI and the ID of the part on the first BOM line.
+    pub async fn given_project_with_bom(&self, csv: &str) -> anyhow::Result<(String, String)> {
+        let response = self
+            .create_project(&CreateProjectPayload::default())
+            .await?;
+        let project_uri = CreatedResponse::<Value>::from(response).await.location;
+        let response = self
+            .import_bom(&project_uri, csv, "register_missing=true")
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK, "failed to import BOM");
+        let project: Value = self.view_project_by_uri(&project_uri).await?.json().await?;
+        let part_id = project["bom"][0]["part_id"].as_str().unwrap().to_string();
+        Ok((project_uri, part_id))
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -47,3 +91,19 @@ impl Default for CreateProjectPayload {
         Self::new(name)
     }
 }
+
+impl TestServer {
+    pub async fn report_progress(
+        &self,
+        project_uri: &str,
+        payload: &serde_json::Value,
+    ) -> anyhow::Result<Response> {
+        let url = self.uri(&format!("{}/progress", project_uri));
+        self.api_client
+            .post(url)
+            .json(payload)
+            .send()
+            .await
+            .map_err(|e| anyhow!(e))
+    }
+}
@@ -14,7 +14,8 @@ pub async fn start_test_server() -> anyhow::Result<TestServer> {
     let test_listener = TcpListener::bind("0.0.0.0:0").await?;
     let port = &test_listener.local_addr()?.port();
     let db_pool = test_database_pool().await?;
-    let server = start_server(test_listener, db_pool);
+    let files_dir = std::env::temp_dir().join(format!("printtables-{}", uuid::Uuid::now_v7()));
+    let server = start_server(test_listener, db_pool, files_dir);
     tokio::spawn(server.into_future());
     let api_client = Client::new();
     Ok(TestServer {
@@ -29,6 +30,10 @@ async fn test_database_pool() -> anyhow::Result<SqlitePool> {
     SqlitePool::connect(&db_url).await.map_err(|e| anyhow!(e))
 }
 impl TestServer {
+    pub fn base_url(&self) -> String {
+        format!("http://localhost:{}", self.port)
+    }
+
     pub fn uri(&self, path: &str) -> String {
         if !path.starts_with("/") {
             panic!("invalid path {}. path must start with /", path);